@@ -3,7 +3,12 @@
 //! This library provides various adapters and utilities for database interactions.
 
 pub mod error;
+#[cfg(feature = "mdbx")]
+pub mod mdbx;
+pub mod memory;
 pub mod redis;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod traits;
 pub use redis::*;
 
@@ -0,0 +1,510 @@
+//! Adapter trait implementations for the MDBX backend
+//!
+//! Each data type lives in its own named sub-database:
+//!
+//! - `strings`: key → 8-byte big-endian expiry in unix milliseconds (0 for
+//!   none) followed by the value. Expired entries are skipped on read and
+//!   overwritten or removed on the next write.
+//! - `hashes`: 4-byte big-endian key length, key and field → value, so the
+//!   fields of a hash are adjacent and can be walked with one cursor.
+//! - `sets`: a `DUP_SORT` table of key → member.
+//!
+//! A key lives in at most one of them, as in Redis: hash and set writes fail
+//! with [`WrongType`] when the key holds another type, and string writes
+//! replace whatever the key held. Every call runs in its own read or write
+//! transaction.
+
+use async_trait::async_trait;
+use libmdbx::{Database, NoWriteMap, TableFlags, Transaction, TransactionKind, WriteFlags, RW};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::AdapterError;
+use crate::memory::store::WrongType;
+use crate::traits::{
+    AsyncDatabaseAdapter, DatabaseAdapter, HashAdapter, KeyValueAdapter, SetAdapter,
+};
+
+const STRINGS: &str = "strings";
+const HASHES: &str = "hashes";
+const SETS: &str = "sets";
+
+/// Length of the expiry header in front of every string value
+const EXPIRY_LEN: usize = 8;
+
+impl From<libmdbx::Error> for AdapterError {
+    fn from(err: libmdbx::Error) -> Self {
+        AdapterError::Database(err.to_string())
+    }
+}
+
+/// Database adapter backed by an MDBX environment on disk
+///
+/// Clones share the same environment.
+#[derive(Clone)]
+pub struct MdbxAdapter {
+    db: Arc<Database<NoWriteMap>>,
+}
+
+impl MdbxAdapter {
+    /// Open (or create) the environment in the `path` directory
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AdapterError> {
+        let path = path.as_ref();
+        std::fs::create_dir_all(path).map_err(|e| {
+            AdapterError::Configuration(format!("Cannot create {}: {}", path.display(), e))
+        })?;
+
+        let db = Database::<NoWriteMap>::new().set_max_tables(3).open(path)?;
+        let txn = db.begin_rw_txn()?;
+        txn.create_table(Some(STRINGS), TableFlags::empty())?;
+        txn.create_table(Some(HASHES), TableFlags::empty())?;
+        txn.create_table(Some(SETS), TableFlags::DUP_SORT)?;
+        txn.commit()?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Get the remaining time to live: `None` if the key is missing, `Some(None)` if it never expires
+    pub fn ttl(&self, key: &str) -> Result<Option<Option<Duration>>, AdapterError> {
+        let txn = self.db.begin_ro_txn()?;
+        let table = txn.open_table(Some(STRINGS))?;
+        let Some(entry) = txn.get::<Vec<u8>>(&table, key.as_bytes())? else {
+            return Ok(None);
+        };
+        Ok(match expires_at(&entry) {
+            0 => Some(None),
+            at if at <= now_millis() => None,
+            at => Some(Some(Duration::from_millis(at - now_millis()))),
+        })
+    }
+
+    /// Set a hash field, returning whether the field is new
+    pub fn hset_new(&self, key: &str, field: &str, value: &str) -> Result<bool, AdapterError> {
+        let txn = self.db.begin_rw_txn()?;
+        claim(&txn, key, HASHES)?;
+        let table = txn.open_table(Some(HASHES))?;
+        let entry = hash_entry(key, field);
+        let created = txn.get::<()>(&table, &entry)?.is_none();
+        txn.put(&table, &entry, value, WriteFlags::empty())?;
+        txn.commit()?;
+        Ok(created)
+    }
+
+    /// Set several hash fields in one transaction
+    pub fn hset_many(&self, key: &str, fields: &[(&str, &str)]) -> Result<(), AdapterError> {
+        let txn = self.db.begin_rw_txn()?;
+        claim(&txn, key, HASHES)?;
+        let table = txn.open_table(Some(HASHES))?;
+        for (field, value) in fields {
+            txn.put(&table, hash_entry(key, field), value, WriteFlags::empty())?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Add several members to a set, returning how many were new
+    pub fn sadd_many(&self, key: &str, members: &[&str]) -> Result<usize, AdapterError> {
+        let txn = self.db.begin_rw_txn()?;
+        claim(&txn, key, SETS)?;
+        let table = txn.open_table(Some(SETS))?;
+        let mut added = 0;
+        for member in members {
+            match txn.put(&table, key, member, WriteFlags::NO_DUP_DATA) {
+                Ok(()) => added += 1,
+                Err(libmdbx::Error::KeyExist) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        txn.commit()?;
+        Ok(added)
+    }
+
+    /// Get the number of members in a set
+    pub fn scard(&self, key: &str) -> Result<usize, AdapterError> {
+        let txn = self.db.begin_ro_txn()?;
+        let table = txn.open_table(Some(SETS))?;
+        let mut cursor = txn.cursor(&table)?;
+        let mut count = 0;
+        for item in cursor.iter_dup_of::<(), ()>(key.as_bytes()) {
+            item?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn read_string(&self, key: &str) -> Result<Option<Vec<u8>>, AdapterError> {
+        let txn = self.db.begin_ro_txn()?;
+        let table = txn.open_table(Some(STRINGS))?;
+        Ok(txn
+            .get::<Vec<u8>>(&table, key.as_bytes())?
+            .filter(|entry| is_live(entry))
+            .map(|mut entry| entry.split_off(EXPIRY_LEN)))
+    }
+
+    fn write_string(&self, key: &str, value: &str, expires_at: u64) -> Result<(), AdapterError> {
+        let mut entry = Vec::with_capacity(EXPIRY_LEN + value.len());
+        entry.extend_from_slice(&expires_at.to_be_bytes());
+        entry.extend_from_slice(value.as_bytes());
+
+        let txn = self.db.begin_rw_txn()?;
+        // SET replaces a hash or set held under the key
+        remove_key(&txn, key)?;
+        let table = txn.open_table(Some(STRINGS))?;
+        txn.put(&table, key, entry, WriteFlags::empty())?;
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn hash_fields(&self, key: &str) -> Result<HashMap<String, String>, AdapterError> {
+        let txn = self.db.begin_ro_txn()?;
+        let table = txn.open_table(Some(HASHES))?;
+        let prefix = hash_prefix(key);
+        let mut cursor = txn.cursor(&table)?;
+        let mut fields = HashMap::new();
+        for item in cursor.iter_from::<Vec<u8>, Vec<u8>>(&prefix) {
+            let (entry, value) = item?;
+            let Some(field) = entry.strip_prefix(prefix.as_slice()) else {
+                break;
+            };
+            fields.insert(
+                String::from_utf8_lossy(field).into_owned(),
+                String::from_utf8_lossy(&value).into_owned(),
+            );
+        }
+        Ok(fields)
+    }
+}
+
+/// Get the table holding `key`, ignoring an expired string
+fn key_type<K: TransactionKind>(
+    txn: &Transaction<'_, K, NoWriteMap>,
+    key: &str,
+) -> Result<Option<&'static str>, AdapterError> {
+    let strings = txn.open_table(Some(STRINGS))?;
+    if txn
+        .get::<Vec<u8>>(&strings, key.as_bytes())?
+        .is_some_and(|entry| is_live(&entry))
+    {
+        return Ok(Some(STRINGS));
+    }
+
+    let hashes = txn.open_table(Some(HASHES))?;
+    let prefix = hash_prefix(key);
+    let has_hash = txn
+        .cursor(&hashes)?
+        .iter_from::<Vec<u8>, ()>(&prefix)
+        .next()
+        .transpose()?
+        .is_some_and(|(entry, ())| entry.starts_with(&prefix));
+    if has_hash {
+        return Ok(Some(HASHES));
+    }
+
+    let sets = txn.open_table(Some(SETS))?;
+    Ok(txn.get::<()>(&sets, key.as_bytes())?.map(|()| SETS))
+}
+
+/// Fail with [`WrongType`] unless `key` is missing or already lives in `table`
+fn claim(
+    txn: &Transaction<'_, RW, NoWriteMap>,
+    key: &str,
+    table: &str,
+) -> Result<(), AdapterError> {
+    match key_type(txn, key)? {
+        Some(found) if found != table => Err(WrongType.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Remove every entry of `key` from all tables, returning whether any existed
+fn remove_key(txn: &Transaction<'_, RW, NoWriteMap>, key: &str) -> Result<bool, AdapterError> {
+    let strings = txn.open_table(Some(STRINGS))?;
+    let live_string = txn
+        .get::<Vec<u8>>(&strings, key.as_bytes())?
+        .is_some_and(|entry| is_live(&entry));
+    txn.del(&strings, key, None)?;
+
+    let hashes = txn.open_table(Some(HASHES))?;
+    let prefix = hash_prefix(key);
+    let mut entries = Vec::new();
+    {
+        let mut cursor = txn.cursor(&hashes)?;
+        for item in cursor.iter_from::<Vec<u8>, ()>(&prefix) {
+            let (entry, ()) = item?;
+            if !entry.starts_with(&prefix) {
+                break;
+            }
+            entries.push(entry);
+        }
+    }
+    for entry in &entries {
+        txn.del(&hashes, entry, None)?;
+    }
+
+    let sets = txn.open_table(Some(SETS))?;
+    let had_set = txn.del(&sets, key, None)?;
+
+    Ok(live_string || !entries.is_empty() || had_set)
+}
+
+impl DatabaseAdapter for MdbxAdapter {
+    type Error = AdapterError;
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl AsyncDatabaseAdapter for MdbxAdapter {
+    async fn ping(&self) -> Result<bool, Self::Error> {
+        Ok(self.db.begin_ro_txn().is_ok())
+    }
+
+    /// The environment is closed when the last clone is dropped
+    async fn close(&self) -> Result<(), Self::Error> {
+        self.db.sync(true)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl KeyValueAdapter for MdbxAdapter {
+    async fn get<K: AsRef<str> + Send>(&self, key: K) -> Result<Option<String>, Self::Error> {
+        Ok(self
+            .read_string(key.as_ref())?
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+    }
+
+    async fn set<K: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        value: V,
+    ) -> Result<(), Self::Error> {
+        self.write_string(key.as_ref(), value.as_ref(), 0)
+    }
+
+    async fn set_with_expiry<K: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        value: V,
+        expiry_seconds: u64,
+    ) -> Result<(), Self::Error> {
+        let expires_at = now_millis().saturating_add(expiry_seconds.saturating_mul(1000));
+        self.write_string(key.as_ref(), value.as_ref(), expires_at)
+    }
+
+    async fn delete<K: AsRef<str> + Send>(&self, key: K) -> Result<bool, Self::Error> {
+        let txn = self.db.begin_rw_txn()?;
+        let removed = remove_key(&txn, key.as_ref())?;
+        txn.commit()?;
+        Ok(removed)
+    }
+
+    async fn exists<K: AsRef<str> + Send>(&self, key: K) -> Result<bool, Self::Error> {
+        let txn = self.db.begin_ro_txn()?;
+        Ok(key_type(&txn, key.as_ref())?.is_some())
+    }
+}
+
+#[async_trait]
+impl HashAdapter for MdbxAdapter {
+    async fn hget<K: AsRef<str> + Send, F: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+    ) -> Result<Option<String>, Self::Error> {
+        let txn = self.db.begin_ro_txn()?;
+        let table = txn.open_table(Some(HASHES))?;
+        Ok(txn
+            .get::<Vec<u8>>(&table, &hash_entry(key.as_ref(), field.as_ref()))?
+            .map(|value| String::from_utf8_lossy(&value).into_owned()))
+    }
+
+    async fn hset<K: AsRef<str> + Send, F: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+        value: V,
+    ) -> Result<(), Self::Error> {
+        self.hset_new(key.as_ref(), field.as_ref(), value.as_ref())
+            .map(|_| ())
+    }
+
+    async fn hgetall<K: AsRef<str> + Send>(
+        &self,
+        key: K,
+    ) -> Result<HashMap<String, String>, Self::Error> {
+        self.hash_fields(key.as_ref())
+    }
+
+    async fn hdel<K: AsRef<str> + Send, F: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+    ) -> Result<bool, Self::Error> {
+        let txn = self.db.begin_rw_txn()?;
+        let table = txn.open_table(Some(HASHES))?;
+        let removed = txn.del(&table, hash_entry(key.as_ref(), field.as_ref()), None)?;
+        txn.commit()?;
+        Ok(removed)
+    }
+}
+
+#[async_trait]
+impl SetAdapter for MdbxAdapter {
+    async fn sadd<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.sadd_many(key.as_ref(), &[member.as_ref()])? == 1)
+    }
+
+    async fn srem<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let txn = self.db.begin_rw_txn()?;
+        let table = txn.open_table(Some(SETS))?;
+        let removed = txn.del(&table, key.as_ref(), Some(member.as_ref().as_bytes()))?;
+        txn.commit()?;
+        Ok(removed)
+    }
+
+    async fn sismember<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let txn = self.db.begin_ro_txn()?;
+        let table = txn.open_table(Some(SETS))?;
+        Ok(txn
+            .cursor(&table)?
+            .get_both::<()>(key.as_ref().as_bytes(), member.as_ref().as_bytes())?
+            .is_some())
+    }
+
+    async fn smembers<K: AsRef<str> + Send>(&self, key: K) -> Result<Vec<String>, Self::Error> {
+        let txn = self.db.begin_ro_txn()?;
+        let table = txn.open_table(Some(SETS))?;
+        let mut cursor = txn.cursor(&table)?;
+        // Duplicates come back sorted, so members are already in order
+        cursor
+            .iter_dup_of::<(), Vec<u8>>(key.as_ref().as_bytes())
+            .map(|item| {
+                let ((), member) = item?;
+                Ok(String::from_utf8_lossy(&member).into_owned())
+            })
+            .collect()
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+fn expires_at(entry: &[u8]) -> u64 {
+    entry
+        .get(..EXPIRY_LEN)
+        .and_then(|header| header.try_into().ok())
+        .map_or(0, u64::from_be_bytes)
+}
+
+fn is_live(entry: &[u8]) -> bool {
+    entry.len() >= EXPIRY_LEN
+        && match expires_at(entry) {
+            0 => true,
+            at => at > now_millis(),
+        }
+}
+
+fn hash_prefix(key: &str) -> Vec<u8> {
+    let mut prefix = Vec::with_capacity(4 + key.len());
+    prefix.extend_from_slice(&(key.len() as u32).to_be_bytes());
+    prefix.extend_from_slice(key.as_bytes());
+    prefix
+}
+
+fn hash_entry(key: &str, field: &str) -> Vec<u8> {
+    let mut entry = hash_prefix(key);
+    entry.extend_from_slice(field.as_bytes());
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_adapter(name: &str) -> MdbxAdapter {
+        let path = std::env::temp_dir().join(format!("dbx-mdbx-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        MdbxAdapter::open(path).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mdbx_adapter_traits() {
+        let adapter = temp_adapter("traits");
+        adapter.set("greeting", "hello").await.unwrap();
+        assert_eq!(
+            adapter.get("greeting").await.unwrap().as_deref(),
+            Some("hello")
+        );
+
+        assert!(adapter.hset_new("user:1", "name", "ada").unwrap());
+        assert!(!adapter.hset_new("user:1", "name", "grace").unwrap());
+        adapter.hset("user:10", "name", "linus").await.unwrap();
+        assert_eq!(adapter.hgetall("user:1").await.unwrap().len(), 1);
+        assert!(adapter.hdel("user:1", "name").await.unwrap());
+        assert!(!adapter.exists("user:1").await.unwrap());
+        assert!(adapter.exists("user:10").await.unwrap());
+
+        assert_eq!(
+            adapter.sadd_many("tags", &["rust", "db", "rust"]).unwrap(),
+            2
+        );
+        assert_eq!(adapter.smembers("tags").await.unwrap(), vec!["db", "rust"]);
+        assert!(adapter.sismember("tags", "db").await.unwrap());
+        assert!(adapter.delete("tags").await.unwrap());
+        assert_eq!(adapter.scard("tags").unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_mdbx_adapter_expiry() {
+        let adapter = temp_adapter("expiry");
+        adapter.set_with_expiry("session", "abc", 60).await.unwrap();
+        assert!(
+            matches!(adapter.ttl("session").unwrap(), Some(Some(ttl)) if ttl <= Duration::from_secs(60))
+        );
+
+        adapter.set_with_expiry("session", "abc", 0).await.unwrap();
+        assert_eq!(adapter.get("session").await.unwrap(), None);
+        assert!(!adapter.delete("session").await.unwrap());
+        // An expired string no longer claims the key
+        assert!(adapter.sadd("session", "abc").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_mdbx_adapter_one_type_per_key() {
+        let adapter = temp_adapter("types");
+        adapter.set("key", "value").await.unwrap();
+        let err = adapter.hset("key", "field", "value").await.unwrap_err();
+        assert_eq!(err.to_string(), AdapterError::from(WrongType).to_string());
+        assert!(adapter.sadd("key", "member").await.is_err());
+
+        adapter.delete("key").await.unwrap();
+        adapter.hset_many("key", &[("field", "value")]).unwrap();
+        assert!(adapter.sadd_many("key", &["member"]).is_err());
+        assert!(adapter.hset_new("key", "other", "value").unwrap());
+
+        // SET replaces the hash, as it does in Redis
+        adapter.set("key", "value").await.unwrap();
+        assert!(adapter.hgetall("key").await.unwrap().is_empty());
+        assert_eq!(adapter.get("key").await.unwrap().as_deref(), Some("value"));
+    }
+}
@@ -0,0 +1,27 @@
+//! MDBX backend
+//!
+//! An embedded, on-disk key-value store built on [libmdbx](https://libmdbx.dqdkfa.ru/).
+//! [`MdbxAdapter`] implements the string, hash and set adapter traits over
+//! named sub-databases, so the same data model the Redis adapter exposes can
+//! be served without a separate database process.
+//!
+//! Building this module needs `libclang`, which `mdbx-sys` uses to generate
+//! its bindings, so it sits behind the opt-in `mdbx` feature.
+
+pub mod adapter;
+
+pub use adapter::MdbxAdapter;
+
+/// URL scheme that selects the MDBX backend, followed by the environment path
+pub const MDBX_URL_SCHEME: &str = "mdbx://";
+
+/// Check whether a connection URL selects the MDBX backend
+pub fn is_mdbx_url(url: &str) -> bool {
+    url.starts_with(MDBX_URL_SCHEME)
+}
+
+/// Get the environment path from an `mdbx://` URL
+pub fn mdbx_path(url: &str) -> Option<&str> {
+    url.strip_prefix(MDBX_URL_SCHEME)
+        .filter(|path| !path.is_empty())
+}
@@ -0,0 +1,370 @@
+//! Adapter trait implementations for the in-memory backend
+//!
+//! [`MemoryAdapter`] works on a [`MemoryStore`] directly, without a network
+//! round trip, which makes it a drop-in backend for tests and for embedding.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::store::{Db, MemoryStore, Value, WrongType};
+use crate::error::{AdapterError, OperationError};
+use crate::traits::{
+    AsyncDatabaseAdapter, DatabaseAdapter, HashAdapter, KeyValueAdapter, SetAdapter,
+    SortedSetAdapter,
+};
+
+impl From<WrongType> for AdapterError {
+    fn from(err: WrongType) -> Self {
+        OperationError::Failed(err.to_string()).into()
+    }
+}
+
+/// Database adapter backed by process memory
+///
+/// Clones share the same data.
+#[derive(Clone, Default)]
+pub struct MemoryAdapter {
+    store: MemoryStore,
+}
+
+impl MemoryAdapter {
+    /// Create an adapter with an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an adapter over an existing store, e.g. one a `MemoryServer` also serves
+    pub fn with_store(store: MemoryStore) -> Self {
+        Self { store }
+    }
+
+    /// Get the underlying store
+    pub fn store(&self) -> &MemoryStore {
+        &self.store
+    }
+
+    /// Run a write against the keyspace and save what it changed
+    fn write<T>(
+        &self,
+        f: impl FnOnce(&mut Db) -> Result<T, AdapterError>,
+    ) -> Result<T, AdapterError> {
+        let mut db = self.store.lock();
+        db.begin_write();
+        let result = f(&mut db);
+        // Whatever the write changed before failing is saved too, or rolled back
+        db.save_changes().map_err(AdapterError::Database)?;
+        result
+    }
+
+    /// Set a time to live on a key, returning whether the key exists
+    pub fn expire(&self, key: &str, ttl: Duration) -> bool {
+        self.store.lock().expire(key, ttl)
+    }
+
+    /// Get the remaining time to live: `None` if the key is missing, `Some(None)` if it never expires
+    pub fn ttl(&self, key: &str) -> Option<Option<Duration>> {
+        self.store.lock().ttl(key)
+    }
+
+    /// Remove the time to live of a key, returning whether it had one
+    pub fn persist(&self, key: &str) -> bool {
+        self.store.lock().persist(key)
+    }
+}
+
+impl DatabaseAdapter for MemoryAdapter {
+    type Error = AdapterError;
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl AsyncDatabaseAdapter for MemoryAdapter {
+    async fn ping(&self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    /// The data lives as long as any clone of the store, so there is nothing to close
+    async fn close(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl KeyValueAdapter for MemoryAdapter {
+    async fn get<K: AsRef<str> + Send>(&self, key: K) -> Result<Option<String>, Self::Error> {
+        let mut db = self.store.lock();
+        Ok(db
+            .string(key.as_ref())?
+            .map(|bytes| String::from_utf8_lossy(bytes).into_owned()))
+    }
+
+    async fn set<K: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        value: V,
+    ) -> Result<(), Self::Error> {
+        self.write(|db| {
+            db.insert(
+                key.as_ref(),
+                Value::String(value.as_ref().as_bytes().to_vec()),
+            );
+            Ok(())
+        })
+    }
+
+    async fn set_with_expiry<K: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        value: V,
+        expiry_seconds: u64,
+    ) -> Result<(), Self::Error> {
+        self.write(|db| {
+            db.insert(
+                key.as_ref(),
+                Value::String(value.as_ref().as_bytes().to_vec()),
+            );
+            db.expire(key.as_ref(), Duration::from_secs(expiry_seconds));
+            Ok(())
+        })
+    }
+
+    async fn delete<K: AsRef<str> + Send>(&self, key: K) -> Result<bool, Self::Error> {
+        self.write(|db| Ok(db.remove(key.as_ref())))
+    }
+
+    async fn exists<K: AsRef<str> + Send>(&self, key: K) -> Result<bool, Self::Error> {
+        Ok(self.store.lock().contains(key.as_ref()))
+    }
+}
+
+#[async_trait]
+impl HashAdapter for MemoryAdapter {
+    async fn hget<K: AsRef<str> + Send, F: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+    ) -> Result<Option<String>, Self::Error> {
+        let mut db = self.store.lock();
+        Ok(db
+            .hash(key.as_ref())?
+            .and_then(|hash| hash.get(field.as_ref()).cloned()))
+    }
+
+    async fn hset<K: AsRef<str> + Send, F: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+        value: V,
+    ) -> Result<(), Self::Error> {
+        self.write(|db| {
+            db.hash_or_create(key.as_ref())?
+                .insert(field.as_ref().to_string(), value.as_ref().to_string());
+            Ok(())
+        })
+    }
+
+    async fn hgetall<K: AsRef<str> + Send>(
+        &self,
+        key: K,
+    ) -> Result<HashMap<String, String>, Self::Error> {
+        let mut db = self.store.lock();
+        Ok(db.hash(key.as_ref())?.cloned().unwrap_or_default())
+    }
+
+    async fn hdel<K: AsRef<str> + Send, F: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+    ) -> Result<bool, Self::Error> {
+        self.write(|db| {
+            let removed = db
+                .hash(key.as_ref())?
+                .is_some_and(|hash| hash.remove(field.as_ref()).is_some());
+            db.remove_if_empty(key.as_ref());
+            Ok(removed)
+        })
+    }
+}
+
+#[async_trait]
+impl SetAdapter for MemoryAdapter {
+    async fn sadd<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        self.write(|db| {
+            Ok(db
+                .set_or_create(key.as_ref())?
+                .insert(member.as_ref().to_string()))
+        })
+    }
+
+    async fn srem<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        self.write(|db| {
+            let removed = db
+                .set(key.as_ref())?
+                .is_some_and(|set| set.remove(member.as_ref()));
+            db.remove_if_empty(key.as_ref());
+            Ok(removed)
+        })
+    }
+
+    async fn sismember<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let mut db = self.store.lock();
+        Ok(db
+            .set(key.as_ref())?
+            .is_some_and(|set| set.contains(member.as_ref())))
+    }
+
+    async fn smembers<K: AsRef<str> + Send>(&self, key: K) -> Result<Vec<String>, Self::Error> {
+        let mut db = self.store.lock();
+        let mut members: Vec<String> = db
+            .set(key.as_ref())?
+            .map(|set| set.iter().cloned().collect())
+            .unwrap_or_default();
+        members.sort();
+        Ok(members)
+    }
+}
+
+#[async_trait]
+impl SortedSetAdapter for MemoryAdapter {
+    async fn zadd<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        score: f64,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        if score.is_nan() {
+            return Err(OperationError::InvalidValue("score is not a number".to_string()).into());
+        }
+        self.write(|db| {
+            Ok(db
+                .zset_or_create(key.as_ref())?
+                .insert(member.as_ref().to_string(), score)
+                .is_none())
+        })
+    }
+
+    async fn zrem<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        self.write(|db| {
+            let removed = db
+                .zset(key.as_ref())?
+                .is_some_and(|zset| zset.remove(member.as_ref()).is_some());
+            db.remove_if_empty(key.as_ref());
+            Ok(removed)
+        })
+    }
+
+    async fn zscore<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<Option<f64>, Self::Error> {
+        let mut db = self.store.lock();
+        Ok(db
+            .zset(key.as_ref())?
+            .and_then(|zset| zset.get(member.as_ref()).copied()))
+    }
+
+    async fn zcard<K: AsRef<str> + Send>(&self, key: K) -> Result<usize, Self::Error> {
+        let mut db = self.store.lock();
+        Ok(db.zset(key.as_ref())?.map_or(0, |zset| zset.len()))
+    }
+
+    async fn zrange<K: AsRef<str> + Send>(
+        &self,
+        key: K,
+        start: isize,
+        stop: isize,
+    ) -> Result<Vec<(String, f64)>, Self::Error> {
+        let mut db = self.store.lock();
+        let Some(zset) = db.zset(key.as_ref())? else {
+            return Ok(Vec::new());
+        };
+        let mut members: Vec<(String, f64)> = zset
+            .iter()
+            .map(|(member, score)| (member.clone(), *score))
+            .collect();
+        members.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+        let len = members.len() as isize;
+        let start = if start < 0 {
+            (len + start).max(0)
+        } else {
+            start
+        };
+        let stop = if stop < 0 {
+            len + stop
+        } else {
+            stop.min(len - 1)
+        };
+        if start > stop || start >= len {
+            return Ok(Vec::new());
+        }
+        Ok(members[start as usize..=stop as usize].to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_adapter_traits() {
+        let adapter = MemoryAdapter::new();
+        adapter.set("greeting", "hello").await.unwrap();
+        assert_eq!(
+            adapter.get("greeting").await.unwrap().as_deref(),
+            Some("hello")
+        );
+
+        adapter.hset("user:1", "name", "ada").await.unwrap();
+        assert!(adapter.hdel("user:1", "name").await.unwrap());
+        assert!(!adapter.exists("user:1").await.unwrap());
+
+        assert!(adapter.sadd("tags", "rust").await.unwrap());
+        assert!(matches!(
+            adapter.hget("tags", "rust").await,
+            Err(AdapterError::Operation(OperationError::Failed(_)))
+        ));
+
+        adapter.zadd("board", 2.0, "bob").await.unwrap();
+        adapter.zadd("board", 1.0, "alice").await.unwrap();
+        assert_eq!(
+            adapter.zrange("board", 0, -1).await.unwrap(),
+            vec![("alice".to_string(), 1.0), ("bob".to_string(), 2.0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_adapter_expiry() {
+        let adapter = MemoryAdapter::new();
+        adapter.set_with_expiry("session", "abc", 60).await.unwrap();
+        assert!(
+            matches!(adapter.ttl("session"), Some(Some(ttl)) if ttl <= Duration::from_secs(60))
+        );
+        assert!(adapter.persist("session"));
+        assert_eq!(adapter.ttl("session"), Some(None));
+
+        adapter.expire("session", Duration::ZERO);
+        assert_eq!(adapter.get("session").await.unwrap(), None);
+    }
+}
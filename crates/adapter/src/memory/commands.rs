@@ -0,0 +1,2185 @@
+//! Redis command implementations for the in-memory server
+//!
+//! Covers the string, bitmap, key, hash, set, sorted set, list and server commands
+//! used by the adapter primitives. Transactions and pub/sub need connection
+//! state and are handled by the server itself; Lua scripting is not supported.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::glob::glob_match;
+use super::resp::Reply;
+use super::store::{Db, Value, WrongType, WRONGTYPE};
+
+type CommandResult = Result<Reply, Reply>;
+
+const NOT_AN_INTEGER: &str = "ERR value is not an integer or out of range";
+const NOT_A_FLOAT: &str = "ERR value is not a valid float";
+const SYNTAX_ERROR: &str = "ERR syntax error";
+
+impl From<WrongType> for Reply {
+    fn from(_: WrongType) -> Self {
+        Reply::error(WRONGTYPE)
+    }
+}
+
+/// Arguments of a command, without the command name
+struct Args<'a> {
+    name: &'a str,
+    items: &'a [Vec<u8>],
+}
+
+impl<'a> Args<'a> {
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks the argument count is within `min..=max`
+    fn arity(&self, min: usize, max: Option<usize>) -> Result<(), Reply> {
+        if self.len() < min || max.is_some_and(|max| self.len() > max) {
+            return Err(Reply::error(format!(
+                "ERR wrong number of arguments for '{}' command",
+                self.name.to_ascii_lowercase()
+            )));
+        }
+        Ok(())
+    }
+
+    fn bytes(&self, i: usize) -> &'a [u8] {
+        &self.items[i]
+    }
+
+    fn str(&self, i: usize) -> String {
+        String::from_utf8_lossy(&self.items[i]).into_owned()
+    }
+
+    fn strs(&self, from: usize) -> Vec<String> {
+        (from..self.len()).map(|i| self.str(i)).collect()
+    }
+
+    fn upper(&self, i: usize) -> String {
+        self.str(i).to_ascii_uppercase()
+    }
+
+    fn int(&self, i: usize) -> Result<i64, Reply> {
+        parse_int(self.bytes(i)).ok_or_else(|| Reply::error(NOT_AN_INTEGER))
+    }
+
+    fn float(&self, i: usize) -> Result<f64, Reply> {
+        parse_float(self.bytes(i)).ok_or_else(|| Reply::error(NOT_A_FLOAT))
+    }
+}
+
+fn parse_int(bytes: &[u8]) -> Option<i64> {
+    std::str::from_utf8(bytes).ok()?.parse().ok()
+}
+
+fn parse_float(bytes: &[u8]) -> Option<f64> {
+    std::str::from_utf8(bytes)
+        .ok()?
+        .parse::<f64>()
+        .ok()
+        .filter(|f| !f.is_nan())
+}
+
+fn format_float(value: f64) -> String {
+    if value.is_infinite() {
+        if value > 0.0 { "inf" } else { "-inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Resolves an inclusive, possibly negative index range against `len`
+fn normalize_range(start: i64, end: i64, len: usize) -> Option<(usize, usize)> {
+    let len = len as i64;
+    let start = if start < 0 {
+        (len + start).max(0)
+    } else {
+        start
+    };
+    let end = if end < 0 { len + end } else { end.min(len - 1) };
+    if start > end || start >= len {
+        None
+    } else {
+        Some((start as usize, end as usize))
+    }
+}
+
+/// Runs one command against the keyspace
+pub fn execute(db: &mut Db, args: &[Vec<u8>]) -> Reply {
+    db.stats.commands_processed += 1;
+    let Some(name) = args.first() else {
+        return Reply::error("ERR empty command");
+    };
+    let name = String::from_utf8_lossy(name).to_ascii_uppercase();
+    let args = Args {
+        name: &name,
+        items: &args[1..],
+    };
+    let write = is_write(&name);
+    if write {
+        db.begin_write();
+    }
+    let result = match name.as_str() {
+        // Strings
+        "GET" => get(db, &args),
+        "SET" => set(db, &args),
+        "SETEX" => setex(db, &args, Duration::from_secs),
+        "PSETEX" => setex(db, &args, Duration::from_millis),
+        "SETNX" => setnx(db, &args),
+        "GETDEL" => getdel(db, &args),
+        "MGET" => mget(db, &args),
+        "MSET" => mset(db, &args),
+        "INCR" => incr_by(db, &args, 1, false),
+        "DECR" => incr_by(db, &args, -1, false),
+        "INCRBY" => incr_by(db, &args, 1, true),
+        "DECRBY" => incr_by(db, &args, -1, true),
+        "INCRBYFLOAT" => incr_by_float(db, &args),
+        "APPEND" => append(db, &args),
+        "STRLEN" => strlen(db, &args),
+        "GETRANGE" => getrange(db, &args),
+        "SETRANGE" => setrange(db, &args),
+        // Bitmaps
+        "SETBIT" => setbit(db, &args),
+        "GETBIT" => getbit(db, &args),
+        "BITCOUNT" => bitcount(db, &args),
+        "BITPOS" => bitpos(db, &args),
+        "BITOP" => bitop(db, &args),
+        // Keys
+        "DEL" | "UNLINK" => del(db, &args),
+        "EXISTS" => exists(db, &args),
+        "EXPIRE" => expire(db, &args, Duration::from_secs),
+        "PEXPIRE" => expire(db, &args, Duration::from_millis),
+        "PERSIST" => persist(db, &args),
+        "TTL" => ttl(db, &args, false),
+        "PTTL" => ttl(db, &args, true),
+        "KEYS" => keys(db, &args),
+        "SCAN" => scan(db, &args),
+        "TYPE" => key_type(db, &args),
+        "RENAME" => rename(db, &args),
+        // Hashes
+        "HSET" => hset(db, &args, false),
+        "HMSET" => hset(db, &args, true),
+        "HSETNX" => hsetnx(db, &args),
+        "HGET" => hget(db, &args),
+        "HMGET" => hmget(db, &args),
+        "HGETALL" => hgetall(db, &args),
+        "HKEYS" => hkeys(db, &args, false),
+        "HVALS" => hkeys(db, &args, true),
+        "HLEN" => hlen(db, &args),
+        "HEXISTS" => hexists(db, &args),
+        "HDEL" => hdel(db, &args),
+        "HINCRBY" => hincrby(db, &args),
+        "HINCRBYFLOAT" => hincrbyfloat(db, &args),
+        "HSTRLEN" => hstrlen(db, &args),
+        "HRANDFIELD" => hrandfield(db, &args),
+        "HSCAN" => hscan(db, &args),
+        // Sets
+        "SADD" => sadd(db, &args),
+        "SREM" => srem(db, &args),
+        "SMEMBERS" => smembers(db, &args),
+        "SISMEMBER" => sismember(db, &args),
+        "SMISMEMBER" => smismember(db, &args),
+        "SCARD" => scard(db, &args),
+        "SPOP" => spop(db, &args),
+        "SRANDMEMBER" => srandmember(db, &args),
+        "SMOVE" => smove(db, &args),
+        "SDIFF" => set_algebra(db, &args, SetOp::Diff, false),
+        "SINTER" => set_algebra(db, &args, SetOp::Inter, false),
+        "SUNION" => set_algebra(db, &args, SetOp::Union, false),
+        "SDIFFSTORE" => set_algebra(db, &args, SetOp::Diff, true),
+        "SINTERSTORE" => set_algebra(db, &args, SetOp::Inter, true),
+        "SUNIONSTORE" => set_algebra(db, &args, SetOp::Union, true),
+        "SSCAN" => sscan(db, &args),
+        // Sorted sets
+        "ZADD" => zadd(db, &args),
+        "ZREM" => zrem(db, &args),
+        "ZSCORE" => zscore(db, &args),
+        "ZMSCORE" => zmscore(db, &args),
+        "ZINCRBY" => zincrby(db, &args),
+        "ZCARD" => zcard(db, &args),
+        "ZCOUNT" => zcount(db, &args),
+        "ZRANK" => zrank(db, &args, false),
+        "ZREVRANK" => zrank(db, &args, true),
+        "ZRANGE" => zrange(db, &args, false),
+        "ZREVRANGE" => zrange(db, &args, true),
+        "ZRANGEBYSCORE" => zrangebyscore(db, &args, false),
+        "ZREVRANGEBYSCORE" => zrangebyscore(db, &args, true),
+        "ZREMRANGEBYRANK" => zremrangebyrank(db, &args),
+        "ZREMRANGEBYSCORE" => zremrangebyscore(db, &args),
+        "ZINTERSTORE" => zstore(db, &args, true),
+        "ZUNIONSTORE" => zstore(db, &args, false),
+        "ZPOPMIN" => zpop(db, &args, false),
+        "ZPOPMAX" => zpop(db, &args, true),
+        // Lists
+        "LPUSH" => push(db, &args, true, false),
+        "RPUSH" => push(db, &args, false, false),
+        "LPUSHX" => push(db, &args, true, true),
+        "RPUSHX" => push(db, &args, false, true),
+        "LPOP" => pop(db, &args, true),
+        "RPOP" => pop(db, &args, false),
+        "LLEN" => llen(db, &args),
+        "LRANGE" => lrange(db, &args),
+        "LINDEX" => lindex(db, &args),
+        "LSET" => lset(db, &args),
+        "LTRIM" => ltrim(db, &args),
+        "LREM" => lrem(db, &args),
+        "LINSERT" => linsert(db, &args),
+        "LMOVE" => lmove(db, &args),
+        "RPOPLPUSH" => rpoplpush(db, &args),
+        // Server
+        "PING" => ping(&args),
+        "ECHO" => echo(&args),
+        "SELECT" => select(&args),
+        "DBSIZE" => Ok(Reply::Integer(db.len() as i64)),
+        "FLUSHDB" | "FLUSHALL" => {
+            db.clear();
+            Ok(Reply::ok())
+        }
+        "TIME" => time(),
+        "INFO" => info(db, &args),
+        "CONFIG" => config(db, &args),
+        "CLIENT" => client(&args),
+        "COMMAND" => Ok(Reply::Array(Vec::new())),
+        "EVAL" | "EVALSHA" | "EVAL_RO" | "EVALSHA_RO" | "SCRIPT" | "FUNCTION" | "FCALL" => Err(
+            Reply::error("ERR scripting is not supported by the in-memory backend"),
+        ),
+        _ => Err(Reply::error(format!(
+            "ERR unknown command '{}', with args beginning with: {}",
+            name.to_ascii_lowercase(),
+            args.strs(0)
+                .iter()
+                .map(|arg| format!("'{arg}'"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ))),
+    };
+    let reply = result.unwrap_or_else(|error| error);
+
+    if !write {
+        db.discard_changes();
+        return reply;
+    }
+    // A write that could not be saved has been undone
+    match db.save_changes() {
+        Ok(()) => reply,
+        Err(e) => Reply::error(format!("ERR failed to persist changes: {e}")),
+    }
+}
+
+/// Whether a command may change the keyspace, so its changes must be persisted
+fn is_write(name: &str) -> bool {
+    matches!(
+        name,
+        "SET"
+            | "SETEX"
+            | "PSETEX"
+            | "SETNX"
+            | "GETDEL"
+            | "MSET"
+            | "INCR"
+            | "DECR"
+            | "INCRBY"
+            | "DECRBY"
+            | "INCRBYFLOAT"
+            | "APPEND"
+            | "SETRANGE"
+            | "SETBIT"
+            | "BITOP"
+            | "DEL"
+            | "UNLINK"
+            | "EXPIRE"
+            | "PEXPIRE"
+            | "PERSIST"
+            | "RENAME"
+            | "HSET"
+            | "HMSET"
+            | "HSETNX"
+            | "HDEL"
+            | "HINCRBY"
+            | "HINCRBYFLOAT"
+            | "SADD"
+            | "SREM"
+            | "SPOP"
+            | "SMOVE"
+            | "SDIFFSTORE"
+            | "SINTERSTORE"
+            | "SUNIONSTORE"
+            | "ZADD"
+            | "ZREM"
+            | "ZINCRBY"
+            | "ZREMRANGEBYRANK"
+            | "ZREMRANGEBYSCORE"
+            | "ZINTERSTORE"
+            | "ZUNIONSTORE"
+            | "ZPOPMIN"
+            | "ZPOPMAX"
+            | "LPUSH"
+            | "RPUSH"
+            | "LPUSHX"
+            | "RPUSHX"
+            | "LPOP"
+            | "RPOP"
+            | "LSET"
+            | "LTRIM"
+            | "LREM"
+            | "LINSERT"
+            | "LMOVE"
+            | "RPOPLPUSH"
+            | "FLUSHDB"
+            | "FLUSHALL"
+    )
+}
+
+// =========================
+// Strings
+// =========================
+
+fn get(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::from_option(db.string(&args.str(0))?.cloned()))
+}
+
+fn set(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    let key = args.str(0);
+    let mut ttl = None;
+    let (mut nx, mut xx, mut keep_ttl, mut return_old) = (false, false, false, false);
+    let mut i = 2;
+    while i < args.len() {
+        match args.upper(i).as_str() {
+            "NX" => nx = true,
+            "XX" => xx = true,
+            "KEEPTTL" => keep_ttl = true,
+            "GET" => return_old = true,
+            unit @ ("EX" | "PX") if i + 1 < args.len() => {
+                let amount = args.int(i + 1)?;
+                if amount <= 0 {
+                    return Err(Reply::error("ERR invalid expire time in 'set' command"));
+                }
+                ttl = Some(if unit == "EX" {
+                    Duration::from_secs(amount as u64)
+                } else {
+                    Duration::from_millis(amount as u64)
+                });
+                i += 1;
+            }
+            _ => return Err(Reply::error(SYNTAX_ERROR)),
+        }
+        i += 1;
+    }
+    if nx && xx {
+        return Err(Reply::error(SYNTAX_ERROR));
+    }
+
+    let old = if return_old {
+        db.string(&key)?.cloned()
+    } else {
+        None
+    };
+    let exists = db.contains(&key);
+    if (nx && exists) || (xx && !exists) {
+        return Ok(if return_old {
+            Reply::from_option(old)
+        } else {
+            Reply::Nil
+        });
+    }
+    let kept_ttl = if keep_ttl {
+        db.ttl(&key).flatten()
+    } else {
+        None
+    };
+    db.insert(&key, Value::String(args.bytes(1).to_vec()));
+    if let Some(ttl) = ttl.or(kept_ttl) {
+        db.expire(&key, ttl);
+    }
+    Ok(if return_old {
+        Reply::from_option(old)
+    } else {
+        Reply::ok()
+    })
+}
+
+fn setex(db: &mut Db, args: &Args, unit: fn(u64) -> Duration) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let amount = args.int(1)?;
+    if amount <= 0 {
+        return Err(Reply::error(format!(
+            "ERR invalid expire time in '{}' command",
+            args.name.to_ascii_lowercase()
+        )));
+    }
+    let key = args.str(0);
+    db.insert(&key, Value::String(args.bytes(2).to_vec()));
+    db.expire(&key, unit(amount as u64));
+    Ok(Reply::ok())
+}
+
+fn setnx(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let key = args.str(0);
+    if db.contains(&key) {
+        return Ok(Reply::bool(false));
+    }
+    db.insert(&key, Value::String(args.bytes(1).to_vec()));
+    Ok(Reply::bool(true))
+}
+
+fn getdel(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    let key = args.str(0);
+    let value = db.string(&key)?.cloned();
+    db.remove(&key);
+    Ok(Reply::from_option(value))
+}
+
+fn mget(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, None)?;
+    let values = (0..args.len())
+        .map(|i| match db.value(&args.str(i)) {
+            Some(Value::String(bytes)) => Reply::bulk(bytes.clone()),
+            _ => Reply::Nil,
+        })
+        .collect();
+    Ok(Reply::Array(values))
+}
+
+fn mset(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    if !args.len().is_multiple_of(2) {
+        return Err(Reply::error(
+            "ERR wrong number of arguments for 'mset' command",
+        ));
+    }
+    for i in (0..args.len()).step_by(2) {
+        db.insert(&args.str(i), Value::String(args.bytes(i + 1).to_vec()));
+    }
+    Ok(Reply::ok())
+}
+
+fn incr_by(db: &mut Db, args: &Args, sign: i64, with_amount: bool) -> CommandResult {
+    let arity = if with_amount { 2 } else { 1 };
+    args.arity(arity, Some(arity))?;
+    let amount = if with_amount { args.int(1)? } else { 1 };
+    let value = db.string_or_create(&args.str(0))?;
+    let current = if value.is_empty() {
+        0
+    } else {
+        parse_int(value).ok_or_else(|| Reply::error(NOT_AN_INTEGER))?
+    };
+    let updated = amount
+        .checked_mul(sign)
+        .and_then(|delta| current.checked_add(delta))
+        .ok_or_else(|| Reply::error("ERR increment or decrement would overflow"))?;
+    *value = updated.to_string().into_bytes();
+    Ok(Reply::Integer(updated))
+}
+
+fn incr_by_float(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let amount = args.float(1)?;
+    let value = db.string_or_create(&args.str(0))?;
+    let current = if value.is_empty() {
+        0.0
+    } else {
+        parse_float(value).ok_or_else(|| Reply::error(NOT_A_FLOAT))?
+    };
+    let updated = current + amount;
+    if !updated.is_finite() {
+        return Err(Reply::error("ERR increment would produce NaN or Infinity"));
+    }
+    *value = format_float(updated).into_bytes();
+    Ok(Reply::bulk(value.clone()))
+}
+
+fn append(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let value = db.string_or_create(&args.str(0))?;
+    value.extend_from_slice(args.bytes(1));
+    Ok(Reply::Integer(value.len() as i64))
+}
+
+fn strlen(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::Integer(
+        db.string(&args.str(0))?.map_or(0, |value| value.len()) as i64,
+    ))
+}
+
+fn getrange(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let (start, end) = (args.int(1)?, args.int(2)?);
+    let Some(value) = db.string(&args.str(0))? else {
+        return Ok(Reply::bulk(Vec::new()));
+    };
+    Ok(Reply::bulk(
+        match normalize_range(start, end, value.len()) {
+            Some((start, end)) => value[start..=end].to_vec(),
+            None => Vec::new(),
+        },
+    ))
+}
+
+fn setrange(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let offset =
+        usize::try_from(args.int(1)?).map_err(|_| Reply::error("ERR offset is out of range"))?;
+    let patch = args.bytes(2);
+    let value = db.string_or_create(&args.str(0))?;
+    if !patch.is_empty() {
+        if value.len() < offset + patch.len() {
+            value.resize(offset + patch.len(), 0);
+        }
+        value[offset..offset + patch.len()].copy_from_slice(patch);
+    }
+    let len = value.len();
+    db.remove_if_empty(&args.str(0));
+    Ok(Reply::Integer(len as i64))
+}
+
+// =========================
+// Bitmaps
+// =========================
+
+fn bit_at(bytes: &[u8], offset: usize) -> bool {
+    bytes
+        .get(offset / 8)
+        .is_some_and(|byte| byte & (0x80 >> (offset % 8)) != 0)
+}
+
+fn setbit(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let offset = usize::try_from(args.int(1)?)
+        .map_err(|_| Reply::error("ERR bit offset is not an integer or out of range"))?;
+    let on = match args.bytes(2) {
+        b"1" => true,
+        b"0" => false,
+        _ => return Err(Reply::error("ERR bit is not an integer or out of range")),
+    };
+    let value = db.string_or_create(&args.str(0))?;
+    if value.len() <= offset / 8 {
+        value.resize(offset / 8 + 1, 0);
+    }
+    let old = bit_at(value, offset);
+    let mask = 0x80 >> (offset % 8);
+    if on {
+        value[offset / 8] |= mask;
+    } else {
+        value[offset / 8] &= !mask;
+    }
+    Ok(Reply::bool(old))
+}
+
+fn getbit(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let offset = usize::try_from(args.int(1)?)
+        .map_err(|_| Reply::error("ERR bit offset is not an integer or out of range"))?;
+    let value = db.string(&args.str(0))?;
+    Ok(Reply::bool(
+        value.is_some_and(|value| bit_at(value, offset)),
+    ))
+}
+
+/// Parses an optional trailing BYTE / BIT unit, returning whether ranges are in bits
+fn bit_unit(args: &Args, i: usize) -> Result<bool, Reply> {
+    if i >= args.len() {
+        return Ok(false);
+    }
+    match args.upper(i).as_str() {
+        "BYTE" => Ok(false),
+        "BIT" => Ok(true),
+        _ => Err(Reply::error(SYNTAX_ERROR)),
+    }
+}
+
+fn bitcount(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(4))?;
+    if args.len() == 2 {
+        return Err(Reply::error(SYNTAX_ERROR));
+    }
+    let range = if args.len() >= 3 {
+        Some((args.int(1)?, args.int(2)?, bit_unit(args, 3)?))
+    } else {
+        None
+    };
+    let Some(value) = db.string(&args.str(0))? else {
+        return Ok(Reply::Integer(0));
+    };
+    let count = match range {
+        None => value.iter().map(|b| b.count_ones() as usize).sum(),
+        Some((start, end, false)) => match normalize_range(start, end, value.len()) {
+            Some((start, end)) => value[start..=end]
+                .iter()
+                .map(|b| b.count_ones() as usize)
+                .sum(),
+            None => 0,
+        },
+        Some((start, end, true)) => match normalize_range(start, end, value.len() * 8) {
+            Some((start, end)) => (start..=end).filter(|&bit| bit_at(value, bit)).count(),
+            None => 0,
+        },
+    };
+    Ok(Reply::Integer(count as i64))
+}
+
+fn bitpos(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(5))?;
+    let wanted = match args.bytes(1) {
+        b"1" => true,
+        b"0" => false,
+        _ => return Err(Reply::error("ERR The bit argument must be 1 or 0.")),
+    };
+    let start = if args.len() > 2 { args.int(2)? } else { 0 };
+    let end_given = args.len() > 3;
+    let end = if end_given { args.int(3)? } else { -1 };
+    let in_bits = bit_unit(args, 4)?;
+
+    let Some(value) = db.string(&args.str(0))? else {
+        return Ok(Reply::Integer(if wanted { -1 } else { 0 }));
+    };
+    let total_bits = value.len() * 8;
+    let bit_range = if in_bits {
+        normalize_range(start, end, total_bits)
+    } else {
+        normalize_range(start, end, value.len()).map(|(start, end)| (start * 8, end * 8 + 7))
+    };
+    let Some((first, last)) = bit_range else {
+        return Ok(Reply::Integer(-1));
+    };
+    if let Some(pos) = (first..=last).find(|&bit| bit_at(value, bit) == wanted) {
+        return Ok(Reply::Integer(pos as i64));
+    }
+    // Looking for a clear bit without an end treats the string as padded with zeros
+    if !wanted && !end_given {
+        return Ok(Reply::Integer(total_bits as i64));
+    }
+    Ok(Reply::Integer(-1))
+}
+
+fn bitop(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, None)?;
+    let op = args.upper(0);
+    if op == "NOT" && args.len() != 3 {
+        return Err(Reply::error(
+            "ERR BITOP NOT must be called with a single source key.",
+        ));
+    }
+    let mut sources = Vec::new();
+    for i in 2..args.len() {
+        sources.push(db.string(&args.str(i))?.cloned().unwrap_or_default());
+    }
+    let len = sources.iter().map(Vec::len).max().unwrap_or(0);
+    let byte = |source: &Vec<u8>, i: usize| source.get(i).copied().unwrap_or(0);
+    let result: Vec<u8> = (0..len)
+        .map(|i| {
+            let mut bytes = sources.iter().map(|source| byte(source, i));
+            match op.as_str() {
+                "AND" => bytes.fold(0xff, |acc, b| acc & b),
+                "OR" => bytes.fold(0, |acc, b| acc | b),
+                "XOR" => bytes.fold(0, |acc, b| acc ^ b),
+                _ => !bytes.next().unwrap_or(0),
+            }
+        })
+        .collect();
+    if !matches!(op.as_str(), "AND" | "OR" | "XOR" | "NOT") {
+        return Err(Reply::error(SYNTAX_ERROR));
+    }
+
+    let dest = args.str(1);
+    if result.is_empty() {
+        db.remove(&dest);
+    } else {
+        db.insert(&dest, Value::String(result));
+    }
+    Ok(Reply::Integer(len as i64))
+}
+
+// =========================
+// Keys
+// =========================
+
+fn del(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, None)?;
+    let removed = (0..args.len()).filter(|&i| db.remove(&args.str(i))).count();
+    Ok(Reply::Integer(removed as i64))
+}
+
+fn exists(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, None)?;
+    let found = (0..args.len())
+        .filter(|&i| db.contains(&args.str(i)))
+        .count();
+    Ok(Reply::Integer(found as i64))
+}
+
+fn expire(db: &mut Db, args: &Args, unit: fn(u64) -> Duration) -> CommandResult {
+    args.arity(2, Some(3))?;
+    let key = args.str(0);
+    let amount = args.int(1)?;
+    // A non-positive time to live deletes the key right away
+    if amount <= 0 {
+        return Ok(Reply::bool(db.remove(&key)));
+    }
+    Ok(Reply::bool(db.expire(&key, unit(amount as u64))))
+}
+
+fn persist(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::bool(db.persist(&args.str(0))))
+}
+
+fn ttl(db: &mut Db, args: &Args, millis: bool) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::Integer(match db.ttl(&args.str(0)) {
+        None => -2,
+        Some(None) => -1,
+        Some(Some(left)) if millis => left.as_millis() as i64,
+        Some(Some(left)) => ((left.as_millis() + 500) / 1000) as i64,
+    }))
+}
+
+fn keys(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::strings(db.keys(&args.str(0))))
+}
+
+/// Parses the MATCH / COUNT options of the SCAN family starting at `i`
+fn scan_pattern(args: &Args, mut i: usize) -> Result<String, Reply> {
+    let mut pattern = "*".to_string();
+    while i < args.len() {
+        match args.upper(i).as_str() {
+            "MATCH" if i + 1 < args.len() => pattern = args.str(i + 1),
+            "COUNT" if i + 1 < args.len() => {
+                args.int(i + 1)?;
+            }
+            "TYPE" if i + 1 < args.len() => {}
+            _ => return Err(Reply::error(SYNTAX_ERROR)),
+        }
+        i += 2;
+    }
+    Ok(pattern)
+}
+
+/// Cursor replies always return everything in one page
+fn scan_reply(items: Vec<Reply>) -> Reply {
+    Reply::Array(vec![Reply::bulk("0"), Reply::Array(items)])
+}
+
+fn scan(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, None)?;
+    let pattern = scan_pattern(args, 1)?;
+    Ok(scan_reply(
+        db.keys(&pattern).into_iter().map(Reply::bulk).collect(),
+    ))
+}
+
+fn key_type(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::Status(
+        db.value(&args.str(0))
+            .map_or("none", |value| value.type_name())
+            .to_string(),
+    ))
+}
+
+fn rename(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let (from, to) = (args.str(0), args.str(1));
+    let ttl = db.ttl(&from).flatten();
+    let value = db
+        .take(&from)
+        .ok_or_else(|| Reply::error("ERR no such key"))?;
+    db.insert(&to, value);
+    if let Some(ttl) = ttl {
+        db.expire(&to, ttl);
+    }
+    Ok(Reply::ok())
+}
+
+// =========================
+// Hashes
+// =========================
+
+fn sorted_fields(hash: &HashMap<String, String>) -> Vec<(&String, &String)> {
+    let mut fields: Vec<_> = hash.iter().collect();
+    fields.sort();
+    fields
+}
+
+fn hset(db: &mut Db, args: &Args, legacy: bool) -> CommandResult {
+    args.arity(3, None)?;
+    if args.len() % 2 != 1 {
+        return Err(Reply::error(format!(
+            "ERR wrong number of arguments for '{}' command",
+            args.name.to_ascii_lowercase()
+        )));
+    }
+    let hash = db.hash_or_create(&args.str(0))?;
+    let mut added = 0;
+    for i in (1..args.len()).step_by(2) {
+        if hash.insert(args.str(i), args.str(i + 1)).is_none() {
+            added += 1;
+        }
+    }
+    Ok(if legacy {
+        Reply::ok()
+    } else {
+        Reply::Integer(added)
+    })
+}
+
+fn hsetnx(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let hash = db.hash_or_create(&args.str(0))?;
+    let field = args.str(1);
+    if hash.contains_key(&field) {
+        return Ok(Reply::bool(false));
+    }
+    hash.insert(field, args.str(2));
+    Ok(Reply::bool(true))
+}
+
+fn hget(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let hash = db.hash(&args.str(0))?;
+    Ok(Reply::from_option(
+        hash.and_then(|hash| hash.get(&args.str(1)).cloned()),
+    ))
+}
+
+fn hmget(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    let hash = db.hash(&args.str(0))?;
+    Ok(Reply::Array(
+        (1..args.len())
+            .map(|i| {
+                Reply::from_option(
+                    hash.as_ref()
+                        .and_then(|hash| hash.get(&args.str(i)).cloned()),
+                )
+            })
+            .collect(),
+    ))
+}
+
+fn hgetall(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    let Some(hash) = db.hash(&args.str(0))? else {
+        return Ok(Reply::Array(Vec::new()));
+    };
+    Ok(Reply::strings(
+        sorted_fields(hash)
+            .into_iter()
+            .flat_map(|(field, value)| [field.clone(), value.clone()]),
+    ))
+}
+
+fn hkeys(db: &mut Db, args: &Args, values: bool) -> CommandResult {
+    args.arity(1, Some(1))?;
+    let Some(hash) = db.hash(&args.str(0))? else {
+        return Ok(Reply::Array(Vec::new()));
+    };
+    Ok(Reply::strings(sorted_fields(hash).into_iter().map(
+        |(field, value)| {
+            if values {
+                value.clone()
+            } else {
+                field.clone()
+            }
+        },
+    )))
+}
+
+fn hlen(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::Integer(
+        db.hash(&args.str(0))?.map_or(0, |hash| hash.len()) as i64,
+    ))
+}
+
+fn hexists(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let hash = db.hash(&args.str(0))?;
+    Ok(Reply::bool(
+        hash.is_some_and(|hash| hash.contains_key(&args.str(1))),
+    ))
+}
+
+fn hdel(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    let key = args.str(0);
+    let Some(hash) = db.hash(&key)? else {
+        return Ok(Reply::Integer(0));
+    };
+    let removed = (1..args.len())
+        .filter(|&i| hash.remove(&args.str(i)).is_some())
+        .count();
+    db.remove_if_empty(&key);
+    Ok(Reply::Integer(removed as i64))
+}
+
+fn hincrby(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let amount = args.int(2)?;
+    let hash = db.hash_or_create(&args.str(0))?;
+    let value = hash.entry(args.str(1)).or_insert_with(|| "0".to_string());
+    let current: i64 = value
+        .parse()
+        .map_err(|_| Reply::error("ERR hash value is not an integer"))?;
+    let updated = current
+        .checked_add(amount)
+        .ok_or_else(|| Reply::error("ERR increment or decrement would overflow"))?;
+    *value = updated.to_string();
+    Ok(Reply::Integer(updated))
+}
+
+fn hincrbyfloat(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let amount = args.float(2)?;
+    let hash = db.hash_or_create(&args.str(0))?;
+    let value = hash.entry(args.str(1)).or_insert_with(|| "0".to_string());
+    let current: f64 = value
+        .parse()
+        .map_err(|_| Reply::error("ERR hash value is not a float"))?;
+    let updated = current + amount;
+    if !updated.is_finite() {
+        return Err(Reply::error("ERR increment would produce NaN or Infinity"));
+    }
+    *value = format_float(updated);
+    Ok(Reply::bulk(value.clone()))
+}
+
+fn hstrlen(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let hash = db.hash(&args.str(0))?;
+    Ok(Reply::Integer(
+        hash.and_then(|hash| hash.get(&args.str(1)).map(String::len))
+            .unwrap_or(0) as i64,
+    ))
+}
+
+/// Picks `count` items like HRANDFIELD / SRANDMEMBER: distinct when positive,
+/// possibly repeated when negative
+fn pick<T: Clone>(items: Vec<T>, count: i64) -> Vec<T> {
+    if items.is_empty() {
+        return Vec::new();
+    }
+    if count >= 0 {
+        items.into_iter().take(count as usize).collect()
+    } else {
+        items
+            .iter()
+            .cycle()
+            .take(count.unsigned_abs() as usize)
+            .cloned()
+            .collect()
+    }
+}
+
+fn hrandfield(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(3))?;
+    let count = if args.len() > 1 {
+        Some(args.int(1)?)
+    } else {
+        None
+    };
+    let with_values = match args.len() {
+        3 if args.upper(2) == "WITHVALUES" => true,
+        3 => return Err(Reply::error(SYNTAX_ERROR)),
+        _ => false,
+    };
+    // HashMap iteration order is randomized per map, which is random enough here
+    let fields: Vec<(String, String)> = db
+        .hash(&args.str(0))?
+        .map(|hash| hash.iter().map(|(f, v)| (f.clone(), v.clone())).collect())
+        .unwrap_or_default();
+    let Some(count) = count else {
+        return Ok(Reply::from_option(
+            fields.into_iter().next().map(|(f, _)| f),
+        ));
+    };
+    let picked = pick(fields, count);
+    Ok(if with_values {
+        Reply::strings(picked.into_iter().flat_map(|(f, v)| [f, v]))
+    } else {
+        Reply::strings(picked.into_iter().map(|(f, _)| f))
+    })
+}
+
+fn hscan(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    let pattern = scan_pattern(args, 2)?;
+    let Some(hash) = db.hash(&args.str(0))? else {
+        return Ok(scan_reply(Vec::new()));
+    };
+    Ok(scan_reply(
+        sorted_fields(hash)
+            .into_iter()
+            .filter(|(field, _)| glob_match(&pattern, field))
+            .flat_map(|(field, value)| [Reply::bulk(field.clone()), Reply::bulk(value.clone())])
+            .collect(),
+    ))
+}
+
+// =========================
+// Sets
+// =========================
+
+fn sorted_members(set: &HashSet<String>) -> Vec<String> {
+    let mut members: Vec<String> = set.iter().cloned().collect();
+    members.sort();
+    members
+}
+
+fn sadd(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    let set = db.set_or_create(&args.str(0))?;
+    let added = (1..args.len()).filter(|&i| set.insert(args.str(i))).count();
+    Ok(Reply::Integer(added as i64))
+}
+
+fn srem(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    let key = args.str(0);
+    let Some(set) = db.set(&key)? else {
+        return Ok(Reply::Integer(0));
+    };
+    let removed = (1..args.len())
+        .filter(|&i| set.remove(&args.str(i)))
+        .count();
+    db.remove_if_empty(&key);
+    Ok(Reply::Integer(removed as i64))
+}
+
+fn smembers(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    let members = db.set(&args.str(0))?.map(|set| sorted_members(set));
+    Ok(Reply::strings(members.unwrap_or_default()))
+}
+
+fn sismember(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let set = db.set(&args.str(0))?;
+    Ok(Reply::bool(
+        set.is_some_and(|set| set.contains(&args.str(1))),
+    ))
+}
+
+fn smismember(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    let set = db.set(&args.str(0))?;
+    Ok(Reply::Array(
+        (1..args.len())
+            .map(|i| Reply::bool(set.as_ref().is_some_and(|set| set.contains(&args.str(i)))))
+            .collect(),
+    ))
+}
+
+fn scard(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::Integer(
+        db.set(&args.str(0))?.map_or(0, |set| set.len()) as i64,
+    ))
+}
+
+fn spop(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(2))?;
+    let key = args.str(0);
+    let count = if args.len() > 1 {
+        let count = args.int(1)?;
+        if count < 0 {
+            return Err(Reply::error("ERR value is out of range, must be positive"));
+        }
+        Some(count as usize)
+    } else {
+        None
+    };
+    let Some(set) = db.set(&key)? else {
+        return Ok(if count.is_some() {
+            Reply::Array(Vec::new())
+        } else {
+            Reply::Nil
+        });
+    };
+    let popped: Vec<String> = set.iter().take(count.unwrap_or(1)).cloned().collect();
+    for member in &popped {
+        set.remove(member);
+    }
+    db.remove_if_empty(&key);
+    Ok(match count {
+        Some(_) => Reply::strings(popped),
+        None => Reply::from_option(popped.into_iter().next()),
+    })
+}
+
+fn srandmember(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(2))?;
+    let count = if args.len() > 1 {
+        Some(args.int(1)?)
+    } else {
+        None
+    };
+    let members: Vec<String> = db
+        .set(&args.str(0))?
+        .map(|set| set.iter().cloned().collect())
+        .unwrap_or_default();
+    Ok(match count {
+        Some(count) => Reply::strings(pick(members, count)),
+        None => Reply::from_option(members.into_iter().next()),
+    })
+}
+
+fn smove(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let (source, dest, member) = (args.str(0), args.str(1), args.str(2));
+    // Check the destination type before touching the source
+    db.set(&dest)?;
+    let Some(set) = db.set(&source)? else {
+        return Ok(Reply::bool(false));
+    };
+    if !set.remove(&member) {
+        return Ok(Reply::bool(false));
+    }
+    db.remove_if_empty(&source);
+    db.set_or_create(&dest)?.insert(member);
+    Ok(Reply::bool(true))
+}
+
+#[derive(Clone, Copy)]
+enum SetOp {
+    Diff,
+    Inter,
+    Union,
+}
+
+fn set_algebra(db: &mut Db, args: &Args, op: SetOp, store: bool) -> CommandResult {
+    let first = if store { 1 } else { 0 };
+    args.arity(first + 1, None)?;
+    let mut sets = Vec::new();
+    for i in first..args.len() {
+        sets.push(db.set(&args.str(i))?.cloned().unwrap_or_default());
+    }
+    let mut sets = sets.into_iter();
+    let mut result = sets.next().unwrap_or_default();
+    for set in sets {
+        match op {
+            SetOp::Diff => result.retain(|member| !set.contains(member)),
+            SetOp::Inter => result.retain(|member| set.contains(member)),
+            SetOp::Union => result.extend(set),
+        }
+    }
+
+    if !store {
+        return Ok(Reply::strings(sorted_members(&result)));
+    }
+    let dest = args.str(0);
+    let len = result.len();
+    if result.is_empty() {
+        db.remove(&dest);
+    } else {
+        db.insert(&dest, Value::Set(result));
+    }
+    Ok(Reply::Integer(len as i64))
+}
+
+fn sscan(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    let pattern = scan_pattern(args, 2)?;
+    let members = db.set(&args.str(0))?.map(|set| sorted_members(set));
+    Ok(scan_reply(
+        members
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|member| glob_match(&pattern, member))
+            .map(Reply::bulk)
+            .collect(),
+    ))
+}
+
+// =========================
+// Sorted Sets
+// =========================
+
+/// Members ordered by score, then by member
+fn ranked(zset: &HashMap<String, f64>) -> Vec<(String, f64)> {
+    let mut members: Vec<(String, f64)> = zset
+        .iter()
+        .map(|(member, score)| (member.clone(), *score))
+        .collect();
+    members.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    members
+}
+
+fn scored_reply(members: Vec<(String, f64)>, with_scores: bool) -> Reply {
+    Reply::strings(members.into_iter().flat_map(|(member, score)| {
+        let mut items = vec![member];
+        if with_scores {
+            items.push(format_float(score));
+        }
+        items
+    }))
+}
+
+/// A ZRANGEBYSCORE style bound: `1.5`, `(1.5` (exclusive), `-inf` or `+inf`
+#[derive(Clone, Copy)]
+struct ScoreBound {
+    value: f64,
+    exclusive: bool,
+}
+
+impl ScoreBound {
+    fn parse(bytes: &[u8]) -> Result<Self, Reply> {
+        let (exclusive, number) = match bytes.split_first() {
+            Some((b'(', rest)) => (true, rest),
+            _ => (false, bytes),
+        };
+        let value =
+            parse_float(number).ok_or_else(|| Reply::error("ERR min or max is not a float"))?;
+        Ok(Self { value, exclusive })
+    }
+
+    fn below(&self, score: f64) -> bool {
+        if self.exclusive {
+            self.value < score
+        } else {
+            self.value <= score
+        }
+    }
+
+    fn above(&self, score: f64) -> bool {
+        if self.exclusive {
+            score < self.value
+        } else {
+            score <= self.value
+        }
+    }
+}
+
+fn zadd(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, None)?;
+    let (mut nx, mut xx, mut gt, mut lt, mut ch, mut incr) =
+        (false, false, false, false, false, false);
+    let mut i = 1;
+    while i < args.len() {
+        match args.upper(i).as_str() {
+            "NX" => nx = true,
+            "XX" => xx = true,
+            "GT" => gt = true,
+            "LT" => lt = true,
+            "CH" => ch = true,
+            "INCR" => incr = true,
+            _ => break,
+        }
+        i += 1;
+    }
+    let pairs = args.len() - i;
+    if pairs == 0 || !pairs.is_multiple_of(2) || (nx && xx) || (gt && lt) || (nx && (gt || lt)) {
+        return Err(Reply::error(SYNTAX_ERROR));
+    }
+    if incr && pairs != 2 {
+        return Err(Reply::error(
+            "ERR INCR option supports a single increment-element pair",
+        ));
+    }
+    let mut updates = Vec::new();
+    for j in (i..args.len()).step_by(2) {
+        updates.push((args.float(j)?, args.str(j + 1)));
+    }
+
+    let key = args.str(0);
+    let zset = db.zset_or_create(&key)?;
+    let (mut added, mut changed) = (0, 0);
+    let mut incr_result = None;
+    for (score, member) in updates {
+        let current = zset.get(&member).copied();
+        if (nx && current.is_some()) || (xx && current.is_none()) {
+            continue;
+        }
+        let score = match (incr, current) {
+            (true, Some(current)) => current + score,
+            _ => score,
+        };
+        if let Some(current) = current {
+            if (gt && score <= current) || (lt && score >= current) {
+                continue;
+            }
+        }
+        if current.is_none() {
+            added += 1;
+        } else if current != Some(score) {
+            changed += 1;
+        }
+        zset.insert(member, score);
+        incr_result = Some(score);
+    }
+    db.remove_if_empty(&key);
+    Ok(if incr {
+        Reply::from_option(incr_result.map(format_float))
+    } else if ch {
+        Reply::Integer(added + changed)
+    } else {
+        Reply::Integer(added)
+    })
+}
+
+fn zrem(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    let key = args.str(0);
+    let Some(zset) = db.zset(&key)? else {
+        return Ok(Reply::Integer(0));
+    };
+    let removed = (1..args.len())
+        .filter(|&i| zset.remove(&args.str(i)).is_some())
+        .count();
+    db.remove_if_empty(&key);
+    Ok(Reply::Integer(removed as i64))
+}
+
+fn zscore(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let zset = db.zset(&args.str(0))?;
+    Ok(Reply::from_option(
+        zset.and_then(|zset| zset.get(&args.str(1)).copied())
+            .map(format_float),
+    ))
+}
+
+fn zmscore(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, None)?;
+    let zset = db.zset(&args.str(0))?;
+    Ok(Reply::Array(
+        (1..args.len())
+            .map(|i| {
+                Reply::from_option(
+                    zset.as_ref()
+                        .and_then(|zset| zset.get(&args.str(i)).copied())
+                        .map(format_float),
+                )
+            })
+            .collect(),
+    ))
+}
+
+fn zincrby(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let amount = args.float(1)?;
+    let zset = db.zset_or_create(&args.str(0))?;
+    let score = zset.entry(args.str(2)).or_insert(0.0);
+    *score += amount;
+    if score.is_nan() {
+        return Err(Reply::error("ERR resulting score is not a number (NaN)"));
+    }
+    Ok(Reply::bulk(format_float(*score)))
+}
+
+fn zcard(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::Integer(
+        db.zset(&args.str(0))?.map_or(0, |zset| zset.len()) as i64,
+    ))
+}
+
+fn zcount(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let (min, max) = (
+        ScoreBound::parse(args.bytes(1))?,
+        ScoreBound::parse(args.bytes(2))?,
+    );
+    let zset = db.zset(&args.str(0))?;
+    Ok(Reply::Integer(zset.map_or(0, |zset| {
+        zset.values()
+            .filter(|&&score| min.below(score) && max.above(score))
+            .count()
+    }) as i64))
+}
+
+fn zrank(db: &mut Db, args: &Args, reverse: bool) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let Some(zset) = db.zset(&args.str(0))? else {
+        return Ok(Reply::Nil);
+    };
+    let mut members = ranked(zset);
+    if reverse {
+        members.reverse();
+    }
+    let member = args.str(1);
+    Ok(members
+        .iter()
+        .position(|(m, _)| *m == member)
+        .map_or(Reply::Nil, |rank| Reply::Integer(rank as i64)))
+}
+
+fn zrange(db: &mut Db, args: &Args, reverse: bool) -> CommandResult {
+    args.arity(3, None)?;
+    let (start, stop) = (args.int(1)?, args.int(2)?);
+    let (mut with_scores, mut reverse) = (false, reverse);
+    for i in 3..args.len() {
+        match args.upper(i).as_str() {
+            "WITHSCORES" => with_scores = true,
+            "REV" if args.name == "ZRANGE" => reverse = true,
+            _ => return Err(Reply::error(SYNTAX_ERROR)),
+        }
+    }
+    let Some(zset) = db.zset(&args.str(0))? else {
+        return Ok(Reply::Array(Vec::new()));
+    };
+    let mut members = ranked(zset);
+    if reverse {
+        members.reverse();
+    }
+    let members = match normalize_range(start, stop, members.len()) {
+        Some((start, end)) => members[start..=end].to_vec(),
+        None => Vec::new(),
+    };
+    Ok(scored_reply(members, with_scores))
+}
+
+fn zrangebyscore(db: &mut Db, args: &Args, reverse: bool) -> CommandResult {
+    args.arity(3, None)?;
+    // ZREVRANGEBYSCORE takes max before min
+    let (min, max) = if reverse {
+        (
+            ScoreBound::parse(args.bytes(2))?,
+            ScoreBound::parse(args.bytes(1))?,
+        )
+    } else {
+        (
+            ScoreBound::parse(args.bytes(1))?,
+            ScoreBound::parse(args.bytes(2))?,
+        )
+    };
+    let mut with_scores = false;
+    let mut limit = None;
+    let mut i = 3;
+    while i < args.len() {
+        match args.upper(i).as_str() {
+            "WITHSCORES" => with_scores = true,
+            "LIMIT" if i + 2 < args.len() => {
+                limit = Some((args.int(i + 1)?, args.int(i + 2)?));
+                i += 2;
+            }
+            _ => return Err(Reply::error(SYNTAX_ERROR)),
+        }
+        i += 1;
+    }
+    let Some(zset) = db.zset(&args.str(0))? else {
+        return Ok(Reply::Array(Vec::new()));
+    };
+    let mut members: Vec<(String, f64)> = ranked(zset)
+        .into_iter()
+        .filter(|(_, score)| min.below(*score) && max.above(*score))
+        .collect();
+    if reverse {
+        members.reverse();
+    }
+    if let Some((offset, count)) = limit {
+        if offset < 0 {
+            members.clear();
+        } else {
+            let count = if count < 0 {
+                usize::MAX
+            } else {
+                count as usize
+            };
+            members = members
+                .into_iter()
+                .skip(offset as usize)
+                .take(count)
+                .collect();
+        }
+    }
+    Ok(scored_reply(members, with_scores))
+}
+
+fn zremrangebyrank(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let (start, stop) = (args.int(1)?, args.int(2)?);
+    let key = args.str(0);
+    let Some(zset) = db.zset(&key)? else {
+        return Ok(Reply::Integer(0));
+    };
+    let members = ranked(zset);
+    let removed = match normalize_range(start, stop, members.len()) {
+        Some((start, end)) => {
+            for (member, _) in &members[start..=end] {
+                zset.remove(member);
+            }
+            end - start + 1
+        }
+        None => 0,
+    };
+    db.remove_if_empty(&key);
+    Ok(Reply::Integer(removed as i64))
+}
+
+fn zremrangebyscore(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let (min, max) = (
+        ScoreBound::parse(args.bytes(1))?,
+        ScoreBound::parse(args.bytes(2))?,
+    );
+    let key = args.str(0);
+    let Some(zset) = db.zset(&key)? else {
+        return Ok(Reply::Integer(0));
+    };
+    let before = zset.len();
+    zset.retain(|_, score| !(min.below(*score) && max.above(*score)));
+    let removed = before - zset.len();
+    db.remove_if_empty(&key);
+    Ok(Reply::Integer(removed as i64))
+}
+
+fn zstore(db: &mut Db, args: &Args, intersect: bool) -> CommandResult {
+    args.arity(3, None)?;
+    let numkeys = usize::try_from(args.int(1)?).unwrap_or(0);
+    if numkeys == 0 {
+        return Err(Reply::error(format!(
+            "ERR at least 1 input key is needed for '{}' command",
+            args.name.to_ascii_lowercase()
+        )));
+    }
+    if args.len() < 2 + numkeys {
+        return Err(Reply::error(SYNTAX_ERROR));
+    }
+    let mut weights = vec![1.0; numkeys];
+    let mut aggregate = "SUM".to_string();
+    let mut i = 2 + numkeys;
+    while i < args.len() {
+        match args.upper(i).as_str() {
+            "WEIGHTS" if i + numkeys < args.len() => {
+                for (j, weight) in weights.iter_mut().enumerate() {
+                    *weight = args
+                        .float(i + 1 + j)
+                        .map_err(|_| Reply::error("ERR weight value is not a float"))?;
+                }
+                i += numkeys;
+            }
+            "AGGREGATE" if i + 1 < args.len() => {
+                aggregate = args.upper(i + 1);
+                if !matches!(aggregate.as_str(), "SUM" | "MIN" | "MAX") {
+                    return Err(Reply::error(SYNTAX_ERROR));
+                }
+                i += 1;
+            }
+            _ => return Err(Reply::error(SYNTAX_ERROR)),
+        }
+        i += 1;
+    }
+
+    // Plain sets take part with a score of 1, like in Redis
+    let mut inputs = Vec::new();
+    for j in 0..numkeys {
+        let key = args.str(2 + j);
+        let members: HashMap<String, f64> = match db.value(&key) {
+            None => HashMap::new(),
+            Some(Value::SortedSet(zset)) => zset.clone(),
+            Some(Value::Set(set)) => set.iter().map(|m| (m.clone(), 1.0)).collect(),
+            Some(_) => return Err(Reply::error(WRONGTYPE)),
+        };
+        inputs.push(members);
+    }
+    let combine = |a: f64, b: f64| match aggregate.as_str() {
+        "MIN" => a.min(b),
+        "MAX" => a.max(b),
+        _ => a + b,
+    };
+    let mut result: HashMap<String, f64> = HashMap::new();
+    for (j, members) in inputs.iter().enumerate() {
+        for (member, score) in members {
+            let weighted = score * weights[j];
+            result
+                .entry(member.clone())
+                .and_modify(|total| *total = combine(*total, weighted))
+                .or_insert(weighted);
+        }
+    }
+    if intersect {
+        result.retain(|member, _| inputs.iter().all(|members| members.contains_key(member)));
+    }
+
+    let dest = args.str(0);
+    let len = result.len();
+    if result.is_empty() {
+        db.remove(&dest);
+    } else {
+        db.insert(&dest, Value::SortedSet(result));
+    }
+    Ok(Reply::Integer(len as i64))
+}
+
+fn zpop(db: &mut Db, args: &Args, max: bool) -> CommandResult {
+    args.arity(1, Some(2))?;
+    let count = if args.len() > 1 {
+        usize::try_from(args.int(1)?)
+            .map_err(|_| Reply::error("ERR value is out of range, must be positive"))?
+    } else {
+        1
+    };
+    let key = args.str(0);
+    let Some(zset) = db.zset(&key)? else {
+        return Ok(Reply::Array(Vec::new()));
+    };
+    let mut members = ranked(zset);
+    if max {
+        members.reverse();
+    }
+    members.truncate(count);
+    for (member, _) in &members {
+        zset.remove(member);
+    }
+    db.remove_if_empty(&key);
+    Ok(scored_reply(members, true))
+}
+
+// =========================
+// Lists
+// =========================
+
+/// Resolves a possibly negative list index, `None` when it is out of range
+fn list_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { len as i64 + index } else { index };
+    (0..len as i64).contains(&index).then_some(index as usize)
+}
+
+/// Parses a LEFT / RIGHT argument, returning whether it is LEFT
+fn list_side(args: &Args, i: usize) -> Result<bool, Reply> {
+    match args.upper(i).as_str() {
+        "LEFT" => Ok(true),
+        "RIGHT" => Ok(false),
+        _ => Err(Reply::error(SYNTAX_ERROR)),
+    }
+}
+
+fn push(db: &mut Db, args: &Args, left: bool, only_existing: bool) -> CommandResult {
+    args.arity(2, None)?;
+    let key = args.str(0);
+    let list = if only_existing {
+        match db.list(&key)? {
+            Some(list) => list,
+            None => return Ok(Reply::Integer(0)),
+        }
+    } else {
+        db.list_or_create(&key)?
+    };
+    for value in args.strs(1) {
+        if left {
+            list.push_front(value);
+        } else {
+            list.push_back(value);
+        }
+    }
+    Ok(Reply::Integer(list.len() as i64))
+}
+
+fn pop(db: &mut Db, args: &Args, left: bool) -> CommandResult {
+    args.arity(1, Some(2))?;
+    let key = args.str(0);
+    let count = if args.len() > 1 {
+        let count = args.int(1)?;
+        if count < 0 {
+            return Err(Reply::error("ERR value is out of range, must be positive"));
+        }
+        Some(count as usize)
+    } else {
+        None
+    };
+    let Some(list) = db.list(&key)? else {
+        return Ok(Reply::Nil);
+    };
+    let take = count.unwrap_or(1).min(list.len());
+    let popped: Vec<String> = if left {
+        list.drain(..take).collect()
+    } else {
+        list.drain(list.len() - take..).rev().collect()
+    };
+    db.remove_if_empty(&key);
+    Ok(match count {
+        Some(_) => Reply::strings(popped),
+        None => Reply::from_option(popped.into_iter().next()),
+    })
+}
+
+fn llen(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::Integer(
+        db.list(&args.str(0))?.map_or(0, |list| list.len()) as i64,
+    ))
+}
+
+fn lrange(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let (start, stop) = (args.int(1)?, args.int(2)?);
+    let items = match db.list(&args.str(0))? {
+        Some(list) => match normalize_range(start, stop, list.len()) {
+            Some((start, end)) => list.range(start..=end).cloned().collect(),
+            None => Vec::new(),
+        },
+        None => Vec::new(),
+    };
+    Ok(Reply::strings(items))
+}
+
+fn lindex(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    let index = args.int(1)?;
+    let item = db
+        .list(&args.str(0))?
+        .and_then(|list| list_index(index, list.len()).map(|i| list[i].clone()));
+    Ok(Reply::from_option(item))
+}
+
+fn lset(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let index = args.int(1)?;
+    let Some(list) = db.list(&args.str(0))? else {
+        return Err(Reply::error("ERR no such key"));
+    };
+    let Some(i) = list_index(index, list.len()) else {
+        return Err(Reply::error("ERR index out of range"));
+    };
+    list[i] = args.str(2);
+    Ok(Reply::ok())
+}
+
+fn ltrim(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let (start, stop) = (args.int(1)?, args.int(2)?);
+    let key = args.str(0);
+    let Some(list) = db.list(&key)? else {
+        return Ok(Reply::ok());
+    };
+    match normalize_range(start, stop, list.len()) {
+        Some((start, end)) => {
+            list.truncate(end + 1);
+            list.drain(..start);
+        }
+        None => list.clear(),
+    }
+    db.remove_if_empty(&key);
+    Ok(Reply::ok())
+}
+
+fn lrem(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(3, Some(3))?;
+    let count = args.int(1)?;
+    let (key, value) = (args.str(0), args.str(2));
+    let Some(list) = db.list(&key)? else {
+        return Ok(Reply::Integer(0));
+    };
+    let limit = if count == 0 {
+        usize::MAX
+    } else {
+        count.unsigned_abs() as usize
+    };
+    let mut removed = 0;
+    let kept: VecDeque<String> = if count < 0 {
+        let mut kept: VecDeque<String> = list
+            .drain(..)
+            .rev()
+            .filter(|item| {
+                let remove = removed < limit && *item == value;
+                removed += remove as usize;
+                !remove
+            })
+            .collect();
+        kept.make_contiguous().reverse();
+        kept
+    } else {
+        list.drain(..)
+            .filter(|item| {
+                let remove = removed < limit && *item == value;
+                removed += remove as usize;
+                !remove
+            })
+            .collect()
+    };
+    *list = kept;
+    db.remove_if_empty(&key);
+    Ok(Reply::Integer(removed as i64))
+}
+
+fn linsert(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(4, Some(4))?;
+    let before = match args.upper(1).as_str() {
+        "BEFORE" => true,
+        "AFTER" => false,
+        _ => return Err(Reply::error(SYNTAX_ERROR)),
+    };
+    let (pivot, value) = (args.str(2), args.str(3));
+    let Some(list) = db.list(&args.str(0))? else {
+        return Ok(Reply::Integer(0));
+    };
+    let Some(at) = list.iter().position(|item| *item == pivot) else {
+        return Ok(Reply::Integer(-1));
+    };
+    list.insert(if before { at } else { at + 1 }, value);
+    Ok(Reply::Integer(list.len() as i64))
+}
+
+/// Pops from one end of `source` and pushes onto one end of `dest`
+fn move_item(
+    db: &mut Db,
+    source: &str,
+    dest: &str,
+    from_left: bool,
+    to_left: bool,
+) -> CommandResult {
+    // Check the destination type before touching the source
+    db.list(dest)?;
+    let Some(list) = db.list(source)? else {
+        return Ok(Reply::Nil);
+    };
+    let item = if from_left {
+        list.pop_front()
+    } else {
+        list.pop_back()
+    };
+    let Some(item) = item else {
+        return Ok(Reply::Nil);
+    };
+    db.remove_if_empty(source);
+    let list = db.list_or_create(dest)?;
+    if to_left {
+        list.push_front(item.clone());
+    } else {
+        list.push_back(item.clone());
+    }
+    Ok(Reply::bulk(item))
+}
+
+fn lmove(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(4, Some(4))?;
+    let (from_left, to_left) = (list_side(args, 2)?, list_side(args, 3)?);
+    move_item(db, &args.str(0), &args.str(1), from_left, to_left)
+}
+
+fn rpoplpush(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(2, Some(2))?;
+    move_item(db, &args.str(0), &args.str(1), false, true)
+}
+
+// =========================
+// Server
+// =========================
+
+fn ping(args: &Args) -> CommandResult {
+    args.arity(0, Some(1))?;
+    Ok(if args.len() == 1 {
+        Reply::bulk(args.bytes(0).to_vec())
+    } else {
+        Reply::Status("PONG".to_string())
+    })
+}
+
+fn echo(args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    Ok(Reply::bulk(args.bytes(0).to_vec()))
+}
+
+fn select(args: &Args) -> CommandResult {
+    args.arity(1, Some(1))?;
+    match args.int(0)? {
+        0 => Ok(Reply::ok()),
+        _ => Err(Reply::error("ERR DB index is out of range")),
+    }
+}
+
+fn time() -> CommandResult {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Ok(Reply::strings([
+        now.as_secs().to_string(),
+        now.subsec_micros().to_string(),
+    ]))
+}
+
+fn human_bytes(bytes: usize) -> String {
+    let bytes = bytes as f64;
+    match bytes {
+        b if b < 1024.0 => format!("{}B", b as usize),
+        b if b < 1024.0 * 1024.0 => format!("{:.2}K", b / 1024.0),
+        b if b < 1024.0 * 1024.0 * 1024.0 => format!("{:.2}M", b / 1024.0 / 1024.0),
+        b => format!("{:.2}G", b / 1024.0 / 1024.0 / 1024.0),
+    }
+}
+
+fn info(db: &mut Db, args: &Args) -> CommandResult {
+    let wanted: Vec<String> = args
+        .strs(0)
+        .into_iter()
+        .map(|section| section.to_ascii_lowercase())
+        .collect();
+    let everything = wanted.is_empty()
+        || wanted
+            .iter()
+            .any(|section| matches!(section.as_str(), "all" | "everything" | "default"));
+
+    let uptime = db.uptime().as_secs();
+    let used_memory = db.approximate_memory();
+    let keys = db.len();
+    let expires = db.expiring_len();
+    let stats = db.stats.clone();
+    let sections: Vec<(&str, Vec<(&str, String)>)> = vec![
+        (
+            "Server",
+            vec![
+                ("redis_version", "7.2.0".to_string()),
+                ("redis_mode", "standalone".to_string()),
+                ("dbx_backend", "memory".to_string()),
+                ("process_id", std::process::id().to_string()),
+                ("uptime_in_seconds", uptime.to_string()),
+                ("uptime_in_days", (uptime / 86_400).to_string()),
+            ],
+        ),
+        (
+            "Clients",
+            vec![
+                ("connected_clients", stats.connected_clients.to_string()),
+                ("blocked_clients", "0".to_string()),
+            ],
+        ),
+        (
+            "Memory",
+            vec![
+                ("used_memory", used_memory.to_string()),
+                ("used_memory_human", human_bytes(used_memory)),
+                ("maxmemory", "0".to_string()),
+                ("maxmemory_policy", "noeviction".to_string()),
+            ],
+        ),
+        (
+            "Stats",
+            vec![
+                (
+                    "total_connections_received",
+                    stats.connections_received.to_string(),
+                ),
+                (
+                    "total_commands_processed",
+                    stats.commands_processed.to_string(),
+                ),
+                ("expired_keys", stats.expired_keys.to_string()),
+                ("evicted_keys", "0".to_string()),
+                ("keyspace_hits", stats.keyspace_hits.to_string()),
+                ("keyspace_misses", stats.keyspace_misses.to_string()),
+            ],
+        ),
+        (
+            "Replication",
+            vec![
+                ("role", "master".to_string()),
+                ("connected_slaves", "0".to_string()),
+            ],
+        ),
+        (
+            "Keyspace",
+            if keys > 0 {
+                vec![("db0", format!("keys={keys},expires={expires},avg_ttl=0"))]
+            } else {
+                Vec::new()
+            },
+        ),
+    ];
+
+    let mut text = String::new();
+    for (name, fields) in sections {
+        if !everything && !wanted.contains(&name.to_ascii_lowercase()) {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push_str("\r\n");
+        }
+        text.push_str(&format!("# {name}\r\n"));
+        for (field, value) in fields {
+            text.push_str(&format!("{field}:{value}\r\n"));
+        }
+    }
+    Ok(Reply::bulk(text))
+}
+
+fn config(db: &mut Db, args: &Args) -> CommandResult {
+    args.arity(1, None)?;
+    match args.upper(0).as_str() {
+        "GET" => {
+            args.arity(2, None)?;
+            let mut params = Vec::new();
+            for i in 1..args.len() {
+                params.extend(db.config_get(&args.str(i).to_ascii_lowercase()));
+            }
+            Ok(Reply::strings(
+                params.into_iter().flat_map(|(name, value)| [name, value]),
+            ))
+        }
+        "SET" => {
+            if args.len() < 3 || args.len() % 2 != 1 {
+                return Err(Reply::error(
+                    "ERR wrong number of arguments for 'config|set' command",
+                ));
+            }
+            for i in (1..args.len()).step_by(2) {
+                db.config_set(&args.str(i), &args.str(i + 1));
+            }
+            Ok(Reply::ok())
+        }
+        "RESETSTAT" => {
+            let connected_clients = db.stats.connected_clients;
+            db.stats = Default::default();
+            db.stats.connected_clients = connected_clients;
+            Ok(Reply::ok())
+        }
+        // Nothing is persisted, so there is no config file to rewrite
+        "REWRITE" => Ok(Reply::ok()),
+        sub => Err(Reply::error(format!(
+            "ERR unknown subcommand '{}'",
+            sub.to_ascii_lowercase()
+        ))),
+    }
+}
+
+fn client(args: &Args) -> CommandResult {
+    args.arity(1, None)?;
+    match args.upper(0).as_str() {
+        "SETNAME" | "SETINFO" | "NO-EVICT" | "NO-TOUCH" => Ok(Reply::ok()),
+        "GETNAME" => Ok(Reply::Nil),
+        "ID" => Ok(Reply::Integer(1)),
+        sub => Err(Reply::error(format!(
+            "ERR unknown subcommand '{}'",
+            sub.to_ascii_lowercase()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::store::MemoryStore;
+
+    fn run(db: &mut Db, command: &str) -> Reply {
+        let args: Vec<Vec<u8>> = command
+            .split_whitespace()
+            .map(|arg| arg.as_bytes().to_vec())
+            .collect();
+        execute(db, &args)
+    }
+
+    #[test]
+    fn test_string_and_key_commands() {
+        let store = MemoryStore::new();
+        let mut db = store.lock();
+        assert_eq!(run(&mut db, "SET counter 10"), Reply::ok());
+        assert_eq!(run(&mut db, "INCRBY counter 5"), Reply::Integer(15));
+        assert_eq!(run(&mut db, "GET counter"), Reply::bulk("15"));
+        assert_eq!(run(&mut db, "SET counter 1 NX"), Reply::Nil);
+        assert_eq!(run(&mut db, "EXPIRE counter 100"), Reply::Integer(1));
+        assert_eq!(run(&mut db, "TTL counter"), Reply::Integer(100));
+        assert_eq!(run(&mut db, "TTL missing"), Reply::Integer(-2));
+        assert_eq!(
+            run(&mut db, "HSET counter field value"),
+            Reply::error(WRONGTYPE)
+        );
+        assert_eq!(run(&mut db, "DEL counter missing"), Reply::Integer(1));
+    }
+
+    #[test]
+    fn test_sorted_set_ranges() {
+        let store = MemoryStore::new();
+        let mut db = store.lock();
+        assert_eq!(
+            run(&mut db, "ZADD board 3 carol 1 alice 2 bob"),
+            Reply::Integer(3)
+        );
+        assert_eq!(
+            run(&mut db, "ZRANGE board 0 -1 WITHSCORES"),
+            Reply::strings(["alice", "1", "bob", "2", "carol", "3"])
+        );
+        assert_eq!(
+            run(&mut db, "ZREVRANGEBYSCORE board +inf (1 LIMIT 0 1"),
+            Reply::strings(["carol"])
+        );
+        assert_eq!(run(&mut db, "ZREVRANK board alice"), Reply::Integer(2));
+        assert_eq!(run(&mut db, "ZINCRBY board 2.5 alice"), Reply::bulk("3.5"));
+    }
+
+    #[test]
+    fn test_bitmap_commands() {
+        let store = MemoryStore::new();
+        let mut db = store.lock();
+        assert_eq!(run(&mut db, "SETBIT bits 7 1"), Reply::Integer(0));
+        assert_eq!(run(&mut db, "SETBIT bits 9 1"), Reply::Integer(0));
+        assert_eq!(run(&mut db, "BITCOUNT bits"), Reply::Integer(2));
+        assert_eq!(run(&mut db, "BITPOS bits 1"), Reply::Integer(7));
+        assert_eq!(run(&mut db, "BITPOS bits 0 0 0"), Reply::Integer(0));
+        assert_eq!(run(&mut db, "BITOP NOT inverted bits"), Reply::Integer(2));
+        assert_eq!(run(&mut db, "BITCOUNT inverted"), Reply::Integer(14));
+    }
+
+    #[test]
+    fn test_list_commands() {
+        let store = MemoryStore::new();
+        let mut db = store.lock();
+        assert_eq!(run(&mut db, "RPUSH queue b c d"), Reply::Integer(3));
+        assert_eq!(run(&mut db, "LPUSH queue a"), Reply::Integer(4));
+        assert_eq!(run(&mut db, "LPUSHX missing a"), Reply::Integer(0));
+        assert_eq!(run(&mut db, "LINDEX queue -1"), Reply::bulk("d"));
+        assert_eq!(run(&mut db, "LINSERT queue AFTER b b"), Reply::Integer(5));
+        assert_eq!(run(&mut db, "LREM queue -1 b"), Reply::Integer(1));
+        assert_eq!(
+            run(&mut db, "LRANGE queue 0 -1"),
+            Reply::strings(["a", "b", "c", "d"])
+        );
+        assert_eq!(run(&mut db, "RPOP queue 2"), Reply::strings(["d", "c"]));
+        assert_eq!(
+            run(&mut db, "LMOVE queue done LEFT RIGHT"),
+            Reply::bulk("a")
+        );
+        assert_eq!(
+            run(&mut db, "LSET queue 5 x"),
+            Reply::error("ERR index out of range")
+        );
+        assert_eq!(run(&mut db, "LTRIM queue 1 0"), Reply::ok());
+        assert_eq!(run(&mut db, "EXISTS queue"), Reply::Integer(0));
+        assert_eq!(run(&mut db, "TYPE done"), Reply::Status("list".to_string()));
+        assert_eq!(run(&mut db, "LPOP missing 2"), Reply::Nil);
+    }
+
+    /// Storage that fails to save while `failing` is set
+    #[derive(Default)]
+    struct FlakyStorage {
+        failing: std::sync::atomic::AtomicBool,
+    }
+
+    impl crate::memory::store::Persistence for FlakyStorage {
+        fn save(&self, _db: &Db, _changes: &crate::memory::store::Changes) -> Result<(), String> {
+            if self.failing.load(std::sync::atomic::Ordering::Relaxed) {
+                Err("disk full".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsaved_writes_are_rolled_back() {
+        let storage = std::sync::Arc::new(FlakyStorage::default());
+        let store = MemoryStore::with_persistence(storage.clone());
+        let mut db = store.lock();
+        assert_eq!(run(&mut db, "SET kept 1"), Reply::ok());
+        assert_eq!(run(&mut db, "SADD tags rust"), Reply::Integer(1));
+
+        storage
+            .failing
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        let failed = Reply::error("ERR failed to persist changes: disk full");
+        assert_eq!(run(&mut db, "SET kept 2"), failed);
+        assert_eq!(run(&mut db, "SET added 1"), failed);
+        assert_eq!(run(&mut db, "SMOVE tags moved rust"), failed);
+        assert_eq!(run(&mut db, "FLUSHALL"), failed);
+
+        assert_eq!(run(&mut db, "GET kept"), Reply::bulk("1"));
+        assert_eq!(run(&mut db, "EXISTS added moved"), Reply::Integer(0));
+        assert_eq!(run(&mut db, "SMEMBERS tags"), Reply::strings(["rust"]));
+    }
+}
@@ -0,0 +1,86 @@
+//! Glob-style pattern matching as used by KEYS, CONFIG GET and PSUBSCRIBE
+
+/// Matches `text` against a Redis glob pattern
+///
+/// Supports `*`, `?`, character classes like `[abc]`, `[^a]` and `[a-z]`, and
+/// `\` to escape the next character.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    matches(&pattern, &text)
+}
+
+fn matches(pattern: &[char], text: &[char]) -> bool {
+    let Some((&first, rest)) = pattern.split_first() else {
+        return text.is_empty();
+    };
+    match first {
+        '*' => (0..=text.len()).any(|skip| matches(rest, &text[skip..])),
+        '?' => !text.is_empty() && matches(rest, &text[1..]),
+        '[' => {
+            let Some((&c, text_rest)) = text.split_first() else {
+                return false;
+            };
+            match class_matches(rest, c) {
+                Some((matched, pattern_rest)) => matched && matches(pattern_rest, text_rest),
+                // An unterminated class matches a literal '['
+                None => c == '[' && matches(rest, text_rest),
+            }
+        }
+        '\\' if !rest.is_empty() => {
+            text.first() == Some(&rest[0]) && matches(&rest[1..], &text[1..])
+        }
+        literal => text.first() == Some(&literal) && matches(rest, &text[1..]),
+    }
+}
+
+/// Checks `c` against the class starting after '[', returning the pattern after ']'
+fn class_matches(pattern: &[char], c: char) -> Option<(bool, &[char])> {
+    let (negated, mut i) = match pattern.first() {
+        Some('^') => (true, 1),
+        _ => (false, 0),
+    };
+    let mut matched = false;
+    while i < pattern.len() {
+        match pattern[i] {
+            ']' => return Some((matched != negated, &pattern[i + 1..])),
+            '\\' if i + 1 < pattern.len() => {
+                matched |= pattern[i + 1] == c;
+                i += 2;
+            }
+            start if i + 2 < pattern.len() && pattern[i + 1] == '-' && pattern[i + 2] != ']' => {
+                let end = pattern[i + 2];
+                let (low, high) = if start <= end {
+                    (start, end)
+                } else {
+                    (end, start)
+                };
+                matched |= (low..=high).contains(&c);
+                i += 3;
+            }
+            literal => {
+                matched |= literal == c;
+                i += 1;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_match;
+
+    #[test]
+    fn test_glob_patterns() {
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("user:*:name", "user:42:name"));
+        assert!(!glob_match("user:*:name", "user:42:email"));
+        assert!(glob_match("h?llo", "hello"));
+        assert!(glob_match("h[ae]llo", "hallo"));
+        assert!(!glob_match("h[^e]llo", "hello"));
+        assert!(glob_match("key[0-9]", "key7"));
+        assert!(glob_match("literal\\*", "literal*"));
+        assert!(!glob_match("literal\\*", "literalx"));
+    }
+}
@@ -0,0 +1,37 @@
+//! In-memory backend
+//!
+//! A Redis-compatible keyspace kept in process memory, for tests and embedded
+//! use. It can be used in two ways:
+//!
+//! - [`MemoryAdapter`] implements the adapter traits directly on a
+//!   [`MemoryStore`].
+//! - [`MemoryServer`] (feature `async`) serves a store over RESP2 on a local
+//!   port, so any Redis client — including [`RedisPool`](crate::redis::client::RedisPool)
+//!   and the async primitives — can use it unchanged.
+//!
+//! Strings, hashes, sets, sorted sets, lists, bitmaps, key expiry,
+//! transactions and pub/sub are supported. Lua scripting and streams are not.
+
+pub mod adapter;
+mod glob;
+pub mod store;
+
+#[cfg(feature = "async")]
+mod commands;
+#[cfg(feature = "async")]
+mod resp;
+#[cfg(feature = "async")]
+pub mod server;
+
+pub use adapter::MemoryAdapter;
+#[cfg(feature = "async")]
+pub use server::MemoryServer;
+pub use store::{MemoryStore, Persistence};
+
+/// URL scheme that selects the in-memory backend
+pub const MEMORY_URL_SCHEME: &str = "memory://";
+
+/// Check whether a connection URL selects the in-memory backend
+pub fn is_memory_url(url: &str) -> bool {
+    url.starts_with(MEMORY_URL_SCHEME)
+}
@@ -0,0 +1,239 @@
+//! RESP2 framing for the in-memory server
+
+/// A reply sent back to a client
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reply {
+    Status(String),
+    Error(String),
+    Integer(i64),
+    Bulk(Vec<u8>),
+    Nil,
+    Array(Vec<Reply>),
+}
+
+impl Reply {
+    pub fn ok() -> Self {
+        Reply::Status("OK".to_string())
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Reply::Error(message.into())
+    }
+
+    pub fn bulk(value: impl Into<Vec<u8>>) -> Self {
+        Reply::Bulk(value.into())
+    }
+
+    pub fn bool(value: bool) -> Self {
+        Reply::Integer(value as i64)
+    }
+
+    pub fn from_option(value: Option<impl Into<Vec<u8>>>) -> Self {
+        value.map_or(Reply::Nil, Reply::bulk)
+    }
+
+    /// An array of bulk strings
+    pub fn strings<S: Into<Vec<u8>>>(values: impl IntoIterator<Item = S>) -> Self {
+        Reply::Array(values.into_iter().map(Reply::bulk).collect())
+    }
+
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Reply::Status(status) => {
+                out.push(b'+');
+                out.extend_from_slice(status.as_bytes());
+            }
+            Reply::Error(message) => {
+                out.push(b'-');
+                out.extend_from_slice(message.as_bytes());
+            }
+            Reply::Integer(n) => out.extend_from_slice(format!(":{n}").as_bytes()),
+            Reply::Bulk(bytes) => {
+                out.extend_from_slice(format!("${}\r\n", bytes.len()).as_bytes());
+                out.extend_from_slice(bytes);
+            }
+            Reply::Nil => out.extend_from_slice(b"$-1"),
+            Reply::Array(items) => {
+                out.extend_from_slice(format!("*{}\r\n", items.len()).as_bytes());
+                for item in items {
+                    item.encode(out);
+                }
+                return;
+            }
+        }
+        out.extend_from_slice(b"\r\n");
+    }
+}
+
+/// A command name followed by its arguments
+pub type Command = Vec<Vec<u8>>;
+
+/// How large a command a client may send
+///
+/// Lengths come from the client, so they are checked before anything is
+/// allocated or buffered for them.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Most arguments in one command
+    pub max_args: usize,
+    /// Largest single argument, like Redis' `proto-max-bulk-len`
+    pub max_bulk_len: usize,
+}
+
+impl Limits {
+    /// Redis' defaults for authenticated clients
+    pub const DEFAULT: Limits = Limits {
+        max_args: 1024 * 1024,
+        max_bulk_len: 512 * 1024 * 1024,
+    };
+
+    /// Enough for AUTH, like Redis allows before a client has authenticated
+    pub const UNAUTHENTICATED: Limits = Limits {
+        max_args: 10,
+        max_bulk_len: 16 * 1024,
+    };
+}
+
+/// Longest inline command, and longest length line of a multibulk command
+const MAX_INLINE_LEN: usize = 64 * 1024;
+
+/// Parses one command from the front of `buf`
+///
+/// Returns the arguments and the number of bytes consumed, or `None` if the
+/// command is not complete yet. Inline commands (plain text lines) are
+/// accepted too, which keeps the server usable from telnet.
+pub fn parse_command(buf: &[u8], limits: Limits) -> Result<Option<(Command, usize)>, String> {
+    if buf.is_empty() {
+        return Ok(None);
+    }
+    if buf[0] != b'*' {
+        let Some(end) = find_line(buf, 0)? else {
+            return Ok(None);
+        };
+        let args: Command = buf[..end]
+            .split(|b| b.is_ascii_whitespace())
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| arg.to_vec())
+            .collect();
+        if args.len() > limits.max_args {
+            return Err("Protocol error: invalid multibulk length".to_string());
+        }
+        return Ok(Some((args, end + 2)));
+    }
+
+    let Some((count, mut pos)) = read_length(buf, 1)? else {
+        return Ok(None);
+    };
+    let count = usize::try_from(count.max(0)).unwrap_or(usize::MAX);
+    if count > limits.max_args {
+        return Err("Protocol error: invalid multibulk length".to_string());
+    }
+    // Every argument takes at least `$0\r\n\r\n`, so what is buffered
+    // bounds the allocation rather than the count the client sent
+    let mut args = Vec::with_capacity(count.min((buf.len() - pos) / 6));
+    for _ in 0..count {
+        if pos >= buf.len() {
+            return Ok(None);
+        }
+        if buf[pos] != b'$' {
+            return Err(format!(
+                "Protocol error: expected '$', got '{}'",
+                buf[pos] as char
+            ));
+        }
+        let Some((len, start)) = read_length(buf, pos + 1)? else {
+            return Ok(None);
+        };
+        let len = usize::try_from(len)
+            .ok()
+            .filter(|&len| len <= limits.max_bulk_len)
+            .ok_or("Protocol error: invalid bulk length")?;
+        let end = start
+            .checked_add(len)
+            .and_then(|end| end.checked_add(2))
+            .ok_or("Protocol error: invalid bulk length")?;
+        if buf.len() < end {
+            return Ok(None);
+        }
+        args.push(buf[start..start + len].to_vec());
+        pos = end;
+    }
+    Ok(Some((args, pos)))
+}
+
+/// Reads the number ending in CRLF at `start`, returning it and the position after the CRLF
+fn read_length(buf: &[u8], start: usize) -> Result<Option<(i64, usize)>, String> {
+    let Some(end) = find_line(buf, start)? else {
+        return Ok(None);
+    };
+    let n = std::str::from_utf8(&buf[start..end])
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or("Protocol error: invalid length")?;
+    Ok(Some((n, end + 2)))
+}
+
+/// Finds the CRLF ending the line at `start`, failing once the line is too long to be valid
+fn find_line(buf: &[u8], start: usize) -> Result<Option<usize>, String> {
+    match find_crlf(buf, start) {
+        Some(end) if end - start <= MAX_INLINE_LEN => Ok(Some(end)),
+        None if buf.len().saturating_sub(start) <= MAX_INLINE_LEN => Ok(None),
+        _ => Err("Protocol error: too big inline request".to_string()),
+    }
+}
+
+fn find_crlf(buf: &[u8], start: usize) -> Option<usize> {
+    buf.get(start..)?
+        .windows(2)
+        .position(|w| w == b"\r\n")
+        .map(|i| start + i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_partial_and_complete_commands() {
+        let frame = b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n";
+        assert_eq!(parse_command(&frame[..10], Limits::DEFAULT), Ok(None));
+        let (args, used) = parse_command(frame, Limits::DEFAULT).unwrap().unwrap();
+        assert_eq!(args, vec![b"GET".to_vec(), b"key".to_vec()]);
+        assert_eq!(used, frame.len());
+
+        let (args, _) = parse_command(b"PING hello\r\n", Limits::DEFAULT)
+            .unwrap()
+            .unwrap();
+        assert_eq!(args, vec![b"PING".to_vec(), b"hello".to_vec()]);
+    }
+
+    #[test]
+    fn test_lengths_are_limited() {
+        let limits = Limits {
+            max_args: 2,
+            max_bulk_len: 4,
+        };
+        assert!(parse_command(b"*2\r\n$4\r\nPING\r\n$4\r\nPONG\r\n", limits).is_ok());
+        assert!(parse_command(b"*3\r\n", limits).is_err());
+        assert!(parse_command(b"*1\r\n$5\r\n", limits).is_err());
+        assert!(parse_command(b"PING a b\r\n", limits).is_err());
+
+        // Lengths that would overflow are rejected, not waited for
+        let huge = format!("*1\r\n${}\r\n", i64::MAX);
+        assert!(parse_command(huge.as_bytes(), Limits::DEFAULT).is_err());
+        let huge = format!("*{}\r\n", i64::MAX);
+        assert!(parse_command(huge.as_bytes(), Limits::DEFAULT).is_err());
+
+        // A line never ending in CRLF can't grow the buffer forever
+        let line = vec![b'a'; MAX_INLINE_LEN + 1];
+        assert!(parse_command(&line, Limits::DEFAULT).is_err());
+        assert_eq!(parse_command(&line[..100], Limits::DEFAULT), Ok(None));
+    }
+
+    #[test]
+    fn test_encode_nested_reply() {
+        let mut out = Vec::new();
+        Reply::Array(vec![Reply::bulk("a"), Reply::Integer(1), Reply::Nil]).encode(&mut out);
+        assert_eq!(out, b"*3\r\n$1\r\na\r\n:1\r\n$-1\r\n");
+    }
+}
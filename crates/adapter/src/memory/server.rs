@@ -0,0 +1,657 @@
+//! A local RESP2 server backed by a [`MemoryStore`]
+//!
+//! Lets code written against a Redis client — including the connection pool
+//! and the async primitives — run on the in-memory backend unchanged.
+//! Besides the keyspace commands it supports MULTI/EXEC transactions, WATCH and
+//! pub/sub. Connections are accepted on 127.0.0.1 only, and must AUTH with
+//! a password generated for each server before running any command.
+
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::commands;
+use super::glob::glob_match;
+use super::resp::{parse_command, Command, Limits, Reply};
+use super::store::MemoryStore;
+
+/// Subscriptions of one connection and the channel used to push messages to it
+struct Subscriber {
+    channels: HashSet<String>,
+    patterns: HashSet<String>,
+    tx: mpsc::UnboundedSender<Reply>,
+}
+
+/// Subscribers of all connections, keyed by connection id
+#[derive(Default)]
+struct Broker {
+    subscribers: Mutex<HashMap<u64, Subscriber>>,
+}
+
+impl Broker {
+    /// Delivers a message, returning how many subscriptions received it
+    fn publish(&self, channel: &str, payload: &[u8]) -> i64 {
+        let subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        let mut delivered = 0;
+        for subscriber in subscribers.values() {
+            if subscriber.channels.contains(channel) {
+                let message = Reply::Array(vec![
+                    Reply::bulk("message"),
+                    Reply::bulk(channel),
+                    Reply::bulk(payload.to_vec()),
+                ]);
+                if subscriber.tx.send(message).is_ok() {
+                    delivered += 1;
+                }
+            }
+            for pattern in &subscriber.patterns {
+                if glob_match(pattern, channel) {
+                    let message = Reply::Array(vec![
+                        Reply::bulk("pmessage"),
+                        Reply::bulk(pattern.as_str()),
+                        Reply::bulk(channel),
+                        Reply::bulk(payload.to_vec()),
+                    ]);
+                    if subscriber.tx.send(message).is_ok() {
+                        delivered += 1;
+                    }
+                }
+            }
+        }
+        delivered
+    }
+}
+
+/// State shared by the accept loop and every connection
+struct Shared {
+    store: MemoryStore,
+    password: String,
+    broker: Broker,
+    next_id: AtomicU64,
+}
+
+/// An in-memory Redis server listening on a local port
+///
+/// The server stops accepting connections when dropped.
+pub struct MemoryServer {
+    addr: SocketAddr,
+    store: MemoryStore,
+    password: String,
+    accept: JoinHandle<()>,
+}
+
+impl MemoryServer {
+    /// Start serving `store` on an ephemeral port of 127.0.0.1
+    pub async fn start(store: MemoryStore) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let password = random_password();
+        let shared = Arc::new(Shared {
+            store: store.clone(),
+            password: password.clone(),
+            broker: Broker::default(),
+            next_id: AtomicU64::new(1),
+        });
+        let accept = tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let shared = shared.clone();
+                tokio::spawn(async move {
+                    Session::new(shared).run(socket).await;
+                });
+            }
+        });
+        Ok(Self {
+            addr,
+            store,
+            password,
+            accept,
+        })
+    }
+
+    /// The address the server listens on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The password clients must AUTH with
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    /// A `redis://` URL clients can connect to, password included
+    pub fn url(&self) -> String {
+        format!("redis://:{}@{}", self.password, self.addr)
+    }
+
+    /// The store behind the server
+    pub fn store(&self) -> &MemoryStore {
+        &self.store
+    }
+}
+
+impl Drop for MemoryServer {
+    fn drop(&mut self) {
+        self.accept.abort();
+    }
+}
+
+/// 128 random bits as hex
+///
+/// `RandomState` is keyed from the OS random source, so its hashes are
+/// unpredictable without pulling in a random number crate.
+fn random_password() -> String {
+    (0..2)
+        .map(|_| format!("{:016x}", RandomState::new().build_hasher().finish()))
+        .collect()
+}
+
+/// Compares without stopping at the first difference
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// What the session should do after handling a command
+enum Flow {
+    Continue,
+    Close,
+}
+
+/// One client connection
+struct Session {
+    id: u64,
+    shared: Arc<Shared>,
+    authenticated: bool,
+    /// Commands queued since MULTI, or `None` outside a transaction
+    queued: Option<Vec<Command>>,
+    /// Whether a command failed to queue, which aborts the EXEC
+    queue_failed: bool,
+    /// Keys given to WATCH and their versions at the time
+    watched: Vec<(String, u64)>,
+    subscriptions: usize,
+    push_tx: mpsc::UnboundedSender<Reply>,
+    push_rx: mpsc::UnboundedReceiver<Reply>,
+}
+
+impl Session {
+    fn new(shared: Arc<Shared>) -> Self {
+        let id = shared.next_id.fetch_add(1, Ordering::Relaxed);
+        let (push_tx, push_rx) = mpsc::unbounded_channel();
+        Self {
+            id,
+            shared,
+            authenticated: false,
+            queued: None,
+            queue_failed: false,
+            watched: Vec::new(),
+            subscriptions: 0,
+            push_tx,
+            push_rx,
+        }
+    }
+
+    async fn run(mut self, mut socket: TcpStream) {
+        {
+            let mut db = self.shared.store.lock();
+            db.stats.connections_received += 1;
+            db.stats.connected_clients += 1;
+        }
+        let _ = self.serve(&mut socket).await;
+        self.unwatch();
+        self.unsubscribe_all();
+        self.shared.store.lock().stats.connected_clients -= 1;
+    }
+
+    async fn serve(&mut self, socket: &mut TcpStream) -> io::Result<()> {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 16 * 1024];
+        loop {
+            let mut out = Vec::new();
+            tokio::select! {
+                read = socket.read(&mut chunk) => {
+                    let n = read?;
+                    if n == 0 {
+                        return Ok(());
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+                    loop {
+                        let limits = if self.authenticated {
+                            Limits::DEFAULT
+                        } else {
+                            Limits::UNAUTHENTICATED
+                        };
+                        match parse_command(&buf, limits) {
+                            Ok(Some((args, used))) => {
+                                buf.drain(..used);
+                                if args.is_empty() {
+                                    continue;
+                                }
+                                if let Flow::Close = self.handle(&args, &mut out) {
+                                    socket.write_all(&out).await?;
+                                    return Ok(());
+                                }
+                            }
+                            Ok(None) => break,
+                            Err(message) => {
+                                Reply::error(format!("ERR {message}")).encode(&mut out);
+                                socket.write_all(&out).await?;
+                                return Ok(());
+                            }
+                        }
+                    }
+                }
+                Some(message) = self.push_rx.recv() => message.encode(&mut out),
+            }
+            socket.write_all(&out).await?;
+        }
+    }
+
+    fn handle(&mut self, args: &[Vec<u8>], out: &mut Vec<u8>) -> Flow {
+        let name = String::from_utf8_lossy(&args[0]).to_ascii_uppercase();
+
+        if !self.authenticated && !matches!(name.as_str(), "AUTH" | "QUIT") {
+            Reply::error("NOAUTH Authentication required.").encode(out);
+            return Flow::Continue;
+        }
+
+        // RESP2 connections with subscriptions only take pub/sub commands
+        if self.subscriptions > 0 {
+            match name.as_str() {
+                "SUBSCRIBE" | "PSUBSCRIBE" | "UNSUBSCRIBE" | "PUNSUBSCRIBE" | "QUIT" => {}
+                "PING" if args.len() <= 2 => {
+                    let payload = args.get(1).cloned().unwrap_or_default();
+                    Reply::Array(vec![Reply::bulk("pong"), Reply::Bulk(payload)]).encode(out);
+                    return Flow::Continue;
+                }
+                "PING" => {}
+                _ => {
+                    Reply::error(format!(
+                        "ERR Can't execute '{}': only (P|S)SUBSCRIBE / (P|S)UNSUBSCRIBE / PING / QUIT / RESET are allowed in this context",
+                        name.to_ascii_lowercase()
+                    ))
+                    .encode(out);
+                    return Flow::Continue;
+                }
+            }
+        }
+
+        if let Some(queued) = self.queued.as_mut() {
+            if !matches!(
+                name.as_str(),
+                "EXEC" | "DISCARD" | "MULTI" | "WATCH" | "QUIT"
+            ) {
+                if matches!(name.as_str(), "SUBSCRIBE" | "PSUBSCRIBE") {
+                    self.queue_failed = true;
+                    Reply::error("ERR Command not allowed inside a transaction").encode(out);
+                } else {
+                    queued.push(args.to_vec());
+                    Reply::Status("QUEUED".to_string()).encode(out);
+                }
+                return Flow::Continue;
+            }
+        }
+
+        let reply = match name.as_str() {
+            "QUIT" => {
+                Reply::ok().encode(out);
+                return Flow::Close;
+            }
+            "AUTH" => self.auth(&args[1..]),
+            "WATCH" if self.queued.is_some() => {
+                Reply::error("ERR WATCH inside MULTI is not allowed")
+            }
+            "WATCH" if args.len() < 2 => {
+                Reply::error("ERR wrong number of arguments for 'watch' command")
+            }
+            "WATCH" => self.watch(&args[1..]),
+            "UNWATCH" => {
+                self.unwatch();
+                Reply::ok()
+            }
+            "MULTI" if self.queued.is_some() => Reply::error("ERR MULTI calls can not be nested"),
+            "MULTI" => {
+                self.queued = Some(Vec::new());
+                self.queue_failed = false;
+                Reply::ok()
+            }
+            "DISCARD" => match self.queued.take() {
+                Some(_) => {
+                    self.unwatch();
+                    Reply::ok()
+                }
+                None => Reply::error("ERR DISCARD without MULTI"),
+            },
+            "EXEC" => match self.queued.take() {
+                None => Reply::error("ERR EXEC without MULTI"),
+                Some(_) if self.queue_failed => {
+                    self.unwatch();
+                    Reply::error("EXECABORT Transaction discarded because of previous errors.")
+                }
+                Some(queued) => {
+                    let reply = self.exec(queued);
+                    self.unwatch();
+                    reply
+                }
+            },
+            "SUBSCRIBE" | "PSUBSCRIBE" if args.len() < 2 => Reply::error(format!(
+                "ERR wrong number of arguments for '{}' command",
+                name.to_ascii_lowercase()
+            )),
+            "SUBSCRIBE" | "PSUBSCRIBE" => {
+                self.subscribe(&args[1..], name == "PSUBSCRIBE", out);
+                return Flow::Continue;
+            }
+            "UNSUBSCRIBE" | "PUNSUBSCRIBE" => {
+                self.unsubscribe(&args[1..], name == "PUNSUBSCRIBE", out);
+                return Flow::Continue;
+            }
+            _ => self.execute(args),
+        };
+        reply.encode(out);
+        Flow::Continue
+    }
+
+    /// `AUTH password` or `AUTH default password`
+    fn auth(&mut self, args: &[Vec<u8>]) -> Reply {
+        let password = match args {
+            [password] => password,
+            [user, password] if user.as_slice() == b"default" => password,
+            [_, _] => {
+                return Reply::error(
+                    "WRONGPASS invalid username-password pair or user is disabled.",
+                )
+            }
+            _ => return Reply::error("ERR wrong number of arguments for 'auth' command"),
+        };
+        self.authenticated = constant_time_eq(password, self.shared.password.as_bytes());
+        if self.authenticated {
+            Reply::ok()
+        } else {
+            Reply::error("WRONGPASS invalid username-password pair or user is disabled.")
+        }
+    }
+
+    /// Runs a command that doesn't depend on the connection
+    fn execute(&self, args: &[Vec<u8>]) -> Reply {
+        if args[0].eq_ignore_ascii_case(b"PUBLISH") {
+            if args.len() != 3 {
+                return Reply::error("ERR wrong number of arguments for 'publish' command");
+            }
+            let channel = String::from_utf8_lossy(&args[1]);
+            self.shared.store.lock().stats.commands_processed += 1;
+            return Reply::Integer(self.shared.broker.publish(&channel, &args[2]));
+        }
+        commands::execute(&mut self.shared.store.lock(), args)
+    }
+
+    fn watch(&mut self, keys: &[Vec<u8>]) -> Reply {
+        let mut db = self.shared.store.lock();
+        for key in keys {
+            let key = String::from_utf8_lossy(key).into_owned();
+            if !self.watched.iter().any(|(watched, _)| *watched == key) {
+                let version = db.watch(&key);
+                self.watched.push((key, version));
+            }
+        }
+        Reply::ok()
+    }
+
+    fn unwatch(&mut self) {
+        if self.watched.is_empty() {
+            return;
+        }
+        let mut db = self.shared.store.lock();
+        for (key, _) in self.watched.drain(..) {
+            db.unwatch(&key);
+        }
+    }
+
+    /// Runs queued commands back to back so no other client sees a partial result
+    ///
+    /// Nothing runs, and the reply is nil, if a watched key changed since WATCH.
+    fn exec(&self, queued: Vec<Command>) -> Reply {
+        let mut db = self.shared.store.lock();
+        if self
+            .watched
+            .iter()
+            .any(|(key, version)| db.watched_version(key) != Some(*version))
+        {
+            return Reply::Nil;
+        }
+        let replies = queued
+            .iter()
+            .map(|args| {
+                if args[0].eq_ignore_ascii_case(b"PUBLISH") && args.len() == 3 {
+                    let channel = String::from_utf8_lossy(&args[1]);
+                    Reply::Integer(self.shared.broker.publish(&channel, &args[2]))
+                } else {
+                    commands::execute(&mut db, args)
+                }
+            })
+            .collect();
+        Reply::Array(replies)
+    }
+
+    fn subscribe(&mut self, names: &[Vec<u8>], pattern: bool, out: &mut Vec<u8>) {
+        let kind = if pattern { "psubscribe" } else { "subscribe" };
+        let mut subscribers = self
+            .shared
+            .broker
+            .subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let subscriber = subscribers.entry(self.id).or_insert_with(|| Subscriber {
+            channels: HashSet::new(),
+            patterns: HashSet::new(),
+            tx: self.push_tx.clone(),
+        });
+        for name in names {
+            let name = String::from_utf8_lossy(name).into_owned();
+            let targets = if pattern {
+                &mut subscriber.patterns
+            } else {
+                &mut subscriber.channels
+            };
+            targets.insert(name.clone());
+            self.subscriptions = subscriber.channels.len() + subscriber.patterns.len();
+            Reply::Array(vec![
+                Reply::bulk(kind),
+                Reply::bulk(name),
+                Reply::Integer(self.subscriptions as i64),
+            ])
+            .encode(out);
+        }
+    }
+
+    fn unsubscribe(&mut self, names: &[Vec<u8>], pattern: bool, out: &mut Vec<u8>) {
+        let kind = if pattern {
+            "punsubscribe"
+        } else {
+            "unsubscribe"
+        };
+        let mut subscribers = self
+            .shared
+            .broker
+            .subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let Some(subscriber) = subscribers.get_mut(&self.id) else {
+            Reply::Array(vec![Reply::bulk(kind), Reply::Nil, Reply::Integer(0)]).encode(out);
+            return;
+        };
+        // Without arguments every channel (or pattern) is dropped
+        let mut names: Vec<String> = if names.is_empty() {
+            let current = if pattern {
+                &subscriber.patterns
+            } else {
+                &subscriber.channels
+            };
+            current.iter().cloned().collect()
+        } else {
+            names
+                .iter()
+                .map(|name| String::from_utf8_lossy(name).into_owned())
+                .collect()
+        };
+        names.sort();
+        if names.is_empty() {
+            Reply::Array(vec![
+                Reply::bulk(kind),
+                Reply::Nil,
+                Reply::Integer(self.subscriptions as i64),
+            ])
+            .encode(out);
+        }
+        for name in names {
+            if pattern {
+                subscriber.patterns.remove(&name);
+            } else {
+                subscriber.channels.remove(&name);
+            }
+            self.subscriptions = subscriber.channels.len() + subscriber.patterns.len();
+            Reply::Array(vec![
+                Reply::bulk(kind),
+                Reply::bulk(name),
+                Reply::Integer(self.subscriptions as i64),
+            ])
+            .encode(out);
+        }
+        if self.subscriptions == 0 {
+            subscribers.remove(&self.id);
+        }
+    }
+
+    fn unsubscribe_all(&mut self) {
+        self.shared
+            .broker
+            .subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.id);
+        self.subscriptions = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn roundtrip(socket: &mut TcpStream, request: &[u8], expected: &[u8]) {
+        socket.write_all(request).await.unwrap();
+        let mut reply = vec![0u8; expected.len()];
+        socket.read_exact(&mut reply).await.unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&reply),
+            String::from_utf8_lossy(expected)
+        );
+    }
+
+    /// Connects and authenticates
+    async fn connect(server: &MemoryServer) -> TcpStream {
+        let mut socket = TcpStream::connect(server.addr()).await.unwrap();
+        let auth = format!("AUTH {}\r\n", server.password());
+        roundtrip(&mut socket, auth.as_bytes(), b"+OK\r\n").await;
+        socket
+    }
+
+    #[tokio::test]
+    async fn test_commands_need_auth() {
+        let server = MemoryServer::start(MemoryStore::new()).await.unwrap();
+        assert_eq!(server.password().len(), 32);
+        assert!(server.url().contains(server.password()));
+
+        let mut client = TcpStream::connect(server.addr()).await.unwrap();
+        roundtrip(
+            &mut client,
+            b"SET key value\r\n",
+            b"-NOAUTH Authentication required.\r\n",
+        )
+        .await;
+        roundtrip(
+            &mut client,
+            b"AUTH guess\r\n",
+            b"-WRONGPASS invalid username-password pair or user is disabled.\r\n",
+        )
+        .await;
+        let auth = format!("AUTH default {}\r\n", server.password());
+        roundtrip(&mut client, auth.as_bytes(), b"+OK\r\n").await;
+        roundtrip(&mut client, b"SET key value\r\n", b"+OK\r\n").await;
+
+        let client = redis::Client::open(server.url()).unwrap();
+        let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+        let value: String = redis::cmd("GET")
+            .arg("key")
+            .query_async(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(value, "value");
+    }
+
+    #[tokio::test]
+    async fn test_transactions_and_pubsub_over_tcp() {
+        let server = MemoryServer::start(MemoryStore::new()).await.unwrap();
+        let mut client = connect(&server).await;
+        roundtrip(&mut client, b"MULTI\r\n", b"+OK\r\n").await;
+        roundtrip(&mut client, b"INCR hits\r\n", b"+QUEUED\r\n").await;
+        roundtrip(&mut client, b"INCR hits\r\n", b"+QUEUED\r\n").await;
+        roundtrip(&mut client, b"EXEC\r\n", b"*2\r\n:1\r\n:2\r\n").await;
+
+        // A transaction on a key that changed after WATCH doesn't run
+        roundtrip(&mut client, b"WATCH hits\r\n", b"+OK\r\n").await;
+        let mut other = connect(&server).await;
+        roundtrip(&mut other, b"INCR hits\r\n", b":3\r\n").await;
+        roundtrip(&mut client, b"MULTI\r\n", b"+OK\r\n").await;
+        roundtrip(&mut client, b"INCR hits\r\n", b"+QUEUED\r\n").await;
+        roundtrip(&mut client, b"EXEC\r\n", b"$-1\r\n").await;
+        roundtrip(&mut client, b"GET hits\r\n", b"$1\r\n3\r\n").await;
+
+        // EXEC dropped the watch, and reads don't count as changes
+        roundtrip(&mut client, b"WATCH hits\r\n", b"+OK\r\n").await;
+        roundtrip(&mut other, b"GET hits\r\n", b"$1\r\n3\r\n").await;
+        roundtrip(&mut client, b"MULTI\r\n", b"+OK\r\n").await;
+        roundtrip(&mut client, b"INCR hits\r\n", b"+QUEUED\r\n").await;
+        roundtrip(&mut client, b"EXEC\r\n", b"*1\r\n:4\r\n").await;
+
+        let mut subscriber = connect(&server).await;
+        roundtrip(
+            &mut subscriber,
+            b"SUBSCRIBE news\r\n",
+            b"*3\r\n$9\r\nsubscribe\r\n$4\r\nnews\r\n:1\r\n",
+        )
+        .await;
+        roundtrip(&mut client, b"PUBLISH news hello\r\n", b":1\r\n").await;
+        let expected = b"*3\r\n$7\r\nmessage\r\n$4\r\nnews\r\n$5\r\nhello\r\n";
+        let mut message = vec![0u8; expected.len()];
+        subscriber.read_exact(&mut message).await.unwrap();
+        assert_eq!(message, expected);
+
+        // A subscribed connection only takes pub/sub commands, as in RESP2
+        roundtrip(
+            &mut subscriber,
+            b"GET hits\r\n",
+            b"-ERR Can't execute 'get': only (P|S)SUBSCRIBE / (P|S)UNSUBSCRIBE / PING / QUIT / RESET are allowed in this context\r\n",
+        )
+        .await;
+        roundtrip(
+            &mut subscriber,
+            b"PING\r\n",
+            b"*2\r\n$4\r\npong\r\n$0\r\n\r\n",
+        )
+        .await;
+        roundtrip(
+            &mut subscriber,
+            b"UNSUBSCRIBE news\r\n",
+            b"*3\r\n$11\r\nunsubscribe\r\n$4\r\nnews\r\n:0\r\n",
+        )
+        .await;
+        roundtrip(&mut subscriber, b"GET hits\r\n", b"$1\r\n4\r\n").await;
+    }
+}
@@ -0,0 +1,569 @@
+//! Keyspace of the in-memory backend
+//!
+//! Values are typed like in Redis, keys may carry an expiry, and collections
+//! that become empty are removed. Expired keys are dropped lazily when they
+//! are next looked at.
+//!
+//! A store can write through to durable storage: with a [`Persistence`]
+//! attached, the keys each write command touched are handed to it before the
+//! command replies. A write that cannot be saved is rolled back, so memory
+//! never holds data the storage does not.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::glob::glob_match;
+
+/// Error text Redis uses when a command hits a key of another type
+pub const WRONGTYPE: &str = "WRONGTYPE Operation against a key holding the wrong kind of value";
+
+/// A key was accessed as a type it doesn't hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{}", WRONGTYPE)]
+pub struct WrongType;
+
+/// A value stored under a key
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(Vec<u8>),
+    Hash(HashMap<String, String>),
+    Set(HashSet<String>),
+    SortedSet(HashMap<String, f64>),
+    List(VecDeque<String>),
+}
+
+impl Value {
+    /// The name TYPE reports for this value
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Hash(_) => "hash",
+            Value::Set(_) => "set",
+            Value::SortedSet(_) => "zset",
+            Value::List(_) => "list",
+        }
+    }
+
+    fn is_empty_collection(&self) -> bool {
+        match self {
+            Value::String(_) => false,
+            Value::Hash(hash) => hash.is_empty(),
+            Value::Set(set) => set.is_empty(),
+            Value::SortedSet(zset) => zset.is_empty(),
+            Value::List(list) => list.is_empty(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Entry {
+    value: Value,
+    expires_at: Option<Instant>,
+}
+
+impl Entry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+}
+
+/// Server counters reported by INFO
+#[derive(Debug, Default, Clone)]
+pub struct Stats {
+    pub commands_processed: u64,
+    pub connections_received: u64,
+    pub connected_clients: u64,
+    pub keyspace_hits: u64,
+    pub keyspace_misses: u64,
+    pub expired_keys: u64,
+}
+
+/// Keys touched since changes were last saved
+#[derive(Debug, Default)]
+pub struct Changes {
+    /// Whether every key was removed first
+    pub cleared: bool,
+    pub keys: HashSet<String>,
+}
+
+impl Changes {
+    pub fn is_empty(&self) -> bool {
+        !self.cleared && self.keys.is_empty()
+    }
+}
+
+/// Durable storage a [`MemoryStore`] writes through to
+pub trait Persistence: Send + Sync {
+    /// Save the current state of the changed keys
+    ///
+    /// Called with the keyspace locked; keys that no longer exist have been
+    /// removed.
+    fn save(&self, db: &Db, changes: &Changes) -> Result<(), String>;
+}
+
+/// The state of the keys a write touched, from before it touched them
+#[derive(Default)]
+struct Undo {
+    /// `None` for keys that did not exist
+    entries: HashMap<String, Option<Entry>>,
+    /// Every entry, when the write removed them all
+    cleared: Option<HashMap<String, Entry>>,
+}
+
+/// A key some connection is watching, for WATCH
+struct Watched {
+    watchers: usize,
+    /// Bumped whenever the key may have changed
+    version: u64,
+}
+
+/// The keyspace and server state behind a [`MemoryStore`]
+pub struct Db {
+    entries: HashMap<String, Entry>,
+    config: HashMap<String, String>,
+    started: Instant,
+    persistence: Option<Arc<dyn Persistence>>,
+    changes: Changes,
+    /// Set between [`Db::begin_write`] and the save that ends the write
+    undo: Option<Undo>,
+    /// Whether a write has begun, so the keys it touches count as modified
+    writing: bool,
+    watched: HashMap<String, Watched>,
+    pub stats: Stats,
+}
+
+impl Default for Db {
+    fn default() -> Self {
+        let config = [
+            ("appendonly", "no"),
+            ("databases", "1"),
+            ("maxmemory", "0"),
+            ("maxmemory-policy", "noeviction"),
+            ("save", ""),
+            ("timeout", "0"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+        Self {
+            entries: HashMap::new(),
+            config,
+            started: Instant::now(),
+            persistence: None,
+            changes: Changes::default(),
+            undo: None,
+            writing: false,
+            watched: HashMap::new(),
+            stats: Stats::default(),
+        }
+    }
+}
+
+/// Generates the accessors for one value type: a lookup that leaves missing
+/// keys alone and one that creates an empty value first
+macro_rules! typed_accessors {
+    ($get:ident, $get_or_create:ident, $variant:ident, $ty:ty) => {
+        pub fn $get(&mut self, key: &str) -> Result<Option<&mut $ty>, WrongType> {
+            match self.value(key) {
+                None => Ok(None),
+                Some(Value::$variant(value)) => Ok(Some(value)),
+                Some(_) => Err(WrongType),
+            }
+        }
+
+        pub fn $get_or_create(&mut self, key: &str) -> Result<&mut $ty, WrongType> {
+            self.purge_if_expired(key);
+            let entry = self
+                .entries
+                .entry(key.to_string())
+                .or_insert_with(|| Entry {
+                    value: Value::$variant(Default::default()),
+                    expires_at: None,
+                });
+            match &mut entry.value {
+                Value::$variant(value) => Ok(value),
+                _ => Err(WrongType),
+            }
+        }
+    };
+}
+
+impl Db {
+    /// Records a key that may change, when there is somewhere to save it
+    fn touch(&mut self, key: &str) {
+        if self.writing {
+            self.modified(key);
+        }
+        if self.persistence.is_some() && !self.changes.keys.contains(key) {
+            self.changes.keys.insert(key.to_string());
+        }
+        if let Some(undo) = &mut self.undo {
+            if !undo.entries.contains_key(key) {
+                // After a clear the key's old state is in the cleared entries
+                let before = self
+                    .entries
+                    .get(key)
+                    .or_else(|| undo.cleared.as_ref().and_then(|cleared| cleared.get(key)))
+                    .cloned();
+                undo.entries.insert(key.to_string(), before);
+            }
+        }
+    }
+
+    fn purge_if_expired(&mut self, key: &str) {
+        self.touch(key);
+        if self
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.is_expired(Instant::now()))
+        {
+            self.entries.remove(key);
+            self.stats.expired_keys += 1;
+            self.modified(key);
+        }
+    }
+
+    fn purge_expired(&mut self) {
+        let now = Instant::now();
+        let before = self.entries.len();
+        let watched = &mut self.watched;
+        self.entries.retain(|key, entry| {
+            let expired = entry.is_expired(now);
+            if let Some(watched) = watched.get_mut(key).filter(|_| expired) {
+                watched.version += 1;
+            }
+            !expired
+        });
+        self.stats.expired_keys += (before - self.entries.len()) as u64;
+    }
+
+    /// Lets the connections watching `key` know it may have changed
+    fn modified(&mut self, key: &str) {
+        if let Some(watched) = self.watched.get_mut(key) {
+            watched.version += 1;
+        }
+    }
+
+    /// Looks up a live value, counting the keyspace hit or miss
+    pub fn value(&mut self, key: &str) -> Option<&mut Value> {
+        self.purge_if_expired(key);
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                self.stats.keyspace_hits += 1;
+                Some(&mut entry.value)
+            }
+            None => {
+                self.stats.keyspace_misses += 1;
+                None
+            }
+        }
+    }
+
+    typed_accessors!(string, string_or_create, String, Vec<u8>);
+    typed_accessors!(hash, hash_or_create, Hash, HashMap<String, String>);
+    typed_accessors!(set, set_or_create, Set, HashSet<String>);
+    typed_accessors!(zset, zset_or_create, SortedSet, HashMap<String, f64>);
+    typed_accessors!(list, list_or_create, List, VecDeque<String>);
+
+    pub fn contains(&mut self, key: &str) -> bool {
+        self.purge_if_expired(key);
+        self.entries.contains_key(key)
+    }
+
+    /// Stores a value, replacing the old one and its expiry
+    pub fn insert(&mut self, key: &str, value: Value) {
+        self.touch(key);
+        self.entries.insert(
+            key.to_string(),
+            Entry {
+                value,
+                expires_at: None,
+            },
+        );
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.purge_if_expired(key);
+        self.entries.remove(key).is_some()
+    }
+
+    /// Removes and returns a value, e.g. to move it to another key
+    pub fn take(&mut self, key: &str) -> Option<Value> {
+        self.purge_if_expired(key);
+        self.entries.remove(key).map(|entry| entry.value)
+    }
+
+    /// Drops `key` if it holds a collection that has become empty
+    pub fn remove_if_empty(&mut self, key: &str) {
+        self.touch(key);
+        if self
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.value.is_empty_collection())
+        {
+            self.entries.remove(key);
+        }
+    }
+
+    /// Sets the time to live of an existing key, returning whether it exists
+    pub fn expire(&mut self, key: &str, ttl: Duration) -> bool {
+        self.purge_if_expired(key);
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                entry.expires_at = Some(Instant::now() + ttl);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the expiry of a key, returning whether it had one
+    pub fn persist(&mut self, key: &str) -> bool {
+        self.purge_if_expired(key);
+        self.entries
+            .get_mut(key)
+            .and_then(|entry| entry.expires_at.take())
+            .is_some()
+    }
+
+    /// Remaining time to live: `None` if the key is missing, `Some(None)` if it never expires
+    pub fn ttl(&mut self, key: &str) -> Option<Option<Duration>> {
+        self.purge_if_expired(key);
+        self.entries.get(key).map(|entry| {
+            entry
+                .expires_at
+                .map(|at| at.saturating_duration_since(Instant::now()))
+        })
+    }
+
+    /// Live keys matching a glob-style pattern, sorted
+    pub fn keys(&mut self, pattern: &str) -> Vec<String> {
+        self.purge_expired();
+        let mut keys: Vec<String> = self
+            .entries
+            .keys()
+            .filter(|key| glob_match(pattern, key))
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    pub fn len(&mut self) -> usize {
+        self.purge_expired();
+        self.entries.len()
+    }
+
+    pub fn is_empty(&mut self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every key
+    pub fn clear(&mut self) {
+        match &mut self.undo {
+            Some(undo) if undo.cleared.is_none() => {
+                undo.cleared = Some(std::mem::take(&mut self.entries));
+            }
+            _ => self.entries.clear(),
+        }
+        for watched in self.watched.values_mut() {
+            watched.version += 1;
+        }
+        self.changes.keys.clear();
+        self.changes.cleared = self.persistence.is_some();
+    }
+
+    /// A live value and its remaining time to live, without counting a keyspace hit
+    pub fn snapshot(&self, key: &str) -> Option<(&Value, Option<Duration>)> {
+        let now = Instant::now();
+        self.entries
+            .get(key)
+            .filter(|entry| !entry.is_expired(now))
+            .map(|entry| {
+                (
+                    &entry.value,
+                    entry.expires_at.map(|at| at.saturating_duration_since(now)),
+                )
+            })
+    }
+
+    /// Remember the keys the next write touches, so it can be rolled back
+    /// if [`Db::save_changes`] fails
+    pub fn begin_write(&mut self) {
+        self.writing = true;
+        if self.persistence.is_some() {
+            self.undo = Some(Undo::default());
+        }
+    }
+
+    /// Hands the keys touched since the last call to the attached persistence
+    ///
+    /// When saving fails, the write begun with [`Db::begin_write`] is undone.
+    pub fn save_changes(&mut self) -> Result<(), String> {
+        let changes = std::mem::take(&mut self.changes);
+        let undo = self.undo.take();
+        self.writing = false;
+        let result = match &self.persistence {
+            Some(persistence) if !changes.is_empty() => persistence.save(self, &changes),
+            _ => Ok(()),
+        };
+        if let (Err(_), Some(undo)) = (&result, undo) {
+            self.rollback(undo);
+        }
+        result
+    }
+
+    fn rollback(&mut self, undo: Undo) {
+        if let Some(cleared) = undo.cleared {
+            self.entries = cleared;
+        }
+        for (key, entry) in undo.entries {
+            match entry {
+                Some(entry) => self.entries.insert(key, entry),
+                None => self.entries.remove(&key),
+            };
+        }
+    }
+
+    /// Forgets the keys touched since the last save, e.g. after a read-only command
+    pub fn discard_changes(&mut self) {
+        self.changes = Changes::default();
+        self.undo = None;
+        self.writing = false;
+    }
+
+    /// Starts watching `key` for one more connection, returning its version
+    pub fn watch(&mut self, key: &str) -> u64 {
+        let watched = self.watched.entry(key.to_string()).or_insert(Watched {
+            watchers: 0,
+            version: 0,
+        });
+        watched.watchers += 1;
+        watched.version
+    }
+
+    /// Stops watching `key` for one connection
+    pub fn unwatch(&mut self, key: &str) {
+        if let Some(watched) = self.watched.get_mut(key) {
+            watched.watchers -= 1;
+            if watched.watchers == 0 {
+                self.watched.remove(key);
+            }
+        }
+    }
+
+    /// The version of a watched key, which changes when the key is written or expires
+    pub fn watched_version(&mut self, key: &str) -> Option<u64> {
+        self.contains(key);
+        self.discard_changes();
+        self.watched.get(key).map(|watched| watched.version)
+    }
+
+    /// Number of live keys that have an expiry
+    pub fn expiring_len(&mut self) -> usize {
+        self.purge_expired();
+        self.entries
+            .values()
+            .filter(|entry| entry.expires_at.is_some())
+            .count()
+    }
+
+    /// Rough number of bytes held by keys and values
+    pub fn approximate_memory(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|(key, entry)| {
+                key.len()
+                    + match &entry.value {
+                        Value::String(bytes) => bytes.len(),
+                        Value::Hash(hash) => hash.iter().map(|(f, v)| f.len() + v.len()).sum(),
+                        Value::Set(set) => set.iter().map(String::len).sum(),
+                        Value::SortedSet(zset) => zset.keys().map(|m| m.len() + 8).sum(),
+                        Value::List(list) => list.iter().map(String::len).sum(),
+                    }
+            })
+            .sum()
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Configuration parameters matching a glob-style pattern, sorted by name
+    pub fn config_get(&self, pattern: &str) -> Vec<(String, String)> {
+        let mut params: Vec<(String, String)> = self
+            .config
+            .iter()
+            .filter(|(name, _)| glob_match(pattern, name))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        params.sort();
+        params
+    }
+
+    pub fn config_set(&mut self, name: &str, value: &str) {
+        self.config
+            .insert(name.to_ascii_lowercase(), value.to_string());
+    }
+}
+
+/// Shared handle to an in-memory keyspace
+///
+/// Clones share the same data, so a store can be handed to a
+/// [`MemoryAdapter`](super::MemoryAdapter) and a
+/// [`MemoryServer`](super::MemoryServer) at the same time.
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    db: Arc<Mutex<Db>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a store whose write commands are saved to `persistence`
+    ///
+    /// Existing data is not loaded; fill the store before serving it.
+    pub fn with_persistence(persistence: Arc<dyn Persistence>) -> Self {
+        let store = Self::default();
+        store.lock().persistence = Some(persistence);
+        store
+    }
+
+    /// Locks the keyspace; keep the guard short-lived and never hold it across an await
+    pub fn lock(&self) -> MutexGuard<'_, Db> {
+        self.db
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_keys_are_dropped() {
+        let store = MemoryStore::new();
+        let mut db = store.lock();
+        db.insert("session", Value::String(b"abc".to_vec()));
+        assert!(db.expire("session", Duration::ZERO));
+        assert!(!db.contains("session"));
+        assert_eq!(db.ttl("session"), None);
+        assert_eq!(db.stats.expired_keys, 1);
+    }
+
+    #[test]
+    fn test_typed_access_and_empty_collections() {
+        let store = MemoryStore::new();
+        let mut db = store.lock();
+        db.set_or_create("tags").unwrap().insert("rust".to_string());
+        assert_eq!(db.hash("tags"), Err(WrongType));
+
+        db.set("tags").unwrap().unwrap().remove("rust");
+        db.remove_if_empty("tags");
+        assert!(db.is_empty());
+    }
+}
@@ -0,0 +1,557 @@
+//! Adapter trait implementations for Redis
+//!
+//! [`Redis`] and [`RedisPoolAdapter`] implement the traits in
+//! [`crate::traits`], so services can be written against the traits and run
+//! on another backend or a mock. Redis errors are mapped into
+//! [`AdapterError`].
+
+use async_trait::async_trait;
+use redis::{Commands, ConnectionLike, ErrorKind, RedisError};
+use std::collections::HashMap;
+
+use super::Redis;
+use crate::error::{AdapterError, ConnectionError, OperationError};
+use crate::traits::{
+    AsyncDatabaseAdapter, DatabaseAdapter, HashAdapter, KeyValueAdapter, SetAdapter,
+    SortedSetAdapter,
+};
+
+#[cfg(feature = "connection-pool")]
+use super::async_primitives::{
+    admin::AsyncAdminOperations, hash::AsyncRedisHash, set::AsyncRedisSet,
+    sorted_set::AsyncRedisSortedSet, string::AsyncRedisString,
+};
+#[cfg(feature = "connection-pool")]
+use super::client::{AsyncPooledConnection, PooledConnection};
+#[cfg(feature = "connection-pool")]
+use super::RedisPoolAdapter;
+#[cfg(feature = "connection-pool")]
+use crate::traits::PooledAdapter;
+
+impl From<RedisError> for AdapterError {
+    fn from(err: RedisError) -> Self {
+        let message = err.to_string();
+        if err.is_timeout() {
+            return ConnectionError::Timeout(message).into();
+        }
+        if err.is_connection_refusal() {
+            return ConnectionError::ConnectionFailed(message).into();
+        }
+        if err.is_connection_dropped() {
+            return ConnectionError::ConnectionClosed(message).into();
+        }
+        match err.kind() {
+            ErrorKind::AuthenticationFailed => {
+                ConnectionError::AuthenticationFailed(message).into()
+            }
+            ErrorKind::InvalidClientConfig => ConnectionError::InvalidUrl(message).into(),
+            ErrorKind::IoError => ConnectionError::ConnectionFailed(message).into(),
+            ErrorKind::TypeError => OperationError::Deserialization(message).into(),
+            ErrorKind::ResponseError | ErrorKind::ExecAbortError | ErrorKind::NoScriptError => {
+                OperationError::Failed(message).into()
+            }
+            _ => AdapterError::Database(message),
+        }
+    }
+}
+
+// Redis runs every command on its single blocking connection; use
+// RedisPoolAdapter where calls must not block the runtime.
+
+impl DatabaseAdapter for Redis {
+    type Error = AdapterError;
+
+    fn is_connected(&self) -> bool {
+        self.client
+            .connection()
+            .lock()
+            .map(|conn| conn.is_open())
+            .unwrap_or(false)
+    }
+}
+
+#[async_trait]
+impl AsyncDatabaseAdapter for Redis {
+    async fn ping(&self) -> Result<bool, Self::Error> {
+        Ok(Redis::ping(self)?)
+    }
+
+    /// The connection closes when the last clone of the client is dropped,
+    /// so there is nothing to do here.
+    async fn close(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl KeyValueAdapter for Redis {
+    async fn get<K: AsRef<str> + Send>(&self, key: K) -> Result<Option<String>, Self::Error> {
+        Ok(self.string().get(key.as_ref())?)
+    }
+
+    async fn set<K: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        value: V,
+    ) -> Result<(), Self::Error> {
+        Ok(self.string().set(key.as_ref(), value.as_ref())?)
+    }
+
+    async fn set_with_expiry<K: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        value: V,
+        expiry_seconds: u64,
+    ) -> Result<(), Self::Error> {
+        Ok(self
+            .string()
+            .set_with_expiry(key.as_ref(), value.as_ref(), expiry_seconds as usize)?)
+    }
+
+    async fn delete<K: AsRef<str> + Send>(&self, key: K) -> Result<bool, Self::Error> {
+        let mut conn = self.client.connection().lock().unwrap();
+        let removed: usize = conn.del(key.as_ref())?;
+        Ok(removed > 0)
+    }
+
+    async fn exists<K: AsRef<str> + Send>(&self, key: K) -> Result<bool, Self::Error> {
+        Ok(self.string().exists(key.as_ref())?)
+    }
+}
+
+#[async_trait]
+impl HashAdapter for Redis {
+    async fn hget<K: AsRef<str> + Send, F: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+    ) -> Result<Option<String>, Self::Error> {
+        Ok(self.hash().hget(key.as_ref(), field.as_ref())?)
+    }
+
+    async fn hset<K: AsRef<str> + Send, F: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+        value: V,
+    ) -> Result<(), Self::Error> {
+        self.hash()
+            .hset(key.as_ref(), field.as_ref(), value.as_ref())?;
+        Ok(())
+    }
+
+    async fn hgetall<K: AsRef<str> + Send>(
+        &self,
+        key: K,
+    ) -> Result<HashMap<String, String>, Self::Error> {
+        Ok(self.hash().hgetall(key.as_ref())?)
+    }
+
+    async fn hdel<K: AsRef<str> + Send, F: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.hash().hdel(key.as_ref(), &[field.as_ref()])? > 0)
+    }
+}
+
+#[async_trait]
+impl SetAdapter for Redis {
+    async fn sadd<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.set().sadd(key.as_ref(), &[member.as_ref()])? > 0)
+    }
+
+    async fn srem<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.set().srem(key.as_ref(), &[member.as_ref()])? > 0)
+    }
+
+    async fn sismember<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.set().sismember(key.as_ref(), member.as_ref())?)
+    }
+
+    async fn smembers<K: AsRef<str> + Send>(&self, key: K) -> Result<Vec<String>, Self::Error> {
+        Ok(self.set().smembers(key.as_ref())?)
+    }
+}
+
+#[async_trait]
+impl SortedSetAdapter for Redis {
+    async fn zadd<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        score: f64,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        Ok(self
+            .sorted_set()
+            .zadd_single(key.as_ref(), score, member.as_ref())?
+            > 0)
+    }
+
+    async fn zrem<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.sorted_set().zrem(key.as_ref(), &[member.as_ref()])? > 0)
+    }
+
+    async fn zscore<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<Option<f64>, Self::Error> {
+        Ok(self.sorted_set().zscore(key.as_ref(), member.as_ref())?)
+    }
+
+    async fn zcard<K: AsRef<str> + Send>(&self, key: K) -> Result<usize, Self::Error> {
+        Ok(self.sorted_set().zcard(key.as_ref())?)
+    }
+
+    async fn zrange<K: AsRef<str> + Send>(
+        &self,
+        key: K,
+        start: isize,
+        stop: isize,
+    ) -> Result<Vec<(String, f64)>, Self::Error> {
+        Ok(self
+            .sorted_set()
+            .zrange_withscores(key.as_ref(), start, stop)?)
+    }
+}
+
+// RedisPoolAdapter sends commands over checkouts of the pool's multiplexed
+// connection and only checks out a dedicated connection for
+// PooledAdapter::get_connection.
+
+#[cfg(feature = "connection-pool")]
+impl RedisPoolAdapter {
+    async fn multiplexed(&self) -> Result<AsyncPooledConnection, AdapterError> {
+        Ok(self.pool().get_multiplexed_connection().await?)
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+impl DatabaseAdapter for RedisPoolAdapter {
+    type Error = AdapterError;
+
+    fn is_connected(&self) -> bool {
+        self.pool().is_connected()
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+#[async_trait]
+impl AsyncDatabaseAdapter for RedisPoolAdapter {
+    async fn ping(&self) -> Result<bool, Self::Error> {
+        let pong = AsyncAdminOperations::new(self.multiplexed().await?)
+            .ping()
+            .await?;
+        Ok(pong == "PONG")
+    }
+
+    async fn close(&self) -> Result<(), Self::Error> {
+        self.pool().close().await;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+#[async_trait]
+impl KeyValueAdapter for RedisPoolAdapter {
+    async fn get<K: AsRef<str> + Send>(&self, key: K) -> Result<Option<String>, Self::Error> {
+        let string = AsyncRedisString::new(self.multiplexed().await?);
+        Ok(string.get(key.as_ref()).await?)
+    }
+
+    async fn set<K: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        value: V,
+    ) -> Result<(), Self::Error> {
+        let string = AsyncRedisString::new(self.multiplexed().await?);
+        Ok(string.set(key.as_ref(), value.as_ref()).await?)
+    }
+
+    async fn set_with_expiry<K: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        value: V,
+        expiry_seconds: u64,
+    ) -> Result<(), Self::Error> {
+        let string = AsyncRedisString::new(self.multiplexed().await?);
+        Ok(string
+            .set_with_expiry(key.as_ref(), value.as_ref(), expiry_seconds as usize)
+            .await?)
+    }
+
+    async fn delete<K: AsRef<str> + Send>(&self, key: K) -> Result<bool, Self::Error> {
+        let mut conn = self.multiplexed().await?;
+        let removed: usize = redis::AsyncCommands::del(&mut conn, key.as_ref()).await?;
+        Ok(removed > 0)
+    }
+
+    async fn exists<K: AsRef<str> + Send>(&self, key: K) -> Result<bool, Self::Error> {
+        let string = AsyncRedisString::new(self.multiplexed().await?);
+        Ok(string.exists(key.as_ref()).await?)
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+#[async_trait]
+impl HashAdapter for RedisPoolAdapter {
+    async fn hget<K: AsRef<str> + Send, F: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+    ) -> Result<Option<String>, Self::Error> {
+        let hash = AsyncRedisHash::new(self.multiplexed().await?);
+        Ok(hash.hget(key.as_ref(), field.as_ref()).await?)
+    }
+
+    async fn hset<K: AsRef<str> + Send, F: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+        value: V,
+    ) -> Result<(), Self::Error> {
+        let hash = AsyncRedisHash::new(self.multiplexed().await?);
+        hash.hset(key.as_ref(), field.as_ref(), value.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn hgetall<K: AsRef<str> + Send>(
+        &self,
+        key: K,
+    ) -> Result<HashMap<String, String>, Self::Error> {
+        let hash = AsyncRedisHash::new(self.multiplexed().await?);
+        Ok(hash.hgetall(key.as_ref()).await?)
+    }
+
+    async fn hdel<K: AsRef<str> + Send, F: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+    ) -> Result<bool, Self::Error> {
+        let hash = AsyncRedisHash::new(self.multiplexed().await?);
+        Ok(hash.hdel(key.as_ref(), &[field.as_ref()]).await? > 0)
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+#[async_trait]
+impl SetAdapter for RedisPoolAdapter {
+    async fn sadd<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let set = AsyncRedisSet::new(self.multiplexed().await?);
+        Ok(set.sadd(key.as_ref(), &[member.as_ref()]).await? > 0)
+    }
+
+    async fn srem<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let set = AsyncRedisSet::new(self.multiplexed().await?);
+        Ok(set.srem(key.as_ref(), &[member.as_ref()]).await? > 0)
+    }
+
+    async fn sismember<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let set = AsyncRedisSet::new(self.multiplexed().await?);
+        Ok(set.sismember(key.as_ref(), member.as_ref()).await?)
+    }
+
+    async fn smembers<K: AsRef<str> + Send>(&self, key: K) -> Result<Vec<String>, Self::Error> {
+        let set = AsyncRedisSet::new(self.multiplexed().await?);
+        Ok(set.smembers(key.as_ref()).await?)
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+#[async_trait]
+impl SortedSetAdapter for RedisPoolAdapter {
+    async fn zadd<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        score: f64,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let zset = AsyncRedisSortedSet::new(self.multiplexed().await?);
+        Ok(zset
+            .zadd_single(key.as_ref(), score, member.as_ref())
+            .await?
+            > 0)
+    }
+
+    async fn zrem<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let zset = AsyncRedisSortedSet::new(self.multiplexed().await?);
+        Ok(zset.zrem(key.as_ref(), &[member.as_ref()]).await? > 0)
+    }
+
+    async fn zscore<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<Option<f64>, Self::Error> {
+        let zset = AsyncRedisSortedSet::new(self.multiplexed().await?);
+        Ok(zset.zscore(key.as_ref(), member.as_ref()).await?)
+    }
+
+    async fn zcard<K: AsRef<str> + Send>(&self, key: K) -> Result<usize, Self::Error> {
+        let zset = AsyncRedisSortedSet::new(self.multiplexed().await?);
+        Ok(zset.zcard(key.as_ref()).await?)
+    }
+
+    async fn zrange<K: AsRef<str> + Send>(
+        &self,
+        key: K,
+        start: isize,
+        stop: isize,
+    ) -> Result<Vec<(String, f64)>, Self::Error> {
+        let zset = AsyncRedisSortedSet::new(self.multiplexed().await?);
+        Ok(zset.zrange_withscores(key.as_ref(), start, stop).await?)
+    }
+}
+
+/// A connection checked out through [`PooledAdapter::get_connection`]
+///
+/// The connection goes back to the pool when this is dropped.
+#[cfg(feature = "connection-pool")]
+pub struct PooledRedisConnection {
+    conn: PooledConnection,
+}
+
+#[cfg(feature = "connection-pool")]
+impl PooledRedisConnection {
+    /// Get the pool guard, e.g. to run primitives on this connection
+    pub fn pooled(&self) -> &PooledConnection {
+        &self.conn
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+impl DatabaseAdapter for PooledRedisConnection {
+    type Error = AdapterError;
+
+    fn is_connected(&self) -> bool {
+        self.conn
+            .shared()
+            .try_lock()
+            .map(|conn| conn.is_open())
+            // Locked by a command in flight
+            .unwrap_or(true)
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+#[async_trait]
+impl AsyncDatabaseAdapter for PooledRedisConnection {
+    async fn ping(&self) -> Result<bool, Self::Error> {
+        let conn = self.conn.shared();
+        let pong = tokio::task::spawn_blocking(move || {
+            let mut conn = conn.lock().unwrap();
+            redis::cmd("PING").query::<String>(&mut *conn)
+        })
+        .await
+        .map_err(|e| AdapterError::Internal(e.to_string()))??;
+        Ok(pong == "PONG")
+    }
+
+    /// The connection is returned to the pool on drop instead
+    async fn close(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+#[async_trait]
+impl PooledAdapter for RedisPoolAdapter {
+    async fn get_connection(
+        &self,
+    ) -> Result<Box<dyn AsyncDatabaseAdapter<Error = Self::Error>>, Self::Error> {
+        // Checkout blocks for up to the pool's checkout timeout
+        let pool = self.pool().clone();
+        let conn = tokio::task::spawn_blocking(move || pool.get_connection())
+            .await
+            .map_err(|e| AdapterError::Internal(e.to_string()))??;
+        Ok(Box::new(PooledRedisConnection { conn }))
+    }
+
+    async fn return_connection(
+        &self,
+        connection: Box<dyn AsyncDatabaseAdapter<Error = Self::Error>>,
+    ) -> Result<(), Self::Error> {
+        drop(connection);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redis_errors_map_to_adapter_errors() {
+        let err = RedisError::from((ErrorKind::AuthenticationFailed, "bad password"));
+        assert!(matches!(
+            AdapterError::from(err),
+            AdapterError::Connection(ConnectionError::AuthenticationFailed(_))
+        ));
+
+        let err = RedisError::from((ErrorKind::TypeError, "not a string"));
+        assert!(matches!(
+            AdapterError::from(err),
+            AdapterError::Operation(OperationError::Deserialization(_))
+        ));
+
+        let err = RedisError::from(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+        assert!(matches!(
+            AdapterError::from(err),
+            AdapterError::Connection(ConnectionError::ConnectionFailed(_))
+        ));
+    }
+
+    #[cfg(feature = "connection-pool")]
+    #[tokio::test]
+    async fn test_pool_adapter_reports_connection_errors() {
+        let adapter = RedisPoolAdapter::new(
+            super::super::client::RedisPool::new("redis://127.0.0.1:1", 1).unwrap(),
+        );
+        assert!(!adapter.is_connected());
+        assert!(matches!(
+            AsyncDatabaseAdapter::ping(&adapter).await,
+            Err(AdapterError::Connection(_))
+        ));
+        assert!(matches!(
+            PooledAdapter::get_connection(&adapter).await,
+            Err(AdapterError::Connection(ConnectionError::ConnectionFailed(
+                _
+            )))
+        ));
+    }
+}
@@ -0,0 +1,317 @@
+use crate::redis::primitives::admin::{HealthCheck, ServerStatus};
+use redis::aio::{ConnectionLike, MultiplexedConnection};
+use redis::RedisResult;
+use std::collections::HashMap;
+
+/// Async counterpart of [`AdminOperations`](crate::redis::primitives::admin::AdminOperations) on a multiplexed connection.
+///
+/// Method names and return values match the blocking type.
+#[derive(Clone)]
+pub struct AsyncAdminOperations<C = MultiplexedConnection> {
+    conn: C,
+}
+
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncAdminOperations<C> {
+    /// Creates a new instance of `AsyncAdminOperations`.
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    /// Flushes all keys from the current database.
+    pub async fn flushdb(&self) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        redis::cmd("FLUSHDB").query_async(&mut conn).await
+    }
+
+    /// Flushes all keys from all databases.
+    pub async fn flushall(&self) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        redis::cmd("FLUSHALL").query_async(&mut conn).await
+    }
+
+    /// Retrieves the Redis server's information and statistics.
+    pub async fn info(&self) -> RedisResult<String> {
+        let mut conn = self.conn.clone();
+        redis::cmd("INFO").query_async(&mut conn).await
+    }
+
+    /// Retrieves specific sections of Redis server information.
+    pub async fn info_section(&self, section: &str) -> RedisResult<String> {
+        let mut conn = self.conn.clone();
+        redis::cmd("INFO").arg(section).query_async(&mut conn).await
+    }
+
+    /// Pings the Redis server to check connectivity.
+    pub async fn ping(&self) -> RedisResult<String> {
+        let mut conn = self.conn.clone();
+        redis::cmd("PING").query_async(&mut conn).await
+    }
+
+    /// Configures Redis server parameters.
+    pub async fn config_set(&self, parameter: &str, value: &str) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        redis::cmd("CONFIG")
+            .arg("SET")
+            .arg(parameter)
+            .arg(value)
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Retrieves the value of a Redis server configuration parameter.
+    pub async fn config_get(&self, parameter: &str) -> RedisResult<String> {
+        let mut conn = self.conn.clone();
+        let result: Vec<String> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg(parameter)
+            .query_async(&mut conn)
+            .await?;
+        if result.len() >= 2 {
+            Ok(result[1].clone())
+        } else {
+            // If we don't get enough results, the parameter probably doesn't exist
+            // Return the original error from the query
+            redis::cmd("CONFIG")
+                .arg("GET")
+                .arg(parameter)
+                .query_async(&mut conn)
+                .await
+        }
+    }
+
+    /// Retrieves all Redis server configuration parameters.
+    pub async fn config_get_all(&self) -> RedisResult<HashMap<String, String>> {
+        let mut conn = self.conn.clone();
+        let result: Vec<String> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg("*")
+            .query_async(&mut conn)
+            .await?;
+
+        let mut config = HashMap::new();
+        for chunk in result.chunks(2) {
+            if chunk.len() == 2 {
+                config.insert(chunk[0].clone(), chunk[1].clone());
+            }
+        }
+        Ok(config)
+    }
+
+    /// Resets Redis server configuration to default values.
+    pub async fn config_resetstat(&self) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        redis::cmd("CONFIG")
+            .arg("RESETSTAT")
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Rewrites the Redis configuration file.
+    pub async fn config_rewrite(&self) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        redis::cmd("CONFIG")
+            .arg("REWRITE")
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Returns the number of keys in the current database.
+    pub async fn dbsize(&self) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        redis::cmd("DBSIZE").query_async(&mut conn).await
+    }
+
+    /// Returns the current server time.
+    pub async fn time(&self) -> RedisResult<(i64, i64)> {
+        let mut conn = self.conn.clone();
+        redis::cmd("TIME").query_async(&mut conn).await
+    }
+
+    /// Returns the Redis server version.
+    pub async fn version(&self) -> RedisResult<String> {
+        let info = self.info_section("server").await?;
+        for line in info.lines() {
+            if line.starts_with("redis_version:") {
+                return Ok(line.split(':').nth(1).unwrap_or("unknown").to_string());
+            }
+        }
+        // If we can't find the version, return a default
+        Ok("unknown".to_string())
+    }
+
+    /// Returns memory usage statistics.
+    pub async fn memory_stats(&self) -> RedisResult<HashMap<String, String>> {
+        let info = self.info_section("memory").await?;
+        let mut stats = HashMap::new();
+
+        for line in info.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                stats.insert(key.to_string(), value.to_string());
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Returns client connection statistics.
+    pub async fn client_stats(&self) -> RedisResult<HashMap<String, String>> {
+        let info = self.info_section("clients").await?;
+        let mut stats = HashMap::new();
+
+        for line in info.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                stats.insert(key.to_string(), value.to_string());
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Returns server statistics.
+    pub async fn server_stats(&self) -> RedisResult<HashMap<String, String>> {
+        let info = self.info_section("stats").await?;
+        let mut stats = HashMap::new();
+
+        for line in info.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                stats.insert(key.to_string(), value.to_string());
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Returns a comprehensive health check of the Redis server.
+    pub async fn health_check(&self) -> RedisResult<HealthCheck> {
+        let ping_result = self.ping().await;
+        let dbsize_result = self.dbsize().await;
+        let version_result = self.version().await;
+        let memory_result = self.memory_stats().await;
+
+        let is_healthy = ping_result.is_ok() && dbsize_result.is_ok() && version_result.is_ok();
+
+        Ok(HealthCheck {
+            is_healthy,
+            ping_response: ping_result.unwrap_or_else(|_| "FAILED".to_string()),
+            database_size: dbsize_result.unwrap_or(-1),
+            version: version_result.unwrap_or_else(|_| "unknown".to_string()),
+            memory_usage: memory_result.unwrap_or_default(),
+        })
+    }
+
+    /// Returns a comprehensive server status report.
+    pub async fn server_status(&self) -> RedisResult<ServerStatus> {
+        let info = self.info().await?;
+        let (time, _) = self.time().await?;
+
+        let mut status = ServerStatus {
+            timestamp: time,
+            uptime_seconds: 0,
+            connected_clients: 0,
+            used_memory: 0,
+            total_commands_processed: 0,
+            keyspace_hits: 0,
+            keyspace_misses: 0,
+            version: "unknown".to_string(),
+            role: "unknown".to_string(),
+        };
+
+        for line in info.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                match key {
+                    "uptime_in_seconds" => {
+                        status.uptime_seconds = value.parse().unwrap_or(0);
+                    }
+                    "connected_clients" => {
+                        status.connected_clients = value.parse().unwrap_or(0);
+                    }
+                    "used_memory" => {
+                        status.used_memory = value.parse().unwrap_or(0);
+                    }
+                    "total_commands_processed" => {
+                        status.total_commands_processed = value.parse().unwrap_or(0);
+                    }
+                    "keyspace_hits" => {
+                        status.keyspace_hits = value.parse().unwrap_or(0);
+                    }
+                    "keyspace_misses" => {
+                        status.keyspace_misses = value.parse().unwrap_or(0);
+                    }
+                    "redis_version" => {
+                        status.version = value.to_string();
+                    }
+                    "role" => {
+                        status.role = value.to_string();
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::async_primitives::memory_connection;
+
+    #[tokio::test]
+    async fn test_admin_operations() {
+        let (_server, conn) = memory_connection().await;
+        let admin = AsyncAdminOperations::new(conn.clone());
+
+        assert_eq!(admin.ping().await.unwrap(), "PONG");
+        assert!(admin.info().await.unwrap().contains("redis_version"));
+        assert!(admin
+            .info_section("clients")
+            .await
+            .unwrap()
+            .contains("connected_clients"));
+        assert_ne!(admin.version().await.unwrap(), "unknown");
+        assert!(admin.time().await.unwrap().0 > 0);
+
+        admin
+            .config_set("maxmemory-policy", "allkeys-lru")
+            .await
+            .unwrap();
+        assert_eq!(
+            admin.config_get("maxmemory-policy").await.unwrap(),
+            "allkeys-lru"
+        );
+        assert_eq!(
+            admin.config_get_all().await.unwrap()["maxmemory-policy"],
+            "allkeys-lru"
+        );
+        admin.config_resetstat().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dbsize_and_flush() {
+        let (_server, mut conn) = memory_connection().await;
+        let admin = AsyncAdminOperations::new(conn.clone());
+
+        let _: () = redis::cmd("MSET")
+            .arg(&["a", "1", "b", "2"])
+            .query_async(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(admin.dbsize().await.unwrap(), 2);
+        admin.flushdb().await.unwrap();
+        assert_eq!(admin.dbsize().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_health_and_status() {
+        let (_server, conn) = memory_connection().await;
+        let admin = AsyncAdminOperations::new(conn);
+
+        let health = admin.health_check().await.unwrap();
+        assert!(health.is_healthy);
+        assert_eq!(health.ping_response, "PONG");
+        assert_eq!(health.database_size, 0);
+
+        let status = admin.server_status().await.unwrap();
+        assert!(status.connected_clients >= 1);
+        assert!(status.timestamp > 0);
+        assert!(!admin.server_stats().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,440 @@
+use redis::aio::{ConnectionLike, MultiplexedConnection};
+use redis::{AsyncCommands, FromRedisValue, Pipeline, RedisResult, Script, ToRedisArgs};
+
+/// Async counterpart of [`RedisBitmap`](crate::redis::primitives::bitmap::RedisBitmap) on a multiplexed connection.
+///
+/// Method names and return values match the blocking type, except for `transaction`. It
+/// runs the commands as one MULTI/EXEC block with [`Pipeline::atomic`], so `T` is decoded
+/// from the results of the queued commands. The blocking `transaction` sends MULTI and
+/// EXEC as ordinary pipeline commands and decodes every reply, including the `OK` and
+/// `QUEUED` acknowledgements.
+///
+/// Every call works on a clone of the multiplexed connection, so one instance can be
+/// shared between tasks without locking. Lua scripts are shared with the blocking type,
+/// e.g. `RedisBitmap::create_script`.
+#[derive(Clone)]
+pub struct AsyncRedisBitmap<C = MultiplexedConnection> {
+    conn: C,
+}
+
+/// Core implementation with basic bitmap operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisBitmap<C> {
+    /// Creates a new AsyncRedisBitmap instance with the provided connection
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    /// Gets the connection reference for direct usage
+    pub fn connection(&self) -> &C {
+        &self.conn
+    }
+
+    /// Sets or clears the bit at offset in the string value stored at key
+    pub async fn setbit(&self, key: &str, offset: usize, value: bool) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.setbit(key, offset, value).await?;
+        Ok(result == 1)
+    }
+
+    /// Returns the bit value at offset in the string value stored at key
+    pub async fn getbit(&self, key: &str, offset: usize) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.getbit(key, offset).await?;
+        Ok(result == 1)
+    }
+
+    /// Counts the number of set bits (population counting) in a string
+    pub async fn bitcount(&self, key: &str) -> RedisResult<u64> {
+        let mut conn = self.conn.clone();
+        conn.bitcount(key).await
+    }
+
+    /// Counts the number of set bits (population counting) in a string within a range
+    pub async fn bitcount_range(&self, key: &str, start: i64, end: i64) -> RedisResult<u64> {
+        let mut conn = self.conn.clone();
+        redis::cmd("BITCOUNT")
+            .arg(key)
+            .arg(start)
+            .arg(end)
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Performs a bitwise operation between multiple keys and stores the result
+    pub async fn bitop(&self, operation: &str, destkey: &str, keys: &[&str]) -> RedisResult<u64> {
+        let mut conn = self.conn.clone();
+        redis::cmd("BITOP")
+            .arg(operation)
+            .arg(destkey)
+            .arg(keys)
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Performs a bitwise AND operation between multiple keys and stores the result
+    pub async fn bitop_and(&self, destkey: &str, keys: &[&str]) -> RedisResult<u64> {
+        self.bitop("AND", destkey, keys).await
+    }
+
+    /// Performs a bitwise OR operation between multiple keys and stores the result
+    pub async fn bitop_or(&self, destkey: &str, keys: &[&str]) -> RedisResult<u64> {
+        self.bitop("OR", destkey, keys).await
+    }
+
+    /// Performs a bitwise XOR operation between multiple keys and stores the result
+    pub async fn bitop_xor(&self, destkey: &str, keys: &[&str]) -> RedisResult<u64> {
+        self.bitop("XOR", destkey, keys).await
+    }
+
+    /// Performs a bitwise NOT operation on a key and stores the result
+    pub async fn bitop_not(&self, destkey: &str, sourcekey: &str) -> RedisResult<u64> {
+        let mut conn = self.conn.clone();
+        redis::cmd("BITOP")
+            .arg("NOT")
+            .arg(destkey)
+            .arg(sourcekey)
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Returns the position of the first bit set to 1 or 0 in a string
+    pub async fn bitpos(&self, key: &str, bit: bool) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        let bit_value = if bit { 1 } else { 0 };
+        redis::cmd("BITPOS")
+            .arg(key)
+            .arg(bit_value)
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Returns the position of the first bit set to 1 or 0 in a string within a range
+    pub async fn bitpos_range(
+        &self,
+        key: &str,
+        bit: bool,
+        start: i64,
+        end: i64,
+    ) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        let bit_value = if bit { 1 } else { 0 };
+        redis::cmd("BITPOS")
+            .arg(key)
+            .arg(bit_value)
+            .arg(start)
+            .arg(end)
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Returns the position of the first bit set to 1 or 0 in a string within a range with byte granularity
+    pub async fn bitpos_range_bytes(
+        &self,
+        key: &str,
+        bit: bool,
+        start: i64,
+        end: i64,
+    ) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        let bit_value = if bit { 1 } else { 0 };
+        redis::cmd("BITPOS")
+            .arg(key)
+            .arg(bit_value)
+            .arg(start)
+            .arg(end)
+            .arg("BYTE")
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Returns the position of the first bit set to 1 or 0 in a string within a range with bit granularity
+    pub async fn bitpos_range_bits(
+        &self,
+        key: &str,
+        bit: bool,
+        start: i64,
+        end: i64,
+    ) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        let bit_value = if bit { 1 } else { 0 };
+        redis::cmd("BITPOS")
+            .arg(key)
+            .arg(bit_value)
+            .arg(start)
+            .arg(end)
+            .arg("BIT")
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Returns the string value stored at key
+    pub async fn get(&self, key: &str) -> RedisResult<Option<Vec<u8>>> {
+        let mut conn = self.conn.clone();
+        conn.get(key).await
+    }
+
+    /// Sets the string value of a key
+    pub async fn set(&self, key: &str, value: &[u8]) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        conn.set(key, value).await
+    }
+
+    /// Returns the length of the string value stored at key
+    pub async fn strlen(&self, key: &str) -> RedisResult<u64> {
+        let mut conn = self.conn.clone();
+        conn.strlen(key).await
+    }
+
+    /// Deletes a bitmap
+    pub async fn del(&self, key: &str) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        conn.del(key).await
+    }
+
+    /// Checks if a bitmap exists
+    pub async fn exists(&self, key: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.exists(key).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets the TTL of a bitmap in seconds
+    pub async fn ttl(&self, key: &str) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        conn.ttl(key).await
+    }
+
+    /// Sets the TTL of a bitmap in seconds
+    pub async fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.expire(key, seconds as i64).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets keys matching a pattern
+    pub async fn keys(&self, pattern: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.keys(pattern).await
+    }
+
+    /// Sets multiple bits at once using a byte array
+    pub async fn set_bits_from_bytes(
+        &self,
+        key: &str,
+        offset: u64,
+        bytes: &[u8],
+    ) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        // Set the string value starting at the specified offset
+        redis::cmd("SETRANGE")
+            .arg(key)
+            .arg(offset)
+            .arg(bytes)
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Gets multiple bits as bytes
+    pub async fn get_bits_as_bytes(
+        &self,
+        key: &str,
+        offset: u64,
+        length: u64,
+    ) -> RedisResult<Vec<u8>> {
+        let mut conn = self.conn.clone();
+        redis::cmd("GETRANGE")
+            .arg(key)
+            .arg(offset)
+            .arg(offset + length - 1)
+            .query_async(&mut conn)
+            .await
+    }
+}
+
+/// Pipeline operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisBitmap<C> {
+    /// Executes a function with a pipeline
+    pub async fn with_pipeline<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        let result = f(&mut pipe).query_async(&mut conn).await?;
+        Ok(result)
+    }
+
+    /// Helper: batch set multiple bits using pipeline
+    pub async fn setbit_many(
+        &self,
+        key: &str,
+        bit_offsets: Vec<(usize, bool)>,
+    ) -> RedisResult<Vec<bool>> {
+        self.with_pipeline(|pipe| {
+            for (offset, value) in bit_offsets {
+                pipe.cmd("SETBIT")
+                    .arg(key)
+                    .arg(offset)
+                    .arg(if value { 1 } else { 0 });
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get multiple bits using pipeline
+    pub async fn getbit_many(&self, key: &str, offsets: Vec<usize>) -> RedisResult<Vec<bool>> {
+        self.with_pipeline(|pipe| {
+            for offset in offsets {
+                pipe.cmd("GETBIT").arg(key).arg(offset);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get bitcounts from multiple keys using pipeline
+    pub async fn bitcount_many(&self, keys: Vec<&str>) -> RedisResult<Vec<u64>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("BITCOUNT").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch delete multiple bitmaps using pipeline
+    pub async fn del_many(&self, keys: Vec<&str>) -> RedisResult<()> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("DEL").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch check if bitmaps exist using pipeline
+    pub async fn exists_many(&self, keys: Vec<&str>) -> RedisResult<Vec<bool>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("EXISTS").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+}
+
+/// Transaction operations (MULTI/EXEC)
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisBitmap<C> {
+    /// Executes the pipeline built by `f` atomically with MULTI/EXEC
+    pub async fn transaction<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        f(&mut pipe).query_async(&mut conn).await
+    }
+}
+
+/// Lua script operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisBitmap<C> {
+    /// Executes a Lua script with the given keys and arguments
+    pub async fn eval_script<T, K, A>(&self, script: &Script, keys: K, args: A) -> RedisResult<T>
+    where
+        T: FromRedisValue,
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+    {
+        let mut conn = self.conn.clone();
+        script.key(keys).arg(args).invoke_async(&mut conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::async_primitives::memory_connection;
+
+    #[tokio::test]
+    async fn test_bitmap_operations() {
+        let (_server, conn) = memory_connection().await;
+        let bitmaps = AsyncRedisBitmap::new(conn);
+
+        assert!(!bitmaps.setbit("seen", 3, true).await.unwrap());
+        assert!(bitmaps.setbit("seen", 3, true).await.unwrap());
+        assert!(bitmaps.getbit("seen", 3).await.unwrap());
+        assert!(!bitmaps.getbit("seen", 4).await.unwrap());
+        bitmaps.setbit("seen", 12, true).await.unwrap();
+        assert_eq!(bitmaps.bitcount("seen").await.unwrap(), 2);
+        assert_eq!(bitmaps.bitcount_range("seen", 1, 1).await.unwrap(), 1);
+        assert_eq!(bitmaps.bitpos("seen", true).await.unwrap(), 3);
+        assert_eq!(bitmaps.strlen("seen").await.unwrap(), 2);
+
+        bitmaps.set("mask", &[0b1111_0000]).await.unwrap();
+        bitmaps.set("other", &[0b1010_1010]).await.unwrap();
+        assert_eq!(
+            bitmaps.bitop_and("both", &["mask", "other"]).await.unwrap(),
+            1
+        );
+        assert_eq!(bitmaps.get("both").await.unwrap(), Some(vec![0b1010_0000]));
+        bitmaps.bitop_not("inverse", "mask").await.unwrap();
+        assert_eq!(
+            bitmaps.get("inverse").await.unwrap(),
+            Some(vec![0b0000_1111])
+        );
+
+        bitmaps.set_bits_from_bytes("raw", 1, b"ab").await.unwrap();
+        assert_eq!(bitmaps.get_bits_as_bytes("raw", 1, 2).await.unwrap(), b"ab");
+        bitmaps.del("raw").await.unwrap();
+        assert!(!bitmaps.exists("raw").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_batch_operations() {
+        let (_server, conn) = memory_connection().await;
+        let bitmaps = AsyncRedisBitmap::new(conn);
+
+        assert_eq!(
+            bitmaps
+                .setbit_many("flags", vec![(0, true), (1, true), (0, false)])
+                .await
+                .unwrap(),
+            [false, false, true]
+        );
+        assert_eq!(
+            bitmaps.getbit_many("flags", vec![0, 1]).await.unwrap(),
+            [false, true]
+        );
+        bitmaps.setbit("other", 7, true).await.unwrap();
+        assert_eq!(
+            bitmaps.bitcount_many(vec!["flags", "other"]).await.unwrap(),
+            [1, 1]
+        );
+        assert_eq!(
+            bitmaps.exists_many(vec!["flags", "missing"]).await.unwrap(),
+            [true, false]
+        );
+
+        bitmaps.del_many(vec!["flags", "other"]).await.unwrap();
+        assert!(bitmaps.keys("*").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transaction() {
+        let (_server, conn) = memory_connection().await;
+        let bitmaps = AsyncRedisBitmap::new(conn);
+
+        let (previous, count): (bool, u64) = bitmaps
+            .transaction(|pipe| pipe.setbit("b", 5, true).bitcount("b"))
+            .await
+            .unwrap();
+        assert_eq!((previous, count), (false, 1));
+    }
+}
@@ -0,0 +1,443 @@
+use redis::aio::{ConnectionLike, MultiplexedConnection};
+use redis::{AsyncCommands, FromRedisValue, Pipeline, RedisResult, Script, ToRedisArgs};
+
+/// Async counterpart of [`RedisHash`](crate::redis::primitives::hash::RedisHash) on a multiplexed connection.
+///
+/// Method names and return values match the blocking type, except for transactions: the
+/// blocking `with_transaction` is called `transaction` here, and the `hset_many_atomic`
+/// and `hdel_many_atomic` helpers are not provided. Both run the commands as one
+/// MULTI/EXEC block and return the results of the queued commands.
+///
+/// Every call works on a clone of the multiplexed connection, so one instance can be
+/// shared between tasks without locking. Lua scripts are shared with the blocking type,
+/// e.g. `RedisHash::create_script`.
+#[derive(Clone)]
+pub struct AsyncRedisHash<C = MultiplexedConnection> {
+    conn: C,
+}
+
+/// Core implementation with basic hash operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisHash<C> {
+    /// Creates a new AsyncRedisHash instance with the provided connection
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    /// Gets the connection reference for direct usage
+    pub fn connection(&self) -> &C {
+        &self.conn
+    }
+
+    /// Sets a field in a hash
+    pub async fn hset(&self, key: &str, field: &str, value: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.hset(key, field, value).await?;
+        Ok(result == 1)
+    }
+
+    /// Sets multiple fields in a hash
+    pub async fn hmset(&self, key: &str, field_values: &[(&str, &str)]) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        conn.hset_multiple(key, field_values).await
+    }
+
+    /// Gets a field from a hash
+    pub async fn hget(&self, key: &str, field: &str) -> RedisResult<Option<String>> {
+        let mut conn = self.conn.clone();
+        conn.hget(key, field).await
+    }
+
+    /// Gets multiple fields from a hash
+    pub async fn hmget(&self, key: &str, fields: &[&str]) -> RedisResult<Vec<Option<String>>> {
+        let mut conn = self.conn.clone();
+        let mut cmd = redis::cmd("HMGET");
+        cmd.arg(key);
+        for field in fields {
+            cmd.arg(field);
+        }
+        cmd.query_async(&mut conn).await
+    }
+
+    /// Gets all fields and values from a hash
+    pub async fn hgetall(
+        &self,
+        key: &str,
+    ) -> RedisResult<std::collections::HashMap<String, String>> {
+        let mut conn = self.conn.clone();
+        conn.hgetall(key).await
+    }
+
+    /// Gets all field names from a hash
+    pub async fn hkeys(&self, key: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.hkeys(key).await
+    }
+
+    /// Gets all values from a hash
+    pub async fn hvals(&self, key: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.hvals(key).await
+    }
+
+    /// Gets the number of fields in a hash
+    pub async fn hlen(&self, key: &str) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.hlen(key).await
+    }
+
+    /// Checks if a field exists in a hash
+    pub async fn hexists(&self, key: &str, field: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.hexists(key, field).await?;
+        Ok(result == 1)
+    }
+
+    /// Deletes one or more fields from a hash
+    pub async fn hdel(&self, key: &str, fields: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.hdel(key, fields).await
+    }
+
+    /// Increments a numeric field in a hash
+    pub async fn hincrby(&self, key: &str, field: &str, increment: i64) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        conn.hincr(key, field, increment).await
+    }
+
+    /// Increments a float field in a hash
+    pub async fn hincrbyfloat(&self, key: &str, field: &str, increment: f64) -> RedisResult<f64> {
+        let mut conn = self.conn.clone();
+        let mut cmd = redis::cmd("HINCRBYFLOAT");
+        cmd.arg(key).arg(field).arg(increment);
+        cmd.query_async(&mut conn).await
+    }
+
+    /// Sets a field only if it doesn't exist
+    pub async fn hsetnx(&self, key: &str, field: &str, value: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.hset_nx(key, field, value).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets a random field from a hash
+    pub async fn hrandfield(&self, key: &str) -> RedisResult<Option<String>> {
+        let mut conn = self.conn.clone();
+        let mut cmd = redis::cmd("HRANDFIELD");
+        cmd.arg(key);
+        cmd.query_async(&mut conn).await
+    }
+
+    /// Gets multiple random fields from a hash
+    pub async fn hrandfield_count(&self, key: &str, count: isize) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        let mut cmd = redis::cmd("HRANDFIELD");
+        cmd.arg(key).arg(count);
+        cmd.query_async(&mut conn).await
+    }
+
+    /// Gets a random field with value from a hash
+    pub async fn hrandfield_withvalues(
+        &self,
+        key: &str,
+        count: isize,
+    ) -> RedisResult<Vec<(String, String)>> {
+        let mut conn = self.conn.clone();
+        let mut cmd = redis::cmd("HRANDFIELD");
+        cmd.arg(key).arg(count).arg("WITHVALUES");
+        cmd.query_async(&mut conn).await
+    }
+
+    /// Scans hash fields with pattern matching
+    pub async fn hscan(
+        &self,
+        key: &str,
+        cursor: usize,
+        pattern: Option<&str>,
+        count: Option<usize>,
+    ) -> RedisResult<(usize, Vec<(String, String)>)> {
+        let mut conn = self.conn.clone();
+        let mut cmd = redis::cmd("HSCAN");
+        cmd.arg(key).arg(cursor);
+        if let Some(p) = pattern {
+            cmd.arg("MATCH").arg(p);
+        }
+        if let Some(c) = count {
+            cmd.arg("COUNT").arg(c);
+        }
+        cmd.query_async(&mut conn).await
+    }
+
+    /// Deletes a hash
+    pub async fn del(&self, key: &str) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        conn.del(key).await
+    }
+
+    /// Checks if a hash exists
+    pub async fn exists(&self, key: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.exists(key).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets the TTL of a hash in seconds
+    pub async fn ttl(&self, key: &str) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        conn.ttl(key).await
+    }
+
+    /// Sets the TTL of a hash in seconds
+    pub async fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.expire(key, seconds as i64).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets keys matching a pattern
+    pub async fn keys(&self, pattern: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.keys(pattern).await
+    }
+}
+
+/// Pipeline operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisHash<C> {
+    /// Executes a function with a pipeline
+    pub async fn with_pipeline<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        let result = f(&mut pipe).query_async(&mut conn).await?;
+        Ok(result)
+    }
+
+    /// Helper: batch set multiple fields in multiple hashes using pipeline
+    pub async fn hset_many(
+        &self,
+        hash_fields: Vec<(&str, Vec<(&str, &str)>)>,
+    ) -> RedisResult<Vec<bool>> {
+        let raw_results: Vec<i32> = self
+            .with_pipeline(|pipe| {
+                for (hash_key, fields) in hash_fields {
+                    for (field, value) in fields {
+                        pipe.cmd("HSET").arg(hash_key).arg(field).arg(value);
+                    }
+                }
+                pipe
+            })
+            .await?;
+
+        // Convert raw integer results to booleans (1 = true, 0 = false)
+        Ok(raw_results.into_iter().map(|result| result == 1).collect())
+    }
+
+    /// Helper: batch get multiple fields from multiple hashes using pipeline
+    pub async fn hget_many(
+        &self,
+        hash_fields: Vec<(&str, &str)>,
+    ) -> RedisResult<Vec<Option<String>>> {
+        self.with_pipeline(|pipe| {
+            for (hash_key, field) in hash_fields {
+                pipe.cmd("HGET").arg(hash_key).arg(field);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get all fields from multiple hashes using pipeline
+    pub async fn hgetall_many(
+        &self,
+        keys: Vec<&str>,
+    ) -> RedisResult<Vec<std::collections::HashMap<String, String>>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("HGETALL").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch delete multiple fields from multiple hashes using pipeline
+    pub async fn hdel_many(&self, hash_fields: Vec<(&str, Vec<&str>)>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for (hash_key, fields) in hash_fields {
+                let mut cmd = pipe.cmd("HDEL").arg(hash_key);
+                for field in fields {
+                    cmd = cmd.arg(field);
+                }
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch check if fields exist in hashes using pipeline
+    pub async fn hexists_many(&self, hash_fields: Vec<(&str, &str)>) -> RedisResult<Vec<bool>> {
+        self.with_pipeline(|pipe| {
+            for (hash_key, field) in hash_fields {
+                pipe.cmd("HEXISTS").arg(hash_key).arg(field);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get hash lengths using pipeline
+    pub async fn hlen_many(&self, keys: Vec<&str>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("HLEN").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch delete multiple hashes using pipeline
+    pub async fn del_many(&self, keys: Vec<&str>) -> RedisResult<()> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("DEL").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+}
+
+/// Transaction operations (MULTI/EXEC)
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisHash<C> {
+    /// Executes the pipeline built by `f` atomically with MULTI/EXEC
+    pub async fn transaction<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        f(&mut pipe).query_async(&mut conn).await
+    }
+}
+
+/// Lua script operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisHash<C> {
+    /// Executes a Lua script with the given keys and arguments
+    pub async fn eval_script<T, K, A>(&self, script: &Script, keys: K, args: A) -> RedisResult<T>
+    where
+        T: FromRedisValue,
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+    {
+        let mut conn = self.conn.clone();
+        script.key(keys).arg(args).invoke_async(&mut conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::async_primitives::memory_connection;
+
+    #[tokio::test]
+    async fn test_hash_operations() {
+        let (_server, conn) = memory_connection().await;
+        let hashes = AsyncRedisHash::new(conn);
+
+        assert!(hashes.hset("user:1", "name", "Ada").await.unwrap());
+        assert!(!hashes.hset("user:1", "name", "Ada L").await.unwrap());
+        hashes
+            .hmset("user:1", &[("email", "ada@example.com"), ("visits", "1")])
+            .await
+            .unwrap();
+        assert_eq!(
+            hashes.hget("user:1", "name").await.unwrap().as_deref(),
+            Some("Ada L")
+        );
+        assert_eq!(
+            hashes.hmget("user:1", &["email", "missing"]).await.unwrap(),
+            [Some("ada@example.com".into()), None]
+        );
+        assert_eq!(hashes.hgetall("user:1").await.unwrap().len(), 3);
+        assert_eq!(hashes.hlen("user:1").await.unwrap(), 3);
+        assert!(hashes.hexists("user:1", "email").await.unwrap());
+
+        assert_eq!(hashes.hincrby("user:1", "visits", 2).await.unwrap(), 3);
+        assert_eq!(
+            hashes.hincrbyfloat("user:1", "score", 1.5).await.unwrap(),
+            1.5
+        );
+        assert!(hashes.hsetnx("user:1", "role", "admin").await.unwrap());
+        assert!(!hashes.hsetnx("user:1", "role", "guest").await.unwrap());
+
+        let mut fields = hashes.hkeys("user:1").await.unwrap();
+        fields.sort();
+        assert_eq!(fields, ["email", "name", "role", "score", "visits"]);
+        assert_eq!(
+            hashes.hdel("user:1", &["role", "missing"]).await.unwrap(),
+            1
+        );
+        assert_eq!(hashes.hvals("user:1").await.unwrap().len(), 4);
+
+        hashes.del("user:1").await.unwrap();
+        assert!(!hashes.exists("user:1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_batch_operations() {
+        let (_server, conn) = memory_connection().await;
+        let hashes = AsyncRedisHash::new(conn);
+
+        hashes
+            .hset_many(vec![
+                ("h1", vec![("a", "1"), ("b", "2")]),
+                ("h2", vec![("a", "3")]),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(hashes.hlen_many(vec!["h1", "h2"]).await.unwrap(), [2, 1]);
+        assert_eq!(
+            hashes
+                .hget_many(vec![("h1", "b"), ("h2", "b")])
+                .await
+                .unwrap(),
+            [Some("2".into()), None]
+        );
+        assert_eq!(hashes.hgetall_many(vec!["h2"]).await.unwrap()[0]["a"], "3");
+        assert_eq!(
+            hashes
+                .hexists_many(vec![("h1", "b"), ("h2", "b")])
+                .await
+                .unwrap(),
+            [true, false]
+        );
+        assert_eq!(
+            hashes
+                .hdel_many(vec![("h1", vec!["a", "b"]), ("h2", vec!["z"])])
+                .await
+                .unwrap(),
+            [2, 0]
+        );
+
+        hashes.del_many(vec!["h1", "h2"]).await.unwrap();
+        assert!(hashes.keys("*").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transaction() {
+        let (_server, conn) = memory_connection().await;
+        let hashes = AsyncRedisHash::new(conn);
+
+        let (added, value): (bool, String) = hashes
+            .transaction(|pipe| pipe.hset("h", "field", "value").hget("h", "field"))
+            .await
+            .unwrap();
+        assert!(added);
+        assert_eq!(value, "value");
+    }
+}
@@ -0,0 +1,39 @@
+//! Async Redis primitive data type adapters
+//!
+//! Non-blocking counterparts of the types in [`primitives`](super::primitives),
+//! built on a multiplexed `redis::aio` connection:
+//! - String: Simple string values, numbers, and binary data
+//! - Hash: Hash maps of string field-value pairs
+//! - Set: Unordered collections of unique strings
+//! - Sorted Set: Ordered collections of strings with associated scores
+//! - Bitmap: Bit-level operations on string values
+//!
+//! A multiplexed connection pipelines requests from many tasks over a single
+//! socket, so these types are cheap to clone and never hold a lock across a
+//! Redis round trip. Any other cloneable `redis::aio` connection works too,
+//! e.g. a checkout of `RedisPool::get_multiplexed_connection`.
+
+pub mod admin;
+pub mod bitmap;
+pub mod hash;
+pub mod set;
+pub mod sorted_set;
+pub mod string;
+
+/// A multiplexed connection to a fresh in-memory backend, served for as long
+/// as the returned server is kept
+#[cfg(test)]
+async fn memory_connection() -> (
+    crate::memory::MemoryServer,
+    redis::aio::MultiplexedConnection,
+) {
+    use crate::memory::{MemoryServer, MemoryStore};
+
+    let server = MemoryServer::start(MemoryStore::new()).await.unwrap();
+    let conn = redis::Client::open(server.url())
+        .unwrap()
+        .get_multiplexed_async_connection()
+        .await
+        .unwrap();
+    (server, conn)
+}
@@ -0,0 +1,374 @@
+use redis::aio::{ConnectionLike, MultiplexedConnection};
+use redis::{AsyncCommands, FromRedisValue, Pipeline, RedisResult, Script, ToRedisArgs};
+
+/// Async counterpart of [`RedisSet`](crate::redis::primitives::set::RedisSet) on a multiplexed connection.
+///
+/// Method names and return values match the blocking type, except for `transaction`. It
+/// runs the commands as one MULTI/EXEC block with [`Pipeline::atomic`], so `T` is decoded
+/// from the results of the queued commands. The blocking `transaction` sends MULTI and
+/// EXEC as ordinary pipeline commands and decodes every reply, including the `OK` and
+/// `QUEUED` acknowledgements.
+///
+/// Every call works on a clone of the multiplexed connection, so one instance can be
+/// shared between tasks without locking. Lua scripts are shared with the blocking type,
+/// e.g. `RedisSet::create_script`.
+#[derive(Clone)]
+pub struct AsyncRedisSet<C = MultiplexedConnection> {
+    conn: C,
+}
+
+/// Core implementation with basic set operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisSet<C> {
+    /// Creates a new AsyncRedisSet instance with the provided connection
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    /// Gets the connection reference for direct usage
+    pub fn connection(&self) -> &C {
+        &self.conn
+    }
+
+    /// Adds one or more members to a set
+    pub async fn sadd(&self, key: &str, members: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.sadd(key, members).await
+    }
+
+    /// Removes one or more members from a set
+    pub async fn srem(&self, key: &str, members: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.srem(key, members).await
+    }
+
+    /// Returns all members of a set
+    pub async fn smembers(&self, key: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.smembers(key).await
+    }
+
+    /// Returns the number of members in a set
+    pub async fn scard(&self, key: &str) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.scard(key).await
+    }
+
+    /// Tests if a member exists in a set
+    pub async fn sismember(&self, key: &str, member: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        conn.sismember(key, member).await
+    }
+
+    /// Returns a random member from a set
+    pub async fn srandmember(&self, key: &str) -> RedisResult<Option<String>> {
+        let mut conn = self.conn.clone();
+        conn.srandmember(key).await
+    }
+
+    /// Returns multiple random members from a set
+    pub async fn srandmember_count(&self, key: &str, count: usize) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.srandmember_multiple(key, count).await
+    }
+
+    /// Removes and returns a random member from a set
+    pub async fn spop(&self, key: &str) -> RedisResult<Option<String>> {
+        let mut conn = self.conn.clone();
+        conn.spop(key).await
+    }
+
+    /// Removes and returns multiple random members from a set
+    pub async fn spop_count(&self, key: &str, count: usize) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        redis::cmd("SPOP")
+            .arg(key)
+            .arg(count)
+            .query_async(&mut conn)
+            .await
+    }
+
+    /// Moves a member from one set to another
+    pub async fn smove(&self, source: &str, destination: &str, member: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.smove(source, destination, member).await?;
+        Ok(result == 1)
+    }
+
+    /// Returns the intersection of multiple sets
+    pub async fn sinter(&self, keys: &[&str]) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.sinter(keys).await
+    }
+
+    /// Returns the union of multiple sets
+    pub async fn sunion(&self, keys: &[&str]) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.sunion(keys).await
+    }
+
+    /// Returns the difference between the first set and all the successive sets
+    pub async fn sdiff(&self, keys: &[&str]) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.sdiff(keys).await
+    }
+
+    /// Stores the intersection of multiple sets in a destination set
+    pub async fn sinterstore(&self, destination: &str, keys: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.sinterstore(destination, keys).await
+    }
+
+    /// Stores the union of multiple sets in a destination set
+    pub async fn sunionstore(&self, destination: &str, keys: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.sunionstore(destination, keys).await
+    }
+
+    /// Stores the difference between the first set and all the successive sets in a destination set
+    pub async fn sdiffstore(&self, destination: &str, keys: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.sdiffstore(destination, keys).await
+    }
+
+    /// Returns a random member from a set without removing it
+    pub async fn srandmember_one(&self, key: &str) -> RedisResult<Option<String>> {
+        let mut conn = self.conn.clone();
+        conn.srandmember(key).await
+    }
+
+    /// Returns all members of a set as a HashSet
+    pub async fn smembers_as_set(
+        &self,
+        key: &str,
+    ) -> RedisResult<std::collections::HashSet<String>> {
+        let mut conn = self.conn.clone();
+        conn.smembers(key).await
+    }
+
+    /// Deletes a set
+    pub async fn del(&self, key: &str) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        conn.del(key).await
+    }
+
+    /// Checks if a set exists
+    pub async fn exists(&self, key: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.exists(key).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets the TTL of a set in seconds
+    pub async fn ttl(&self, key: &str) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        conn.ttl(key).await
+    }
+
+    /// Sets the TTL of a set in seconds
+    pub async fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.expire(key, seconds as i64).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets keys matching a pattern
+    pub async fn keys(&self, pattern: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.keys(pattern).await
+    }
+}
+
+/// Pipeline operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisSet<C> {
+    /// Executes a function with a pipeline
+    pub async fn with_pipeline<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        let result = f(&mut pipe).query_async(&mut conn).await?;
+        Ok(result)
+    }
+
+    /// Helper: batch add multiple members to multiple sets using pipeline
+    pub async fn sadd_many(&self, set_members: Vec<(&str, Vec<&str>)>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for (set_key, members) in set_members {
+                let mut cmd = pipe.cmd("SADD").arg(set_key);
+                for member in members {
+                    cmd = cmd.arg(member);
+                }
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch remove multiple members from multiple sets using pipeline
+    pub async fn srem_many(&self, set_members: Vec<(&str, Vec<&str>)>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for (set_key, members) in set_members {
+                let mut cmd = pipe.cmd("SREM").arg(set_key);
+                for member in members {
+                    cmd = cmd.arg(member);
+                }
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get members from multiple sets using pipeline
+    pub async fn smembers_many(&self, keys: Vec<&str>) -> RedisResult<Vec<Vec<String>>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("SMEMBERS").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch check if members exist in sets using pipeline
+    pub async fn sismember_many(&self, key_members: Vec<(&str, &str)>) -> RedisResult<Vec<bool>> {
+        self.with_pipeline(|pipe| {
+            for (key, member) in key_members {
+                pipe.cmd("SISMEMBER").arg(key).arg(member);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get set cardinalities using pipeline
+    pub async fn scard_many(&self, keys: Vec<&str>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("SCARD").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch delete multiple sets using pipeline
+    pub async fn del_many(&self, keys: Vec<&str>) -> RedisResult<()> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("DEL").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+}
+
+/// Transaction operations (MULTI/EXEC)
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisSet<C> {
+    /// Executes the pipeline built by `f` atomically with MULTI/EXEC
+    pub async fn transaction<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        f(&mut pipe).query_async(&mut conn).await
+    }
+}
+
+/// Lua script operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisSet<C> {
+    /// Executes a Lua script with the given keys and arguments
+    pub async fn eval_script<T, K, A>(&self, script: &Script, keys: K, args: A) -> RedisResult<T>
+    where
+        T: FromRedisValue,
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+    {
+        let mut conn = self.conn.clone();
+        script.key(keys).arg(args).invoke_async(&mut conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::async_primitives::memory_connection;
+
+    fn sorted(mut members: Vec<String>) -> Vec<String> {
+        members.sort();
+        members
+    }
+
+    #[tokio::test]
+    async fn test_set_operations() {
+        let (_server, conn) = memory_connection().await;
+        let sets = AsyncRedisSet::new(conn);
+
+        assert_eq!(sets.sadd("a", &["x", "y", "z"]).await.unwrap(), 3);
+        assert_eq!(sets.sadd("b", &["y", "z", "w"]).await.unwrap(), 3);
+        assert_eq!(sets.sadd("a", &["x"]).await.unwrap(), 0);
+        assert_eq!(sets.scard("a").await.unwrap(), 3);
+        assert!(sets.sismember("a", "x").await.unwrap());
+        assert_eq!(sets.smembers_as_set("a").await.unwrap().len(), 3);
+
+        assert_eq!(sorted(sets.sinter(&["a", "b"]).await.unwrap()), ["y", "z"]);
+        assert_eq!(sorted(sets.sdiff(&["a", "b"]).await.unwrap()), ["x"]);
+        assert_eq!(sets.sunion(&["a", "b"]).await.unwrap().len(), 4);
+        assert_eq!(sets.sunionstore("all", &["a", "b"]).await.unwrap(), 4);
+
+        assert!(sets.smove("a", "b", "x").await.unwrap());
+        assert!(!sets.smove("a", "b", "missing").await.unwrap());
+        assert_eq!(sets.srem("b", &["x", "w"]).await.unwrap(), 2);
+        assert_eq!(sorted(sets.smembers("b").await.unwrap()), ["y", "z"]);
+
+        let popped = sets.spop("a").await.unwrap().unwrap();
+        assert!(["y", "z"].contains(&popped.as_str()));
+        sets.del("all").await.unwrap();
+        assert!(!sets.exists("all").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_batch_operations() {
+        let (_server, conn) = memory_connection().await;
+        let sets = AsyncRedisSet::new(conn);
+
+        assert_eq!(
+            sets.sadd_many(vec![("s1", vec!["a", "b"]), ("s2", vec!["c"])])
+                .await
+                .unwrap(),
+            [2, 1]
+        );
+        assert_eq!(sets.scard_many(vec!["s1", "s2"]).await.unwrap(), [2, 1]);
+        assert_eq!(
+            sets.sismember_many(vec![("s1", "a"), ("s2", "a")])
+                .await
+                .unwrap(),
+            [true, false]
+        );
+        assert_eq!(sets.srem_many(vec![("s1", vec!["a"])]).await.unwrap(), [1]);
+        assert_eq!(
+            sets.smembers_many(vec!["s1", "s2"]).await.unwrap(),
+            [vec!["b".to_string()], vec!["c".to_string()]]
+        );
+
+        sets.del_many(vec!["s1", "s2"]).await.unwrap();
+        assert!(sets.keys("*").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transaction() {
+        let (_server, conn) = memory_connection().await;
+        let sets = AsyncRedisSet::new(conn);
+
+        let (added, count): (usize, usize) = sets
+            .transaction(|pipe| pipe.sadd("s", &["a", "b"]).scard("s"))
+            .await
+            .unwrap();
+        assert_eq!((added, count), (2, 2));
+    }
+}
@@ -0,0 +1,555 @@
+use redis::aio::{ConnectionLike, MultiplexedConnection};
+use redis::{AsyncCommands, FromRedisValue, Pipeline, RedisResult, Script, ToRedisArgs};
+
+/// Async counterpart of [`RedisSortedSet`](crate::redis::primitives::sorted_set::RedisSortedSet) on a multiplexed connection.
+///
+/// Method names and return values match the blocking type, except for `transaction`. It
+/// runs the commands as one MULTI/EXEC block with [`Pipeline::atomic`], so `T` is decoded
+/// from the results of the queued commands. The blocking `transaction` sends MULTI and
+/// EXEC as ordinary pipeline commands and decodes every reply, including the `OK` and
+/// `QUEUED` acknowledgements.
+///
+/// Every call works on a clone of the multiplexed connection, so one instance can be
+/// shared between tasks without locking. Lua scripts are shared with the blocking type,
+/// e.g. `RedisSortedSet::create_script`.
+#[derive(Clone)]
+pub struct AsyncRedisSortedSet<C = MultiplexedConnection> {
+    conn: C,
+}
+
+/// Core implementation with basic sorted set operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisSortedSet<C> {
+    /// Creates a new AsyncRedisSortedSet instance with the provided connection
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    /// Gets the connection reference for direct usage
+    pub fn connection(&self) -> &C {
+        &self.conn
+    }
+
+    /// Adds one or more members with scores to a sorted set
+    pub async fn zadd(&self, key: &str, items: &[(f64, &str)]) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        let mut cmd = redis::cmd("ZADD");
+        cmd.arg(key);
+        for (score, member) in items {
+            cmd.arg(score).arg(member);
+        }
+        cmd.query_async(&mut conn).await
+    }
+
+    /// Adds a single member with score to a sorted set
+    pub async fn zadd_single(&self, key: &str, score: f64, member: &str) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.zadd(key, member, score).await
+    }
+
+    /// Removes one or more members from a sorted set
+    pub async fn zrem(&self, key: &str, members: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.zrem(key, members).await
+    }
+
+    /// Returns a range of members from a sorted set by index
+    pub async fn zrange(&self, key: &str, start: isize, stop: isize) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.zrange(key, start, stop).await
+    }
+
+    /// Returns a range of members with scores from a sorted set by index
+    pub async fn zrange_withscores(
+        &self,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        let mut conn = self.conn.clone();
+        conn.zrange_withscores(key, start, stop).await
+    }
+
+    /// Returns a range of members from a sorted set by score
+    pub async fn zrangebyscore(&self, key: &str, min: f64, max: f64) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.zrangebyscore(key, min, max).await
+    }
+
+    /// Returns a range of members with scores from a sorted set by score
+    pub async fn zrangebyscore_withscores(
+        &self,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        let mut conn = self.conn.clone();
+        conn.zrangebyscore_withscores(key, min, max).await
+    }
+
+    /// Returns a range of members from a sorted set by score with limit
+    pub async fn zrangebyscore_limit(
+        &self,
+        key: &str,
+        min: f64,
+        max: f64,
+        offset: isize,
+        count: isize,
+    ) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.zrangebyscore_limit(key, min, max, offset, count).await
+    }
+
+    /// Returns a range of members with scores from a sorted set by score with limit
+    pub async fn zrangebyscore_limit_withscores(
+        &self,
+        key: &str,
+        min: f64,
+        max: f64,
+        offset: isize,
+        count: isize,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        let mut conn = self.conn.clone();
+        conn.zrangebyscore_limit_withscores(key, min, max, offset, count)
+            .await
+    }
+
+    /// Returns a reverse range of members from a sorted set by index
+    pub async fn zrevrange(
+        &self,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.zrevrange(key, start, stop).await
+    }
+
+    /// Returns a reverse range of members with scores from a sorted set by index
+    pub async fn zrevrange_withscores(
+        &self,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        let mut conn = self.conn.clone();
+        conn.zrevrange_withscores(key, start, stop).await
+    }
+
+    /// Returns a reverse range of members from a sorted set by score
+    pub async fn zrevrangebyscore(
+        &self,
+        key: &str,
+        max: f64,
+        min: f64,
+    ) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.zrevrangebyscore(key, max, min).await
+    }
+
+    /// Returns a reverse range of members with scores from a sorted set by score
+    pub async fn zrevrangebyscore_withscores(
+        &self,
+        key: &str,
+        max: f64,
+        min: f64,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        let mut conn = self.conn.clone();
+        conn.zrevrangebyscore_withscores(key, max, min).await
+    }
+
+    /// Returns a reverse range of members from a sorted set by score with limit
+    pub async fn zrevrangebyscore_limit(
+        &self,
+        key: &str,
+        max: f64,
+        min: f64,
+        offset: isize,
+        count: isize,
+    ) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.zrevrangebyscore_limit(key, max, min, offset, count)
+            .await
+    }
+
+    /// Returns a reverse range of members with scores from a sorted set by score with limit
+    pub async fn zrevrangebyscore_limit_withscores(
+        &self,
+        key: &str,
+        max: f64,
+        min: f64,
+        offset: isize,
+        count: isize,
+    ) -> RedisResult<Vec<(String, f64)>> {
+        let mut conn = self.conn.clone();
+        conn.zrevrangebyscore_limit_withscores(key, max, min, offset, count)
+            .await
+    }
+
+    /// Returns the rank of a member in a sorted set
+    pub async fn zrank(&self, key: &str, member: &str) -> RedisResult<Option<usize>> {
+        let mut conn = self.conn.clone();
+        conn.zrank(key, member).await
+    }
+
+    /// Returns the reverse rank of a member in a sorted set
+    pub async fn zrevrank(&self, key: &str, member: &str) -> RedisResult<Option<usize>> {
+        let mut conn = self.conn.clone();
+        conn.zrevrank(key, member).await
+    }
+
+    /// Returns the score of a member in a sorted set
+    pub async fn zscore(&self, key: &str, member: &str) -> RedisResult<Option<f64>> {
+        let mut conn = self.conn.clone();
+        conn.zscore(key, member).await
+    }
+
+    /// Returns the number of members in a sorted set
+    pub async fn zcard(&self, key: &str) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.zcard(key).await
+    }
+
+    /// Returns the number of members in a sorted set with scores between min and max
+    pub async fn zcount(&self, key: &str, min: f64, max: f64) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.zcount(key, min, max).await
+    }
+
+    /// Increments the score of a member in a sorted set
+    pub async fn zincrby(&self, key: &str, delta: f64, member: &str) -> RedisResult<f64> {
+        let mut conn = self.conn.clone();
+        conn.zincr(key, member, delta).await
+    }
+
+    /// Removes all members in a sorted set with rank between start and stop
+    pub async fn zremrangebyrank(
+        &self,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.zremrangebyrank(key, start, stop).await
+    }
+
+    /// Removes all members in a sorted set with scores between min and max
+    pub async fn zremrangebyscore(&self, key: &str, min: f64, max: f64) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.zrembyscore(key, min, max).await
+    }
+
+    /// Returns the intersection of multiple sorted sets
+    pub async fn zinterstore(&self, destination: &str, keys: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.zinterstore(destination, keys).await
+    }
+
+    /// Returns the union of multiple sorted sets
+    pub async fn zunionstore(&self, destination: &str, keys: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.zunionstore(destination, keys).await
+    }
+
+    /// Returns the intersection of multiple sorted sets with weights
+    pub async fn zinterstore_weights(
+        &self,
+        destination: &str,
+        keys: &[&str],
+        weights: &[f64],
+    ) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        let key_weight_pairs: Vec<(&str, f64)> = keys
+            .iter()
+            .zip(weights.iter())
+            .map(|(k, w)| (*k, *w))
+            .collect();
+        conn.zinterstore_weights(destination, &key_weight_pairs)
+            .await
+    }
+
+    /// Returns the union of multiple sorted sets with weights
+    pub async fn zunionstore_weights(
+        &self,
+        destination: &str,
+        keys: &[&str],
+        weights: &[f64],
+    ) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        let key_weight_pairs: Vec<(&str, f64)> = keys
+            .iter()
+            .zip(weights.iter())
+            .map(|(k, w)| (*k, *w))
+            .collect();
+        conn.zunionstore_weights(destination, &key_weight_pairs)
+            .await
+    }
+
+    /// Deletes a sorted set
+    pub async fn del(&self, key: &str) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        conn.del(key).await
+    }
+
+    /// Checks if a sorted set exists
+    pub async fn exists(&self, key: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.exists(key).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets the TTL of a sorted set in seconds
+    pub async fn ttl(&self, key: &str) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        conn.ttl(key).await
+    }
+
+    /// Sets the TTL of a sorted set in seconds
+    pub async fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.expire(key, seconds as i64).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets keys matching a pattern
+    pub async fn keys(&self, pattern: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.keys(pattern).await
+    }
+}
+
+/// Pipeline operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisSortedSet<C> {
+    /// Executes a function with a pipeline
+    pub async fn with_pipeline<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        let result = f(&mut pipe).query_async(&mut conn).await?;
+        Ok(result)
+    }
+
+    /// Helper: batch add multiple members with scores to multiple sorted sets using pipeline
+    pub async fn zadd_many(
+        &self,
+        set_items: Vec<(&str, Vec<(f64, &str)>)>,
+    ) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for (set_key, items) in set_items {
+                let mut cmd = pipe.cmd("ZADD").arg(set_key);
+                for (score, member) in items {
+                    cmd = cmd.arg(score).arg(member);
+                }
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch remove multiple members from multiple sorted sets using pipeline
+    pub async fn zrem_many(&self, set_members: Vec<(&str, Vec<&str>)>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for (set_key, members) in set_members {
+                let mut cmd = pipe.cmd("ZREM").arg(set_key);
+                for member in members {
+                    cmd = cmd.arg(member);
+                }
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get ranges from multiple sorted sets using pipeline
+    pub async fn zrange_many(
+        &self,
+        set_ranges: Vec<(&str, isize, isize)>,
+    ) -> RedisResult<Vec<Vec<String>>> {
+        self.with_pipeline(|pipe| {
+            for (key, start, stop) in set_ranges {
+                pipe.cmd("ZRANGE").arg(key).arg(start).arg(stop);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get scores from multiple sorted sets using pipeline
+    pub async fn zscore_many(
+        &self,
+        key_members: Vec<(&str, &str)>,
+    ) -> RedisResult<Vec<Option<f64>>> {
+        self.with_pipeline(|pipe| {
+            for (key, member) in key_members {
+                pipe.cmd("ZSCORE").arg(key).arg(member);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get ranks from multiple sorted sets using pipeline
+    pub async fn zrank_many(
+        &self,
+        key_members: Vec<(&str, &str)>,
+    ) -> RedisResult<Vec<Option<usize>>> {
+        self.with_pipeline(|pipe| {
+            for (key, member) in key_members {
+                pipe.cmd("ZRANK").arg(key).arg(member);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get cardinalities from multiple sorted sets using pipeline
+    pub async fn zcard_many(&self, keys: Vec<&str>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("ZCARD").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch delete multiple sorted sets using pipeline
+    pub async fn del_many(&self, keys: Vec<&str>) -> RedisResult<()> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("DEL").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+}
+
+/// Transaction operations (MULTI/EXEC)
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisSortedSet<C> {
+    /// Executes the pipeline built by `f` atomically with MULTI/EXEC
+    pub async fn transaction<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        f(&mut pipe).query_async(&mut conn).await
+    }
+}
+
+/// Lua script operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisSortedSet<C> {
+    /// Executes a Lua script with the given keys and arguments
+    pub async fn eval_script<T, K, A>(&self, script: &Script, keys: K, args: A) -> RedisResult<T>
+    where
+        T: FromRedisValue,
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+    {
+        let mut conn = self.conn.clone();
+        script.key(keys).arg(args).invoke_async(&mut conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::async_primitives::memory_connection;
+
+    #[tokio::test]
+    async fn test_sorted_set_operations() {
+        let (_server, conn) = memory_connection().await;
+        let zsets = AsyncRedisSortedSet::new(conn);
+
+        assert_eq!(
+            zsets
+                .zadd("board", &[(10.0, "ada"), (20.0, "bob"), (30.0, "cy")])
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(zsets.zadd_single("board", 5.0, "dee").await.unwrap(), 1);
+        assert_eq!(zsets.zcard("board").await.unwrap(), 4);
+        assert_eq!(zsets.zrange("board", 0, 1).await.unwrap(), ["dee", "ada"]);
+        assert_eq!(zsets.zrevrange("board", 0, 0).await.unwrap(), ["cy"]);
+        assert_eq!(
+            zsets.zrangebyscore("board", 10.0, 20.0).await.unwrap(),
+            ["ada", "bob"]
+        );
+        assert_eq!(
+            zsets.zrange_withscores("board", 0, 0).await.unwrap(),
+            [("dee".to_string(), 5.0)]
+        );
+        assert_eq!(zsets.zrank("board", "bob").await.unwrap(), Some(2));
+        assert_eq!(zsets.zrevrank("board", "bob").await.unwrap(), Some(1));
+        assert_eq!(zsets.zscore("board", "missing").await.unwrap(), None);
+        assert_eq!(zsets.zincrby("board", 15.0, "ada").await.unwrap(), 25.0);
+        assert_eq!(zsets.zcount("board", 20.0, 30.0).await.unwrap(), 3);
+
+        assert_eq!(zsets.zremrangebyscore("board", 0.0, 5.0).await.unwrap(), 1);
+        assert_eq!(zsets.zrem("board", &["cy"]).await.unwrap(), 1);
+        assert_eq!(zsets.zrange("board", 0, -1).await.unwrap(), ["bob", "ada"]);
+        zsets.del("board").await.unwrap();
+        assert!(!zsets.exists("board").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_batch_operations() {
+        let (_server, conn) = memory_connection().await;
+        let zsets = AsyncRedisSortedSet::new(conn);
+
+        assert_eq!(
+            zsets
+                .zadd_many(vec![
+                    ("z1", vec![(1.0, "a"), (2.0, "b")]),
+                    ("z2", vec![(3.0, "c")]),
+                ])
+                .await
+                .unwrap(),
+            [2, 1]
+        );
+        assert_eq!(zsets.zcard_many(vec!["z1", "z2"]).await.unwrap(), [2, 1]);
+        assert_eq!(
+            zsets
+                .zscore_many(vec![("z1", "b"), ("z2", "a")])
+                .await
+                .unwrap(),
+            [Some(2.0), None]
+        );
+        assert_eq!(
+            zsets
+                .zrank_many(vec![("z1", "b"), ("z2", "c")])
+                .await
+                .unwrap(),
+            [Some(1), Some(0)]
+        );
+        assert_eq!(zsets.zrem_many(vec![("z1", vec!["a"])]).await.unwrap(), [1]);
+        assert_eq!(
+            zsets
+                .zrange_many(vec![("z1", 0, -1), ("z2", 0, -1)])
+                .await
+                .unwrap(),
+            [vec!["b".to_string()], vec!["c".to_string()]]
+        );
+
+        zsets.del_many(vec!["z1", "z2"]).await.unwrap();
+        assert!(zsets.keys("*").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transaction() {
+        let (_server, conn) = memory_connection().await;
+        let zsets = AsyncRedisSortedSet::new(conn);
+
+        let (added, score): (usize, f64) = zsets
+            .transaction(|pipe| pipe.zadd("z", "a", 1.5).zincr("z", "a", 1.0))
+            .await
+            .unwrap();
+        assert_eq!((added, score), (1, 2.5));
+    }
+}
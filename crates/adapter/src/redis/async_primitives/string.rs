@@ -0,0 +1,310 @@
+use redis::aio::{ConnectionLike, MultiplexedConnection};
+use redis::{AsyncCommands, FromRedisValue, Pipeline, RedisResult, Script, ToRedisArgs};
+
+/// Async counterpart of [`RedisString`](crate::redis::primitives::string::RedisString) on a multiplexed connection.
+///
+/// Method names and return values match the blocking type, except for `transaction`. It
+/// runs the commands as one MULTI/EXEC block with [`Pipeline::atomic`], so `T` is decoded
+/// from the results of the queued commands. The blocking `transaction` sends MULTI and
+/// EXEC as ordinary pipeline commands and decodes every reply, including the `OK` and
+/// `QUEUED` acknowledgements.
+///
+/// Every call works on a clone of the multiplexed connection, so one instance can be
+/// shared between tasks without locking. Lua scripts are shared with the blocking type,
+/// e.g. `RedisString::create_script`.
+#[derive(Clone)]
+pub struct AsyncRedisString<C = MultiplexedConnection> {
+    conn: C,
+}
+
+/// Core implementation with basic string operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisString<C> {
+    /// Creates a new AsyncRedisString instance with the provided connection
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    /// Gets the connection reference for direct usage
+    pub fn connection(&self) -> &C {
+        &self.conn
+    }
+
+    /// Sets a key to hold the string value
+    pub async fn set(&self, key: &str, value: &str) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        conn.set(key, value).await
+    }
+
+    /// Gets the string value of a key
+    pub async fn get(&self, key: &str) -> RedisResult<Option<String>> {
+        let mut conn = self.conn.clone();
+        conn.get(key).await
+    }
+
+    /// Appends a value to a key
+    pub async fn append(&self, key: &str, value: &str) -> RedisResult<usize> {
+        let mut conn = self.conn.clone();
+        conn.append(key, value).await
+    }
+
+    /// Increments the number stored at key by one
+    pub async fn incr(&self, key: &str) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        conn.incr(key, 1).await
+    }
+
+    /// Sets a key with expiration
+    pub async fn set_with_expiry(
+        &self,
+        key: &str,
+        value: &str,
+        ttl_seconds: usize,
+    ) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        conn.set_ex(key, value, ttl_seconds as u64).await
+    }
+
+    /// Increments the number stored at key by the given amount
+    pub async fn incr_by(&self, key: &str, amount: i64) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        conn.incr(key, amount).await
+    }
+
+    /// Decrements the number stored at key by one
+    pub async fn decr(&self, key: &str) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        conn.decr(key, 1).await
+    }
+
+    /// Decrements the number stored at key by the given amount
+    pub async fn decr_by(&self, key: &str, amount: i64) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        conn.decr(key, amount).await
+    }
+
+    /// Deletes a key
+    pub async fn del(&self, key: &str) -> RedisResult<()> {
+        let mut conn = self.conn.clone();
+        conn.del(key).await
+    }
+
+    /// Checks if a key exists
+    pub async fn exists(&self, key: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.exists(key).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets the TTL of a key in seconds
+    pub async fn ttl(&self, key: &str) -> RedisResult<i64> {
+        let mut conn = self.conn.clone();
+        conn.ttl(key).await
+    }
+
+    /// Sets the TTL of a key in seconds
+    pub async fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
+        let mut conn = self.conn.clone();
+        let result: i32 = conn.expire(key, seconds as i64).await?;
+        Ok(result == 1)
+    }
+
+    /// Gets keys matching a pattern
+    pub async fn keys(&self, pattern: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.clone();
+        conn.keys(pattern).await
+    }
+}
+
+/// Pipeline operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisString<C> {
+    /// Executes a function with a pipeline
+    pub async fn with_pipeline<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        let result = f(&mut pipe).query_async(&mut conn).await?;
+        Ok(result)
+    }
+
+    /// Helper: batch set multiple keys using pipeline
+    pub async fn set_many(&self, kvs: Vec<(&str, &str)>) -> RedisResult<()> {
+        self.with_pipeline(|pipe| {
+            for (key, val) in kvs {
+                pipe.cmd("SET").arg(key).arg(val);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch get multiple keys using pipeline
+    pub async fn get_many(&self, keys: Vec<&str>) -> RedisResult<Vec<Option<String>>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("GET").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch set multiple keys with expiry using pipeline
+    pub async fn set_many_with_expiry(&self, kvs: Vec<(&str, &str, usize)>) -> RedisResult<()> {
+        self.with_pipeline(|pipe| {
+            for (key, val, ttl) in kvs {
+                pipe.cmd("SETEX").arg(key).arg(ttl).arg(val);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch increment multiple keys using pipeline
+    pub async fn incr_many(&self, keys: Vec<&str>) -> RedisResult<Vec<i64>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("INCRBY").arg(key).arg(1);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch increment multiple keys by specific amounts using pipeline
+    pub async fn incr_many_by(&self, kvs: Vec<(&str, i64)>) -> RedisResult<Vec<i64>> {
+        self.with_pipeline(|pipe| {
+            for (key, amount) in kvs {
+                pipe.cmd("INCRBY").arg(key).arg(amount);
+            }
+            pipe
+        })
+        .await
+    }
+
+    /// Helper: batch delete multiple keys using pipeline
+    pub async fn del_many(&self, keys: Vec<&str>) -> RedisResult<()> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("DEL").arg(key);
+            }
+            pipe
+        })
+        .await
+    }
+}
+
+/// Transaction operations (MULTI/EXEC)
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisString<C> {
+    /// Executes the pipeline built by `f` atomically with MULTI/EXEC
+    pub async fn transaction<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.clone();
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        f(&mut pipe).query_async(&mut conn).await
+    }
+}
+
+/// Lua script operations
+impl<C: ConnectionLike + Clone + Send + Sync> AsyncRedisString<C> {
+    /// Executes a Lua script with the given keys and arguments
+    pub async fn eval_script<T, K, A>(&self, script: &Script, keys: K, args: A) -> RedisResult<T>
+    where
+        T: FromRedisValue,
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+    {
+        let mut conn = self.conn.clone();
+        script.key(keys).arg(args).invoke_async(&mut conn).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::redis::async_primitives::memory_connection;
+
+    #[tokio::test]
+    async fn test_string_operations() {
+        let (_server, conn) = memory_connection().await;
+        let strings = AsyncRedisString::new(conn);
+
+        strings.set("greeting", "hello").await.unwrap();
+        assert_eq!(strings.append("greeting", " world").await.unwrap(), 11);
+        assert_eq!(
+            strings.get("greeting").await.unwrap().as_deref(),
+            Some("hello world")
+        );
+        assert_eq!(strings.get("missing").await.unwrap(), None);
+
+        assert_eq!(strings.incr("counter").await.unwrap(), 1);
+        assert_eq!(strings.incr_by("counter", 9).await.unwrap(), 10);
+        assert_eq!(strings.decr_by("counter", 4).await.unwrap(), 6);
+        assert_eq!(strings.decr("counter").await.unwrap(), 5);
+
+        strings
+            .set_with_expiry("session", "token", 60)
+            .await
+            .unwrap();
+        assert!(strings.ttl("session").await.unwrap() > 0);
+        assert!(strings.expire("greeting", 60).await.unwrap());
+        let mut keys = strings.keys("*").await.unwrap();
+        keys.sort();
+        assert_eq!(keys, ["counter", "greeting", "session"]);
+
+        strings.del("session").await.unwrap();
+        assert!(!strings.exists("session").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_batch_operations() {
+        let (_server, conn) = memory_connection().await;
+        let strings = AsyncRedisString::new(conn);
+
+        strings
+            .set_many(vec![("a", "1"), ("b", "2")])
+            .await
+            .unwrap();
+        strings
+            .set_many_with_expiry(vec![("c", "3", 60)])
+            .await
+            .unwrap();
+        assert_eq!(
+            strings.get_many(vec!["a", "b", "c", "d"]).await.unwrap(),
+            [Some("1".into()), Some("2".into()), Some("3".into()), None]
+        );
+        assert!(strings.ttl("c").await.unwrap() > 0);
+        assert_eq!(strings.incr_many(vec!["a", "b"]).await.unwrap(), [2, 3]);
+        assert_eq!(
+            strings
+                .incr_many_by(vec![("a", 10), ("z", -1)])
+                .await
+                .unwrap(),
+            [12, -1]
+        );
+
+        strings.del_many(vec!["a", "b", "c"]).await.unwrap();
+        assert_eq!(strings.keys("*").await.unwrap(), ["z"]);
+    }
+
+    #[tokio::test]
+    async fn test_transaction() {
+        let (_server, conn) = memory_connection().await;
+        let strings = AsyncRedisString::new(conn);
+
+        // Only the queued commands' results come back, not MULTI's or QUEUED
+        let (set, count): (String, i64) = strings
+            .transaction(|pipe| pipe.set("key", "value").incr("counter", 1))
+            .await
+            .unwrap();
+        assert_eq!(set, "OK");
+        assert_eq!(count, 1);
+        assert_eq!(strings.get("key").await.unwrap().as_deref(), Some("value"));
+    }
+}
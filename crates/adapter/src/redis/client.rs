@@ -5,7 +5,24 @@
 //! connection types.
 
 use redis::{Client, Connection, RedisError, RedisResult};
+use std::path::Path;
 use std::sync::{Arc, Mutex};
+#[cfg(feature = "connection-pool")]
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicU64, Ordering},
+    sync::Condvar,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "connection-pool")]
+use crate::error::ConnectionError;
+#[cfg(feature = "connection-pool")]
+use redis::{aio::MultiplexedConnection, Cmd, ConnectionLike, Pipeline, RedisFuture, Value};
+#[cfg(feature = "connection-pool")]
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+#[cfg(feature = "connection-pool")]
+use tokio::task::JoinHandle;
 
 use super::primitives::hash::RedisHash;
 use super::primitives::set::RedisSet;
@@ -28,7 +45,12 @@ impl RedisClient {
     /// let client = RedisClient::from_url(&redis_url).unwrap();
     /// ```
     pub fn from_url(url: &str) -> RedisResult<Self> {
-        let client = Client::open(url)?;
+        Self::from_url_with_tls(url, &RedisTls::default())
+    }
+
+    /// Create a new Redis client for a `rediss://` URL, verified with `tls`
+    pub fn from_url_with_tls(url: &str, tls: &RedisTls) -> RedisResult<Self> {
+        let client = open_client(url, tls)?;
         let connection = client.get_connection()?;
         Ok(Self {
             client: Arc::new(client),
@@ -82,12 +104,303 @@ impl RedisClient {
     }
 }
 
-/// A Redis connection pool for handling concurrent requests
-/// This is available when the "connection-pool" feature is enabled
+/// Certificates for `rediss://` connections
+///
+/// Without a CA bundle the server is verified against the system roots.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RedisTls {
+    /// PEM CA bundle to verify the server with
+    pub ca_cert: Option<Vec<u8>>,
+    /// Certificate to present to servers that require one
+    pub client_cert: Option<RedisClientCert>,
+}
+
+/// PEM client certificate chain and private key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedisClientCert {
+    pub cert: Vec<u8>,
+    pub key: Vec<u8>,
+}
+
+impl RedisTls {
+    /// Read the CA bundle and the client certificate and key from PEM files
+    pub fn from_files(
+        ca_cert: Option<&Path>,
+        client_cert: Option<(&Path, &Path)>,
+    ) -> std::io::Result<Self> {
+        Ok(Self {
+            ca_cert: ca_cert.map(std::fs::read).transpose()?,
+            client_cert: match client_cert {
+                Some((cert, key)) => Some(RedisClientCert {
+                    cert: std::fs::read(cert)?,
+                    key: std::fs::read(key)?,
+                }),
+                None => None,
+            },
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ca_cert.is_none() && self.client_cert.is_none()
+    }
+}
+
+/// Open a client for `url`, using the certificates in `tls` if there are any
+///
+/// Certificates need a `rediss://` URL and the "tls" feature.
+pub fn open_client(url: &str, tls: &RedisTls) -> RedisResult<Client> {
+    if tls.is_empty() {
+        return Client::open(url);
+    }
+
+    #[cfg(feature = "tls")]
+    {
+        Client::build_with_tls(
+            url,
+            redis::TlsCertificates {
+                client_tls: tls
+                    .client_cert
+                    .as_ref()
+                    .map(|client| redis::ClientTlsConfig {
+                        client_cert: client.cert.clone(),
+                        client_key: client.key.clone(),
+                    }),
+                root_cert: tls.ca_cert.clone(),
+            },
+        )
+    }
+    #[cfg(not(feature = "tls"))]
+    {
+        Err(RedisError::from((
+            redis::ErrorKind::InvalidClientConfig,
+            "TLS certificates need the \"tls\" feature",
+        )))
+    }
+}
+
+/// Settings for a [`RedisPool`]
 #[cfg(feature = "connection-pool")]
-pub struct RedisPool {
+#[derive(Debug, Clone)]
+pub struct RedisPoolConfig {
+    /// Maximum number of open connections, idle and in use, and of
+    /// multiplexed checkouts held at once
+    pub max_size: u32,
+    /// How long a checkout waits for a free connection before failing
+    pub checkout_timeout: Duration,
+    /// Idle connections older than this are closed; `None` keeps them forever
+    pub idle_timeout: Option<Duration>,
+    /// PING idle connections before handing them out
+    pub test_on_checkout: bool,
+    /// Certificates for `rediss://` URLs
+    pub tls: RedisTls,
+}
+
+#[cfg(feature = "connection-pool")]
+impl Default for RedisPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 10,
+            checkout_timeout: Duration::from_secs(5),
+            idle_timeout: Some(Duration::from_secs(300)),
+            test_on_checkout: true,
+            tls: RedisTls::default(),
+        }
+    }
+}
+
+/// A point-in-time snapshot of pool usage
+#[cfg(feature = "connection-pool")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PoolMetrics {
+    pub max_size: u32,
+    /// Open connections, idle and in use
+    pub connections: u32,
+    pub idle: u32,
+    /// Checked out connections, blocking and multiplexed
+    pub in_use: u32,
+    pub connections_created: u64,
+    pub checkouts: u64,
+    /// Checkouts that failed with `ConnectionError::PoolExhausted`
+    pub checkout_timeouts: u64,
+    pub health_check_failures: u64,
+    pub idle_evictions: u64,
+}
+
+/// Callback told how long each pool checkout waited, e.g. to feed a histogram
+#[cfg(feature = "connection-pool")]
+pub type CheckoutObserver = Arc<dyn Fn(Duration) + Send + Sync>;
+
+#[cfg(feature = "connection-pool")]
+struct IdleConnection {
+    conn: Arc<Mutex<Connection>>,
+    idle_since: Instant,
+}
+
+#[cfg(feature = "connection-pool")]
+#[derive(Default)]
+struct PoolState {
+    idle: VecDeque<IdleConnection>,
+    /// Connections that are idle, checked out or being opened
+    open: u32,
+}
+
+#[cfg(feature = "connection-pool")]
+#[derive(Default)]
+struct PoolCounters {
+    connections_created: AtomicU64,
+    checkouts: AtomicU64,
+    checkout_timeouts: AtomicU64,
+    health_check_failures: AtomicU64,
+    idle_evictions: AtomicU64,
+}
+
+#[cfg(feature = "connection-pool")]
+struct PoolInner {
     client: Arc<Client>,
-    pool_size: u32,
+    config: RedisPoolConfig,
+    state: Mutex<PoolState>,
+    available: Condvar,
+    counters: PoolCounters,
+    checkout_observer: std::sync::RwLock<Option<CheckoutObserver>>,
+    /// Shared multiplexed connection and the task driving it
+    multiplexed: tokio::sync::Mutex<Option<(MultiplexedConnection, JoinHandle<()>)>>,
+    /// One permit per multiplexed checkout that may be held at once
+    multiplexed_checkouts: Arc<Semaphore>,
+}
+
+#[cfg(feature = "connection-pool")]
+impl PoolInner {
+    /// Closes idle connections past the idle timeout, returning how many were closed
+    fn evict_expired(&self, state: &mut PoolState) -> usize {
+        let Some(idle_timeout) = self.config.idle_timeout else {
+            return 0;
+        };
+        let before = state.idle.len();
+        state
+            .idle
+            .retain(|idle| idle.idle_since.elapsed() < idle_timeout);
+        let evicted = before - state.idle.len();
+        if evicted > 0 {
+            state.open -= evicted as u32;
+            self.counters
+                .idle_evictions
+                .fetch_add(evicted as u64, Ordering::Relaxed);
+            self.available.notify_all();
+        }
+        evicted
+    }
+
+    fn observe_checkout(&self, started: Instant) {
+        if let Some(observer) = self.checkout_observer.read().unwrap().as_ref() {
+            observer(started.elapsed());
+        }
+    }
+
+    /// Gives up a connection slot, e.g. after a failed health check or a broken connection
+    fn discard(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.open -= 1;
+        self.available.notify_one();
+    }
+
+    fn release(&self, conn: Arc<Mutex<Connection>>) {
+        // A connection still shared elsewhere or closed by the server is not reusable
+        let reusable = Arc::strong_count(&conn) == 1
+            && conn.try_lock().map(|conn| conn.is_open()).unwrap_or(false);
+        if !reusable {
+            self.discard();
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.idle.push_back(IdleConnection {
+            conn,
+            idle_since: Instant::now(),
+        });
+        self.evict_expired(&mut state);
+        self.available.notify_one();
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+fn is_healthy(conn: &Arc<Mutex<Connection>>) -> bool {
+    let mut conn = conn.lock().unwrap();
+    redis::cmd("PING")
+        .query::<String>(&mut *conn)
+        .map(|pong| pong == "PONG")
+        .unwrap_or(false)
+}
+
+/// A connection checked out of a [`RedisPool`], returned to it when dropped
+#[cfg(feature = "connection-pool")]
+pub struct PooledConnection {
+    conn: Option<Arc<Mutex<Connection>>>,
+    pool: Arc<PoolInner>,
+}
+
+#[cfg(feature = "connection-pool")]
+impl PooledConnection {
+    /// Get the shared connection handle for use with the Redis primitives
+    ///
+    /// Clones should not outlive this guard; a connection that is still
+    /// shared when the guard drops is closed instead of being reused.
+    pub fn shared(&self) -> Arc<Mutex<Connection>> {
+        self.conn.as_ref().expect("connection present").clone()
+    }
+}
+
+#[cfg(feature = "connection-pool")]
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.release(conn);
+        }
+    }
+}
+
+/// The pool's multiplexed connection, checked out of a [`RedisPool`]
+///
+/// Clones share the checkout, which is returned once the last of them is
+/// dropped. Works anywhere a `redis::aio` connection does, including the
+/// async primitives.
+#[cfg(feature = "connection-pool")]
+#[derive(Clone)]
+pub struct AsyncPooledConnection {
+    conn: MultiplexedConnection,
+    _checkout: Arc<OwnedSemaphorePermit>,
+}
+
+#[cfg(feature = "connection-pool")]
+impl redis::aio::ConnectionLike for AsyncPooledConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        self.conn.req_packed_command(cmd)
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        self.conn.req_packed_commands(cmd, offset, count)
+    }
+
+    fn get_db(&self) -> i64 {
+        self.conn.get_db()
+    }
+}
+
+/// A bounded Redis connection pool for handling concurrent requests
+///
+/// Idle connections are reused most-recently-used first, at most `max_size`
+/// connections are open at once, and checkouts wait up to the checkout
+/// timeout before failing with `ConnectionError::PoolExhausted`. Async callers
+/// share one multiplexed connection, but at most `max_size` checkouts of it
+/// are held at once, with the same timeout. Clones share the same pool. This
+/// is available when the "connection-pool" feature is enabled
+#[cfg(feature = "connection-pool")]
+#[derive(Clone)]
+pub struct RedisPool {
+    inner: Arc<PoolInner>,
 }
 
 #[cfg(feature = "connection-pool")]
@@ -101,41 +414,258 @@ impl RedisPool {
     /// let pool = RedisPool::new(&redis_url, 10).unwrap();
     /// ```
     pub fn new(url: &str, pool_size: u32) -> RedisResult<Self> {
-        let client = Client::open(url)?;
+        Self::with_config(
+            url,
+            RedisPoolConfig {
+                max_size: pool_size,
+                ..RedisPoolConfig::default()
+            },
+        )
+    }
+
+    /// Create a new Redis pool with custom settings
+    pub fn with_config(url: &str, config: RedisPoolConfig) -> RedisResult<Self> {
+        let client = open_client(url, &config.tls)?;
         Ok(Self {
-            client: Arc::new(client),
-            pool_size,
+            inner: Arc::new(PoolInner {
+                client: Arc::new(client),
+                state: Mutex::new(PoolState::default()),
+                available: Condvar::new(),
+                counters: PoolCounters::default(),
+                checkout_observer: std::sync::RwLock::new(None),
+                multiplexed: tokio::sync::Mutex::new(None),
+                multiplexed_checkouts: Arc::new(Semaphore::new(config.max_size as usize)),
+                config,
+            }),
         })
     }
 
     /// Get the pool size
     pub fn pool_size(&self) -> u32 {
-        self.pool_size
+        self.inner.config.max_size
+    }
+
+    /// Get the pool settings
+    pub fn config(&self) -> &RedisPoolConfig {
+        &self.inner.config
     }
 
     /// Get the raw Redis client
     pub fn client(&self) -> &Arc<Client> {
-        &self.client
+        &self.inner.client
     }
 
-    /// Get a synchronous connection from the pool
-    pub fn get_connection(&self) -> RedisResult<Connection> {
-        self.client.get_connection()
+    /// Report how long every checkout waits, successful or not
+    ///
+    /// Covers [`get_connection`](Self::get_connection) and
+    /// [`get_multiplexed_connection`](Self::get_multiplexed_connection);
+    /// replaces any previous observer.
+    pub fn set_checkout_observer(&self, observer: CheckoutObserver) {
+        *self.inner.checkout_observer.write().unwrap() = Some(observer);
+    }
+
+    /// Check out a connection, reusing an idle one or opening a new one below `max_size`
+    ///
+    /// Waits up to the checkout timeout when the pool is at capacity.
+    pub fn get_connection(&self) -> Result<PooledConnection, ConnectionError> {
+        let started = Instant::now();
+        let result = self.checkout();
+        self.inner.observe_checkout(started);
+        result
+    }
+
+    fn checkout(&self) -> Result<PooledConnection, ConnectionError> {
+        let inner = &self.inner;
+        let deadline = Instant::now() + inner.config.checkout_timeout;
+        let mut state = inner.state.lock().unwrap();
+
+        loop {
+            inner.evict_expired(&mut state);
+
+            if let Some(idle) = state.idle.pop_back() {
+                drop(state);
+                if !inner.config.test_on_checkout || is_healthy(&idle.conn) {
+                    inner.counters.checkouts.fetch_add(1, Ordering::Relaxed);
+                    return Ok(self.guard(idle.conn));
+                }
+                inner
+                    .counters
+                    .health_check_failures
+                    .fetch_add(1, Ordering::Relaxed);
+                inner.discard();
+                state = inner.state.lock().unwrap();
+                continue;
+            }
+
+            if state.open < inner.config.max_size {
+                state.open += 1;
+                drop(state);
+                return match inner.client.get_connection() {
+                    Ok(conn) => {
+                        inner
+                            .counters
+                            .connections_created
+                            .fetch_add(1, Ordering::Relaxed);
+                        inner.counters.checkouts.fetch_add(1, Ordering::Relaxed);
+                        Ok(self.guard(Arc::new(Mutex::new(conn))))
+                    }
+                    Err(e) => {
+                        inner.discard();
+                        Err(ConnectionError::ConnectionFailed(e.to_string()))
+                    }
+                };
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                inner
+                    .counters
+                    .checkout_timeouts
+                    .fetch_add(1, Ordering::Relaxed);
+                return Err(ConnectionError::PoolExhausted(format!(
+                    "no connection available within {:?} (max size {})",
+                    inner.config.checkout_timeout, inner.config.max_size
+                )));
+            }
+            state = inner
+                .available
+                .wait_timeout(state, deadline - now)
+                .unwrap()
+                .0;
+        }
+    }
+
+    /// Close idle connections past the idle timeout, returning how many were closed
+    ///
+    /// Expired connections are also evicted on every checkout and return, so
+    /// calling this is only needed to reclaim sockets on an otherwise quiet pool.
+    pub fn evict_idle(&self) -> usize {
+        let mut state = self.inner.state.lock().unwrap();
+        self.inner.evict_expired(&mut state)
+    }
+
+    /// Get a snapshot of the pool usage counters
+    pub fn metrics(&self) -> PoolMetrics {
+        let (connections, idle) = {
+            let state = self.inner.state.lock().unwrap();
+            (state.open, state.idle.len() as u32)
+        };
+        let counters = &self.inner.counters;
+        PoolMetrics {
+            max_size: self.inner.config.max_size,
+            connections,
+            idle,
+            in_use: connections - idle + self.multiplexed_in_use(),
+            connections_created: counters.connections_created.load(Ordering::Relaxed),
+            checkouts: counters.checkouts.load(Ordering::Relaxed),
+            checkout_timeouts: counters.checkout_timeouts.load(Ordering::Relaxed),
+            health_check_failures: counters.health_check_failures.load(Ordering::Relaxed),
+            idle_evictions: counters.idle_evictions.load(Ordering::Relaxed),
+        }
+    }
+
+    fn multiplexed_in_use(&self) -> u32 {
+        self.inner.config.max_size - self.inner.multiplexed_checkouts.available_permits() as u32
     }
 
-    /// Get an asynchronous connection from the pool
+    /// Whether the pool currently holds an open connection
+    pub fn is_connected(&self) -> bool {
+        let multiplexed = self
+            .inner
+            .multiplexed
+            .try_lock()
+            .map(|multiplexed| {
+                multiplexed
+                    .as_ref()
+                    .is_some_and(|(_, driver)| !driver.is_finished())
+            })
+            // Someone is opening or handing out the multiplexed connection right now
+            .unwrap_or(true);
+        multiplexed || self.inner.state.lock().unwrap().open > 0
+    }
+
+    /// Close the idle connections and the multiplexed connection
+    ///
+    /// Checked out connections stay usable and go back to the pool as usual;
+    /// new connections are opened on the next checkout.
+    pub async fn close(&self) {
+        if let Some((_, driver)) = self.inner.multiplexed.lock().await.take() {
+            driver.abort();
+        }
+        let mut state = self.inner.state.lock().unwrap();
+        let idle = state.idle.len() as u32;
+        state.idle.clear();
+        state.open -= idle;
+        self.inner.available.notify_all();
+    }
+
+    /// Get an asynchronous connection, opened outside the pool
     #[cfg(feature = "async")]
+    // Superseded by multiplexed connections upstream; kept for existing callers
+    #[allow(deprecated)]
     pub async fn get_async_connection(&self) -> RedisResult<redis::aio::Connection> {
-        self.client.get_async_connection().await
+        self.inner.client.get_async_connection().await
     }
-}
 
-#[cfg(feature = "connection-pool")]
-impl Clone for RedisPool {
-    fn clone(&self) -> Self {
-        Self {
-            client: self.client.clone(),
-            pool_size: self.pool_size,
+    /// Check out the shared multiplexed connection, reconnecting if it was closed
+    ///
+    /// The connection is opened on first use and requests from every checkout
+    /// are pipelined over one socket. At most `max_size` checkouts are held at
+    /// once; past that this waits up to the checkout timeout and then fails
+    /// with `ConnectionError::PoolExhausted`.
+    pub async fn get_multiplexed_connection(&self) -> RedisResult<AsyncPooledConnection> {
+        let started = Instant::now();
+        let result = self.checkout_multiplexed().await;
+        self.inner.observe_checkout(started);
+        result
+    }
+
+    async fn checkout_multiplexed(&self) -> RedisResult<AsyncPooledConnection> {
+        let inner = &self.inner;
+        let permit = tokio::time::timeout(
+            inner.config.checkout_timeout,
+            inner.multiplexed_checkouts.clone().acquire_owned(),
+        )
+        .await;
+        let Ok(Ok(permit)) = permit else {
+            inner
+                .counters
+                .checkout_timeouts
+                .fetch_add(1, Ordering::Relaxed);
+            return Err(pool_error(ConnectionError::PoolExhausted(format!(
+                "no multiplexed checkout available within {:?} (max size {})",
+                inner.config.checkout_timeout, inner.config.max_size
+            ))));
+        };
+        let conn = self.multiplexed_connection().await?;
+        inner.counters.checkouts.fetch_add(1, Ordering::Relaxed);
+        Ok(AsyncPooledConnection {
+            conn,
+            _checkout: Arc::new(permit),
+        })
+    }
+
+    async fn multiplexed_connection(&self) -> RedisResult<MultiplexedConnection> {
+        let mut multiplexed = self.inner.multiplexed.lock().await;
+        if let Some((conn, driver)) = multiplexed.as_ref() {
+            if !driver.is_finished() {
+                return Ok(conn.clone());
+            }
+        }
+
+        let (conn, driver) = self
+            .inner
+            .client
+            .create_multiplexed_tokio_connection()
+            .await?;
+        *multiplexed = Some((conn.clone(), tokio::spawn(driver)));
+        Ok(conn)
+    }
+
+    fn guard(&self, conn: Arc<Mutex<Connection>>) -> PooledConnection {
+        PooledConnection {
+            conn: Some(conn),
+            pool: self.inner.clone(),
         }
     }
 }
@@ -161,17 +691,29 @@ impl RedisClientTrait for RedisClient {
 
 #[cfg(feature = "connection-pool")]
 impl RedisClientTrait for RedisPool {
+    /// Opens a dedicated connection that is not managed by the pool
     fn get_connection(&self) -> RedisResult<Connection> {
-        self.get_connection()
+        self.client().get_connection()
     }
 
     fn ping(&self) -> RedisResult<bool> {
-        let mut conn = self.get_connection()?;
-        let pong: String = redis::cmd("PING").query(&mut conn)?;
+        let pooled = self.get_connection().map_err(pool_error)?;
+        let conn = pooled.shared();
+        let pong: String = redis::cmd("PING").query(&mut *conn.lock().unwrap())?;
         Ok(pong == "PONG")
     }
 }
 
+/// Convert a pool checkout error into a Redis error
+#[cfg(feature = "connection-pool")]
+pub fn pool_error(error: ConnectionError) -> RedisError {
+    RedisError::from((
+        redis::ErrorKind::IoError,
+        "Connection pool error",
+        error.to_string(),
+    ))
+}
+
 /// Create a Redis client from a connection string
 pub fn create_client(url: &str) -> RedisResult<RedisClient> {
     RedisClient::from_url(url)
@@ -187,3 +729,193 @@ pub fn create_pool(url: &str, pool_size: u32) -> RedisResult<RedisPool> {
 pub fn format_redis_error(error: &RedisError) -> String {
     format!("Redis error: {error}")
 }
+
+#[cfg(all(test, feature = "connection-pool"))]
+mod tests {
+    use super::*;
+
+    fn unreachable_pool(max_size: u32) -> RedisPool {
+        RedisPool::with_config(
+            "redis://127.0.0.1:1",
+            RedisPoolConfig {
+                max_size,
+                checkout_timeout: Duration::from_millis(50),
+                ..RedisPoolConfig::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_checkout_times_out_when_pool_is_full() {
+        let pool = unreachable_pool(0);
+        let started = Instant::now();
+        match pool.get_connection() {
+            Err(ConnectionError::PoolExhausted(_)) => {}
+            Err(e) => panic!("expected PoolExhausted, got {e}"),
+            Ok(_) => panic!("expected PoolExhausted, got a connection"),
+        }
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert_eq!(pool.metrics().checkout_timeouts, 1);
+    }
+
+    #[test]
+    fn test_checkout_observer_sees_wait() {
+        let pool = unreachable_pool(0);
+        let waits = Arc::new(Mutex::new(Vec::new()));
+        let recorded = waits.clone();
+        pool.set_checkout_observer(Arc::new(move |wait| recorded.lock().unwrap().push(wait)));
+
+        assert!(pool.get_connection().is_err());
+        let waits = waits.lock().unwrap();
+        assert_eq!(waits.len(), 1);
+        assert!(waits[0] >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_failed_connect_frees_its_slot() {
+        let pool = unreachable_pool(1);
+        for _ in 0..2 {
+            assert!(matches!(
+                pool.get_connection(),
+                Err(ConnectionError::ConnectionFailed(_))
+            ));
+        }
+        let metrics = pool.metrics();
+        assert_eq!(metrics.connections, 0);
+        assert_eq!(metrics.checkout_timeouts, 0);
+    }
+
+    #[tokio::test]
+    async fn test_multiplexed_connection_is_not_cached_on_failure() {
+        let pool = unreachable_pool(1);
+        assert!(pool.get_multiplexed_connection().await.is_err());
+        assert!(pool.inner.multiplexed.lock().await.is_none());
+        assert_eq!(pool.metrics().connections, 0);
+    }
+
+    #[tokio::test]
+    async fn test_multiplexed_checkouts_are_bounded() {
+        use crate::memory::{MemoryServer, MemoryStore};
+
+        let backend = MemoryServer::start(MemoryStore::new()).await.unwrap();
+        let pool = RedisPool::with_config(
+            &backend.url(),
+            RedisPoolConfig {
+                max_size: 1,
+                checkout_timeout: Duration::from_millis(50),
+                ..RedisPoolConfig::default()
+            },
+        )
+        .unwrap();
+
+        // A clone keeps the checkout after the original is dropped
+        let mut conn = pool.get_multiplexed_connection().await.unwrap().clone();
+        assert_eq!(pool.metrics().in_use, 1);
+        assert!(pool.get_multiplexed_connection().await.is_err());
+        assert_eq!(pool.metrics().checkout_timeouts, 1);
+
+        let pong: String = redis::cmd("PING").query_async(&mut conn).await.unwrap();
+        assert_eq!(pong, "PONG");
+        drop(conn);
+        assert_eq!(pool.metrics().in_use, 0);
+        assert!(pool.get_multiplexed_connection().await.is_ok());
+        assert_eq!(pool.metrics().checkouts, 2);
+    }
+
+    /// A TLS proxy in front of an in-memory backend, with its certificate
+    /// signed by a CA made for the test; returns the proxy's `host:port`
+    /// with the backend password, and the CA
+    #[cfg(feature = "tls")]
+    async fn tls_backend() -> (String, Vec<u8>) {
+        use crate::memory::{MemoryServer, MemoryStore};
+        use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa};
+        use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+        use tokio_rustls::rustls::ServerConfig;
+
+        let mut ca_params = CertificateParams::new(Vec::new());
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = Certificate::from_params(ca_params).unwrap();
+        let cert =
+            Certificate::from_params(CertificateParams::new(vec!["localhost".into()])).unwrap();
+        let config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![CertificateDer::from(
+                    cert.serialize_der_with_signer(&ca).unwrap(),
+                )],
+                PrivateKeyDer::Pkcs8(cert.serialize_private_key_der().into()),
+            )
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+        let backend = MemoryServer::start(MemoryStore::new()).await.unwrap();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let host = format!(
+            ":{}@localhost:{}",
+            backend.password(),
+            listener.local_addr().unwrap().port()
+        );
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                let backend = backend.addr();
+                tokio::spawn(async move {
+                    let Ok(mut client) = acceptor.accept(socket).await else {
+                        return;
+                    };
+                    let mut server = tokio::net::TcpStream::connect(backend).await.unwrap();
+                    let _ = tokio::io::copy_bidirectional(&mut client, &mut server).await;
+                });
+            }
+        });
+        (host, ca.serialize_pem().unwrap().into_bytes())
+    }
+
+    #[cfg(feature = "tls")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rediss_with_custom_ca() {
+        let (host, ca_cert) = tls_backend().await;
+        let url = format!("rediss://{host}");
+        let tls = RedisTls {
+            ca_cert: Some(ca_cert),
+            client_cert: None,
+        };
+
+        let pool = RedisPool::with_config(
+            &url,
+            RedisPoolConfig {
+                tls: tls.clone(),
+                ..RedisPoolConfig::default()
+            },
+        )
+        .unwrap();
+        let mut conn = pool.get_multiplexed_connection().await.unwrap();
+        let pong: String = redis::cmd("PING").query_async(&mut conn).await.unwrap();
+        assert_eq!(pong, "PONG");
+
+        let client =
+            tokio::task::spawn_blocking(move || RedisClient::from_url_with_tls(&url, &tls))
+                .await
+                .unwrap()
+                .unwrap();
+        assert!(client.ping().unwrap());
+    }
+
+    #[cfg(feature = "tls")]
+    #[tokio::test]
+    async fn test_rediss_rejects_unknown_ca() {
+        let (host, _) = tls_backend().await;
+        let pool = RedisPool::new(&format!("rediss://{host}"), 1).unwrap();
+        assert!(pool.get_multiplexed_connection().await.is_err());
+    }
+
+    #[test]
+    fn test_tls_certificates_need_rediss() {
+        let tls = RedisTls {
+            ca_cert: Some(b"-----BEGIN CERTIFICATE-----".to_vec()),
+            client_cert: None,
+        };
+        assert!(open_client("redis://localhost:6379", &tls).is_err());
+    }
+}
@@ -1,10 +1,13 @@
 //! Redis adapter module
 //!
 //! This module provides adapters for interacting with Redis,
-//! organized by Redis data type (string, list, hash, set, sorted set).
+//! organized by Redis data type (string, list, hash, set, sorted set, stream).
 //! It includes support for individual commands, pipelined operations,
 //! transactions, and Lua scripts.
 
+pub mod adapter;
+#[cfg(feature = "async")]
+pub mod async_primitives;
 pub mod client;
 pub mod primitives;
 
@@ -14,18 +17,32 @@ use client::RedisClient;
 use primitives::admin::AdminOperations;
 use primitives::bitmap::RedisBitmap;
 use primitives::hash::RedisHash;
+use primitives::list::RedisList;
 use primitives::set::RedisSet;
+use primitives::sorted_set::RedisSortedSet;
+use primitives::stream::RedisStream;
 use primitives::string::RedisString;
 
 /// Redis data type adapters providing type-specific operations
 pub mod types {
     pub use super::primitives::bitmap::RedisBitmap;
     pub use super::primitives::hash::RedisHash;
+    pub use super::primitives::list::RedisList;
     pub use super::primitives::set::RedisSet;
-    pub use super::primitives::string::RedisString;
-    // Other Redis types will be added here as they're implemented:
-    // pub use super::primitives::list::RedisList;
     pub use super::primitives::sorted_set::RedisSortedSet;
+    pub use super::primitives::stream::RedisStream;
+    pub use super::primitives::string::RedisString;
+
+    #[cfg(feature = "async")]
+    pub use super::async_primitives::bitmap::AsyncRedisBitmap;
+    #[cfg(feature = "async")]
+    pub use super::async_primitives::hash::AsyncRedisHash;
+    #[cfg(feature = "async")]
+    pub use super::async_primitives::set::AsyncRedisSet;
+    #[cfg(feature = "async")]
+    pub use super::async_primitives::sorted_set::AsyncRedisSortedSet;
+    #[cfg(feature = "async")]
+    pub use super::async_primitives::string::AsyncRedisString;
 }
 
 /// Commonly used Redis Lua scripts
@@ -117,11 +134,26 @@ impl Redis {
         RedisSet::new(self.client.connection().clone())
     }
 
+    /// Get access to sorted set operations
+    pub fn sorted_set(&self) -> RedisSortedSet {
+        RedisSortedSet::new(self.client.connection().clone())
+    }
+
     /// Get access to hash operations
     pub fn hash(&self) -> RedisHash {
         RedisHash::new(self.client.connection().clone())
     }
 
+    /// Get access to list operations
+    pub fn list(&self) -> RedisList {
+        RedisList::new(self.client.connection().clone())
+    }
+
+    /// Get access to stream operations
+    pub fn stream(&self) -> RedisStream {
+        RedisStream::new(self.client.connection().clone())
+    }
+
     /// Get access to bitmap operations
     pub fn bitmap(&self) -> RedisBitmap {
         RedisBitmap::new(self.client.connection().clone())
@@ -240,14 +272,16 @@ impl RedisPoolAdapter {
     }
 
     /// Get a connection from the pool
-    pub fn get_connection(&self) -> RedisResult<Connection> {
+    pub fn get_connection(
+        &self,
+    ) -> Result<client::PooledConnection, crate::error::ConnectionError> {
         self.pool.get_connection()
     }
 
-    /// Get a Redis instance with a connection from the pool
+    /// Get a Redis instance with a dedicated connection opened outside the pool
     pub fn get_instance(&self) -> RedisResult<Redis> {
         use redis::Client;
-        let connection = self.get_connection()?;
+        let connection = self.pool.client().get_connection()?;
         let client = Client::clone(self.pool.client());
         let redis_client = RedisClient::new(client, connection);
         Ok(Redis::new(redis_client))
@@ -255,6 +289,8 @@ impl RedisPoolAdapter {
 
     /// Get an asynchronous connection from the pool
     #[cfg(feature = "async")]
+    // Superseded by multiplexed connections upstream; kept for existing callers
+    #[allow(deprecated)]
     pub async fn get_async_connection(&self) -> RedisResult<redis::aio::Connection> {
         self.pool.get_async_connection().await
     }
@@ -636,9 +636,7 @@ impl ServerStatus {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::redis::Redis;
-    use crate::test_helpers::get_test_redis_url;
 
     // Helper function to get Redis URL from environment or use default
     fn get_redis_url() -> String {
@@ -763,7 +761,7 @@ mod tests {
         let status = admin.server_status().unwrap();
 
         let hit_rate = status.hit_rate();
-        assert!(hit_rate >= 0.0 && hit_rate <= 100.0);
+        assert!((0.0..=100.0).contains(&hit_rate));
 
         let memory_mb = status.memory_usage_mb();
         assert!(memory_mb >= 0.0);
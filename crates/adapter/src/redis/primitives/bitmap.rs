@@ -207,7 +207,7 @@ impl RedisBitmap {
     /// Sets the TTL of a bitmap in seconds
     pub fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
         let mut conn = self.conn.lock().unwrap();
-        let result: i32 = conn.expire(key, seconds as usize)?;
+        let result: i32 = conn.expire(key, seconds as i64)?;
         Ok(result == 1)
     }
 
@@ -616,7 +616,6 @@ impl RedisBitmap {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_helpers::get_test_redis_url;
     use redis::pipe;
     use std::sync::{Arc, Mutex};
 
@@ -742,7 +741,6 @@ mod tests {
 #[cfg(test)]
 mod examples {
     use super::*;
-    use crate::test_helpers::get_test_redis_url;
 
     #[test]
     #[ignore = "This example is for demonstration only"]
@@ -185,7 +185,7 @@ impl RedisHash {
     /// Sets the TTL of a hash in seconds
     pub fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
         let mut conn = self.conn.lock().unwrap();
-        let result: i32 = conn.expire(key, seconds as usize)?;
+        let result: i32 = conn.expire(key, seconds as i64)?;
         Ok(result == 1)
     }
 
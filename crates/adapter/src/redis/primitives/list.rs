@@ -0,0 +1,722 @@
+use redis::{
+    Commands, Connection, Direction, FromRedisValue, Pipeline, RedisResult, Script, ToRedisArgs,
+};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// Represents a Redis list data type with operations for manipulating list values.
+///
+/// This implementation supports:
+/// - Individual commands (lpush, rpop, lrange, lmove, etc.)
+/// - Pipelined operations (for efficiency)
+/// - Transactions (for atomicity)
+/// - Lua script execution (for complex operations)
+///
+/// Lists are commonly used as queues: producers `lpush` jobs and consumers
+/// `rpop` them, or `lmove` them into a processing list for reliable delivery.
+#[derive(Clone)]
+pub struct RedisList {
+    conn: Arc<Mutex<Connection>>,
+}
+
+/// Core implementation with basic list operations
+impl RedisList {
+    /// Creates a new RedisList instance with the provided connection
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Gets the connection reference for direct usage
+    pub fn connection(&self) -> &Arc<Mutex<Connection>> {
+        &self.conn
+    }
+
+    /// Prepends one or more values to a list, returning the new length
+    pub fn lpush(&self, key: &str, values: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.lpush(key, values)
+    }
+
+    /// Appends one or more values to a list, returning the new length
+    pub fn rpush(&self, key: &str, values: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.rpush(key, values)
+    }
+
+    /// Removes and returns the first element of a list
+    pub fn lpop(&self, key: &str) -> RedisResult<Option<String>> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.lpop(key, None)
+    }
+
+    /// Removes and returns up to `count` elements from the head of a list
+    pub fn lpop_count(&self, key: &str, count: usize) -> RedisResult<Vec<String>> {
+        let Some(count) = NonZeroUsize::new(count) else {
+            return Ok(Vec::new());
+        };
+        let mut conn = self.conn.lock().unwrap();
+        let popped: Option<Vec<String>> = conn.lpop(key, Some(count))?;
+        Ok(popped.unwrap_or_default())
+    }
+
+    /// Removes and returns the last element of a list
+    pub fn rpop(&self, key: &str) -> RedisResult<Option<String>> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.rpop(key, None)
+    }
+
+    /// Removes and returns up to `count` elements from the tail of a list
+    pub fn rpop_count(&self, key: &str, count: usize) -> RedisResult<Vec<String>> {
+        let Some(count) = NonZeroUsize::new(count) else {
+            return Ok(Vec::new());
+        };
+        let mut conn = self.conn.lock().unwrap();
+        let popped: Option<Vec<String>> = conn.rpop(key, Some(count))?;
+        Ok(popped.unwrap_or_default())
+    }
+
+    /// Returns a range of elements from a list by index (inclusive, negative indexes allowed)
+    pub fn lrange(&self, key: &str, start: isize, stop: isize) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.lrange(key, start, stop)
+    }
+
+    /// Returns the length of a list
+    pub fn llen(&self, key: &str) -> RedisResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.llen(key)
+    }
+
+    /// Returns the element at the given index of a list
+    pub fn lindex(&self, key: &str, index: isize) -> RedisResult<Option<String>> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.lindex(key, index)
+    }
+
+    /// Sets the element at the given index of a list
+    pub fn lset(&self, key: &str, index: isize, value: &str) -> RedisResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.lset(key, index, value)
+    }
+
+    /// Trims a list so that it only contains the specified range of elements
+    pub fn ltrim(&self, key: &str, start: isize, stop: isize) -> RedisResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.ltrim(key, start, stop)
+    }
+
+    /// Removes occurrences of a value from a list
+    ///
+    /// A positive `count` removes from head to tail, a negative `count` removes
+    /// from tail to head and zero removes all occurrences.
+    pub fn lrem(&self, key: &str, count: isize, value: &str) -> RedisResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.lrem(key, count, value)
+    }
+
+    /// Inserts a value before the pivot element, returning the new length
+    /// (or -1 if the pivot was not found)
+    pub fn linsert_before(&self, key: &str, pivot: &str, value: &str) -> RedisResult<isize> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.linsert_before(key, pivot, value)
+    }
+
+    /// Inserts a value after the pivot element, returning the new length
+    /// (or -1 if the pivot was not found)
+    pub fn linsert_after(&self, key: &str, pivot: &str, value: &str) -> RedisResult<isize> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.linsert_after(key, pivot, value)
+    }
+
+    /// Atomically moves an element from one list to another
+    pub fn lmove(
+        &self,
+        source: &str,
+        destination: &str,
+        source_dir: Direction,
+        destination_dir: Direction,
+    ) -> RedisResult<Option<String>> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.lmove(source, destination, source_dir, destination_dir)
+    }
+
+    /// Deletes a list
+    pub fn del(&self, key: &str) -> RedisResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.del(key)
+    }
+
+    /// Checks if a list exists
+    pub fn exists(&self, key: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let result: i32 = conn.exists(key)?;
+        Ok(result == 1)
+    }
+
+    /// Gets the TTL of a list in seconds
+    pub fn ttl(&self, key: &str) -> RedisResult<i64> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.ttl(key)
+    }
+
+    /// Sets the TTL of a list in seconds
+    pub fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let result: i32 = conn.expire(key, seconds as i64)?;
+        Ok(result == 1)
+    }
+
+    /// Gets keys matching a pattern
+    pub fn keys(&self, pattern: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.keys(pattern)
+    }
+}
+
+/// Pipeline operations
+impl RedisList {
+    /// Executes a function with a pipeline
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use redis::{Connection, RedisResult};
+    /// # use std::sync::{Arc, Mutex};
+    /// # use dbx_adapter::redis::primitives::list::RedisList;
+    /// # fn example(conn: Connection) -> RedisResult<()> {
+    /// let redis_list = RedisList::new(Arc::new(Mutex::new(conn)));
+    /// let results: (usize, Vec<String>) = redis_list.with_pipeline(|pipe| {
+    ///     pipe.cmd("RPUSH").arg("list1").arg("job1").arg("job2")
+    ///        .cmd("LRANGE").arg("list1").arg(0).arg(-1)
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_pipeline<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.lock().unwrap();
+        let mut pipe = redis::pipe();
+        let result = f(&mut pipe).query(&mut *conn)?;
+        Ok(result)
+    }
+
+    /// Helper: batch prepend values to multiple lists using pipeline
+    pub fn lpush_many(&self, list_values: Vec<(&str, Vec<&str>)>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for (list_key, values) in list_values {
+                let mut cmd = pipe.cmd("LPUSH").arg(list_key);
+                for value in values {
+                    cmd = cmd.arg(value);
+                }
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch append values to multiple lists using pipeline
+    pub fn rpush_many(&self, list_values: Vec<(&str, Vec<&str>)>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for (list_key, values) in list_values {
+                let mut cmd = pipe.cmd("RPUSH").arg(list_key);
+                for value in values {
+                    cmd = cmd.arg(value);
+                }
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch pop the head element of multiple lists using pipeline
+    pub fn lpop_many(&self, keys: Vec<&str>) -> RedisResult<Vec<Option<String>>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("LPOP").arg(key);
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch pop the tail element of multiple lists using pipeline
+    pub fn rpop_many(&self, keys: Vec<&str>) -> RedisResult<Vec<Option<String>>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("RPOP").arg(key);
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch get ranges from multiple lists using pipeline
+    pub fn lrange_many(
+        &self,
+        list_ranges: Vec<(&str, isize, isize)>,
+    ) -> RedisResult<Vec<Vec<String>>> {
+        self.with_pipeline(|pipe| {
+            for (key, start, stop) in list_ranges {
+                pipe.cmd("LRANGE").arg(key).arg(start).arg(stop);
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch get list lengths using pipeline
+    pub fn llen_many(&self, keys: Vec<&str>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("LLEN").arg(key);
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch trim multiple lists using pipeline
+    pub fn ltrim_many(&self, list_ranges: Vec<(&str, isize, isize)>) -> RedisResult<()> {
+        self.with_pipeline(|pipe| {
+            for (key, start, stop) in list_ranges {
+                pipe.cmd("LTRIM").arg(key).arg(start).arg(stop).ignore();
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch delete multiple lists using pipeline
+    pub fn del_many(&self, keys: Vec<&str>) -> RedisResult<()> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("DEL").arg(key);
+            }
+            pipe
+        })
+    }
+}
+
+/// Transaction operations (MULTI/EXEC)
+///
+/// Transactions in Redis are atomic command blocks executed with MULTI/EXEC.
+/// Unlike pipelines, transactions guarantee atomicity - either all commands
+/// execute or none do.
+impl RedisList {
+    /// Executes a transaction using MULTI/EXEC
+    ///
+    /// This ensures all commands are executed atomically.
+    /// If any command fails, the entire transaction is aborted.
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use redis::{Connection, RedisResult};
+    /// # use std::sync::{Arc, Mutex};
+    /// # use dbx_adapter::redis::primitives::list::RedisList;
+    /// # fn example(conn: Connection) -> RedisResult<()> {
+    /// let redis_list = RedisList::new(Arc::new(Mutex::new(conn)));
+    /// let _: () = redis_list.transaction(|pipe| {
+    ///     pipe.cmd("LPUSH").arg("queue").arg("job1")
+    ///        .cmd("LTRIM").arg("queue").arg(0).arg(999)
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transaction<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.lock().unwrap();
+        let mut pipe = redis::pipe();
+        // Add MULTI command at the beginning
+        pipe.cmd("MULTI");
+        // Apply the user's commands
+        f(&mut pipe);
+        // Add EXEC command at the end
+        pipe.cmd("EXEC");
+        // Execute the transaction
+        let result = pipe.query(&mut *conn)?;
+        Ok(result)
+    }
+}
+
+/// Lua script operations
+///
+/// Lua scripts in Redis provide a way to execute complex operations atomically.
+/// Scripts are executed atomically and can access keys, allowing for custom
+/// atomic operations that aren't possible with standard Redis commands.
+impl RedisList {
+    /// Creates a new Lua script
+    ///
+    /// # Example
+    /// ```ignore
+    /// use redis::Script;
+    /// use dbx_adapter::redis::primitives::list::RedisList;
+    ///
+    /// let script = RedisList::create_script(r#"
+    ///     redis.call('RPUSH', KEYS[1], ARGV[1])
+    ///     return redis.call('LLEN', KEYS[1])
+    /// "#);
+    /// ```
+    pub fn create_script(script_source: &str) -> Script {
+        Script::new(script_source)
+    }
+
+    /// Executes a Lua script with the given keys and arguments
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use redis::{Connection, RedisResult, Script};
+    /// # use std::sync::{Arc, Mutex};
+    /// # use dbx_adapter::redis::primitives::list::RedisList;
+    /// # fn example(conn: Connection) -> RedisResult<()> {
+    /// let redis_list = RedisList::new(Arc::new(Mutex::new(conn)));
+    /// let script = RedisList::create_script("return redis.call('LLEN', KEYS[1])");
+    ///
+    /// // Execute the script with "mylist" as the key and no arguments
+    /// let result: usize = redis_list.eval_script::<usize, _, _>(&script, &["mylist"], &[""])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn eval_script<T, K, A>(&self, script: &Script, keys: K, args: A) -> RedisResult<T>
+    where
+        T: FromRedisValue,
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+    {
+        let mut conn = self.conn.lock().unwrap();
+        script.key(keys).arg(args).invoke(&mut *conn)
+    }
+
+    /// Loads a Lua script into the server's script cache, returning its SHA1
+    ///
+    /// Scripts added to a pipeline run by hash, so load them first. If the
+    /// pipeline fails with a NOSCRIPT error (the cache was flushed), load the
+    /// script again and retry.
+    pub fn load_script(&self, script: &Script) -> RedisResult<String> {
+        let mut conn = self.conn.lock().unwrap();
+        script.prepare_invoke().load(&mut *conn)
+    }
+
+    /// Add a Lua script to a pipeline as an EVALSHA of its hash
+    ///
+    /// The script must already be loaded, see [`RedisList::load_script`].
+    pub fn add_script_to_pipeline<'a, K, A>(
+        pipe: &'a mut Pipeline,
+        script: &Script,
+        keys: K,
+        args: A,
+    ) -> &'a mut Pipeline
+    where
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+    {
+        let keys = keys.to_redis_args();
+        let mut evalsha_cmd = redis::cmd("EVALSHA");
+        evalsha_cmd
+            .arg(script.get_hash())
+            .arg(keys.len())
+            .arg(keys)
+            .arg(args);
+        pipe.add_command(evalsha_cmd)
+    }
+}
+
+/// Utility functions for common list operations with Lua scripts
+///
+/// These predefined scripts provide common atomic operations that can be reused
+/// across your application.
+impl RedisList {
+    /// Gets a script that pushes a value and caps the list at a maximum length
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use redis::{Connection, RedisResult};
+    /// # use std::sync::{Arc, Mutex};
+    /// # use dbx_adapter::redis::primitives::list::RedisList;
+    /// # fn example(conn: Connection) -> RedisResult<()> {
+    /// let redis_list = RedisList::new(Arc::new(Mutex::new(conn)));
+    /// let script = RedisList::capped_push_script();
+    ///
+    /// // Push an entry and keep only the 100 most recent ones
+    /// let length: usize = redis_list.eval_script(
+    ///     &script,
+    ///     &["recent_events"], // KEYS[1]
+    ///     &["event", "100"]   // ARGV[1], ARGV[2]
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn capped_push_script() -> Script {
+        Script::new(
+            r#"
+            redis.call('LPUSH', KEYS[1], ARGV[1])
+            redis.call('LTRIM', KEYS[1], 0, tonumber(ARGV[2]) - 1)
+            return redis.call('LLEN', KEYS[1])
+            "#,
+        )
+    }
+
+    /// Gets a script that appends a value only if it is not already in the list
+    pub fn push_if_not_exists_script() -> Script {
+        Script::new(
+            r#"
+            local items = redis.call('LRANGE', KEYS[1], 0, -1)
+            for i=1, #items do
+                if items[i] == ARGV[1] then
+                    return 0
+                end
+            end
+            redis.call('RPUSH', KEYS[1], ARGV[1])
+            return 1
+            "#,
+        )
+    }
+
+    /// Gets a script that pops up to N elements from the head of a list atomically
+    pub fn pop_many_script() -> Script {
+        Script::new(
+            r#"
+            local count = tonumber(ARGV[1])
+            local items = redis.call('LRANGE', KEYS[1], 0, count - 1)
+            if #items > 0 then
+                redis.call('LTRIM', KEYS[1], #items, -1)
+            end
+            return items
+            "#,
+        )
+    }
+
+    /// Gets a script that implements a reliable queue pop
+    ///
+    /// The job is moved from the pending list to a processing list and its
+    /// claim time is recorded in a hash, so crashed consumers can be detected.
+    pub fn reliable_pop_script() -> Script {
+        Script::new(
+            r#"
+            local job = redis.call('RPOP', KEYS[1])
+            if job then
+                redis.call('LPUSH', KEYS[2], job)
+                redis.call('HSET', KEYS[3], job, ARGV[1])
+            end
+            return job
+            "#,
+        )
+    }
+
+    /// Gets a script that acknowledges a job taken with the reliable pop script
+    pub fn reliable_ack_script() -> Script {
+        Script::new(
+            r#"
+            local removed = redis.call('LREM', KEYS[1], 1, ARGV[1])
+            redis.call('HDEL', KEYS[2], ARGV[1])
+            return removed
+            "#,
+        )
+    }
+
+    /// Gets a script that requeues jobs claimed before a deadline
+    pub fn requeue_stale_script() -> Script {
+        Script::new(
+            r#"
+            local deadline = tonumber(ARGV[1])
+            local claims = redis.call('HGETALL', KEYS[3])
+            local requeued = 0
+            for i=1, #claims, 2 do
+                local job = claims[i]
+                if tonumber(claims[i + 1]) < deadline then
+                    redis.call('LREM', KEYS[2], 1, job)
+                    redis.call('RPUSH', KEYS[1], job)
+                    redis.call('HDEL', KEYS[3], job)
+                    requeued = requeued + 1
+                end
+            end
+            return requeued
+            "#,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A list served by the in-memory backend, running until dropped
+    #[cfg(feature = "async")]
+    struct MemoryList {
+        list: RedisList,
+        _server: crate::memory::MemoryServer,
+        _runtime: tokio::runtime::Runtime,
+    }
+
+    #[cfg(feature = "async")]
+    fn memory_list() -> MemoryList {
+        use crate::memory::{MemoryServer, MemoryStore};
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = runtime
+            .block_on(MemoryServer::start(MemoryStore::new()))
+            .unwrap();
+        let conn = redis::Client::open(server.url())
+            .unwrap()
+            .get_connection()
+            .unwrap();
+        MemoryList {
+            list: RedisList::new(Arc::new(Mutex::new(conn))),
+            _server: server,
+            _runtime: runtime,
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_list_operations() {
+        let MemoryList { list, .. } = &memory_list();
+
+        assert_eq!(list.rpush("jobs", &["b", "c"]).unwrap(), 2);
+        assert_eq!(list.lpush("jobs", &["a"]).unwrap(), 3);
+        assert_eq!(list.lrange("jobs", 0, -1).unwrap(), ["a", "b", "c"]);
+        assert_eq!(list.lindex("jobs", -1).unwrap().as_deref(), Some("c"));
+        assert_eq!(list.linsert_before("jobs", "c", "b").unwrap(), 4);
+        assert_eq!(list.linsert_after("jobs", "missing", "x").unwrap(), -1);
+        assert_eq!(list.lrem("jobs", 0, "b").unwrap(), 2);
+        list.lset("jobs", 0, "z").unwrap();
+        assert!(list.lset("jobs", 9, "z").is_err());
+        assert_eq!(list.lrange("jobs", 0, -1).unwrap(), ["z", "c"]);
+
+        assert_eq!(
+            list.lmove("jobs", "done", Direction::Right, Direction::Left)
+                .unwrap()
+                .as_deref(),
+            Some("c")
+        );
+        assert_eq!(list.lpop("jobs").unwrap().as_deref(), Some("z"));
+        assert_eq!(list.rpop("jobs").unwrap(), None);
+        assert!(!list.exists("jobs").unwrap());
+        assert_eq!(list.llen("done").unwrap(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_pop_count_and_trim() {
+        let MemoryList { list, .. } = &memory_list();
+
+        list.rpush("events", &["1", "2", "3", "4", "5"]).unwrap();
+        assert_eq!(list.lpop_count("events", 2).unwrap(), ["1", "2"]);
+        assert_eq!(list.rpop_count("events", 1).unwrap(), ["5"]);
+        assert!(list.lpop_count("events", 0).unwrap().is_empty());
+        assert!(list.rpop_count("missing", 3).unwrap().is_empty());
+
+        list.ltrim("events", 0, 0).unwrap();
+        assert_eq!(list.lrange("events", 0, -1).unwrap(), ["3"]);
+        assert!(list.expire("events", 60).unwrap());
+        assert!(list.ttl("events").unwrap() > 0);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn test_batch_operations() {
+        let MemoryList { list, .. } = &memory_list();
+
+        let lengths = list
+            .rpush_many(vec![("list1", vec!["a", "b", "c"]), ("list2", vec!["d"])])
+            .unwrap();
+        assert_eq!(lengths, [3, 1]);
+        assert_eq!(list.lpush_many(vec![("list2", vec!["e"])]).unwrap(), [2]);
+        assert_eq!(
+            list.lpop_many(vec!["list1", "missing"]).unwrap(),
+            [Some("a".to_string()), None]
+        );
+        assert_eq!(
+            list.rpop_many(vec!["list2"]).unwrap(),
+            [Some("d".to_string())]
+        );
+        list.ltrim_many(vec![("list1", 0, 0)]).unwrap();
+        assert_eq!(
+            list.lrange_many(vec![("list1", 0, -1), ("list2", 0, -1)])
+                .unwrap(),
+            [vec!["b".to_string()], vec!["e".to_string()]]
+        );
+        assert_eq!(list.llen_many(vec!["list1", "list2"]).unwrap(), [1, 1]);
+
+        list.del_many(vec!["list1", "list2"]).unwrap();
+        assert!(list.keys("list*").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_scripts_are_pipelined_by_hash() {
+        let script = RedisList::capped_push_script();
+        let mut pipe = redis::pipe();
+        RedisList::add_script_to_pipeline(&mut pipe, &script, &["recent"], &["item", "10"]);
+
+        let expected = redis::cmd("EVALSHA")
+            .arg(script.get_hash())
+            .arg(1)
+            .arg("recent")
+            .arg("item")
+            .arg("10")
+            .get_packed_command();
+        assert_eq!(pipe.get_packed_pipeline(), expected);
+    }
+}
+
+/// Examples of how to use RedisList with various features
+///
+/// These examples demonstrate how to use RedisList's features
+/// in real-world scenarios.
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    #[test]
+    #[ignore = "This example is for demonstration only"]
+    fn example_patterns() {
+        // Create a connection for examples
+        let redis_url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let client = redis::Client::open(redis_url).unwrap_or_else(|_| {
+            redis::Client::open("redis://localhost:6379").expect("Creating example client")
+        });
+
+        // This won't actually be used in ignored tests
+        let conn = Arc::new(Mutex::new(client.get_connection().unwrap_or_else(|_| {
+            panic!("This example is only for demonstration and is marked as ignored")
+        })));
+
+        let redis_list = RedisList::new(conn);
+
+        // Example 1: Simple job queue (producer pushes left, consumer pops right)
+        let _ = redis_list.lpush("jobs:pending", &["job:1", "job:2"]);
+        let _ = redis_list.rpop("jobs:pending");
+
+        // Example 2: Reliable queue using LMOVE into a processing list
+        let _ = redis_list.lmove(
+            "jobs:pending",
+            "jobs:processing",
+            Direction::Right,
+            Direction::Left,
+        );
+        let _ = redis_list.lrem("jobs:processing", 1, "job:1");
+
+        // Example 3: Transaction pushing and capping a list
+        let _: Result<(usize, ()), redis::RedisError> = redis_list.transaction(|pipe| {
+            pipe.cmd("LPUSH")
+                .arg("events:recent")
+                .arg("login")
+                .cmd("LTRIM")
+                .arg("events:recent")
+                .arg(0)
+                .arg(99)
+        });
+
+        // Example 4: Reliable pop script with claim tracking
+        let _: Result<Option<String>, redis::RedisError> = redis_list.eval_script(
+            &RedisList::reliable_pop_script(),
+            &["jobs:pending", "jobs:processing", "jobs:claims"],
+            &["1700000000"],
+        );
+
+        // Example 5: Batch operations
+        let _ = redis_list.rpush_many(vec![
+            ("batch:list1", vec!["a", "b"]),
+            ("batch:list2", vec!["c", "d"]),
+        ]);
+    }
+}
@@ -7,6 +7,7 @@
 //! - Set: Unordered collections of unique strings
 //! - Sorted Set: Ordered collections of strings with associated scores
 //! - Bitmap: Bit-level operations on string values
+//! - Stream: Append-only logs with consumer groups
 //!
 //! Each implementation supports individual commands, pipelined operations,
 //! transactions, Lua scripts, and administrative commands.
@@ -14,9 +15,8 @@
 pub mod admin;
 pub mod bitmap;
 pub mod hash;
+pub mod list;
 pub mod set;
 pub mod sorted_set;
+pub mod stream;
 pub mod string;
-
-// These will be implemented in future versions:
-// pub mod list;
@@ -174,7 +174,7 @@ impl RedisSet {
     /// Sets the TTL of a set in seconds
     pub fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
         let mut conn = self.conn.lock().unwrap();
-        let result: i32 = conn.expire(key, seconds as usize)?;
+        let result: i32 = conn.expire(key, seconds as i64)?;
         Ok(result == 1)
     }
 
@@ -681,7 +681,6 @@ mod tests {
 #[cfg(test)]
 mod examples {
     use super::*;
-    use crate::test_helpers::get_test_redis_url;
 
     #[test]
     #[ignore = "This example is for demonstration only"]
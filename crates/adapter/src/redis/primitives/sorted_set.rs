@@ -300,7 +300,7 @@ impl RedisSortedSet {
     /// Sets the TTL of a sorted set in seconds
     pub fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
         let mut conn = self.conn.lock().unwrap();
-        let result: i32 = conn.expire(key, seconds as usize)?;
+        let result: i32 = conn.expire(key, seconds as i64)?;
         Ok(result == 1)
     }
 
@@ -721,7 +721,6 @@ impl RedisSortedSet {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_helpers::get_test_redis_url;
     use redis::pipe;
     use std::sync::{Arc, Mutex};
 
@@ -886,7 +885,6 @@ mod tests {
 #[cfg(test)]
 mod examples {
     use super::*;
-    use crate::test_helpers::get_test_redis_url;
 
     #[test]
     #[ignore = "This example is for demonstration only"]
@@ -0,0 +1,1149 @@
+use redis::{
+    Commands, Connection, ErrorKind, FromRedisValue, Pipeline, RedisError, RedisResult, Script,
+    ToRedisArgs, Value,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// A single entry of a stream: its ID and field-value pairs
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StreamEntry {
+    pub id: String,
+    pub fields: HashMap<String, String>,
+}
+
+/// Entries read from one stream key by XREADGROUP
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StreamReadResult {
+    pub key: String,
+    pub entries: Vec<StreamEntry>,
+}
+
+/// Summary form of XPENDING
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StreamPendingSummary {
+    pub count: usize,
+    pub min_id: Option<String>,
+    pub max_id: Option<String>,
+    /// (consumer name, pending count) pairs
+    pub consumers: Vec<(String, usize)>,
+}
+
+/// Extended form of XPENDING: one pending message
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StreamPendingEntry {
+    pub id: String,
+    pub consumer: String,
+    pub idle_ms: u64,
+    pub delivery_count: u64,
+}
+
+/// Reply of XAUTOCLAIM
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StreamAutoClaimResult {
+    /// Cursor to pass as `start` on the next call, "0-0" once the scan is complete
+    pub next_id: String,
+    pub entries: Vec<StreamEntry>,
+    /// IDs that were pending but no longer exist in the stream (Redis 7+)
+    pub deleted_ids: Vec<String>,
+}
+
+/// Reply of XINFO STREAM
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StreamInfo {
+    pub length: usize,
+    pub radix_tree_keys: usize,
+    pub radix_tree_nodes: usize,
+    pub groups: usize,
+    pub last_generated_id: String,
+    pub first_entry: Option<StreamEntry>,
+    pub last_entry: Option<StreamEntry>,
+}
+
+/// One element of the XINFO GROUPS reply
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StreamGroupInfo {
+    pub name: String,
+    pub consumers: usize,
+    pub pending: usize,
+    pub last_delivered_id: String,
+}
+
+/// One element of the XINFO CONSUMERS reply
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StreamConsumerInfo {
+    pub name: String,
+    pub pending: usize,
+    pub idle_ms: u64,
+}
+
+/// Trimming strategy for XADD and XTRIM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StreamTrim {
+    /// Keep at most `threshold` entries
+    MaxLen { threshold: usize, approximate: bool },
+    /// Evict entries with IDs lower than `threshold`
+    MinId {
+        threshold: String,
+        approximate: bool,
+    },
+}
+
+impl StreamTrim {
+    fn write_args(&self, cmd: &mut redis::Cmd) {
+        let (strategy, approximate) = match self {
+            StreamTrim::MaxLen { approximate, .. } => ("MAXLEN", *approximate),
+            StreamTrim::MinId { approximate, .. } => ("MINID", *approximate),
+        };
+        cmd.arg(strategy).arg(if approximate { "~" } else { "=" });
+        match self {
+            StreamTrim::MaxLen { threshold, .. } => cmd.arg(*threshold),
+            StreamTrim::MinId { threshold, .. } => cmd.arg(threshold.as_str()),
+        };
+    }
+}
+
+/// Options for XREADGROUP
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamReadGroupOptions {
+    pub count: Option<usize>,
+    pub block_ms: Option<usize>,
+    pub noack: bool,
+}
+
+fn invalid_response(what: &str, value: &Value) -> RedisError {
+    RedisError::from((
+        ErrorKind::TypeError,
+        "Invalid stream response",
+        format!("{what}: {value:?}"),
+    ))
+}
+
+/// Turns a flat [key, value, key, value, ...] reply into a map
+fn parse_kv_pairs(value: &Value) -> RedisResult<HashMap<String, Value>> {
+    match value {
+        Value::Bulk(items) if items.len() % 2 == 0 => items
+            .chunks(2)
+            .map(|pair| Ok((String::from_redis_value(&pair[0])?, pair[1].clone())))
+            .collect(),
+        _ => Err(invalid_response("expected key-value pairs", value)),
+    }
+}
+
+fn required_field<T: FromRedisValue>(map: &HashMap<String, Value>, name: &str) -> RedisResult<T> {
+    match map.get(name) {
+        Some(value) => T::from_redis_value(value),
+        None => Err(RedisError::from((
+            ErrorKind::TypeError,
+            "Missing field in stream response",
+            name.to_string(),
+        ))),
+    }
+}
+
+impl FromRedisValue for StreamEntry {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        match v {
+            Value::Bulk(items) if items.len() == 2 => {
+                let id = String::from_redis_value(&items[0])?;
+                // XCLAIM on Redis < 7 returns nil fields for deleted entries
+                let fields = match &items[1] {
+                    Value::Nil => HashMap::new(),
+                    fields => parse_kv_pairs(fields)?
+                        .iter()
+                        .map(|(k, v)| Ok((k.clone(), String::from_redis_value(v)?)))
+                        .collect::<RedisResult<_>>()?,
+                };
+                Ok(StreamEntry { id, fields })
+            }
+            _ => Err(invalid_response("expected stream entry", v)),
+        }
+    }
+}
+
+impl FromRedisValue for StreamReadResult {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        match v {
+            Value::Bulk(items) if items.len() == 2 => Ok(StreamReadResult {
+                key: String::from_redis_value(&items[0])?,
+                entries: Vec::from_redis_value(&items[1])?,
+            }),
+            _ => Err(invalid_response("expected stream read result", v)),
+        }
+    }
+}
+
+impl FromRedisValue for StreamPendingSummary {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        match v {
+            Value::Bulk(items) if items.len() == 4 => Ok(StreamPendingSummary {
+                count: usize::from_redis_value(&items[0])?,
+                min_id: Option::from_redis_value(&items[1])?,
+                max_id: Option::from_redis_value(&items[2])?,
+                consumers: Option::<Vec<(String, usize)>>::from_redis_value(&items[3])?
+                    .unwrap_or_default(),
+            }),
+            _ => Err(invalid_response("expected pending summary", v)),
+        }
+    }
+}
+
+impl FromRedisValue for StreamPendingEntry {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let (id, consumer, idle_ms, delivery_count) = FromRedisValue::from_redis_value(v)?;
+        Ok(StreamPendingEntry {
+            id,
+            consumer,
+            idle_ms,
+            delivery_count,
+        })
+    }
+}
+
+impl FromRedisValue for StreamAutoClaimResult {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        match v {
+            Value::Bulk(items) if items.len() == 2 || items.len() == 3 => {
+                Ok(StreamAutoClaimResult {
+                    next_id: String::from_redis_value(&items[0])?,
+                    entries: Vec::from_redis_value(&items[1])?,
+                    deleted_ids: match items.get(2) {
+                        Some(ids) => Vec::from_redis_value(ids)?,
+                        None => Vec::new(),
+                    },
+                })
+            }
+            _ => Err(invalid_response("expected autoclaim result", v)),
+        }
+    }
+}
+
+impl FromRedisValue for StreamInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let map = parse_kv_pairs(v)?;
+        Ok(StreamInfo {
+            length: required_field(&map, "length")?,
+            radix_tree_keys: required_field(&map, "radix-tree-keys")?,
+            radix_tree_nodes: required_field(&map, "radix-tree-nodes")?,
+            groups: required_field(&map, "groups")?,
+            last_generated_id: required_field(&map, "last-generated-id")?,
+            first_entry: required_field(&map, "first-entry")?,
+            last_entry: required_field(&map, "last-entry")?,
+        })
+    }
+}
+
+impl FromRedisValue for StreamGroupInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let map = parse_kv_pairs(v)?;
+        Ok(StreamGroupInfo {
+            name: required_field(&map, "name")?,
+            consumers: required_field(&map, "consumers")?,
+            pending: required_field(&map, "pending")?,
+            last_delivered_id: required_field(&map, "last-delivered-id")?,
+        })
+    }
+}
+
+impl FromRedisValue for StreamConsumerInfo {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let map = parse_kv_pairs(v)?;
+        Ok(StreamConsumerInfo {
+            name: required_field(&map, "name")?,
+            pending: required_field(&map, "pending")?,
+            idle_ms: required_field(&map, "idle")?,
+        })
+    }
+}
+
+/// Represents a Redis stream data type with operations for appending to and consuming streams.
+///
+/// This implementation supports:
+/// - Individual commands (xadd, xrange, xreadgroup, xack, etc.)
+/// - Pipelined operations (for efficiency)
+/// - Transactions (for atomicity)
+/// - Lua script execution (for complex operations)
+///
+/// Replies are decoded into typed structs such as [`StreamEntry`] and
+/// [`StreamPendingEntry`] instead of raw `redis::Value`s.
+#[derive(Clone)]
+pub struct RedisStream {
+    conn: Arc<Mutex<Connection>>,
+}
+
+/// Core implementation with basic stream operations
+impl RedisStream {
+    /// Creates a new RedisStream instance with the provided connection
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Gets the connection reference for direct usage
+    pub fn connection(&self) -> &Arc<Mutex<Connection>> {
+        &self.conn
+    }
+
+    /// Appends an entry to a stream, returning its ID (use "*" to auto-generate)
+    pub fn xadd(&self, key: &str, id: &str, fields: &[(&str, &str)]) -> RedisResult<String> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XADD")
+            .arg(key)
+            .arg(id)
+            .arg(fields)
+            .query(&mut *conn)
+    }
+
+    /// Appends an entry to a stream and trims it in the same command
+    pub fn xadd_trim(
+        &self,
+        key: &str,
+        id: &str,
+        fields: &[(&str, &str)],
+        trim: &StreamTrim,
+    ) -> RedisResult<String> {
+        let mut conn = self.conn.lock().unwrap();
+        let mut cmd = redis::cmd("XADD");
+        cmd.arg(key);
+        trim.write_args(&mut cmd);
+        cmd.arg(id).arg(fields).query(&mut *conn)
+    }
+
+    /// Returns the entries with IDs between start and end ("-" and "+" for the extremes)
+    pub fn xrange(&self, key: &str, start: &str, end: &str) -> RedisResult<Vec<StreamEntry>> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XRANGE")
+            .arg(key)
+            .arg(start)
+            .arg(end)
+            .query(&mut *conn)
+    }
+
+    /// Returns at most `count` entries with IDs between start and end
+    pub fn xrange_count(
+        &self,
+        key: &str,
+        start: &str,
+        end: &str,
+        count: usize,
+    ) -> RedisResult<Vec<StreamEntry>> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XRANGE")
+            .arg(key)
+            .arg(start)
+            .arg(end)
+            .arg("COUNT")
+            .arg(count)
+            .query(&mut *conn)
+    }
+
+    /// Returns the entries with IDs between end and start, newest first
+    pub fn xrevrange(&self, key: &str, end: &str, start: &str) -> RedisResult<Vec<StreamEntry>> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XREVRANGE")
+            .arg(key)
+            .arg(end)
+            .arg(start)
+            .query(&mut *conn)
+    }
+
+    /// Returns at most `count` entries with IDs between end and start, newest first
+    pub fn xrevrange_count(
+        &self,
+        key: &str,
+        end: &str,
+        start: &str,
+        count: usize,
+    ) -> RedisResult<Vec<StreamEntry>> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XREVRANGE")
+            .arg(key)
+            .arg(end)
+            .arg(start)
+            .arg("COUNT")
+            .arg(count)
+            .query(&mut *conn)
+    }
+
+    /// Returns the number of entries in a stream
+    pub fn xlen(&self, key: &str) -> RedisResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XLEN").arg(key).query(&mut *conn)
+    }
+
+    /// Removes entries from a stream, returning the number deleted
+    pub fn xdel(&self, key: &str, ids: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XDEL").arg(key).arg(ids).query(&mut *conn)
+    }
+
+    /// Trims a stream, returning the number of evicted entries
+    pub fn xtrim(&self, key: &str, trim: &StreamTrim) -> RedisResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let mut cmd = redis::cmd("XTRIM");
+        cmd.arg(key);
+        trim.write_args(&mut cmd);
+        cmd.query(&mut *conn)
+    }
+
+    /// Creates a consumer group starting at `id` ("$" for new entries only, "0" for all)
+    ///
+    /// With `mkstream` the stream is created if it does not exist yet.
+    pub fn xgroup_create(
+        &self,
+        key: &str,
+        group: &str,
+        id: &str,
+        mkstream: bool,
+    ) -> RedisResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let mut cmd = redis::cmd("XGROUP");
+        cmd.arg("CREATE").arg(key).arg(group).arg(id);
+        if mkstream {
+            cmd.arg("MKSTREAM");
+        }
+        cmd.query(&mut *conn)
+    }
+
+    /// Destroys a consumer group, returning whether it existed
+    pub fn xgroup_destroy(&self, key: &str, group: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let result: i32 = redis::cmd("XGROUP")
+            .arg("DESTROY")
+            .arg(key)
+            .arg(group)
+            .query(&mut *conn)?;
+        Ok(result == 1)
+    }
+
+    /// Sets the last delivered ID of a consumer group
+    pub fn xgroup_setid(&self, key: &str, group: &str, id: &str) -> RedisResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XGROUP")
+            .arg("SETID")
+            .arg(key)
+            .arg(group)
+            .arg(id)
+            .query(&mut *conn)
+    }
+
+    /// Reads entries on behalf of a consumer in a group
+    ///
+    /// `keys` and `ids` are paired up; use ">" to receive never-delivered entries
+    /// or an explicit ID to re-read the consumer's pending history. Returns an
+    /// empty list when a blocking read times out.
+    pub fn xreadgroup(
+        &self,
+        group: &str,
+        consumer: &str,
+        keys: &[&str],
+        ids: &[&str],
+        options: &StreamReadGroupOptions,
+    ) -> RedisResult<Vec<StreamReadResult>> {
+        let mut conn = self.conn.lock().unwrap();
+        let mut cmd = redis::cmd("XREADGROUP");
+        cmd.arg("GROUP").arg(group).arg(consumer);
+        if let Some(count) = options.count {
+            cmd.arg("COUNT").arg(count);
+        }
+        if let Some(block_ms) = options.block_ms {
+            cmd.arg("BLOCK").arg(block_ms);
+        }
+        if options.noack {
+            cmd.arg("NOACK");
+        }
+        cmd.arg("STREAMS").arg(keys).arg(ids);
+        let result: Option<Vec<StreamReadResult>> = cmd.query(&mut *conn)?;
+        Ok(result.unwrap_or_default())
+    }
+
+    /// Acknowledges entries for a consumer group, returning the number acknowledged
+    pub fn xack(&self, key: &str, group: &str, ids: &[&str]) -> RedisResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XACK")
+            .arg(key)
+            .arg(group)
+            .arg(ids)
+            .query(&mut *conn)
+    }
+
+    /// Returns the summary of pending entries for a consumer group
+    pub fn xpending(&self, key: &str, group: &str) -> RedisResult<StreamPendingSummary> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XPENDING").arg(key).arg(group).query(&mut *conn)
+    }
+
+    /// Returns pending entries between start and end, optionally for a single consumer
+    pub fn xpending_range(
+        &self,
+        key: &str,
+        group: &str,
+        start: &str,
+        end: &str,
+        count: usize,
+        consumer: Option<&str>,
+    ) -> RedisResult<Vec<StreamPendingEntry>> {
+        let mut conn = self.conn.lock().unwrap();
+        let mut cmd = redis::cmd("XPENDING");
+        cmd.arg(key).arg(group).arg(start).arg(end).arg(count);
+        if let Some(consumer) = consumer {
+            cmd.arg(consumer);
+        }
+        cmd.query(&mut *conn)
+    }
+
+    /// Transfers ownership of pending entries idle for at least `min_idle_ms`
+    pub fn xclaim(
+        &self,
+        key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle_ms: u64,
+        ids: &[&str],
+    ) -> RedisResult<Vec<StreamEntry>> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XCLAIM")
+            .arg(key)
+            .arg(group)
+            .arg(consumer)
+            .arg(min_idle_ms)
+            .arg(ids)
+            .query(&mut *conn)
+    }
+
+    /// Scans the pending list from `start` and claims entries idle for at least `min_idle_ms`
+    pub fn xautoclaim(
+        &self,
+        key: &str,
+        group: &str,
+        consumer: &str,
+        min_idle_ms: u64,
+        start: &str,
+        count: Option<usize>,
+    ) -> RedisResult<StreamAutoClaimResult> {
+        let mut conn = self.conn.lock().unwrap();
+        let mut cmd = redis::cmd("XAUTOCLAIM");
+        cmd.arg(key)
+            .arg(group)
+            .arg(consumer)
+            .arg(min_idle_ms)
+            .arg(start);
+        if let Some(count) = count {
+            cmd.arg("COUNT").arg(count);
+        }
+        cmd.query(&mut *conn)
+    }
+
+    /// Returns general information about a stream
+    pub fn xinfo_stream(&self, key: &str) -> RedisResult<StreamInfo> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XINFO").arg("STREAM").arg(key).query(&mut *conn)
+    }
+
+    /// Returns the consumer groups of a stream
+    pub fn xinfo_groups(&self, key: &str) -> RedisResult<Vec<StreamGroupInfo>> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XINFO").arg("GROUPS").arg(key).query(&mut *conn)
+    }
+
+    /// Returns the consumers of a consumer group
+    pub fn xinfo_consumers(&self, key: &str, group: &str) -> RedisResult<Vec<StreamConsumerInfo>> {
+        let mut conn = self.conn.lock().unwrap();
+        redis::cmd("XINFO")
+            .arg("CONSUMERS")
+            .arg(key)
+            .arg(group)
+            .query(&mut *conn)
+    }
+
+    /// Deletes a stream
+    pub fn del(&self, key: &str) -> RedisResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.del(key)
+    }
+
+    /// Checks if a stream exists
+    pub fn exists(&self, key: &str) -> RedisResult<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let result: i32 = conn.exists(key)?;
+        Ok(result == 1)
+    }
+
+    /// Gets the TTL of a stream in seconds
+    pub fn ttl(&self, key: &str) -> RedisResult<i64> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.ttl(key)
+    }
+
+    /// Sets the TTL of a stream in seconds
+    pub fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let result: i32 = conn.expire(key, seconds as i64)?;
+        Ok(result == 1)
+    }
+
+    /// Gets keys matching a pattern
+    pub fn keys(&self, pattern: &str) -> RedisResult<Vec<String>> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.keys(pattern)
+    }
+}
+
+/// Pipeline operations
+impl RedisStream {
+    /// Executes a function with a pipeline
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use redis::{Connection, RedisResult};
+    /// # use std::sync::{Arc, Mutex};
+    /// # use dbx_adapter::redis::primitives::stream::RedisStream;
+    /// # fn example(conn: Connection) -> RedisResult<()> {
+    /// let redis_stream = RedisStream::new(Arc::new(Mutex::new(conn)));
+    /// let results: (String, usize) = redis_stream.with_pipeline(|pipe| {
+    ///     pipe.cmd("XADD").arg("events").arg("*").arg("type").arg("login")
+    ///        .cmd("XLEN").arg("events")
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_pipeline<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.lock().unwrap();
+        let mut pipe = redis::pipe();
+        let result = f(&mut pipe).query(&mut *conn)?;
+        Ok(result)
+    }
+
+    /// Helper: batch append entries to multiple streams using pipeline
+    pub fn xadd_many(
+        &self,
+        stream_entries: Vec<(&str, Vec<(&str, &str)>)>,
+    ) -> RedisResult<Vec<String>> {
+        self.with_pipeline(|pipe| {
+            for (stream_key, fields) in stream_entries {
+                pipe.cmd("XADD").arg(stream_key).arg("*").arg(fields);
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch get stream lengths using pipeline
+    pub fn xlen_many(&self, keys: Vec<&str>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("XLEN").arg(key);
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch acknowledge entries across multiple streams using pipeline
+    pub fn xack_many(&self, stream_ids: Vec<(&str, &str, Vec<&str>)>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for (key, group, ids) in stream_ids {
+                pipe.cmd("XACK").arg(key).arg(group).arg(ids);
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch trim multiple streams using pipeline
+    pub fn xtrim_many(&self, stream_trims: Vec<(&str, StreamTrim)>) -> RedisResult<Vec<usize>> {
+        self.with_pipeline(|pipe| {
+            for (key, trim) in stream_trims {
+                let mut cmd = redis::cmd("XTRIM");
+                cmd.arg(key);
+                trim.write_args(&mut cmd);
+                pipe.add_command(cmd);
+            }
+            pipe
+        })
+    }
+
+    /// Helper: batch delete multiple streams using pipeline
+    pub fn del_many(&self, keys: Vec<&str>) -> RedisResult<()> {
+        self.with_pipeline(|pipe| {
+            for key in keys {
+                pipe.cmd("DEL").arg(key);
+            }
+            pipe
+        })
+    }
+}
+
+/// Transaction operations (MULTI/EXEC)
+///
+/// Transactions in Redis are atomic command blocks executed with MULTI/EXEC.
+/// Unlike pipelines, transactions guarantee atomicity - either all commands
+/// execute or none do.
+impl RedisStream {
+    /// Executes a transaction using MULTI/EXEC
+    ///
+    /// This ensures all commands are executed atomically.
+    /// If any command fails, the entire transaction is aborted.
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use redis::{Connection, RedisResult};
+    /// # use std::sync::{Arc, Mutex};
+    /// # use dbx_adapter::redis::primitives::stream::RedisStream;
+    /// # fn example(conn: Connection) -> RedisResult<()> {
+    /// let redis_stream = RedisStream::new(Arc::new(Mutex::new(conn)));
+    /// let _: () = redis_stream.transaction(|pipe| {
+    ///     pipe.cmd("XACK").arg("orders").arg("billing").arg("1700000000000-0")
+    ///        .cmd("XDEL").arg("orders").arg("1700000000000-0")
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn transaction<F, T>(&self, f: F) -> RedisResult<T>
+    where
+        F: FnOnce(&mut Pipeline) -> &mut Pipeline,
+        T: FromRedisValue,
+    {
+        let mut conn = self.conn.lock().unwrap();
+        let mut pipe = redis::pipe();
+        // Add MULTI command at the beginning
+        pipe.cmd("MULTI");
+        // Apply the user's commands
+        f(&mut pipe);
+        // Add EXEC command at the end
+        pipe.cmd("EXEC");
+        // Execute the transaction
+        let result = pipe.query(&mut *conn)?;
+        Ok(result)
+    }
+}
+
+/// Lua script operations
+///
+/// Lua scripts in Redis provide a way to execute complex operations atomically.
+/// Scripts are executed atomically and can access keys, allowing for custom
+/// atomic operations that aren't possible with standard Redis commands.
+impl RedisStream {
+    /// Creates a new Lua script
+    ///
+    /// # Example
+    /// ```ignore
+    /// use redis::Script;
+    /// use dbx_adapter::redis::primitives::stream::RedisStream;
+    ///
+    /// let script = RedisStream::create_script(r#"
+    ///     return redis.call('XLEN', KEYS[1])
+    /// "#);
+    /// ```
+    pub fn create_script(script_source: &str) -> Script {
+        Script::new(script_source)
+    }
+
+    /// Executes a Lua script with the given keys and arguments
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use redis::{Connection, RedisResult, Script};
+    /// # use std::sync::{Arc, Mutex};
+    /// # use dbx_adapter::redis::primitives::stream::RedisStream;
+    /// # fn example(conn: Connection) -> RedisResult<()> {
+    /// let redis_stream = RedisStream::new(Arc::new(Mutex::new(conn)));
+    /// let script = RedisStream::create_script("return redis.call('XLEN', KEYS[1])");
+    ///
+    /// // Execute the script with "mystream" as the key and no arguments
+    /// let result: usize = redis_stream.eval_script::<usize, _, _>(&script, &["mystream"], &[""])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn eval_script<T, K, A>(&self, script: &Script, keys: K, args: A) -> RedisResult<T>
+    where
+        T: FromRedisValue,
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+    {
+        let mut conn = self.conn.lock().unwrap();
+        script.key(keys).arg(args).invoke(&mut *conn)
+    }
+
+    /// Loads a Lua script into the server's script cache, returning its SHA1
+    ///
+    /// Scripts added to a pipeline run by hash, so load them first. If the
+    /// pipeline fails with a NOSCRIPT error (the cache was flushed), load the
+    /// script again and retry.
+    pub fn load_script(&self, script: &Script) -> RedisResult<String> {
+        let mut conn = self.conn.lock().unwrap();
+        script.prepare_invoke().load(&mut *conn)
+    }
+
+    /// Add a Lua script to a pipeline as an EVALSHA of its hash
+    ///
+    /// The script must already be loaded, see [`RedisStream::load_script`].
+    pub fn add_script_to_pipeline<'a, K, A>(
+        pipe: &'a mut Pipeline,
+        script: &Script,
+        keys: K,
+        args: A,
+    ) -> &'a mut Pipeline
+    where
+        K: ToRedisArgs,
+        A: ToRedisArgs,
+    {
+        let keys = keys.to_redis_args();
+        let mut evalsha_cmd = redis::cmd("EVALSHA");
+        evalsha_cmd
+            .arg(script.get_hash())
+            .arg(keys.len())
+            .arg(keys)
+            .arg(args);
+        pipe.add_command(evalsha_cmd)
+    }
+}
+
+/// Utility functions for common stream operations with Lua scripts
+///
+/// These predefined scripts provide common atomic operations that can be reused
+/// across your application.
+impl RedisStream {
+    /// Gets a script that acknowledges an entry and deletes it from the stream
+    ///
+    /// # Example
+    /// ```ignore
+    /// # use redis::{Connection, RedisResult};
+    /// # use std::sync::{Arc, Mutex};
+    /// # use dbx_adapter::redis::primitives::stream::RedisStream;
+    /// # fn example(conn: Connection) -> RedisResult<()> {
+    /// let redis_stream = RedisStream::new(Arc::new(Mutex::new(conn)));
+    /// let script = RedisStream::ack_and_delete_script();
+    ///
+    /// // Acknowledge and remove a processed entry
+    /// let acked: usize = redis_stream.eval_script(
+    ///     &script,
+    ///     &["orders"],                         // KEYS[1]
+    ///     &["billing", "1700000000000-0"]      // ARGV[1] = group, ARGV[2..] = ids
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn ack_and_delete_script() -> Script {
+        Script::new(
+            r#"
+            local acked = 0
+            for i = 2, #ARGV do
+                acked = acked + redis.call('XACK', KEYS[1], ARGV[1], ARGV[i])
+                redis.call('XDEL', KEYS[1], ARGV[i])
+            end
+            return acked
+            "#,
+        )
+    }
+
+    /// Gets a script that appends an entry only if a dedupe key has not been seen
+    ///
+    /// KEYS[1] is the stream, KEYS[2] a set of seen dedupe keys, ARGV[1] the
+    /// dedupe key and the remaining ARGV the field-value pairs.
+    pub fn add_if_unique_script() -> Script {
+        Script::new(
+            r#"
+            if redis.call('SADD', KEYS[2], ARGV[1]) == 0 then
+                return false
+            end
+            local fields = {}
+            for i = 2, #ARGV do
+                table.insert(fields, ARGV[i])
+            end
+            return redis.call('XADD', KEYS[1], '*', unpack(fields))
+            "#,
+        )
+    }
+
+    /// Gets a script that moves an entry from one stream to another (e.g. a dead-letter stream)
+    pub fn move_entry_script() -> Script {
+        Script::new(
+            r#"
+            local entries = redis.call('XRANGE', KEYS[1], ARGV[1], ARGV[1])
+            if #entries == 0 then
+                return false
+            end
+            local new_id = redis.call('XADD', KEYS[2], '*', unpack(entries[1][2]))
+            redis.call('XDEL', KEYS[1], ARGV[1])
+            return new_id
+            "#,
+        )
+    }
+
+    /// Gets a script that creates a consumer group only if it does not exist yet
+    pub fn ensure_group_script() -> Script {
+        Script::new(
+            r#"
+            if redis.call('EXISTS', KEYS[1]) == 1 then
+                local groups = redis.call('XINFO', 'GROUPS', KEYS[1])
+                for i = 1, #groups do
+                    local group = groups[i]
+                    for j = 1, #group, 2 do
+                        if group[j] == 'name' and group[j + 1] == ARGV[1] then
+                            return 0
+                        end
+                    end
+                end
+            end
+            redis.call('XGROUP', 'CREATE', KEYS[1], ARGV[1], ARGV[2], 'MKSTREAM')
+            return 1
+            "#,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use redis::pipe;
+    use std::sync::{Arc, Mutex};
+
+    // Create a connection for tests that's used just for compilation
+    fn create_test_connection() -> Arc<Mutex<redis::Connection>> {
+        // For tests, just create a client but don't actually connect
+        // This allows the tests to compile without needing a Redis server
+        let redis_url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let client = redis::Client::open(redis_url).unwrap_or_else(|_| {
+            redis::Client::open("redis://localhost:6379").expect("Creating test client")
+        });
+
+        // In real tests, you would use actual connections or proper mocks
+        // We'll just create a connection object for compilation's sake
+        match client.get_connection() {
+            Ok(conn) => Arc::new(Mutex::new(conn)),
+            Err(_) => {
+                // If we can't connect (which is expected in tests), create a fake
+                // Note: This is just to make the tests compile, they're marked as #[ignore]
+                let client =
+                    redis::Client::open("redis://localhost:6379").expect("Creating test client");
+                let conn = client.get_connection().unwrap_or_else(|_| {
+                    panic!("This test is only for compilation and is marked as ignored")
+                });
+                Arc::new(Mutex::new(conn))
+            }
+        }
+    }
+
+    fn bulk(items: &[&str]) -> Value {
+        Value::Bulk(
+            items
+                .iter()
+                .map(|s| Value::Data(s.as_bytes().to_vec()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_parse_stream_entry() {
+        let value = Value::Bulk(vec![
+            Value::Data(b"1-0".to_vec()),
+            bulk(&["type", "login", "user", "42"]),
+        ]);
+        let entry = StreamEntry::from_redis_value(&value).unwrap();
+        assert_eq!(entry.id, "1-0");
+        assert_eq!(entry.fields.get("type").map(String::as_str), Some("login"));
+        assert_eq!(entry.fields.get("user").map(String::as_str), Some("42"));
+    }
+
+    #[test]
+    fn test_parse_pending_summary_when_empty() {
+        let value = Value::Bulk(vec![Value::Int(0), Value::Nil, Value::Nil, Value::Nil]);
+        let summary = StreamPendingSummary::from_redis_value(&value).unwrap();
+        assert_eq!(summary, StreamPendingSummary::default());
+    }
+
+    #[test]
+    fn test_parse_autoclaim_result() {
+        let value = Value::Bulk(vec![
+            Value::Data(b"0-0".to_vec()),
+            Value::Bulk(vec![Value::Bulk(vec![
+                Value::Data(b"5-0".to_vec()),
+                bulk(&["k", "v"]),
+            ])]),
+            bulk(&["3-0"]),
+        ]);
+        let result = StreamAutoClaimResult::from_redis_value(&value).unwrap();
+        assert_eq!(result.next_id, "0-0");
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.deleted_ids, vec!["3-0".to_string()]);
+    }
+
+    #[test]
+    #[ignore = "This test is for compilation only"]
+    fn test_compile_operations() {
+        // This test doesn't actually execute Redis commands,
+        // it just verifies that the code compiles correctly
+        let conn = create_test_connection();
+        let redis_stream = RedisStream::new(conn);
+        let trim = StreamTrim::MaxLen {
+            threshold: 1000,
+            approximate: true,
+        };
+
+        // Just make sure these compile
+        let _xadd_cmd = redis_stream.xadd("test_stream", "*", &[("type", "login")]);
+        let _xadd_trim_cmd = redis_stream.xadd_trim("test_stream", "*", &[("a", "1")], &trim);
+        let _xrange_cmd = redis_stream.xrange("test_stream", "-", "+");
+        let _xrevrange_cmd = redis_stream.xrevrange_count("test_stream", "+", "-", 10);
+        let _xlen_cmd = redis_stream.xlen("test_stream");
+        let _xdel_cmd = redis_stream.xdel("test_stream", &["1-0"]);
+        let _xtrim_cmd = redis_stream.xtrim("test_stream", &trim);
+        let _xgroup_create_cmd = redis_stream.xgroup_create("test_stream", "group", "$", true);
+        let _xgroup_setid_cmd = redis_stream.xgroup_setid("test_stream", "group", "0");
+        let _xreadgroup_cmd = redis_stream.xreadgroup(
+            "group",
+            "consumer",
+            &["test_stream"],
+            &[">"],
+            &StreamReadGroupOptions::default(),
+        );
+        let _xack_cmd = redis_stream.xack("test_stream", "group", &["1-0"]);
+        let _xpending_cmd = redis_stream.xpending("test_stream", "group");
+        let _xpending_range_cmd =
+            redis_stream.xpending_range("test_stream", "group", "-", "+", 10, None);
+        let _xclaim_cmd = redis_stream.xclaim("test_stream", "group", "other", 1000, &["1-0"]);
+        let _xautoclaim_cmd =
+            redis_stream.xautoclaim("test_stream", "group", "other", 1000, "0-0", Some(10));
+        let _xinfo_stream_cmd = redis_stream.xinfo_stream("test_stream");
+        let _xinfo_groups_cmd = redis_stream.xinfo_groups("test_stream");
+        let _xinfo_consumers_cmd = redis_stream.xinfo_consumers("test_stream", "group");
+        let _xgroup_destroy_cmd = redis_stream.xgroup_destroy("test_stream", "group");
+    }
+
+    #[test]
+    #[ignore = "This test is for compilation only"]
+    fn test_pipeline_methods() {
+        // Test that pipelines can be used directly with cmd()
+        let mut pipeline = pipe();
+
+        let _pipe_ref1 = pipeline
+            .cmd("XADD")
+            .arg("stream1")
+            .arg("*")
+            .arg("a")
+            .arg("1");
+        let _pipe_ref2 = pipeline.cmd("XRANGE").arg("stream1").arg("-").arg("+");
+        let _pipe_ref3 = pipeline.cmd("XLEN").arg("stream1");
+    }
+
+    #[test]
+    #[ignore = "This test is for compilation only"]
+    fn test_batch_operations() {
+        let conn = create_test_connection();
+        let redis_stream = RedisStream::new(conn);
+
+        // Just check that these methods compile correctly
+        let _ = redis_stream.xadd_many(vec![
+            ("stream1", vec![("type", "login")]),
+            ("stream2", vec![("type", "logout")]),
+        ]);
+        let _ = redis_stream.xlen_many(vec!["stream1", "stream2"]);
+        let _ = redis_stream.xack_many(vec![("stream1", "group", vec!["1-0", "2-0"])]);
+        let _ = redis_stream.xtrim_many(vec![(
+            "stream1",
+            StreamTrim::MinId {
+                threshold: "1-0".to_string(),
+                approximate: false,
+            },
+        )]);
+        let _ = redis_stream.del_many(vec!["old_stream1", "old_stream2"]);
+    }
+
+    #[test]
+    fn test_scripts_are_pipelined_by_hash() {
+        let script = RedisStream::ack_and_delete_script();
+        let mut pipe = redis::pipe();
+        RedisStream::add_script_to_pipeline(&mut pipe, &script, &["stream1"], &["group", "1-0"]);
+
+        let expected = redis::cmd("EVALSHA")
+            .arg(script.get_hash())
+            .arg(1)
+            .arg("stream1")
+            .arg("group")
+            .arg("1-0")
+            .get_packed_command();
+        assert_eq!(pipe.get_packed_pipeline(), expected);
+    }
+
+    #[test]
+    #[ignore = "This test is for compilation only"]
+    fn test_transaction() {
+        let conn = create_test_connection();
+        let _redis_stream = RedisStream::new(conn);
+
+        // This test is just a compilation check
+        // We're not actually executing the transaction
+    }
+
+    // Real execution of transactions and Lua scripts would require integration tests
+    // with an actual Redis instance or more sophisticated mocking.
+}
+
+/// Examples of how to use RedisStream with various features
+///
+/// These examples demonstrate how to use RedisStream's features
+/// in real-world scenarios.
+#[cfg(test)]
+mod examples {
+    use super::*;
+
+    #[test]
+    #[ignore = "This example is for demonstration only"]
+    fn example_patterns() {
+        // Create a connection for examples
+        let redis_url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let client = redis::Client::open(redis_url).unwrap_or_else(|_| {
+            redis::Client::open("redis://localhost:6379").expect("Creating example client")
+        });
+
+        // This won't actually be used in ignored tests
+        let conn = Arc::new(Mutex::new(client.get_connection().unwrap_or_else(|_| {
+            panic!("This example is only for demonstration and is marked as ignored")
+        })));
+
+        let redis_stream = RedisStream::new(conn);
+
+        // Example 1: Event log capped at roughly 10k entries
+        let _ = redis_stream.xadd_trim(
+            "events",
+            "*",
+            &[("type", "signup"), ("user", "42")],
+            &StreamTrim::MaxLen {
+                threshold: 10_000,
+                approximate: true,
+            },
+        );
+
+        // Example 2: Consumer group processing
+        let _ = redis_stream.xgroup_create("orders", "billing", "0", true);
+        if let Ok(batches) = redis_stream.xreadgroup(
+            "billing",
+            "worker-1",
+            &["orders"],
+            &[">"],
+            &StreamReadGroupOptions {
+                count: Some(10),
+                block_ms: Some(5000),
+                noack: false,
+            },
+        ) {
+            for batch in batches {
+                let ids: Vec<&str> = batch.entries.iter().map(|e| e.id.as_str()).collect();
+                let _ = redis_stream.xack(&batch.key, "billing", &ids);
+            }
+        }
+
+        // Example 3: Recover entries from crashed consumers
+        let _ = redis_stream.xautoclaim("orders", "billing", "worker-2", 60_000, "0-0", Some(100));
+
+        // Example 4: Inspect the stream and its groups
+        let _ = redis_stream.xinfo_stream("orders");
+        let _ = redis_stream.xinfo_groups("orders");
+        let _ = redis_stream.xpending("orders", "billing");
+    }
+}
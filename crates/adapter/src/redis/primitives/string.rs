@@ -70,7 +70,7 @@ impl RedisString {
     /// Sets a key with expiration
     pub fn set_with_expiry(&self, key: &str, value: &str, ttl_seconds: usize) -> RedisResult<()> {
         let mut conn = self.conn.lock().unwrap();
-        conn.set_ex(key, value, ttl_seconds)
+        conn.set_ex(key, value, ttl_seconds as u64)
     }
 
     /// Increments the number stored at key by the given amount
@@ -113,7 +113,7 @@ impl RedisString {
     /// Sets the TTL of a key in seconds
     pub fn expire(&self, key: &str, seconds: u64) -> RedisResult<bool> {
         let mut conn = self.conn.lock().unwrap();
-        let result: i32 = conn.expire(key, seconds as usize)?;
+        let result: i32 = conn.expire(key, seconds as i64)?;
         Ok(result == 1)
     }
 
@@ -577,7 +577,6 @@ mod tests {
 #[cfg(test)]
 mod examples {
     use super::*;
-    use crate::test_helpers::get_test_redis_url;
 
     #[test]
     #[ignore = "This example is for demonstration only"]
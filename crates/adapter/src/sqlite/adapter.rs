@@ -0,0 +1,636 @@
+//! Adapter trait implementations for the SQLite backend
+//!
+//! Every key has a row in `keys` holding its type and optional expiry in unix
+//! milliseconds; the values live in one table per type and are removed with
+//! their key. Expired keys are purged when they are next looked at, and
+//! collections that become empty are removed, as in Redis.
+
+use async_trait::async_trait;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::AdapterError;
+use crate::memory::store::{Changes, Db, Value, WrongType};
+use crate::memory::{MemoryStore, Persistence};
+use crate::traits::{
+    AsyncDatabaseAdapter, DatabaseAdapter, HashAdapter, KeyValueAdapter, SetAdapter,
+};
+
+const SCHEMA: &str = "
+PRAGMA foreign_keys = ON;
+CREATE TABLE IF NOT EXISTS keys (
+    key TEXT PRIMARY KEY,
+    type TEXT NOT NULL,
+    expires_at INTEGER
+);
+CREATE TABLE IF NOT EXISTS strings (
+    key TEXT PRIMARY KEY REFERENCES keys (key) ON DELETE CASCADE,
+    value BLOB NOT NULL
+);
+CREATE TABLE IF NOT EXISTS hashes (
+    key TEXT NOT NULL REFERENCES keys (key) ON DELETE CASCADE,
+    field TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (key, field)
+);
+CREATE TABLE IF NOT EXISTS sets (
+    key TEXT NOT NULL REFERENCES keys (key) ON DELETE CASCADE,
+    member TEXT NOT NULL,
+    PRIMARY KEY (key, member)
+);
+CREATE TABLE IF NOT EXISTS zsets (
+    key TEXT NOT NULL REFERENCES keys (key) ON DELETE CASCADE,
+    member TEXT NOT NULL,
+    score REAL NOT NULL,
+    PRIMARY KEY (key, member)
+);
+CREATE TABLE IF NOT EXISTS lists (
+    key TEXT NOT NULL REFERENCES keys (key) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (key, position)
+);
+";
+
+impl From<rusqlite::Error> for AdapterError {
+    fn from(err: rusqlite::Error) -> Self {
+        AdapterError::Database(err.to_string())
+    }
+}
+
+/// Database adapter backed by a SQLite file
+///
+/// Clones share the same connection.
+#[derive(Clone)]
+pub struct SqliteAdapter {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteAdapter {
+    /// Open (or create) the database file at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AdapterError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// Open a private database that lives as long as the adapter
+    pub fn open_in_memory() -> Result<Self, AdapterError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, AdapterError> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        self.conn
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Set a time to live on a key, returning whether the key exists
+    pub fn expire(&self, key: &str, ttl: Duration) -> Result<bool, AdapterError> {
+        let conn = self.lock();
+        purge_if_expired(&conn, key)?;
+        let expires_at = now_millis().saturating_add(ttl.as_millis() as i64);
+        let updated = conn.execute(
+            "UPDATE keys SET expires_at = ?2 WHERE key = ?1",
+            params![key, expires_at],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Get the remaining time to live: `None` if the key is missing, `Some(None)` if it never expires
+    pub fn ttl(&self, key: &str) -> Result<Option<Option<Duration>>, AdapterError> {
+        let conn = self.lock();
+        purge_if_expired(&conn, key)?;
+        let expires_at: Option<Option<i64>> = conn
+            .query_row("SELECT expires_at FROM keys WHERE key = ?1", [key], |row| {
+                row.get(0)
+            })
+            .optional()?;
+        let now = now_millis();
+        Ok(expires_at.map(|at| at.map(|at| Duration::from_millis((at - now).max(0) as u64))))
+    }
+
+    /// Remove the time to live of a key, returning whether it had one
+    pub fn persist(&self, key: &str) -> Result<bool, AdapterError> {
+        let conn = self.lock();
+        purge_if_expired(&conn, key)?;
+        let updated = conn.execute(
+            "UPDATE keys SET expires_at = NULL WHERE key = ?1 AND expires_at IS NOT NULL",
+            [key],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Load every live key into `store`, returning how many were loaded
+    ///
+    /// Used to fill a store that has this adapter as its persistence before
+    /// it is served.
+    pub fn load_into(&self, store: &MemoryStore) -> Result<usize, AdapterError> {
+        let conn = self.lock();
+        let now = now_millis();
+        conn.execute(
+            "DELETE FROM keys WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+            [now],
+        )?;
+
+        let mut values: HashMap<String, (Value, Option<i64>)> = HashMap::new();
+        let mut stmt = conn.prepare("SELECT key, type, expires_at FROM keys")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (key, kind, expires_at) = row?;
+            let value = match kind.as_str() {
+                "string" => Value::String(Vec::new()),
+                "hash" => Value::Hash(HashMap::new()),
+                "set" => Value::Set(HashSet::new()),
+                "zset" => Value::SortedSet(HashMap::new()),
+                "list" => Value::List(VecDeque::new()),
+                other => {
+                    return Err(AdapterError::Database(format!(
+                        "Unknown type '{other}' stored for key '{key}'"
+                    )))
+                }
+            };
+            values.insert(key, (value, expires_at));
+        }
+
+        let mut stmt = conn.prepare("SELECT key, value FROM strings")?;
+        for row in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))? {
+            let (key, bytes) = row?;
+            if let Some((Value::String(value), _)) = values.get_mut(&key) {
+                *value = bytes;
+            }
+        }
+        let mut stmt = conn.prepare("SELECT key, field, value FROM hashes")?;
+        for row in stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get(1)?, row.get(2)?))
+        })? {
+            let (key, field, value) = row?;
+            if let Some((Value::Hash(hash), _)) = values.get_mut(&key) {
+                hash.insert(field, value);
+            }
+        }
+        let mut stmt = conn.prepare("SELECT key, member FROM sets")?;
+        for row in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))? {
+            let (key, member) = row?;
+            if let Some((Value::Set(set), _)) = values.get_mut(&key) {
+                set.insert(member);
+            }
+        }
+        let mut stmt = conn.prepare("SELECT key, member, score FROM zsets")?;
+        for row in stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get(1)?, row.get(2)?))
+        })? {
+            let (key, member, score) = row?;
+            if let Some((Value::SortedSet(zset), _)) = values.get_mut(&key) {
+                zset.insert(member, score);
+            }
+        }
+        let mut stmt = conn.prepare("SELECT key, value FROM lists ORDER BY key, position")?;
+        for row in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get(1)?)))? {
+            let (key, value) = row?;
+            if let Some((Value::List(list), _)) = values.get_mut(&key) {
+                list.push_back(value);
+            }
+        }
+
+        let loaded = values.len();
+        let mut db = store.lock();
+        for (key, (value, expires_at)) in values {
+            db.insert(&key, value);
+            if let Some(at) = expires_at {
+                db.expire(&key, Duration::from_millis((at - now).max(0) as u64));
+            }
+        }
+        // The data came from the file, so there is nothing to write back
+        db.discard_changes();
+        Ok(loaded)
+    }
+
+    fn write_string(
+        &self,
+        key: &str,
+        value: &str,
+        expires_at: Option<i64>,
+    ) -> Result<(), AdapterError> {
+        let mut conn = self.lock();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM keys WHERE key = ?1", [key])?;
+        tx.execute(
+            "INSERT INTO keys (key, type, expires_at) VALUES (?1, 'string', ?2)",
+            params![key, expires_at],
+        )?;
+        tx.execute(
+            "INSERT INTO strings (key, value) VALUES (?1, ?2)",
+            params![key, value.as_bytes()],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn save_changes(&self, db: &Db, changes: &Changes) -> Result<(), AdapterError> {
+        let mut conn = self.lock();
+        let tx = conn.transaction()?;
+        if changes.cleared {
+            tx.execute("DELETE FROM keys", [])?;
+        }
+        let now = now_millis();
+        for key in &changes.keys {
+            tx.execute("DELETE FROM keys WHERE key = ?1", [key])?;
+            let Some((value, ttl)) = db.snapshot(key) else {
+                continue;
+            };
+            let expires_at = ttl.map(|ttl| now.saturating_add(ttl.as_millis() as i64));
+            tx.execute(
+                "INSERT INTO keys (key, type, expires_at) VALUES (?1, ?2, ?3)",
+                params![key, value.type_name(), expires_at],
+            )?;
+            match value {
+                Value::String(bytes) => {
+                    tx.execute(
+                        "INSERT INTO strings (key, value) VALUES (?1, ?2)",
+                        params![key, bytes],
+                    )?;
+                }
+                Value::Hash(hash) => {
+                    let mut stmt =
+                        tx.prepare("INSERT INTO hashes (key, field, value) VALUES (?1, ?2, ?3)")?;
+                    for (field, value) in hash {
+                        stmt.execute(params![key, field, value])?;
+                    }
+                }
+                Value::Set(set) => {
+                    let mut stmt = tx.prepare("INSERT INTO sets (key, member) VALUES (?1, ?2)")?;
+                    for member in set {
+                        stmt.execute(params![key, member])?;
+                    }
+                }
+                Value::SortedSet(zset) => {
+                    let mut stmt =
+                        tx.prepare("INSERT INTO zsets (key, member, score) VALUES (?1, ?2, ?3)")?;
+                    for (member, score) in zset {
+                        stmt.execute(params![key, member, score])?;
+                    }
+                }
+                Value::List(list) => {
+                    let mut stmt =
+                        tx.prepare("INSERT INTO lists (key, position, value) VALUES (?1, ?2, ?3)")?;
+                    for (position, value) in list.iter().enumerate() {
+                        stmt.execute(params![key, position as i64, value])?;
+                    }
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+impl Persistence for SqliteAdapter {
+    fn save(&self, db: &Db, changes: &Changes) -> Result<(), String> {
+        self.save_changes(db, changes).map_err(|e| e.to_string())
+    }
+}
+
+impl DatabaseAdapter for SqliteAdapter {
+    type Error = AdapterError;
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl AsyncDatabaseAdapter for SqliteAdapter {
+    async fn ping(&self) -> Result<bool, Self::Error> {
+        let one: i64 = self.lock().query_row("SELECT 1", [], |row| row.get(0))?;
+        Ok(one == 1)
+    }
+
+    /// The connection is closed when the last clone is dropped
+    async fn close(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl KeyValueAdapter for SqliteAdapter {
+    async fn get<K: AsRef<str> + Send>(&self, key: K) -> Result<Option<String>, Self::Error> {
+        let conn = self.lock();
+        if !has_type(&conn, key.as_ref(), "string")? {
+            return Ok(None);
+        }
+        let bytes: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT value FROM strings WHERE key = ?1",
+                [key.as_ref()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(bytes.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+    }
+
+    async fn set<K: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        value: V,
+    ) -> Result<(), Self::Error> {
+        self.write_string(key.as_ref(), value.as_ref(), None)
+    }
+
+    async fn set_with_expiry<K: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        value: V,
+        expiry_seconds: u64,
+    ) -> Result<(), Self::Error> {
+        let expires_at = now_millis().saturating_add(expiry_seconds.saturating_mul(1000) as i64);
+        self.write_string(key.as_ref(), value.as_ref(), Some(expires_at))
+    }
+
+    async fn delete<K: AsRef<str> + Send>(&self, key: K) -> Result<bool, Self::Error> {
+        let conn = self.lock();
+        purge_if_expired(&conn, key.as_ref())?;
+        Ok(conn.execute("DELETE FROM keys WHERE key = ?1", [key.as_ref()])? > 0)
+    }
+
+    async fn exists<K: AsRef<str> + Send>(&self, key: K) -> Result<bool, Self::Error> {
+        Ok(key_type(&self.lock(), key.as_ref())?.is_some())
+    }
+}
+
+#[async_trait]
+impl HashAdapter for SqliteAdapter {
+    async fn hget<K: AsRef<str> + Send, F: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+    ) -> Result<Option<String>, Self::Error> {
+        let conn = self.lock();
+        if !has_type(&conn, key.as_ref(), "hash")? {
+            return Ok(None);
+        }
+        Ok(conn
+            .query_row(
+                "SELECT value FROM hashes WHERE key = ?1 AND field = ?2",
+                [key.as_ref(), field.as_ref()],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    async fn hset<K: AsRef<str> + Send, F: AsRef<str> + Send, V: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+        value: V,
+    ) -> Result<(), Self::Error> {
+        let mut conn = self.lock();
+        let tx = conn.transaction()?;
+        claim(&tx, key.as_ref(), "hash")?;
+        tx.execute(
+            "INSERT OR REPLACE INTO hashes (key, field, value) VALUES (?1, ?2, ?3)",
+            [key.as_ref(), field.as_ref(), value.as_ref()],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    async fn hgetall<K: AsRef<str> + Send>(
+        &self,
+        key: K,
+    ) -> Result<HashMap<String, String>, Self::Error> {
+        let conn = self.lock();
+        if !has_type(&conn, key.as_ref(), "hash")? {
+            return Ok(HashMap::new());
+        }
+        let mut stmt = conn.prepare("SELECT field, value FROM hashes WHERE key = ?1")?;
+        let fields = stmt
+            .query_map([key.as_ref()], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+        Ok(fields)
+    }
+
+    async fn hdel<K: AsRef<str> + Send, F: AsRef<str> + Send>(
+        &self,
+        key: K,
+        field: F,
+    ) -> Result<bool, Self::Error> {
+        let mut conn = self.lock();
+        let tx = conn.transaction()?;
+        if !has_type(&tx, key.as_ref(), "hash")? {
+            return Ok(false);
+        }
+        let removed = tx.execute(
+            "DELETE FROM hashes WHERE key = ?1 AND field = ?2",
+            [key.as_ref(), field.as_ref()],
+        )?;
+        remove_if_empty(&tx, key.as_ref(), "hashes")?;
+        tx.commit()?;
+        Ok(removed > 0)
+    }
+}
+
+#[async_trait]
+impl SetAdapter for SqliteAdapter {
+    async fn sadd<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let mut conn = self.lock();
+        let tx = conn.transaction()?;
+        claim(&tx, key.as_ref(), "set")?;
+        let added = tx.execute(
+            "INSERT OR IGNORE INTO sets (key, member) VALUES (?1, ?2)",
+            [key.as_ref(), member.as_ref()],
+        )?;
+        tx.commit()?;
+        Ok(added > 0)
+    }
+
+    async fn srem<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let mut conn = self.lock();
+        let tx = conn.transaction()?;
+        if !has_type(&tx, key.as_ref(), "set")? {
+            return Ok(false);
+        }
+        let removed = tx.execute(
+            "DELETE FROM sets WHERE key = ?1 AND member = ?2",
+            [key.as_ref(), member.as_ref()],
+        )?;
+        remove_if_empty(&tx, key.as_ref(), "sets")?;
+        tx.commit()?;
+        Ok(removed > 0)
+    }
+
+    async fn sismember<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error> {
+        let conn = self.lock();
+        if !has_type(&conn, key.as_ref(), "set")? {
+            return Ok(false);
+        }
+        Ok(conn
+            .query_row(
+                "SELECT 1 FROM sets WHERE key = ?1 AND member = ?2",
+                [key.as_ref(), member.as_ref()],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    }
+
+    async fn smembers<K: AsRef<str> + Send>(&self, key: K) -> Result<Vec<String>, Self::Error> {
+        let conn = self.lock();
+        if !has_type(&conn, key.as_ref(), "set")? {
+            return Ok(Vec::new());
+        }
+        let mut stmt = conn.prepare("SELECT member FROM sets WHERE key = ?1 ORDER BY member")?;
+        let members = stmt
+            .query_map([key.as_ref()], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        Ok(members)
+    }
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as i64)
+}
+
+/// Drops `key` if its expiry has passed
+fn purge_if_expired(conn: &Connection, key: &str) -> Result<(), AdapterError> {
+    conn.execute(
+        "DELETE FROM keys WHERE key = ?1 AND expires_at IS NOT NULL AND expires_at <= ?2",
+        params![key, now_millis()],
+    )?;
+    Ok(())
+}
+
+/// The type of a live key
+fn key_type(conn: &Connection, key: &str) -> Result<Option<String>, AdapterError> {
+    purge_if_expired(conn, key)?;
+    Ok(conn
+        .query_row("SELECT type FROM keys WHERE key = ?1", [key], |row| {
+            row.get(0)
+        })
+        .optional()?)
+}
+
+/// Whether `key` holds a value of `kind`; fails if it holds another type
+fn has_type(conn: &Connection, key: &str, kind: &str) -> Result<bool, AdapterError> {
+    match key_type(conn, key)? {
+        None => Ok(false),
+        Some(found) if found == kind => Ok(true),
+        Some(_) => Err(WrongType.into()),
+    }
+}
+
+/// Makes sure `key` exists with type `kind`, creating it if missing
+fn claim(conn: &Connection, key: &str, kind: &str) -> Result<(), AdapterError> {
+    if !has_type(conn, key, kind)? {
+        conn.execute(
+            "INSERT INTO keys (key, type, expires_at) VALUES (?1, ?2, NULL)",
+            [key, kind],
+        )?;
+    }
+    Ok(())
+}
+
+/// Drops `key` if the collection stored in `table` has become empty
+fn remove_if_empty(conn: &Connection, key: &str, table: &str) -> Result<(), AdapterError> {
+    conn.execute(
+        &format!(
+            "DELETE FROM keys WHERE key = ?1 AND NOT EXISTS (SELECT 1 FROM {table} WHERE key = ?1)"
+        ),
+        [key],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::OperationError;
+
+    #[tokio::test]
+    async fn test_sqlite_adapter_traits() {
+        let adapter = SqliteAdapter::open_in_memory().unwrap();
+        adapter.set("greeting", "hello").await.unwrap();
+        assert_eq!(
+            adapter.get("greeting").await.unwrap().as_deref(),
+            Some("hello")
+        );
+
+        adapter.hset("user:1", "name", "ada").await.unwrap();
+        assert!(adapter.hdel("user:1", "name").await.unwrap());
+        assert!(!adapter.exists("user:1").await.unwrap());
+
+        assert!(adapter.sadd("tags", "rust").await.unwrap());
+        assert!(!adapter.sadd("tags", "rust").await.unwrap());
+        assert!(matches!(
+            adapter.hget("tags", "rust").await,
+            Err(AdapterError::Operation(OperationError::Failed(_)))
+        ));
+
+        adapter.set_with_expiry("session", "abc", 60).await.unwrap();
+        assert!(
+            matches!(adapter.ttl("session").unwrap(), Some(Some(ttl)) if ttl <= Duration::from_secs(60))
+        );
+        adapter.expire("session", Duration::ZERO).unwrap();
+        assert_eq!(adapter.get("session").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_persistence_round_trip() {
+        let adapter = SqliteAdapter::open_in_memory().unwrap();
+        let store = MemoryStore::with_persistence(Arc::new(adapter.clone()));
+        {
+            let mut db = store.lock();
+            db.hash_or_create("user:1")
+                .unwrap()
+                .insert("name".to_string(), "ada".to_string());
+            db.zset_or_create("board")
+                .unwrap()
+                .insert("bob".to_string(), 2.0);
+            db.list_or_create("queue")
+                .unwrap()
+                .extend(["job:1".to_string(), "job:2".to_string()]);
+            db.save_changes().unwrap();
+        }
+        assert_eq!(
+            adapter.hget("user:1", "name").await.unwrap().as_deref(),
+            Some("ada")
+        );
+
+        let reloaded = MemoryStore::new();
+        assert_eq!(adapter.load_into(&reloaded).unwrap(), 3);
+        let mut db = reloaded.lock();
+        assert_eq!(db.zset("board").unwrap().unwrap().get("bob"), Some(&2.0));
+        assert_eq!(
+            db.list("queue").unwrap().unwrap(),
+            &VecDeque::from(["job:1".to_string(), "job:2".to_string()])
+        );
+    }
+}
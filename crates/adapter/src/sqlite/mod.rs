@@ -0,0 +1,27 @@
+//! SQLite backend
+//!
+//! Stores strings, hashes, sets, sorted sets, lists and key expiry in a single
+//! SQLite file, for single-node deployments without a Redis server.
+//! [`SqliteAdapter`] implements the adapter traits with plain SQL, and it can
+//! act as the [`Persistence`](crate::memory::Persistence) of a
+//! [`MemoryStore`](crate::memory::MemoryStore), so a
+//! [`MemoryServer`](crate::memory::MemoryServer) can serve the file over the
+//! Redis protocol.
+
+pub mod adapter;
+
+pub use adapter::SqliteAdapter;
+
+/// URL scheme that selects the SQLite backend, followed by the database file path
+pub const SQLITE_URL_SCHEME: &str = "sqlite://";
+
+/// Check whether a connection URL selects the SQLite backend
+pub fn is_sqlite_url(url: &str) -> bool {
+    url.starts_with(SQLITE_URL_SCHEME)
+}
+
+/// Get the database file path from a `sqlite://` URL
+pub fn sqlite_path(url: &str) -> Option<&str> {
+    url.strip_prefix(SQLITE_URL_SCHEME)
+        .filter(|path| !path.is_empty())
+}
@@ -112,6 +112,43 @@ pub trait SetAdapter: AsyncDatabaseAdapter {
     async fn smembers<K: AsRef<str> + Send>(&self, key: K) -> Result<Vec<String>, Self::Error>;
 }
 
+/// Trait for adapters that support sorted set operations
+#[async_trait]
+pub trait SortedSetAdapter: AsyncDatabaseAdapter {
+    /// Add a member with a score, or update its score
+    async fn zadd<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        score: f64,
+        member: M,
+    ) -> Result<bool, Self::Error>;
+
+    /// Remove a member from a sorted set
+    async fn zrem<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<bool, Self::Error>;
+
+    /// Get the score of a member
+    async fn zscore<K: AsRef<str> + Send, M: AsRef<str> + Send>(
+        &self,
+        key: K,
+        member: M,
+    ) -> Result<Option<f64>, Self::Error>;
+
+    /// Get the number of members in a sorted set
+    async fn zcard<K: AsRef<str> + Send>(&self, key: K) -> Result<usize, Self::Error>;
+
+    /// Get members with their scores by rank, lowest score first
+    async fn zrange<K: AsRef<str> + Send>(
+        &self,
+        key: K,
+        start: isize,
+        stop: isize,
+    ) -> Result<Vec<(String, f64)>, Self::Error>;
+}
+
 /// Trait for adapters that support connection pooling
 #[async_trait]
 pub trait PooledAdapter: AsyncDatabaseAdapter {
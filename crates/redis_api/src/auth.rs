@@ -0,0 +1,297 @@
+//! API key and JWT authentication
+//!
+//! Callers present a credential either as `Authorization: Bearer <credential>`
+//! or as `X-API-Key: <key>`. WebSocket upgrades may also pass it in the
+//! `token` query parameter, since browsers cannot set headers on them. A
+//! credential is accepted if it is one of the configured API keys or a JWT
+//! signed with the HS256 secret or an RS256 key from the JWKS file.
+
+use axum::extract::Query;
+use axum::http::{header, HeaderMap, Uri};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use std::collections::HashMap;
+
+use crate::config::AuthConfig;
+
+/// Header carrying an API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Query parameter carrying a credential on WebSocket upgrades
+pub const TOKEN_QUERY_PARAM: &str = "token";
+
+/// Authentication failures
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("Missing credentials")]
+    MissingCredentials,
+
+    #[error("Invalid credentials")]
+    InvalidCredentials,
+
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+}
+
+/// The caller a request was authenticated as
+///
+/// Added to the request extensions by the auth middleware.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    /// The token's `sub` claim, or `api-key` for API key callers
+    pub subject: String,
+    /// Roles from the token's `role` or `roles` claim, or the API key's role
+    pub roles: Vec<String>,
+    /// All claims of the token, or `null` for API key callers
+    pub claims: serde_json::Value,
+}
+
+/// Verifies API keys and JWTs against the configured secrets
+pub struct Authenticator {
+    /// Accepted API keys with their role, if any
+    api_keys: Vec<(String, Option<String>)>,
+    hs256_key: Option<DecodingKey>,
+    jwks: Option<JwkSet>,
+    issuer: Option<String>,
+    audience: Option<String>,
+}
+
+impl Authenticator {
+    /// Build an authenticator, or `None` when no credentials are configured
+    pub fn from_config(config: &AuthConfig) -> anyhow::Result<Option<Self>> {
+        if !config.is_enabled() {
+            return Ok(None);
+        }
+
+        let jwks =
+            match &config.jwks_path {
+                Some(path) => {
+                    let content = std::fs::read_to_string(path)
+                        .map_err(|e| anyhow::anyhow!("Failed to read JWKS file {}: {}", path, e))?;
+                    Some(serde_json::from_str::<JwkSet>(&content).map_err(|e| {
+                        anyhow::anyhow!("Failed to parse JWKS file {}: {}", path, e)
+                    })?)
+                }
+                None => None,
+            };
+
+        Ok(Some(Self {
+            api_keys: config
+                .api_keys
+                .iter()
+                .filter(|key| !config.api_key_roles.contains_key(*key))
+                .map(|key| (key.clone(), None))
+                .chain(
+                    config
+                        .api_key_roles
+                        .iter()
+                        .map(|(key, role)| (key.clone(), Some(role.clone()))),
+                )
+                .collect(),
+            hs256_key: config
+                .jwt_secret
+                .as_ref()
+                .map(|secret| DecodingKey::from_secret(secret.as_bytes())),
+            jwks,
+            issuer: config.jwt_issuer.clone(),
+            audience: config.jwt_audience.clone(),
+        }))
+    }
+
+    /// Check a credential taken from a request
+    pub fn authenticate(&self, credential: &str) -> Result<Principal, AuthError> {
+        if let Some((_, role)) = self
+            .api_keys
+            .iter()
+            .find(|(key, _)| constant_time_eq(key.as_bytes(), credential.as_bytes()))
+        {
+            return Ok(Principal {
+                subject: "api-key".to_string(),
+                roles: role.iter().cloned().collect(),
+                claims: serde_json::Value::Null,
+            });
+        }
+
+        // Anything that isn't shaped like a JWT can only have been a wrong API key
+        if credential.split('.').count() != 3 {
+            return Err(AuthError::InvalidCredentials);
+        }
+        self.verify_jwt(credential)
+    }
+
+    fn verify_jwt(&self, token: &str) -> Result<Principal, AuthError> {
+        let header = decode_header(token).map_err(|e| AuthError::InvalidToken(e.to_string()))?;
+        let rs256_key;
+        let key = match header.alg {
+            Algorithm::HS256 => self.hs256_key.as_ref(),
+            Algorithm::RS256 => {
+                rs256_key = self.jwks_key(header.kid.as_deref())?;
+                rs256_key.as_ref()
+            }
+            _ => None,
+        }
+        .ok_or_else(|| {
+            AuthError::InvalidToken(format!("{:?} tokens are not accepted", header.alg))
+        })?;
+
+        let mut validation = Validation::new(header.alg);
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        let claims = decode::<serde_json::Value>(token, key, &validation)
+            .map_err(|e| AuthError::InvalidToken(e.to_string()))?
+            .claims;
+        Ok(Principal {
+            subject: claims
+                .get("sub")
+                .and_then(|sub| sub.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            roles: roles_claim(&claims),
+            claims,
+        })
+    }
+
+    /// The JWKS key for `kid`, or the only key when the token names none
+    fn jwks_key(&self, kid: Option<&str>) -> Result<Option<DecodingKey>, AuthError> {
+        let Some(jwks) = &self.jwks else {
+            return Ok(None);
+        };
+        let jwk = match kid {
+            Some(kid) => jwks.find(kid),
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| AuthError::InvalidToken("No matching key in the JWKS".to_string()))?;
+        DecodingKey::from_jwk(jwk)
+            .map(Some)
+            .map_err(|e| AuthError::InvalidToken(e.to_string()))
+    }
+}
+
+/// Roles from a `roles` array or a single `role` claim
+fn roles_claim(claims: &serde_json::Value) -> Vec<String> {
+    match (claims.get("roles"), claims.get("role")) {
+        (Some(serde_json::Value::Array(roles)), _) => roles
+            .iter()
+            .filter_map(|role| role.as_str().map(str::to_string))
+            .collect(),
+        (_, Some(serde_json::Value::String(role))) => vec![role.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// Take the credential from the `Authorization` or `X-API-Key` header
+pub fn credential_from_headers(headers: &HeaderMap) -> Option<String> {
+    if let Some(bearer) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    {
+        return Some(bearer.trim().to_string());
+    }
+    headers
+        .get(API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+}
+
+/// Take the credential from the `token` query parameter
+pub fn credential_from_query(uri: &Uri) -> Option<String> {
+    Query::<HashMap<String, String>>::try_from_uri(uri)
+        .ok()?
+        .0
+        .remove(TOKEN_QUERY_PARAM)
+}
+
+/// Take the credential from the headers or, on WebSocket upgrades, the query
+pub fn credential_from_request(headers: &HeaderMap, uri: &Uri) -> Option<String> {
+    credential_from_headers(headers).or_else(|| {
+        is_websocket_upgrade(headers)
+            .then(|| credential_from_query(uri))
+            .flatten()
+    })
+}
+
+/// Whether the request asks to upgrade to a WebSocket
+pub fn is_websocket_upgrade(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::UPGRADE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("websocket"))
+}
+
+/// Compare secrets without leaking where they differ through timing
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    fn authenticator() -> Authenticator {
+        Authenticator::from_config(&AuthConfig {
+            api_keys: vec!["secret-key".to_string()],
+            api_key_roles: [("tenant-key".to_string(), "tenant42".to_string())].into(),
+            jwt_secret: Some("jwt-secret".to_string()),
+            jwt_issuer: Some("dbx".to_string()),
+            ..AuthConfig::default()
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    fn hs256_token(secret: &str, issuer: &str) -> String {
+        let claims = serde_json::json!({
+            "sub": "alice",
+            "iss": issuer,
+            "roles": ["admin"],
+            "exp": chrono::Utc::now().timestamp() + 60,
+        });
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_api_key_and_hs256_token() {
+        let auth = authenticator();
+        assert_eq!(auth.authenticate("secret-key").unwrap().subject, "api-key");
+        assert_eq!(auth.authenticate("tenant-key").unwrap().roles, ["tenant42"]);
+        assert!(matches!(
+            auth.authenticate("wrong-key"),
+            Err(AuthError::InvalidCredentials)
+        ));
+
+        let principal = auth
+            .authenticate(&hs256_token("jwt-secret", "dbx"))
+            .unwrap();
+        assert_eq!(principal.subject, "alice");
+        assert_eq!(principal.roles, ["admin"]);
+        assert!(matches!(
+            auth.authenticate(&hs256_token("other-secret", "dbx")),
+            Err(AuthError::InvalidToken(_))
+        ));
+        assert!(matches!(
+            auth.authenticate(&hs256_token("jwt-secret", "someone-else")),
+            Err(AuthError::InvalidToken(_))
+        ));
+    }
+
+    #[test]
+    fn test_disabled_without_credentials() {
+        assert!(Authenticator::from_config(&AuthConfig::default())
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,151 @@
+//! Command-line flags for the `dbx-redis-api` binary
+
+use clap::Parser;
+use std::path::PathBuf;
+
+use crate::config::{ConfigLoader, LogFormat};
+
+/// REST and WebSocket API server for Redis
+///
+/// Settings are read from the configuration file, then environment variables,
+/// then these flags, each overriding the ones before.
+#[derive(Debug, Parser)]
+#[command(name = "dbx-redis-api", version)]
+pub struct Cli {
+    /// TOML or YAML configuration file
+    #[arg(short, long, env = "DBX_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Print the effective configuration, with secrets redacted, and exit
+    #[arg(long)]
+    pub print_config: bool,
+
+    /// Database connection URL, e.g. redis://localhost:6379 or memory://
+    #[arg(long, alias = "redis-url")]
+    pub database_url: Option<String>,
+
+    /// Address to listen on
+    #[arg(long)]
+    pub host: Option<String>,
+
+    /// Port to listen on
+    #[arg(short, long)]
+    pub port: Option<u16>,
+
+    /// Connection pool size
+    #[arg(long)]
+    pub pool_size: Option<u32>,
+
+    /// Named backend as NAME=URL, served under /redis/NAME; repeat for several
+    #[arg(long = "backend", value_name = "NAME=URL", value_parser = backend_pair)]
+    pub backends: Vec<(String, String)>,
+
+    /// PEM certificate chain to serve HTTPS with
+    #[arg(long)]
+    pub tls_cert: Option<String>,
+
+    /// PEM private key to serve HTTPS with
+    #[arg(long)]
+    pub tls_key: Option<String>,
+
+    /// PEM CA bundle; clients must present a certificate it signed
+    #[arg(long)]
+    pub tls_client_ca: Option<String>,
+
+    /// PEM CA bundle to verify a rediss:// database with
+    #[arg(long)]
+    pub redis_ca_cert: Option<String>,
+
+    /// JSON file defining the authorization roles
+    #[arg(long)]
+    pub policy_file: Option<String>,
+
+    /// Origin allowed to make cross-origin requests; repeat for several
+    #[arg(long = "cors-origin")]
+    pub cors_origins: Vec<String>,
+
+    /// Reject every write
+    #[arg(long)]
+    pub read_only: bool,
+
+    /// Seconds to wait for in-flight requests and WebSockets on shutdown
+    #[arg(long)]
+    pub shutdown_timeout: Option<u64>,
+
+    /// Requests per client per rate limit window (0 = off)
+    #[arg(long)]
+    pub rate_limit_requests: Option<u32>,
+
+    /// Log level filter, e.g. info or dbx_redis_api=debug
+    #[arg(long)]
+    pub log_level: Option<String>,
+
+    /// Log format: text or json
+    #[arg(long)]
+    pub log_format: Option<LogFormat>,
+}
+
+impl Cli {
+    /// A loader for the configuration these flags describe
+    pub fn loader(&self) -> ConfigLoader {
+        let mut loader = ConfigLoader::new();
+        if let Some(path) = &self.config {
+            loader = loader.file(path);
+        }
+        if let Some(url) = &self.database_url {
+            loader = loader.set("database_url", url.as_str());
+        }
+        if let Some(host) = &self.host {
+            loader = loader.set("host", host.as_str());
+        }
+        if let Some(port) = self.port {
+            loader = loader.set("port", port);
+        }
+        if let Some(size) = self.pool_size {
+            loader = loader.set("pool_size", size);
+        }
+        for (name, url) in &self.backends {
+            loader = loader.set(&format!("backends.{name}.database_url"), url.as_str());
+        }
+        if let Some(path) = &self.tls_cert {
+            loader = loader.set("tls.cert_path", path.as_str());
+        }
+        if let Some(path) = &self.tls_key {
+            loader = loader.set("tls.key_path", path.as_str());
+        }
+        if let Some(path) = &self.tls_client_ca {
+            loader = loader.set("tls.client_ca_path", path.as_str());
+        }
+        if let Some(path) = &self.redis_ca_cert {
+            loader = loader.set("redis_tls.ca_cert_path", path.as_str());
+        }
+        if let Some(path) = &self.policy_file {
+            loader = loader.set("policy_file", path.as_str());
+        }
+        if !self.cors_origins.is_empty() {
+            loader = loader.set("cors.allowed_origins", self.cors_origins.clone());
+        }
+        if self.read_only {
+            loader = loader.set("read_only", true);
+        }
+        if let Some(secs) = self.shutdown_timeout {
+            loader = loader.set("shutdown_timeout_secs", secs);
+        }
+        if let Some(requests) = self.rate_limit_requests {
+            loader = loader.set("rate_limit.requests", requests);
+        }
+        if let Some(level) = &self.log_level {
+            loader = loader.set("logging.level", level.as_str());
+        }
+        if let Some(format) = self.log_format {
+            loader = loader.set("logging.format", format.to_string());
+        }
+        loader
+    }
+}
+
+fn backend_pair(raw: &str) -> Result<(String, String), String> {
+    raw.split_once('=')
+        .map(|(name, url)| (name.to_string(), url.to_string()))
+        .ok_or_else(|| format!("expected NAME=URL, got `{raw}`"))
+}
@@ -13,4 +13,25 @@ impl Defaults {
 
     /// Default connection pool size
     pub const POOL_SIZE: u32 = 10;
+
+    /// Name the backend at `database_url` is listed under
+    pub const BACKEND: &'static str = "default";
+
+    /// Default rate limiting window in seconds
+    pub const RATE_LIMIT_WINDOW_SECS: u64 = 60;
+
+    /// How often the configuration file is checked for changes, in seconds
+    pub const CONFIG_POLL_SECS: u64 = 2;
+
+    /// Default time shutdown waits for in-flight requests and WebSockets, in seconds
+    pub const SHUTDOWN_TIMEOUT_SECS: u64 = 30;
+
+    /// Time a client gets to finish the TLS handshake, in seconds
+    pub const TLS_HANDSHAKE_TIMEOUT_SECS: u64 = 10;
+
+    /// Default log level filter
+    pub const LOG_LEVEL: &'static str = "info";
+
+    /// Default service name for exported traces
+    pub const SERVICE_NAME: &'static str = "dbx-redis-api";
 }
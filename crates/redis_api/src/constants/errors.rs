@@ -8,6 +8,12 @@ impl ErrorMessages {
     /// Internal server error
     pub const INTERNAL_SERVER_ERROR: &'static str = "Internal server error";
 
+    /// Backend connection or server trouble
+    pub const BACKEND_UNAVAILABLE: &'static str = "Backend unavailable";
+
+    /// Backend did not answer in time
+    pub const BACKEND_TIMEOUT: &'static str = "Backend timed out";
+
     /// Redis ping failed
     pub const REDIS_PING_FAILED: &'static str = "Redis ping failed";
 
@@ -16,4 +22,26 @@ impl ErrorMessages {
 
     /// Failed to create Redis client
     pub const REDIS_CLIENT_CREATION_FAILED: &'static str = "Failed to create Redis client";
+
+    /// MDBX URL without an environment path
+    pub const MDBX_PATH_MISSING: &'static str = "MDBX URL must include a path, e.g. mdbx://./data";
+
+    /// SQLite URL without a database file path
+    pub const SQLITE_PATH_MISSING: &'static str =
+        "SQLite URL must include a file path, e.g. sqlite://./dbx.db";
+
+    /// MDBX URL on a build without the `mdbx` feature
+    pub const MDBX_NOT_ENABLED: &'static str =
+        "MDBX support is not enabled; rebuild with `--features mdbx`";
+
+    /// Roles configured without any way to authenticate
+    pub const POLICY_WITHOUT_AUTH: &'static str =
+        "Authorization roles need API keys or JWT authentication to be configured";
+
+    /// Health checked while the server drains connections to stop
+    pub const SHUTTING_DOWN: &'static str = "Server is shutting down";
+
+    /// Reload requested on a server given its configuration directly
+    pub const RELOAD_NOT_ENABLED: &'static str =
+        "Reload is not enabled: the server was not started from configuration sources";
 }
@@ -0,0 +1,255 @@
+//! API errors
+//!
+//! Handlers fail with an [`ApiError`]: a stable [`ErrorCode`] clients can match
+//! on and a message for people. Over HTTP it is sent in the [`ApiResponse`]
+//! envelope with the status for its code; WebSocket handlers send it as an
+//! `error` message.
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use dbx_adapter::error::{AdapterError, ConnectionError, OperationError};
+use dbx_adapter::memory::store::WRONGTYPE;
+use redis::{ErrorKind, RedisError};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::AuthError, constants::errors::ErrorMessages, metrics::record_redis_error,
+    models::ApiResponse, policy::AccessDenied, rate_limit::RateLimited,
+};
+
+/// Machine-readable kinds of API error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// The request or its arguments were rejected
+    InvalidArgument,
+    /// The request did not carry valid credentials
+    Unauthorized,
+    /// The caller may not perform the operation
+    Forbidden,
+    /// A script or other named resource does not exist
+    NotFound,
+    /// The route does not support the request method
+    MethodNotAllowed,
+    /// The key holds a different kind of value than the operation needs
+    WrongType,
+    /// The operation conflicts with the current state, e.g. an aborted transaction
+    Conflict,
+    /// The caller is over its rate limit
+    RateLimited,
+    /// The backend cannot serve requests right now
+    Unavailable,
+    /// The backend did not answer in time
+    Timeout,
+    /// Anything else; details are logged rather than returned
+    Internal,
+}
+
+impl ErrorCode {
+    /// HTTP status for errors of this kind
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::InvalidArgument => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            ErrorCode::WrongType | ErrorCode::Conflict => StatusCode::CONFLICT,
+            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// An error returned to API clients
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[error("{message}")]
+pub struct ApiError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// A request the handler rejects before calling the backend
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidArgument, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Conflict, message)
+    }
+
+    /// HTTP status for this error
+    pub fn status(&self) -> StatusCode {
+        self.code.status()
+    }
+
+    /// An error whose details stay in the logs
+    fn internal(error: impl std::fmt::Display) -> Self {
+        tracing::error!("Internal error: {}", error);
+        Self::new(ErrorCode::Internal, ErrorMessages::INTERNAL_SERVER_ERROR)
+    }
+
+    fn unavailable(error: impl std::fmt::Display) -> Self {
+        tracing::warn!("Backend unavailable: {}", error);
+        Self::new(ErrorCode::Unavailable, ErrorMessages::BACKEND_UNAVAILABLE)
+    }
+
+    fn timeout(error: impl std::fmt::Display) -> Self {
+        tracing::warn!("Backend timed out: {}", error);
+        Self::new(ErrorCode::Timeout, ErrorMessages::BACKEND_TIMEOUT)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status(), ApiResponse::<()>::error(self)).into_response()
+    }
+}
+
+impl From<RedisError> for ApiError {
+    /// Count the failed Redis call and classify it
+    ///
+    /// Errors the caller can fix keep Redis' own message; connection and
+    /// server trouble gets a generic one.
+    fn from(error: RedisError) -> Self {
+        record_redis_error(&error);
+        match error.kind() {
+            ErrorKind::IoError if error.is_timeout() => Self::timeout(error),
+            ErrorKind::IoError
+            | ErrorKind::AuthenticationFailed
+            | ErrorKind::BusyLoadingError
+            | ErrorKind::TryAgain
+            | ErrorKind::ClusterDown
+            | ErrorKind::MasterDown
+            | ErrorKind::Moved
+            | ErrorKind::Ask
+            | ErrorKind::ReadOnly => Self::unavailable(error),
+            ErrorKind::ResponseError | ErrorKind::CrossSlot => {
+                Self::invalid_argument(error.to_string())
+            }
+            ErrorKind::NoScriptError => Self::not_found(error.to_string()),
+            ErrorKind::ExecAbortError | ErrorKind::NotBusy => Self::conflict(error.to_string()),
+            ErrorKind::ExtensionError => match error.code() {
+                Some("WRONGTYPE") => Self::new(ErrorCode::WrongType, error.to_string()),
+                Some("OOM") | Some("BUSY") => Self::unavailable(error),
+                _ => Self::internal(error),
+            },
+            _ => Self::internal(error),
+        }
+    }
+}
+
+impl From<AdapterError> for ApiError {
+    fn from(error: AdapterError) -> Self {
+        match error {
+            AdapterError::Connection(ConnectionError::Timeout(_))
+            | AdapterError::Operation(OperationError::Timeout(_)) => Self::timeout(error),
+            AdapterError::Connection(_) => Self::unavailable(error),
+            AdapterError::Operation(OperationError::KeyNotFound(_)) => {
+                Self::not_found(error.to_string())
+            }
+            // Raised by the in-process and MDBX adapters with Redis' message
+            AdapterError::Operation(OperationError::Failed(message)) if message == WRONGTYPE => {
+                Self::new(ErrorCode::WrongType, message)
+            }
+            AdapterError::Operation(
+                OperationError::InvalidKey(_)
+                | OperationError::InvalidValue(_)
+                | OperationError::Unsupported(_),
+            ) => Self::invalid_argument(error.to_string()),
+            _ => Self::internal(error),
+        }
+    }
+}
+
+impl From<AccessDenied> for ApiError {
+    fn from(error: AccessDenied) -> Self {
+        Self::new(ErrorCode::Forbidden, error.to_string())
+    }
+}
+
+impl From<AuthError> for ApiError {
+    fn from(error: AuthError) -> Self {
+        Self::new(ErrorCode::Unauthorized, error.to_string())
+    }
+}
+
+impl From<RateLimited> for ApiError {
+    fn from(error: RateLimited) -> Self {
+        Self::new(ErrorCode::RateLimited, error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redis_error_codes() {
+        let wrong_type: ApiError = redis::parse_redis_value(
+            b"-WRONGTYPE Operation against a key holding the wrong kind of value\r\n",
+        )
+        .unwrap_err()
+        .into();
+        assert_eq!(wrong_type.code, ErrorCode::WrongType);
+        assert_eq!(wrong_type.status(), StatusCode::CONFLICT);
+
+        let bad_ttl: ApiError =
+            RedisError::from((ErrorKind::ResponseError, "invalid expire time")).into();
+        assert_eq!(bad_ttl.code, ErrorCode::InvalidArgument);
+        assert!(bad_ttl.message.contains("invalid expire time"));
+
+        let no_script: ApiError = RedisError::from((ErrorKind::NoScriptError, "NOSCRIPT")).into();
+        assert_eq!(no_script.status(), StatusCode::NOT_FOUND);
+
+        let refused: ApiError =
+            RedisError::from(std::io::Error::from(std::io::ErrorKind::ConnectionRefused)).into();
+        assert_eq!(refused.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(refused.message, ErrorMessages::BACKEND_UNAVAILABLE);
+
+        let timed_out: ApiError =
+            RedisError::from(std::io::Error::from(std::io::ErrorKind::TimedOut)).into();
+        assert_eq!(timed_out.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        let internal: ApiError = RedisError::from((ErrorKind::TypeError, "bad response")).into();
+        assert_eq!(internal.code, ErrorCode::Internal);
+        assert_eq!(internal.message, ErrorMessages::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_adapter_error_codes() {
+        let wrong_type: ApiError =
+            AdapterError::Operation(OperationError::Failed(WRONGTYPE.into())).into();
+        assert_eq!(wrong_type.code, ErrorCode::WrongType);
+        assert_eq!(wrong_type.message, WRONGTYPE);
+
+        let failed: ApiError =
+            AdapterError::Operation(OperationError::Failed("disk full".into())).into();
+        assert_eq!(failed.code, ErrorCode::Internal);
+    }
+
+    #[test]
+    fn test_error_code_serialization() {
+        let error = ApiError::new(ErrorCode::WrongType, "wrong type");
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({"code": "WRONG_TYPE", "message": "wrong type"})
+        );
+    }
+}
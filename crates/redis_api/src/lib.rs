@@ -1,6 +1,17 @@
+pub mod auth;
+pub mod cli;
 pub mod config;
 pub mod constants;
+pub mod error;
+pub mod logging;
+pub mod metrics;
 pub mod middleware;
 pub mod models;
+pub mod policy;
+pub mod rate_limit;
+pub mod reload;
 pub mod routes;
 pub mod server;
+pub mod shutdown;
+pub mod telemetry;
+pub mod tls;
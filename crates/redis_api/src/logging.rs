@@ -0,0 +1,268 @@
+//! Logging, request IDs and value redaction
+//!
+//! Logs go to stdout as text or, for log aggregators, as one JSON object per
+//! line that includes the spans it was written in. Every HTTP request runs in
+//! a `request` span carrying its request ID, taken from the `X-Request-Id`
+//! header when the client sends a usable one and echoed on the response.
+//! WebSocket connections add a `ws` span, and each message they receive a
+//! `message` span with its own ID. Redis calls are traced as `redis` spans at
+//! debug level with the command and key names, never the values. Spans are
+//! logged when they close, with their fields and timing. With value redaction
+//! on, message payloads are replaced by a placeholder wherever they would be
+//! logged.
+
+use axum::http::HeaderValue;
+use serde::Deserialize;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::{Duration, Instant};
+use tracing::Span;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::config::{LogFormat, LoggingConfig};
+use crate::telemetry::Telemetry;
+
+/// Header carrying the request ID
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied request ID that is kept
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Placeholder logged in place of redacted values
+const REDACTED: &str = "[redacted]";
+
+static REDACT_VALUES: AtomicBool = AtomicBool::new(false);
+
+/// Swaps the log filter; unset when `RUST_LOG` pins it
+static LEVEL: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Install the global subscriber, exporting spans through `telemetry` if given
+///
+/// `RUST_LOG` overrides the configured level when set; otherwise the level can
+/// be changed later with [`reconfigure`]. Span export has its own filter, so
+/// Redis calls are exported even when they are not logged.
+pub fn init(config: &LoggingConfig, telemetry: Option<&Telemetry>) -> anyhow::Result<()> {
+    REDACT_VALUES.store(config.redact_values, Ordering::Relaxed);
+
+    let (filter, level) = match EnvFilter::try_from_default_env() {
+        Ok(filter) => (reload::Layer::new(filter).0, None),
+        Err(_) => {
+            let (filter, level) = reload::Layer::new(parse_level(&config.level)?);
+            (filter, Some(level))
+        }
+    };
+    let output = match config.format {
+        LogFormat::Text => tracing_subscriber::fmt::layer()
+            .with_span_events(FmtSpan::CLOSE)
+            .boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_span_list(true)
+            .with_span_events(FmtSpan::CLOSE)
+            .boxed(),
+    };
+
+    tracing_subscriber::registry()
+        .with(output.with_filter(filter))
+        .with(telemetry.map(Telemetry::layer))
+        .try_init()
+        .map_err(|e| anyhow::anyhow!("Failed to initialize logging: {}", e))?;
+    if let Some(level) = level {
+        let _ = LEVEL.set(level);
+    }
+    Ok(())
+}
+
+/// Apply a new level and redaction setting to the running subscriber
+///
+/// The level is left alone when `RUST_LOG` is set or logging was never
+/// initialized; returns whether it was applied.
+pub fn reconfigure(config: &LoggingConfig) -> anyhow::Result<bool> {
+    let filter = parse_level(&config.level)?;
+    REDACT_VALUES.store(config.redact_values, Ordering::Relaxed);
+    match LEVEL.get() {
+        Some(level) => {
+            level
+                .reload(filter)
+                .map_err(|e| anyhow::anyhow!("Failed to change log level: {}", e))?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+fn parse_level(level: &str) -> anyhow::Result<EnvFilter> {
+    EnvFilter::try_new(level).map_err(|e| anyhow::anyhow!("Invalid log level {}: {}", level, e))
+}
+
+/// Logs the wrapped value, or a placeholder when values are redacted
+pub struct Redacted<T>(T);
+
+/// Wrap a value that may hold user data before logging it
+pub fn redact<T>(value: T) -> Redacted<T> {
+    Redacted(value)
+}
+
+impl<T: fmt::Display> fmt::Display for Redacted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if REDACT_VALUES.load(Ordering::Relaxed) {
+            f.write_str(REDACTED)
+        } else {
+            self.0.fmt(f)
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Redacted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if REDACT_VALUES.load(Ordering::Relaxed) {
+            f.write_str(REDACTED)
+        } else {
+            self.0.fmt(f)
+        }
+    }
+}
+
+/// The client's request ID if it is usable, otherwise a new one
+///
+/// Client IDs must be short printable ASCII so they can be echoed in a header
+/// and logged as is.
+pub fn request_id(header: Option<&HeaderValue>) -> String {
+    header
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| is_valid_request_id(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.bytes().all(|byte| byte.is_ascii_graphic())
+}
+
+tokio::task_local! {
+    static CURRENT_REQUEST: RequestContext;
+}
+
+/// The HTTP request the current task is serving
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// The request's ID
+    pub id: String,
+    started: Instant,
+}
+
+impl RequestContext {
+    /// Time since the request was received
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+/// Serve a request, making it the current request while `future` runs
+pub async fn with_request<F: Future>(id: String, future: F) -> F::Output {
+    let context = RequestContext {
+        id,
+        started: Instant::now(),
+    };
+    CURRENT_REQUEST.scope(context, future).await
+}
+
+/// The request being served, if called while serving one
+pub fn current_request() -> Option<RequestContext> {
+    CURRENT_REQUEST.try_with(RequestContext::clone).ok()
+}
+
+/// Spans for one WebSocket connection
+///
+/// The connection's `ws` span is a child of the upgrade request's span. Each
+/// message gets a `message` span below it, identified by the message's
+/// `request_id` field if it has a usable one.
+#[derive(Clone)]
+pub struct WsSpans {
+    connection: Span,
+    current: Arc<Mutex<Span>>,
+}
+
+impl WsSpans {
+    /// Open the span for a connection to `endpoint`
+    pub fn new(endpoint: &'static str) -> Self {
+        let connection = tracing::info_span!("ws", endpoint);
+        Self {
+            current: Arc::new(Mutex::new(connection.clone())),
+            connection,
+        }
+    }
+
+    /// Run the socket loop, each poll inside the current message's span
+    pub fn run<F: Future>(self, future: F) -> impl Future<Output = F::Output> {
+        let mut future = Box::pin(future);
+        std::future::poll_fn(move |cx| {
+            let span = self
+                .current
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone();
+            let _entered = span.enter();
+            future.as_mut().poll(cx)
+        })
+    }
+
+    /// Switch to a new span for a received message
+    ///
+    /// Must be awaited before handling the message: the socket loop moves into
+    /// the new span the next time it is polled.
+    pub async fn start_message(&self, text: &str) {
+        #[derive(Deserialize)]
+        struct Tagged {
+            request_id: Option<String>,
+        }
+
+        let message_id = serde_json::from_str::<Tagged>(text)
+            .ok()
+            .and_then(|tagged| tagged.request_id)
+            .filter(|id| is_valid_request_id(id))
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let span =
+            tracing::info_span!(parent: &self.connection, "message", message_id = %message_id);
+        *self.current.lock().unwrap_or_else(PoisonError::into_inner) = span;
+        tokio::task::yield_now().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id_from_header() {
+        let header = HeaderValue::from_static("client-id-42");
+        assert_eq!(request_id(Some(&header)), "client-id-42");
+
+        let generated = request_id(None);
+        assert!(uuid::Uuid::parse_str(&generated).is_ok());
+    }
+
+    #[test]
+    fn test_unusable_request_ids_are_replaced() {
+        for id in ["", "has space", &"x".repeat(MAX_REQUEST_ID_LEN + 1)] {
+            let header = HeaderValue::from_str(id).unwrap();
+            assert_ne!(request_id(Some(&header)), id);
+        }
+    }
+
+    #[test]
+    fn test_redaction() {
+        assert_eq!(redact("secret").to_string(), "secret");
+        REDACT_VALUES.store(true, Ordering::Relaxed);
+        assert_eq!(redact("secret").to_string(), REDACTED);
+        assert_eq!(format!("{:?}", redact(vec!["secret"])), REDACTED);
+        REDACT_VALUES.store(false, Ordering::Relaxed);
+    }
+}
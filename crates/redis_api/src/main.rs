@@ -1,33 +1,31 @@
-use std::net::SocketAddr;
+use clap::Parser;
 
-use dbx_redis_api::{config::Config, constants::defaults::Defaults, server::Server};
+use dbx_redis_api::{cli::Cli, logging, server::Server, telemetry::Telemetry};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Load environment variables from .env file
     dotenv::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::fmt::init();
-
-    // Load configuration from environment variables
-    let config = Config {
-        database_url: std::env::var("REDIS_URL")
-            .unwrap_or_else(|_| Defaults::REDIS_URL.to_string()),
-        host: std::env::var("HOST").unwrap_or_else(|_| Defaults::HOST.to_string()),
-        port: std::env::var("PORT")
-            .unwrap_or_else(|_| Defaults::PORT.to_string())
-            .parse()
-            .unwrap_or(Defaults::PORT),
-        pool_size: std::env::var("POOL_SIZE")
-            .unwrap_or_else(|_| Defaults::POOL_SIZE.to_string())
-            .parse()
-            .unwrap_or(Defaults::POOL_SIZE),
-    };
-
-    // Create and run server
-    let server = Server::new(config.clone()).await?;
-    let addr = format!("{}:{}", config.host, config.port).parse::<SocketAddr>()?;
+    // Defaults, then the config file, environment variables and flags;
+    // invalid values stop the server here with every problem listed
+    let cli = Cli::parse();
+    let loader = cli.loader();
+    let config = loader.load()?;
+
+    if cli.print_config {
+        print!("{}", toml::to_string_pretty(&config.redacted())?);
+        return Ok(());
+    }
+
+    // Initialize tracing; spans still buffered are exported when this is dropped
+    let telemetry = Telemetry::from_config(&config.telemetry)?;
+    logging::init(&config.logging, telemetry.as_ref())?;
+
+    // Create and run server, reloading from the same sources on SIGHUP or
+    // when the config file changes
+    let addr = config.listen_addr()?;
+    let server = Server::new(config).await?.with_reload(loader);
 
     server.run(addr).await?;
 
@@ -0,0 +1,325 @@
+//! Prometheus metrics
+//!
+//! Metrics are kept process-wide and served in the text exposition format at
+//! `/metrics`. HTTP requests are recorded by [`track_requests`] under their
+//! route pattern, WebSocket traffic by the socket loops through
+//! [`ws_connection`] and [`ws_message`], and pool checkouts through each
+//! pool's checkout observer. Pool and Redis server gauges are labelled by
+//! backend and refreshed on every scrape, the latter from `INFO` over a
+//! connection checked out of the backend's pool.
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use prometheus::{
+    Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use std::sync::{Arc, LazyLock};
+use std::time::Instant;
+
+use dbx_adapter::redis::async_primitives::admin::AsyncAdminOperations;
+use dbx_adapter::redis::client::RedisPool;
+
+use crate::routes::backends::Backend;
+
+/// Checkout waits are usually well under a millisecond
+const CHECKOUT_WAIT_BUCKETS: &[f64] =
+    &[0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0];
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// Every metric the server exports
+struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    ws_connections: IntGaugeVec,
+    ws_messages: IntCounterVec,
+    ws_message_duration: HistogramVec,
+    pool_checkout_wait: HistogramVec,
+    pool_connections: IntGaugeVec,
+    redis_errors: IntCounterVec,
+    redis_connected_clients: IntGaugeVec,
+    redis_used_memory: IntGaugeVec,
+    redis_keyspace_hit_rate: GaugeVec,
+    redis_commands_processed: IntGaugeVec,
+    redis_uptime: IntGaugeVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry =
+            Registry::new_custom(Some("dbx".to_string()), None).expect("valid metrics namespace");
+        let metrics = Self {
+            http_requests: IntCounterVec::new(
+                Opts::new("http_requests_total", "HTTP requests by route and status"),
+                &["method", "route", "status"],
+            )
+            .unwrap(),
+            http_request_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "http_request_duration_seconds",
+                    "HTTP request latency by route",
+                ),
+                &["method", "route"],
+            )
+            .unwrap(),
+            ws_connections: IntGaugeVec::new(
+                Opts::new("ws_connections", "Open WebSocket connections by endpoint"),
+                &["endpoint"],
+            )
+            .unwrap(),
+            ws_messages: IntCounterVec::new(
+                Opts::new(
+                    "ws_messages_total",
+                    "WebSocket messages handled by endpoint and message type",
+                ),
+                &["endpoint", "type"],
+            )
+            .unwrap(),
+            ws_message_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "ws_message_duration_seconds",
+                    "WebSocket message handling latency by endpoint and message type",
+                ),
+                &["endpoint", "type"],
+            )
+            .unwrap(),
+            pool_checkout_wait: HistogramVec::new(
+                HistogramOpts::new(
+                    "redis_pool_checkout_wait_seconds",
+                    "Time spent waiting for a Redis connection by backend",
+                )
+                .buckets(CHECKOUT_WAIT_BUCKETS.to_vec()),
+                &["backend"],
+            )
+            .unwrap(),
+            pool_connections: IntGaugeVec::new(
+                Opts::new(
+                    "redis_pool_connections",
+                    "Pooled Redis connections by backend and state",
+                ),
+                &["backend", "state"],
+            )
+            .unwrap(),
+            redis_errors: IntCounterVec::new(
+                Opts::new("redis_errors_total", "Failed Redis commands by error kind"),
+                &["kind"],
+            )
+            .unwrap(),
+            redis_connected_clients: IntGaugeVec::new(
+                Opts::new(
+                    "redis_connected_clients",
+                    "Clients connected to each backend's Redis server",
+                ),
+                &["backend"],
+            )
+            .unwrap(),
+            redis_used_memory: IntGaugeVec::new(
+                Opts::new(
+                    "redis_used_memory_bytes",
+                    "Memory used by each backend's Redis server",
+                ),
+                &["backend"],
+            )
+            .unwrap(),
+            redis_keyspace_hit_rate: GaugeVec::new(
+                Opts::new(
+                    "redis_keyspace_hit_rate",
+                    "Share of key lookups that found a key, from 0 to 1, by backend",
+                ),
+                &["backend"],
+            )
+            .unwrap(),
+            redis_commands_processed: IntGaugeVec::new(
+                Opts::new(
+                    "redis_commands_processed",
+                    "Commands processed by each backend's Redis server since it started",
+                ),
+                &["backend"],
+            )
+            .unwrap(),
+            redis_uptime: IntGaugeVec::new(
+                Opts::new("redis_uptime_seconds", "Redis server uptime by backend"),
+                &["backend"],
+            )
+            .unwrap(),
+            registry,
+        };
+
+        let collectors: [Box<dyn prometheus::core::Collector>; 13] = [
+            Box::new(metrics.http_requests.clone()),
+            Box::new(metrics.http_request_duration.clone()),
+            Box::new(metrics.ws_connections.clone()),
+            Box::new(metrics.ws_messages.clone()),
+            Box::new(metrics.ws_message_duration.clone()),
+            Box::new(metrics.pool_checkout_wait.clone()),
+            Box::new(metrics.pool_connections.clone()),
+            Box::new(metrics.redis_errors.clone()),
+            Box::new(metrics.redis_connected_clients.clone()),
+            Box::new(metrics.redis_used_memory.clone()),
+            Box::new(metrics.redis_keyspace_hit_rate.clone()),
+            Box::new(metrics.redis_commands_processed.clone()),
+            Box::new(metrics.redis_uptime.clone()),
+        ];
+        for collector in collectors {
+            metrics
+                .registry
+                .register(collector)
+                .expect("metrics are registered once");
+        }
+        metrics
+    }
+}
+
+/// Record request counts and latency under the matched route
+///
+/// Runs outside authentication and rate limiting so rejected requests are
+/// counted too.
+pub async fn track_requests(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    METRICS
+        .http_request_duration
+        .with_label_values(&[method.as_str(), route.as_str()])
+        .observe(started.elapsed().as_secs_f64());
+    METRICS
+        .http_requests
+        .with_label_values(&[method.as_str(), route.as_str(), response.status().as_str()])
+        .inc();
+    response
+}
+
+/// Count a failed Redis command
+pub fn record_redis_error(error: &redis::RedisError) {
+    METRICS
+        .redis_errors
+        .with_label_values(&[&format!("{:?}", error.kind())])
+        .inc();
+}
+
+/// Feed the pool's checkout waits into the wait histogram under `backend`
+pub fn observe_pool(pool: &RedisPool, backend: &str) {
+    let histogram = METRICS.pool_checkout_wait.with_label_values(&[backend]);
+    pool.set_checkout_observer(Arc::new(move |wait| histogram.observe(wait.as_secs_f64())));
+}
+
+/// Counts an open WebSocket connection until dropped
+pub struct WsConnection {
+    endpoint: &'static str,
+}
+
+impl Drop for WsConnection {
+    fn drop(&mut self) {
+        METRICS
+            .ws_connections
+            .with_label_values(&[self.endpoint])
+            .dec();
+    }
+}
+
+/// Count a WebSocket connection to `endpoint` as open for the guard's lifetime
+pub fn ws_connection(endpoint: &'static str) -> WsConnection {
+    METRICS.ws_connections.with_label_values(&[endpoint]).inc();
+    WsConnection { endpoint }
+}
+
+/// Records a WebSocket message and how long it took to handle when dropped
+pub struct WsMessage {
+    endpoint: &'static str,
+    message_type: &'static str,
+    started: Instant,
+}
+
+impl Drop for WsMessage {
+    fn drop(&mut self) {
+        let labels = [self.endpoint, self.message_type];
+        METRICS.ws_messages.with_label_values(&labels).inc();
+        METRICS
+            .ws_message_duration
+            .with_label_values(&labels)
+            .observe(self.started.elapsed().as_secs_f64());
+    }
+}
+
+/// Start timing a message of type `message_type` on `endpoint`
+pub fn ws_message(endpoint: &'static str, message_type: &'static str) -> WsMessage {
+    WsMessage {
+        endpoint,
+        message_type,
+        started: Instant::now(),
+    }
+}
+
+/// Serve every metric in the Prometheus text format
+///
+/// The gauges of every backend are refreshed first, concurrently.
+pub async fn serve_metrics(backends: Arc<[Backend]>) -> Response {
+    futures::future::join_all(backends.iter().map(refresh_backend_gauges)).await;
+
+    let mut body = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&METRICS.registry.gather(), &mut body) {
+        tracing::error!("Failed to encode metrics: {}", e);
+    }
+    ([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], body).into_response()
+}
+
+async fn refresh_backend_gauges(backend: &Backend) {
+    let name = backend.name.as_str();
+    let usage = backend.pool.metrics();
+    METRICS
+        .pool_connections
+        .with_label_values(&[name, "idle"])
+        .set(usage.idle.into());
+    METRICS
+        .pool_connections
+        .with_label_values(&[name, "in_use"])
+        .set(usage.in_use.into());
+
+    // Keep the last known values when Redis can't be reached
+    let status = match backend.pool.get_multiplexed_connection().await {
+        Ok(conn) => AsyncAdminOperations::new(conn).server_status().await,
+        Err(e) => Err(e),
+    };
+    match status {
+        Ok(status) => {
+            let labels = [name];
+            METRICS
+                .redis_connected_clients
+                .with_label_values(&labels)
+                .set(status.connected_clients);
+            METRICS
+                .redis_used_memory
+                .with_label_values(&labels)
+                .set(status.used_memory);
+            METRICS
+                .redis_keyspace_hit_rate
+                .with_label_values(&labels)
+                .set(status.hit_rate() / 100.0);
+            METRICS
+                .redis_commands_processed
+                .with_label_values(&labels)
+                .set(status.total_commands_processed);
+            METRICS
+                .redis_uptime
+                .with_label_values(&labels)
+                .set(status.uptime_seconds);
+        }
+        Err(e) => {
+            record_redis_error(&e);
+            tracing::warn!(backend = name, "Failed to refresh Redis metrics: {}", e);
+        }
+    }
+}
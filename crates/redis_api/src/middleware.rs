@@ -1,36 +1,177 @@
 use axum::{
-    extract::rejection::JsonRejection, http::StatusCode, response::IntoResponse, response::Json,
+    extract::{rejection::JsonRejection, MatchedPath, Request, State},
+    http::{header, HeaderName, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
 };
+use std::sync::Arc;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 
-use crate::{constants::errors::ErrorMessages, models::ApiResponse};
+use crate::{
+    auth::{credential_from_request, AuthError, Principal},
+    error::ApiError,
+    logging::{request_id, with_request, REQUEST_ID_HEADER},
+    policy::Access,
+    rate_limit::client_key,
+    reload::Runtime,
+    telemetry::remote_context,
+};
 
-/// Handle Redis errors and convert them to HTTP responses
-pub fn handle_redis_error(_error: impl std::fmt::Display) -> (StatusCode, Json<ApiResponse<()>>) {
-    (
-        StatusCode::INTERNAL_SERVER_ERROR,
-        Json(ApiResponse::<()>::error(
-            ErrorMessages::INTERNAL_SERVER_ERROR.to_string(),
-        )),
-    )
+/// Answer CORS preflights and tag responses for the allowed origins
+///
+/// Origins are checked against the current [`Runtime`] on every request, so
+/// reloads apply without rebuilding the router. With no origins allowed,
+/// browsers block cross-origin calls.
+pub fn cors(runtime: Arc<Runtime>) -> CorsLayer {
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(move |origin, _| {
+            runtime.current().allows_origin(origin)
+        }))
+        .allow_methods(Any)
+        .allow_headers(Any)
+        .expose_headers([
+            HeaderName::from_static(REQUEST_ID_HEADER),
+            header::RETRY_AFTER,
+        ])
 }
 
 /// Custom error handler for JSON extraction errors
-pub async fn handle_json_rejection(rejection: JsonRejection) -> impl IntoResponse {
-    let (status, error_message) = match rejection {
-        JsonRejection::JsonDataError(_) => (StatusCode::BAD_REQUEST, "Invalid JSON data"),
-        JsonRejection::JsonSyntaxError(_) => (StatusCode::BAD_REQUEST, "Invalid JSON syntax"),
-        JsonRejection::MissingJsonContentType(_) => (
-            StatusCode::BAD_REQUEST,
-            "Missing Content-Type: application/json header",
-        ),
-        JsonRejection::BytesRejection(_) => {
-            (StatusCode::BAD_REQUEST, "Failed to read request body")
+pub async fn handle_json_rejection(rejection: JsonRejection) -> ApiError {
+    let error_message = match rejection {
+        JsonRejection::JsonDataError(_) => "Invalid JSON data",
+        JsonRejection::JsonSyntaxError(_) => "Invalid JSON syntax",
+        JsonRejection::MissingJsonContentType(_) => "Missing Content-Type: application/json header",
+        JsonRejection::BytesRejection(_) => "Failed to read request body",
+        _ => "Invalid request body",
+    };
+    ApiError::invalid_argument(error_message)
+}
+
+/// Reject requests without valid credentials, when credentials are configured
+///
+/// On success the caller's [`Principal`](crate::auth::Principal) is added to
+/// the request extensions. WebSocket upgrades may also carry the credential
+/// in the `token` query parameter.
+pub async fn require_auth(
+    State(runtime): State<Arc<Runtime>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(auth) = runtime.current().auth.clone() else {
+        return next.run(request).await;
+    };
+    let credential = credential_from_request(request.headers(), request.uri());
+
+    let result = match credential {
+        Some(credential) => auth.authenticate(&credential),
+        None => Err(AuthError::MissingCredentials),
+    };
+
+    match result {
+        Ok(principal) => {
+            request.extensions_mut().insert(principal);
+            next.run(request).await
         }
-        _ => (StatusCode::BAD_REQUEST, "Invalid request body"),
+        Err(error) => (
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            ApiError::from(error),
+        )
+            .into_response(),
+    }
+}
+
+/// Resolve the authenticated caller's [`Access`] and add it to the request
+///
+/// Runs inside [`require_auth`]; routes check the access before touching Redis.
+/// Without a policy every caller gets [`Access::unrestricted`]. Either way the
+/// access follows the server's read-only mode.
+pub async fn apply_policy(
+    State(runtime): State<Arc<Runtime>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let access = match &runtime.current().policy {
+        Some(policy) => request
+            .extensions()
+            .get::<Principal>()
+            .map(|principal| policy.access_for(principal))
+            .unwrap_or_else(Access::none),
+        None => Access::unrestricted(),
     };
+    request
+        .extensions_mut()
+        .insert(access.with_read_only(runtime.read_only().clone()));
+    next.run(request).await
+}
+
+/// Reject clients that are over their request limit, when limits are configured
+///
+/// Runs inside [`require_auth`] so authenticated callers are limited by
+/// identity rather than address. Also gives WebSocket upgrades their
+/// [`MessageQuota`](crate::rate_limit::MessageQuota).
+pub async fn rate_limit(
+    State(runtime): State<Arc<Runtime>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(limiter) = runtime.current().rate_limiter.clone() else {
+        return next.run(request).await;
+    };
+    if let Err(limited) = limiter.check(&client_key(&request)) {
+        return (
+            [(header::RETRY_AFTER, limited.retry_after.to_string())],
+            ApiError::from(limited),
+        )
+            .into_response();
+    }
+    request.extensions_mut().insert(limiter.message_quota());
+    next.run(request).await
+}
+
+/// Run the request in a span tagged with its request ID
+///
+/// The ID is taken from the `X-Request-Id` header when the client sends a
+/// usable one and echoed on the response either way; response envelopes
+/// carry it too. The response status is recorded on the span, which is logged
+/// when it closes. A `traceparent` header makes the span part of the caller's
+/// trace.
+pub async fn trace_request(request: Request, next: Next) -> Response {
+    let request_id = request_id(request.headers().get(REQUEST_ID_HEADER));
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+        status = tracing::field::Empty,
+        otel.kind = "server",
+    );
+    // Only fails when spans aren't being exported
+    let _ = span.set_parent(remote_context(request.headers()));
+
+    let mut response = with_request(request_id.clone(), next.run(request))
+        .instrument(span.clone())
+        .await;
+
+    span.record("status", response.status().as_u16());
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
 
-    (
-        status,
-        Json(ApiResponse::<()>::error(error_message.to_string())),
-    )
+/// Run the handler in a span named after the route it matched
+pub async fn trace_handler(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    let span = tracing::info_span!(
+        "handler",
+        route = %route,
+        otel.name = %format!("{} {}", request.method(), route),
+    );
+    next.run(request).instrument(span).await
 }
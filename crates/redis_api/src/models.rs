@@ -1,27 +1,65 @@
+use axum::response::{IntoResponse, Json, Response};
 use serde::{Deserialize, Serialize};
 
-/// API response wrapper
+use crate::error::{ApiError, ErrorCode};
+use crate::logging::current_request;
+
+/// Version of the response envelope, bumped on incompatible changes
+pub const API_VERSION: u32 = 1;
+
+/// Envelope around every REST response
+///
+/// The request ID and timing are filled in when the response is sent.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
+    /// Envelope version, see [`API_VERSION`]
+    pub version: u32,
     pub success: bool,
     pub data: Option<T>,
     pub error: Option<String>,
+    /// Stable code for the error, set whenever `error` is
+    #[serde(default)]
+    pub code: Option<ErrorCode>,
+    /// ID of the request, as echoed in the `X-Request-Id` header
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// Time taken to serve the request, in milliseconds
+    #[serde(default)]
+    pub duration_ms: Option<f64>,
 }
 
 impl<T> ApiResponse<T> {
     pub fn success(data: T) -> Self {
         Self {
+            version: API_VERSION,
             success: true,
             data: Some(data),
             error: None,
+            code: None,
+            request_id: None,
+            duration_ms: None,
         }
     }
 
-    pub fn error(error: String) -> Self {
+    pub fn error(error: ApiError) -> Self {
         Self {
+            version: API_VERSION,
             success: false,
             data: None,
-            error: Some(error),
+            error: Some(error.message),
+            code: Some(error.code),
+            request_id: None,
+            duration_ms: None,
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for ApiResponse<T> {
+    fn into_response(mut self) -> Response {
+        if let Some(request) = current_request() {
+            self.duration_ms = Some(request.elapsed().as_secs_f64() * 1000.0);
+            self.request_id = Some(request.id);
         }
+        Json(self).into_response()
     }
 }
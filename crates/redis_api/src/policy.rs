@@ -0,0 +1,375 @@
+//! Role-based authorization
+//!
+//! A [`Policy`] maps the roles of an authenticated [`Principal`] to the
+//! operations they may perform and the keys they may touch. The resulting
+//! [`Access`] is added to the request extensions and checked by every route
+//! before it calls into Redis; routes served without a policy get
+//! [`Access::unrestricted`].
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::auth::Principal;
+use crate::config::{PolicyConfig, RoleConfig};
+
+/// Kinds of operation a role may be granted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Permission {
+    /// Read keys
+    Read,
+    /// Create, modify and delete keys
+    Write,
+    /// Server administration: config, flushes, stats, script registration
+    Admin,
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Permission::Read => write!(f, "read"),
+            Permission::Write => write!(f, "write"),
+            Permission::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// Authorization failures
+#[derive(Debug, thiserror::Error)]
+pub enum AccessDenied {
+    #[error("Permission denied: {0} access required")]
+    Permission(Permission),
+
+    #[error("Permission denied: {permission} access to {key} not allowed")]
+    Key { permission: Permission, key: String },
+
+    #[error("Server is in read-only mode")]
+    ReadOnly,
+}
+
+/// Server-wide read-only mode, shared with every request and open WebSocket
+#[derive(Debug, Clone, Default)]
+pub struct ReadOnlySwitch(Arc<AtomicBool>);
+
+impl ReadOnlySwitch {
+    pub fn is_on(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, on: bool) {
+        self.0.store(on, Ordering::Relaxed);
+    }
+}
+
+/// Roles by name, resolved against each authenticated principal
+pub struct Policy {
+    roles: HashMap<String, Arc<RoleConfig>>,
+    default_role: Option<String>,
+}
+
+impl Policy {
+    /// Build a policy, or `None` when no roles are configured
+    pub fn from_config(config: &PolicyConfig) -> anyhow::Result<Option<Self>> {
+        if config.roles.is_empty() {
+            return Ok(None);
+        }
+        if let Some(role) = &config.default_role {
+            if !config.roles.contains_key(role) {
+                return Err(anyhow::anyhow!("Unknown default role: {}", role));
+            }
+        }
+
+        Ok(Some(Self {
+            roles: config
+                .roles
+                .iter()
+                .map(|(name, role)| (name.clone(), Arc::new(role.clone())))
+                .collect(),
+            default_role: config.default_role.clone(),
+        }))
+    }
+
+    /// Resolve what a principal may do
+    ///
+    /// Principals with no known role fall back to the default role, or get no
+    /// access at all when there is none.
+    pub fn access_for(&self, principal: &Principal) -> Access {
+        let mut grants: Vec<Arc<RoleConfig>> = principal
+            .roles
+            .iter()
+            .filter_map(|name| self.roles.get(name).cloned())
+            .collect();
+        if grants.is_empty() {
+            grants.extend(
+                self.default_role
+                    .as_ref()
+                    .and_then(|name| self.roles.get(name).cloned()),
+            );
+        }
+        Access {
+            grants: Some(grants.into()),
+            read_only: ReadOnlySwitch::default(),
+        }
+    }
+}
+
+/// What the caller of a request may do
+#[derive(Debug, Clone)]
+pub struct Access {
+    /// `None` when no policy applies
+    grants: Option<Arc<[Arc<RoleConfig>]>>,
+    /// Denies writes while on, whatever the grants
+    read_only: ReadOnlySwitch,
+}
+
+impl Access {
+    /// Access for routes served without a policy
+    pub fn unrestricted() -> Self {
+        Self {
+            grants: None,
+            read_only: ReadOnlySwitch::default(),
+        }
+    }
+
+    /// Access that allows nothing
+    pub fn none() -> Self {
+        Self {
+            grants: Some(Arc::new([])),
+            read_only: ReadOnlySwitch::default(),
+        }
+    }
+
+    /// The same access, with writes denied whenever `switch` is on
+    pub fn with_read_only(self, switch: ReadOnlySwitch) -> Self {
+        Self {
+            read_only: switch,
+            ..self
+        }
+    }
+
+    /// Check read access to every key
+    pub fn read<I>(&self, keys: I) -> Result<(), AccessDenied>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.check(Permission::Read, keys, glob_match)
+    }
+
+    /// Check write access to every key
+    pub fn write<I>(&self, keys: I) -> Result<(), AccessDenied>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.check(Permission::Write, keys, glob_match)
+    }
+
+    /// Check read access to everything each key pattern can match
+    pub fn read_patterns<I>(&self, patterns: I) -> Result<(), AccessDenied>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.check(Permission::Read, patterns, pattern_within)
+    }
+
+    /// Check access to an operation that touches no particular key
+    pub fn require(&self, permission: Permission) -> Result<(), AccessDenied> {
+        self.check(permission, std::iter::empty::<&str>(), glob_match)
+    }
+
+    /// Check access to administrative operations
+    pub fn admin(&self) -> Result<(), AccessDenied> {
+        self.require(Permission::Admin)
+    }
+
+    /// Check the server accepts changes, for administrative operations that
+    /// modify data or server state rather than keys
+    pub fn writable(&self) -> Result<(), AccessDenied> {
+        if self.read_only.is_on() {
+            return Err(AccessDenied::ReadOnly);
+        }
+        Ok(())
+    }
+
+    fn check<I>(
+        &self,
+        permission: Permission,
+        keys: I,
+        allows: fn(&str, &str) -> bool,
+    ) -> Result<(), AccessDenied>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        if permission == Permission::Write {
+            self.writable()?;
+        }
+        let Some(grants) = &self.grants else {
+            return Ok(());
+        };
+        let granted: Vec<&RoleConfig> = grants
+            .iter()
+            .filter(|role| role.permissions.contains(&permission))
+            .map(|role| role.as_ref())
+            .collect();
+        if granted.is_empty() {
+            return Err(AccessDenied::Permission(permission));
+        }
+
+        for key in keys {
+            let key = key.as_ref();
+            let allowed = granted
+                .iter()
+                .any(|role| role.key_patterns.iter().any(|pattern| allows(pattern, key)));
+            if !allowed {
+                return Err(AccessDenied::Key {
+                    permission,
+                    key: key.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Access {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<Access>()
+            .cloned()
+            .unwrap_or_else(Access::unrestricted))
+    }
+}
+
+/// Match a key against a Redis-style glob supporting `*` and `?`
+fn glob_match(pattern: &str, key: &str) -> bool {
+    let (pattern, key) = (pattern.as_bytes(), key.as_bytes());
+    let (mut p, mut k) = (0, 0);
+    let mut backtrack = None;
+    while k < key.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, k));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == key[k] => {
+                p += 1;
+                k += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    k = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Whether every key `requested` can match is also matched by `allowed`
+///
+/// Only exact matches and literal prefixes (`tenant42:*`) are recognised,
+/// which errs on the side of denying.
+fn pattern_within(allowed: &str, requested: &str) -> bool {
+    if allowed == requested || allowed == "*" {
+        return true;
+    }
+    match allowed.strip_suffix('*') {
+        Some(prefix) if !prefix.contains(['*', '?']) => requested.starts_with(prefix),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> Policy {
+        let config: PolicyConfig = serde_json::from_value(serde_json::json!({
+            "roles": {
+                "admin": { "permissions": ["read", "write", "admin"] },
+                "tenant42": {
+                    "permissions": ["read", "write"],
+                    "key_patterns": ["tenant42:*"]
+                },
+                "viewer": { "permissions": ["read"] }
+            },
+            "default_role": "viewer"
+        }))
+        .unwrap();
+        Policy::from_config(&config).unwrap().unwrap()
+    }
+
+    fn principal(roles: &[&str]) -> Principal {
+        Principal {
+            subject: "test".to_string(),
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+            claims: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_key_scoping() {
+        let access = policy().access_for(&principal(&["tenant42"]));
+        assert!(access.write(["tenant42:user:1"]).is_ok());
+        assert!(access.read(["tenant42:a", "tenant42:b"]).is_ok());
+        assert!(matches!(
+            access.read(["tenant42:a", "tenant7:b"]),
+            Err(AccessDenied::Key { .. })
+        ));
+        assert!(access.read_patterns(["tenant42:user:*"]).is_ok());
+        assert!(access.read_patterns(["*"]).is_err());
+        assert!(matches!(
+            access.admin(),
+            Err(AccessDenied::Permission(Permission::Admin))
+        ));
+    }
+
+    #[test]
+    fn test_roles_and_default_role() {
+        let policy = policy();
+        assert!(policy.access_for(&principal(&["admin"])).admin().is_ok());
+
+        let viewer = policy.access_for(&principal(&["unknown"]));
+        assert!(viewer.read(["anything"]).is_ok());
+        assert!(viewer.write(["anything"]).is_err());
+
+        assert!(Access::unrestricted().admin().is_ok());
+        assert!(Access::none().read(["key"]).is_err());
+    }
+
+    #[test]
+    fn test_read_only() {
+        let switch = ReadOnlySwitch::default();
+        let access = Access::unrestricted().with_read_only(switch.clone());
+        assert!(access.write(["key"]).is_ok());
+
+        switch.set(true);
+        assert!(matches!(access.write(["key"]), Err(AccessDenied::ReadOnly)));
+        assert!(matches!(access.writable(), Err(AccessDenied::ReadOnly)));
+        assert!(access.read(["key"]).is_ok());
+        assert!(access.admin().is_ok());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("user:*:name", "user:42:name"));
+        assert!(glob_match("h?llo", "hello"));
+        assert!(!glob_match("h?llo", "heello"));
+        assert!(!glob_match("tenant42:*", "tenant4:x"));
+    }
+}
@@ -0,0 +1,317 @@
+//! Per-client rate limiting
+//!
+//! Each client gets a token bucket holding up to `requests` tokens that
+//! refills evenly over the window, so it may burst up to the limit and then
+//! continue at the average rate. Clients are told apart by JWT subject, API
+//! key or, when they are not authenticated, IP address. Every WebSocket
+//! connection also gets its own bucket for the messages it sends, handed to
+//! the socket as a [`MessageQuota`]. Buckets live in this process, so each
+//! replica enforces its limits separately.
+
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts, Request},
+    http::request::Parts,
+};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::auth::{credential_from_request, Principal};
+use crate::config::RateLimitConfig;
+
+/// Number of tracked clients above which idle buckets are dropped
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Shortest time between two sweeps for idle buckets, so that a flood of new
+/// clients doesn't turn every request into a scan of the whole map
+const PRUNE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A request or message over the limit
+#[derive(Debug, thiserror::Error)]
+#[error("Rate limit exceeded, retry in {retry_after}s")]
+pub struct RateLimited {
+    /// Whole seconds until the next token is available
+    pub retry_after: u64,
+}
+
+/// Refill rate shared by all buckets of one kind
+#[derive(Debug, Clone, Copy)]
+struct Rate {
+    capacity: f64,
+    per_sec: f64,
+}
+
+impl Rate {
+    /// `None` when the limit is zero, i.e. off
+    fn new(limit: u32, window: Duration) -> Option<Self> {
+        (limit > 0).then(|| Self {
+            capacity: limit as f64,
+            per_sec: limit as f64 / window.as_secs_f64(),
+        })
+    }
+
+    fn full_bucket(&self, now: Instant) -> TokenBucket {
+        TokenBucket {
+            tokens: self.capacity,
+            updated: now,
+        }
+    }
+
+    fn refill(&self, bucket: &TokenBucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * self.per_sec).min(self.capacity)
+    }
+
+    /// Take a token from the bucket if one is available
+    fn take(&self, bucket: &mut TokenBucket, now: Instant) -> Result<(), RateLimited> {
+        bucket.tokens = self.refill(bucket, now);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = (1.0 - bucket.tokens) / self.per_sec;
+            Err(RateLimited {
+                retry_after: (wait.ceil() as u64).max(1),
+            })
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Request buckets by client, and when idle ones were last dropped
+struct Buckets {
+    clients: HashMap<String, TokenBucket>,
+    pruned: Instant,
+}
+
+impl Buckets {
+    fn new() -> Self {
+        Self {
+            clients: HashMap::new(),
+            pruned: Instant::now(),
+        }
+    }
+
+    /// Drop buckets that have refilled, at most once per [`PRUNE_INTERVAL`]
+    fn prune(&mut self, rate: &Rate, now: Instant) {
+        if self.clients.len() < PRUNE_THRESHOLD
+            || now.saturating_duration_since(self.pruned) < PRUNE_INTERVAL
+        {
+            return;
+        }
+        // Clients whose bucket has refilled are indistinguishable from new ones
+        self.clients
+            .retain(|_, bucket| rate.refill(bucket, now) < rate.capacity);
+        self.pruned = now;
+    }
+}
+
+/// Request buckets by client, plus the settings for WebSocket message quotas
+pub struct RateLimiter {
+    requests: Option<Rate>,
+    ws_messages: Option<Rate>,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    /// Build a rate limiter, or `None` when no limit is configured
+    pub fn from_config(config: &RateLimitConfig) -> anyhow::Result<Option<Self>> {
+        if !config.is_enabled() {
+            return Ok(None);
+        }
+        if config.window_secs == 0 {
+            return Err(anyhow::anyhow!(
+                "Rate limit window must be at least one second"
+            ));
+        }
+
+        let window = Duration::from_secs(config.window_secs);
+        Ok(Some(Self {
+            requests: Rate::new(config.requests, window),
+            ws_messages: Rate::new(config.ws_messages, window),
+            buckets: Mutex::new(Buckets::new()),
+        }))
+    }
+
+    /// Count a request from `client` against its limit
+    pub fn check(&self, client: &str) -> Result<(), RateLimited> {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: &str, now: Instant) -> Result<(), RateLimited> {
+        let Some(rate) = self.requests else {
+            return Ok(());
+        };
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        if !buckets.clients.contains_key(client) {
+            buckets.prune(&rate, now);
+        }
+
+        let bucket = buckets
+            .clients
+            .entry(client.to_string())
+            .or_insert_with(|| rate.full_bucket(now));
+        rate.take(bucket, now)
+    }
+
+    /// A fresh message quota for a new WebSocket connection
+    pub fn message_quota(&self) -> MessageQuota {
+        MessageQuota {
+            limit: self
+                .ws_messages
+                .map(|rate| (rate, rate.full_bucket(Instant::now()))),
+        }
+    }
+}
+
+/// Identify the client making a request
+///
+/// Authenticated callers are tracked by JWT subject or API key, so that
+/// clients behind one address don't share a limit; anyone else by IP.
+pub fn client_key(request: &Request) -> String {
+    if let Some(principal) = request.extensions().get::<Principal>() {
+        if !principal.claims.is_null() {
+            return format!("sub:{}", principal.subject);
+        }
+        if let Some(credential) = credential_from_request(request.headers(), request.uri()) {
+            return format!("key:{}", credential);
+        }
+    }
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        None => "ip:unknown".to_string(),
+    }
+}
+
+/// Messages a WebSocket connection may still send
+///
+/// Added to the upgrade request by the rate limiting middleware; sockets
+/// served without a limit get [`MessageQuota::unlimited`].
+#[derive(Debug, Clone)]
+pub struct MessageQuota {
+    limit: Option<(Rate, TokenBucket)>,
+}
+
+impl MessageQuota {
+    /// A quota that never runs out
+    pub fn unlimited() -> Self {
+        Self { limit: None }
+    }
+
+    /// Count a received message against the quota
+    pub fn acquire(&mut self) -> Result<(), RateLimited> {
+        match &mut self.limit {
+            Some((rate, bucket)) => rate.take(bucket, Instant::now()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for MessageQuota {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .remove::<MessageQuota>()
+            .unwrap_or_else(MessageQuota::unlimited))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(requests: u32, ws_messages: u32) -> RateLimiter {
+        RateLimiter::from_config(&RateLimitConfig {
+            requests,
+            ws_messages,
+            window_secs: 60,
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_requests_limited_per_client() {
+        let limiter = limiter(2, 0);
+        assert!(limiter.check("ip:10.0.0.1").is_ok());
+        assert!(limiter.check("ip:10.0.0.1").is_ok());
+        let limited = limiter.check("ip:10.0.0.1").unwrap_err();
+        assert!((1..=30).contains(&limited.retry_after));
+
+        assert!(limiter.check("ip:10.0.0.2").is_ok());
+    }
+
+    #[test]
+    fn test_bucket_refills_over_window() {
+        let rate = Rate::new(2, Duration::from_secs(60)).unwrap();
+        let start = Instant::now();
+        let mut bucket = rate.full_bucket(start);
+        assert!(rate.take(&mut bucket, start).is_ok());
+        assert!(rate.take(&mut bucket, start).is_ok());
+        assert_eq!(rate.take(&mut bucket, start).unwrap_err().retry_after, 30);
+
+        let later = start + Duration::from_secs(31);
+        assert!(rate.take(&mut bucket, later).is_ok());
+        assert!(rate.take(&mut bucket, later).is_err());
+    }
+
+    #[test]
+    fn test_idle_buckets_pruned_once_per_interval() {
+        let limiter = limiter(1, 0);
+        let start = Instant::now();
+        {
+            let mut buckets = limiter.buckets.lock().unwrap();
+            buckets.pruned = start;
+            for i in 0..PRUNE_THRESHOLD {
+                buckets.clients.insert(
+                    format!("ip:idle-{i}"),
+                    TokenBucket {
+                        tokens: 1.0,
+                        updated: start,
+                    },
+                );
+            }
+        }
+        let tracked = || limiter.buckets.lock().unwrap().clients.len();
+
+        // New clients right after a sweep don't trigger another
+        assert!(limiter.check_at("ip:10.0.0.1", start).is_ok());
+        assert_eq!(tracked(), PRUNE_THRESHOLD + 1);
+
+        // Once the interval has passed, refilled buckets are dropped and the
+        // client still waiting for its token is kept
+        let later = start + PRUNE_INTERVAL;
+        assert!(limiter.check_at("ip:10.0.0.2", later).is_ok());
+        assert_eq!(tracked(), 2);
+        assert!(limiter.check_at("ip:10.0.0.1", later).is_err());
+    }
+
+    #[test]
+    fn test_message_quota() {
+        let mut quota = limiter(0, 1).message_quota();
+        assert!(quota.acquire().is_ok());
+        assert!(quota.acquire().is_err());
+
+        let mut unlimited = limiter(1, 0).message_quota();
+        assert!((0..100).all(|_| unlimited.acquire().is_ok()));
+    }
+
+    #[test]
+    fn test_disabled_without_limits() {
+        assert!(RateLimiter::from_config(&RateLimitConfig::default())
+            .unwrap()
+            .is_none());
+    }
+}
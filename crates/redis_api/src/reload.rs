@@ -0,0 +1,352 @@
+//! Hot reload of the runtime configuration
+//!
+//! Authentication, roles, rate limits, CORS origins, read-only mode and the
+//! log level and redaction can change while the server runs. They are held in
+//! a [`Runtime`] that the middleware reads on every request, so a reload
+//! applies from the next request on without touching the listener or open
+//! WebSockets. Open sockets keep the identity and message quota they connected
+//! with, but see read-only mode as soon as it changes. Other settings only take
+//! effect on restart; a reload reports them instead of applying them.
+//!
+//! The [`Reloader`] reloads on `SIGHUP`, when the configuration file changes
+//! and on `POST /redis/admin/reload`.
+
+use axum::http::HeaderValue;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+
+use crate::auth::Authenticator;
+use crate::config::{Config, ConfigLoader, CorsConfig};
+use crate::constants::{defaults::Defaults, errors::ErrorMessages};
+use crate::logging;
+use crate::policy::{Policy, ReadOnlySwitch};
+use crate::rate_limit::RateLimiter;
+
+/// Request handling built from the reloadable parts of the configuration
+pub struct RuntimeState {
+    /// Set when credentials are configured; guards every `/redis` and `/redis_ws` route
+    pub auth: Option<Arc<Authenticator>>,
+    /// Set when roles are configured; scopes what authenticated callers may do
+    pub policy: Option<Arc<Policy>>,
+    /// Set when request or WebSocket message limits are configured
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+    /// Origins allowed to make cross-origin requests
+    pub cors: CorsConfig,
+}
+
+impl RuntimeState {
+    pub fn from_config(config: &Config) -> anyhow::Result<Self> {
+        let auth = Authenticator::from_config(&config.auth)?.map(Arc::new);
+        let policy = Policy::from_config(&config.policy)?.map(Arc::new);
+        if policy.is_some() && auth.is_none() {
+            return Err(anyhow::anyhow!(ErrorMessages::POLICY_WITHOUT_AUTH));
+        }
+        let rate_limiter = RateLimiter::from_config(&config.rate_limit)?.map(Arc::new);
+
+        Ok(Self {
+            auth,
+            policy,
+            rate_limiter,
+            cors: config.cors.clone(),
+        })
+    }
+
+    /// Whether `origin` may make cross-origin requests
+    pub fn allows_origin(&self, origin: &HeaderValue) -> bool {
+        self.cors
+            .allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.as_bytes() == origin.as_bytes())
+    }
+}
+
+/// The current [`RuntimeState`], replaced whole on reload, and read-only mode
+pub struct Runtime {
+    state: RwLock<Arc<RuntimeState>>,
+    read_only: ReadOnlySwitch,
+}
+
+impl Runtime {
+    pub fn new(state: RuntimeState, read_only: bool) -> Self {
+        let switch = ReadOnlySwitch::default();
+        switch.set(read_only);
+        Self {
+            state: RwLock::new(Arc::new(state)),
+            read_only: switch,
+        }
+    }
+
+    /// The state to handle a request with
+    pub fn current(&self) -> Arc<RuntimeState> {
+        self.state
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Read-only mode, shared with every request and open WebSocket
+    pub fn read_only(&self) -> &ReadOnlySwitch {
+        &self.read_only
+    }
+
+    fn replace(&self, state: RuntimeState) {
+        *self.state.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(state);
+    }
+}
+
+/// What started a reload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReloadTrigger {
+    /// `SIGHUP`
+    Signal,
+    /// The configuration file changed
+    File,
+    /// `POST /redis/admin/reload`
+    Api,
+}
+
+/// Outcome of one reload
+#[derive(Debug, Clone, Serialize)]
+pub struct ReloadReport {
+    pub trigger: ReloadTrigger,
+    pub at: DateTime<Utc>,
+    /// Whether the configuration loaded and was applied
+    pub success: bool,
+    /// Settings that changed and were applied
+    pub changed: Vec<String>,
+    /// Settings that changed but only take effect on restart
+    pub restart_required: Vec<String>,
+    /// Why nothing was applied
+    pub error: Option<String>,
+}
+
+/// Reload history, as served by `GET /redis/admin/reload`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReloadStatus {
+    /// Whether the server was started from configuration sources it can reload
+    pub enabled: bool,
+    /// Reloads attempted since startup
+    pub reloads: u64,
+    /// The latest reload
+    pub last: Option<ReloadReport>,
+}
+
+/// Setting names with the part of the configuration each covers
+type Settings = &'static [(&'static str, fn(&Config) -> serde_json::Value)];
+
+/// Settings a reload applies
+const RELOADABLE: Settings = &[
+    ("auth", |config| to_value(&config.auth)),
+    ("policy", |config| to_value(&config.policy)),
+    ("cors", |config| to_value(&config.cors)),
+    ("read_only", |config| to_value(&config.read_only)),
+    ("rate_limit", |config| to_value(&config.rate_limit)),
+    ("logging.level", |config| to_value(&config.logging.level)),
+    ("logging.redact_values", |config| {
+        to_value(&config.logging.redact_values)
+    }),
+];
+
+/// Settings that need a restart
+const RESTART_REQUIRED: Settings = &[
+    ("database_url", |config| to_value(&config.database_url)),
+    ("host", |config| to_value(&config.host)),
+    ("port", |config| to_value(&config.port)),
+    ("pool_size", |config| to_value(&config.pool_size)),
+    ("redis_tls", |config| to_value(&config.redis_tls)),
+    ("backends", |config| to_value(&config.backends)),
+    ("tls", |config| to_value(&config.tls)),
+    ("shutdown_timeout_secs", |config| {
+        to_value(&config.shutdown_timeout_secs)
+    }),
+    ("metrics_enabled", |config| {
+        to_value(&config.metrics_enabled)
+    }),
+    ("logging.format", |config| to_value(&config.logging.format)),
+    ("telemetry", |config| to_value(&config.telemetry)),
+];
+
+fn to_value<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap_or_default()
+}
+
+fn changed(settings: Settings, old: &Config, new: &Config) -> Vec<String> {
+    settings
+        .iter()
+        .filter(|(_, value)| value(old) != value(new))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// Reloads the configuration from the sources the server started with
+pub struct Reloader {
+    runtime: Arc<Runtime>,
+    /// `None` when the server was given a [`Config`] directly
+    loader: Option<ConfigLoader>,
+    /// The configuration currently applied
+    config: Mutex<Config>,
+    status: Mutex<ReloadStatus>,
+}
+
+impl Reloader {
+    pub fn new(runtime: Arc<Runtime>, config: Config, loader: Option<ConfigLoader>) -> Self {
+        Self {
+            status: Mutex::new(ReloadStatus {
+                enabled: loader.is_some(),
+                ..ReloadStatus::default()
+            }),
+            runtime,
+            loader,
+            config: Mutex::new(config),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.loader.is_some()
+    }
+
+    pub fn status(&self) -> ReloadStatus {
+        self.status
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Load the configuration again and apply what changed
+    ///
+    /// Returns `None` when there are no sources to reload from. A configuration
+    /// that fails to load or validate is reported and leaves everything as it was.
+    pub fn reload(&self, trigger: ReloadTrigger) -> Option<ReloadReport> {
+        let loader = self.loader.as_ref()?;
+        let mut current = self.config.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let report = match loader.load() {
+            Ok(new) => self.apply(&mut current, new, trigger),
+            Err(e) => ReloadReport {
+                trigger,
+                at: Utc::now(),
+                success: false,
+                changed: Vec::new(),
+                restart_required: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        };
+        match &report.error {
+            None => info!(
+                trigger = ?trigger,
+                changed = ?report.changed,
+                restart_required = ?report.restart_required,
+                "Configuration reloaded"
+            ),
+            Some(error) => warn!(trigger = ?trigger, "Configuration reload failed: {}", error),
+        }
+
+        let mut status = self.status.lock().unwrap_or_else(PoisonError::into_inner);
+        status.reloads += 1;
+        status.last = Some(report.clone());
+        Some(report)
+    }
+
+    fn apply(&self, current: &mut Config, new: Config, trigger: ReloadTrigger) -> ReloadReport {
+        let mut report = ReloadReport {
+            trigger,
+            at: Utc::now(),
+            success: true,
+            changed: changed(RELOADABLE, current, &new),
+            restart_required: changed(RESTART_REQUIRED, current, &new),
+            error: None,
+        };
+
+        let mut state = match RuntimeState::from_config(&new) {
+            Ok(state) => state,
+            Err(e) => {
+                report.success = false;
+                report.changed.clear();
+                report.error = Some(e.to_string());
+                return report;
+            }
+        };
+        // Unchanged limits keep the buckets clients have already drawn from
+        if !report.changed.iter().any(|name| name == "rate_limit") {
+            state.rate_limiter = self.runtime.current().rate_limiter.clone();
+        }
+        self.runtime.replace(state);
+        self.runtime.read_only().set(new.read_only);
+
+        if report
+            .changed
+            .iter()
+            .any(|name| name.starts_with("logging."))
+        {
+            match logging::reconfigure(&new.logging) {
+                Ok(true) => {}
+                Ok(false) => {
+                    warn!("Log level not changed: RUST_LOG is set");
+                    report.changed.retain(|name| name != "logging.level");
+                }
+                Err(e) => warn!("Log level not changed: {}", e),
+            }
+        }
+
+        current.auth = new.auth;
+        current.policy = new.policy;
+        current.policy_file = new.policy_file;
+        current.cors = new.cors;
+        current.read_only = new.read_only;
+        current.rate_limit = new.rate_limit;
+        current.logging.level = new.logging.level;
+        current.logging.redact_values = new.logging.redact_values;
+        report
+    }
+
+    /// Reload on `SIGHUP` and whenever the configuration file changes
+    pub fn watch(self: &Arc<Self>) {
+        let Some(loader) = &self.loader else {
+            return;
+        };
+
+        #[cfg(unix)]
+        {
+            let reloader = self.clone();
+            tokio::spawn(async move {
+                use tokio::signal::unix::{signal, SignalKind};
+                let mut hangups = match signal(SignalKind::hangup()) {
+                    Ok(hangups) => hangups,
+                    Err(e) => {
+                        warn!("Cannot reload on SIGHUP: {}", e);
+                        return;
+                    }
+                };
+                while hangups.recv().await.is_some() {
+                    reloader.reload(ReloadTrigger::Signal);
+                }
+            });
+        }
+
+        if let Some(path) = loader.file_path().map(|path| path.to_path_buf()) {
+            let reloader = self.clone();
+            tokio::spawn(async move {
+                let modified = |path: &std::path::Path| -> Option<SystemTime> {
+                    std::fs::metadata(path)
+                        .and_then(|meta| meta.modified())
+                        .ok()
+                };
+                let mut last = modified(&path);
+                let mut interval =
+                    tokio::time::interval(Duration::from_secs(Defaults::CONFIG_POLL_SECS));
+                loop {
+                    interval.tick().await;
+                    let now = modified(&path);
+                    if now != last {
+                        last = now;
+                        reloader.reload(ReloadTrigger::File);
+                    }
+                }
+            });
+        }
+    }
+}
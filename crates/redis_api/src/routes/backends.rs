@@ -0,0 +1,62 @@
+use axum::{extract::State, routing::get, Router};
+use dbx_adapter::memory::MemoryServer;
+use dbx_adapter::redis::async_primitives::admin::AsyncAdminOperations;
+use dbx_adapter::redis::client::{PoolMetrics, RedisPool};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::error::ApiError;
+use crate::models::ApiResponse;
+use crate::policy::Access;
+
+/// A Redis backend the routes are served from
+#[derive(Clone)]
+pub struct Backend {
+    /// `default` for `database_url`, otherwise the name under `backends`
+    pub name: String,
+    pub pool: Arc<RedisPool>,
+    /// Keeps the in-process backend alive for `memory://` and `sqlite://` URLs
+    pub memory: Option<Arc<MemoryServer>>,
+}
+
+/// Health and pool usage of one backend
+#[derive(Debug, Serialize)]
+pub struct BackendStatus {
+    pub name: String,
+    pub healthy: bool,
+    /// Why the backend did not answer PING
+    pub error: Option<String>,
+    pub pool: PoolMetrics,
+}
+
+async fn status(backend: &Backend) -> BackendStatus {
+    let ping = match backend.pool.get_multiplexed_connection().await {
+        Ok(conn) => AsyncAdminOperations::new(conn).ping().await,
+        Err(e) => Err(e),
+    };
+    let (healthy, error) = match ping {
+        Ok(pong) => (pong == "PONG", None),
+        Err(e) => (false, Some(e.to_string())),
+    };
+    BackendStatus {
+        name: backend.name.clone(),
+        healthy,
+        error,
+        pool: backend.pool.metrics(),
+    }
+}
+
+async fn backends_handler(
+    access: Access,
+    State(backends): State<Arc<[Backend]>>,
+) -> Result<ApiResponse<Vec<BackendStatus>>, ApiError> {
+    access.admin()?;
+    let statuses = futures::future::join_all(backends.iter().map(status)).await;
+    Ok(ApiResponse::success(statuses))
+}
+
+pub fn create_backend_routes(backends: Arc<[Backend]>) -> Router {
+    Router::new()
+        .route("/admin/backends", get(backends_handler))
+        .with_state(backends)
+}
@@ -1,8 +1,8 @@
-use dbx_adapter::redis::primitives::admin::{AdminOperations, HealthCheck, ServerStatus};
-use redis::Connection;
+use dbx_adapter::redis::async_primitives::admin::AsyncAdminOperations;
+use dbx_adapter::redis::client::AsyncPooledConnection;
+use dbx_adapter::redis::primitives::admin::{HealthCheck, ServerStatus};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ConfigSetRequest {
@@ -28,111 +28,224 @@ pub struct ServerInfo {
     pub section: Option<String>,
 }
 
-fn redis_admin(conn: Arc<Mutex<Connection>>) -> AdminOperations {
-    AdminOperations::new(conn)
+fn redis_admin(conn: AsyncPooledConnection) -> AsyncAdminOperations<AsyncPooledConnection> {
+    AsyncAdminOperations::new(conn)
 }
 
 // =========================
 // Basic Health & Status Operations
 // =========================
 
-pub fn ping_server(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<String> {
-    redis_admin(conn).ping()
-}
-
-pub fn get_server_info(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<String> {
-    redis_admin(conn).info()
-}
-
-pub fn get_server_info_section(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "PING", key_count = 0)
+)]
+pub async fn ping_server(conn: AsyncPooledConnection) -> redis::RedisResult<String> {
+    redis_admin(conn).ping().await
+}
+
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "INFO", key_count = 0)
+)]
+pub async fn get_server_info(conn: AsyncPooledConnection) -> redis::RedisResult<String> {
+    redis_admin(conn).info().await
+}
+
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "INFO", key_count = 0)
+)]
+pub async fn get_server_info_section(
+    conn: AsyncPooledConnection,
     section: &str,
 ) -> redis::RedisResult<String> {
-    redis_admin(conn).info_section(section)
+    redis_admin(conn).info_section(section).await
 }
 
-pub fn get_database_size(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<i64> {
-    redis_admin(conn).dbsize()
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "DBSIZE", key_count = 0)
+)]
+pub async fn get_database_size(conn: AsyncPooledConnection) -> redis::RedisResult<i64> {
+    redis_admin(conn).dbsize().await
 }
 
-pub fn get_server_time(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<(i64, i64)> {
-    redis_admin(conn).time()
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "TIME", key_count = 0)
+)]
+pub async fn get_server_time(conn: AsyncPooledConnection) -> redis::RedisResult<(i64, i64)> {
+    redis_admin(conn).time().await
 }
 
-pub fn get_server_version(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<String> {
-    redis_admin(conn).version()
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "INFO", key_count = 0)
+)]
+pub async fn get_server_version(conn: AsyncPooledConnection) -> redis::RedisResult<String> {
+    redis_admin(conn).version().await
 }
 
 // =========================
 // Health Check Operations
 // =========================
 
-pub fn health_check(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<HealthCheck> {
-    redis_admin(conn).health_check()
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "PING", key_count = 0)
+)]
+pub async fn health_check(conn: AsyncPooledConnection) -> redis::RedisResult<HealthCheck> {
+    redis_admin(conn).health_check().await
 }
 
-pub fn server_status(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<ServerStatus> {
-    redis_admin(conn).server_status()
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "INFO", key_count = 0)
+)]
+pub async fn server_status(conn: AsyncPooledConnection) -> redis::RedisResult<ServerStatus> {
+    redis_admin(conn).server_status().await
 }
 
 // =========================
 // Statistics Operations
 // =========================
 
-pub fn get_memory_stats(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "INFO", key_count = 0)
+)]
+pub async fn get_memory_stats(
+    conn: AsyncPooledConnection,
 ) -> redis::RedisResult<HashMap<String, String>> {
-    redis_admin(conn).memory_stats()
+    redis_admin(conn).memory_stats().await
 }
 
-pub fn get_client_stats(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "INFO", key_count = 0)
+)]
+pub async fn get_client_stats(
+    conn: AsyncPooledConnection,
 ) -> redis::RedisResult<HashMap<String, String>> {
-    redis_admin(conn).client_stats()
+    redis_admin(conn).client_stats().await
 }
 
-pub fn get_server_stats(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "INFO", key_count = 0)
+)]
+pub async fn get_server_stats(
+    conn: AsyncPooledConnection,
 ) -> redis::RedisResult<HashMap<String, String>> {
-    redis_admin(conn).server_stats()
+    redis_admin(conn).server_stats().await
 }
 
 // =========================
 // Configuration Operations
 // =========================
 
-pub fn config_set(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "CONFIG SET", key_count = 0)
+)]
+pub async fn config_set(
+    conn: AsyncPooledConnection,
     parameter: &str,
     value: &str,
 ) -> redis::RedisResult<()> {
-    redis_admin(conn).config_set(parameter, value)
+    redis_admin(conn).config_set(parameter, value).await
 }
 
-pub fn config_get(conn: Arc<Mutex<Connection>>, parameter: &str) -> redis::RedisResult<String> {
-    redis_admin(conn).config_get(parameter)
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "CONFIG GET", key_count = 0)
+)]
+pub async fn config_get(
+    conn: AsyncPooledConnection,
+    parameter: &str,
+) -> redis::RedisResult<String> {
+    redis_admin(conn).config_get(parameter).await
 }
 
-pub fn config_get_all(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<HashMap<String, String>> {
-    redis_admin(conn).config_get_all()
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "CONFIG GET", key_count = 0)
+)]
+pub async fn config_get_all(
+    conn: AsyncPooledConnection,
+) -> redis::RedisResult<HashMap<String, String>> {
+    redis_admin(conn).config_get_all().await
 }
 
-pub fn config_reset_statistics(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<()> {
-    redis_admin(conn).config_resetstat()
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "CONFIG RESETSTAT", key_count = 0)
+)]
+pub async fn config_reset_statistics(conn: AsyncPooledConnection) -> redis::RedisResult<()> {
+    redis_admin(conn).config_resetstat().await
 }
 
-pub fn config_rewrite(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<()> {
-    redis_admin(conn).config_rewrite()
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "CONFIG REWRITE", key_count = 0)
+)]
+pub async fn config_rewrite(conn: AsyncPooledConnection) -> redis::RedisResult<()> {
+    redis_admin(conn).config_rewrite().await
 }
 
 // =========================
 // Database Management Operations
 // =========================
 
-pub fn flush_current_database(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<()> {
-    redis_admin(conn).flushdb()
-}
-
-pub fn flush_all_databases(conn: Arc<Mutex<Connection>>) -> redis::RedisResult<()> {
-    redis_admin(conn).flushall()
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "FLUSHDB", key_count = 0)
+)]
+pub async fn flush_current_database(conn: AsyncPooledConnection) -> redis::RedisResult<()> {
+    redis_admin(conn).flushdb().await
+}
+
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "FLUSHALL", key_count = 0)
+)]
+pub async fn flush_all_databases(conn: AsyncPooledConnection) -> redis::RedisResult<()> {
+    redis_admin(conn).flushall().await
 }
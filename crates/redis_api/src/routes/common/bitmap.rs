@@ -0,0 +1,197 @@
+use dbx_adapter::redis::async_primitives::bitmap::AsyncRedisBitmap;
+use dbx_adapter::redis::client::AsyncPooledConnection;
+use serde::{Deserialize, Serialize};
+
+/// Bitwise operations supported by BITOP
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BitOperation {
+    And,
+    Or,
+    Xor,
+    Not,
+}
+
+/// Granularity of the start/end range given to BITPOS
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BitRangeUnit {
+    #[default]
+    Byte,
+    Bit,
+}
+
+fn redis_bitmap(conn: AsyncPooledConnection) -> AsyncRedisBitmap<AsyncPooledConnection> {
+    AsyncRedisBitmap::new(conn)
+}
+
+// =========================
+// Single Bit Operations
+// =========================
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SETBIT", key_count = 1, key = %key))]
+pub async fn set_bit(
+    conn: AsyncPooledConnection,
+    key: &str,
+    offset: usize,
+    value: bool,
+) -> redis::RedisResult<bool> {
+    redis_bitmap(conn).setbit(key, offset, value).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "GETBIT", key_count = 1, key = %key))]
+pub async fn get_bit(
+    conn: AsyncPooledConnection,
+    key: &str,
+    offset: usize,
+) -> redis::RedisResult<bool> {
+    redis_bitmap(conn).getbit(key, offset).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "BITCOUNT", key_count = 1, key = %key))]
+pub async fn count_bits(
+    conn: AsyncPooledConnection,
+    key: &str,
+    range: Option<(i64, i64)>,
+) -> redis::RedisResult<u64> {
+    match range {
+        Some((start, end)) => redis_bitmap(conn).bitcount_range(key, start, end).await,
+        None => redis_bitmap(conn).bitcount(key).await,
+    }
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "BITPOS", key_count = 1, key = %key))]
+pub async fn find_bit_position(
+    conn: AsyncPooledConnection,
+    key: &str,
+    bit: bool,
+    range: Option<(i64, i64)>,
+    unit: BitRangeUnit,
+) -> redis::RedisResult<i64> {
+    match (range, unit) {
+        (None, _) => redis_bitmap(conn).bitpos(key, bit).await,
+        (Some((start, end)), BitRangeUnit::Byte) => {
+            redis_bitmap(conn)
+                .bitpos_range_bytes(key, bit, start, end)
+                .await
+        }
+        (Some((start, end)), BitRangeUnit::Bit) => {
+            redis_bitmap(conn)
+                .bitpos_range_bits(key, bit, start, end)
+                .await
+        }
+    }
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "GET", key_count = 1, key = %key))]
+pub async fn get_bitmap_bytes(
+    conn: AsyncPooledConnection,
+    key: &str,
+    offset: u64,
+    length: u64,
+) -> redis::RedisResult<Vec<u8>> {
+    if length == 0 {
+        return Ok(Vec::new());
+    }
+    redis_bitmap(conn)
+        .get_bits_as_bytes(key, offset, length)
+        .await
+}
+
+// =========================
+// Bitmap Operations
+// =========================
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "BITOP", key_count = keys.len() + 1, destination = %destination, keys = ?keys))]
+pub async fn bit_operation(
+    conn: AsyncPooledConnection,
+    operation: BitOperation,
+    destination: &str,
+    keys: &[&str],
+) -> redis::RedisResult<u64> {
+    let bitmap = redis_bitmap(conn);
+    match operation {
+        BitOperation::And => bitmap.bitop_and(destination, keys).await,
+        BitOperation::Or => bitmap.bitop_or(destination, keys).await,
+        BitOperation::Xor => bitmap.bitop_xor(destination, keys).await,
+        BitOperation::Not => match keys {
+            [source] => bitmap.bitop_not(destination, source).await,
+            _ => Err(redis::RedisError::from((
+                redis::ErrorKind::ClientError,
+                "BITOP NOT requires exactly one source key",
+            ))),
+        },
+    }
+}
+
+// =========================
+// Bitmap Management
+// =========================
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "DEL", key_count = 1, key = %key))]
+pub async fn delete_bitmap(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<bool> {
+    let exists = redis_bitmap(conn.clone()).exists(key).await?;
+    if exists {
+        redis_bitmap(conn).del(key).await?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "EXISTS", key_count = 1, key = %key))]
+pub async fn bitmap_exists_key(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<bool> {
+    redis_bitmap(conn).exists(key).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "TTL", key_count = 1, key = %key))]
+pub async fn get_bitmap_ttl(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<i64> {
+    redis_bitmap(conn).ttl(key).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "EXPIRE", key_count = 1, key = %key))]
+pub async fn set_bitmap_ttl(
+    conn: AsyncPooledConnection,
+    key: &str,
+    ttl: u64,
+) -> redis::RedisResult<bool> {
+    redis_bitmap(conn).expire(key, ttl).await
+}
+
+// =========================
+// Batch Operations
+// =========================
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SETBIT", key_count = 1, key = %key))]
+pub async fn set_multiple_bits(
+    conn: AsyncPooledConnection,
+    key: &str,
+    bits: Vec<(usize, bool)>,
+) -> redis::RedisResult<Vec<bool>> {
+    redis_bitmap(conn).setbit_many(key, bits).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "GETBIT", key_count = 1, key = %key))]
+pub async fn get_multiple_bits(
+    conn: AsyncPooledConnection,
+    key: &str,
+    offsets: Vec<usize>,
+) -> redis::RedisResult<Vec<bool>> {
+    redis_bitmap(conn).getbit_many(key, offsets).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "BITCOUNT", key_count = keys.len(), keys = ?keys))]
+pub async fn count_multiple_bitmaps(
+    conn: AsyncPooledConnection,
+    keys: Vec<&str>,
+) -> redis::RedisResult<Vec<u64>> {
+    redis_bitmap(conn).bitcount_many(keys).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "DEL", key_count = keys.len(), keys = ?keys))]
+pub async fn delete_multiple_bitmaps(
+    conn: AsyncPooledConnection,
+    keys: Vec<&str>,
+) -> redis::RedisResult<()> {
+    redis_bitmap(conn).del_many(keys).await
+}
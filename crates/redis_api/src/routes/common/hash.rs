@@ -1,7 +1,6 @@
-use dbx_adapter::redis::primitives::hash::RedisHash;
-use redis::Connection;
+use dbx_adapter::redis::async_primitives::hash::AsyncRedisHash;
+use dbx_adapter::redis::client::AsyncPooledConnection;
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct HashOperation {
@@ -31,198 +30,253 @@ pub struct HashField {
     pub value: String,
 }
 
-fn redis_hash(conn: Arc<Mutex<Connection>>) -> RedisHash {
-    RedisHash::new(conn)
+fn redis_hash(conn: AsyncPooledConnection) -> AsyncRedisHash<AsyncPooledConnection> {
+    AsyncRedisHash::new(conn)
 }
 
 // =========================
 // Single Field Operations
 // =========================
 
-pub fn get_hash_field(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HGET", key_count = 1, key = %key))]
+pub async fn get_hash_field(
+    conn: AsyncPooledConnection,
     key: &str,
     field: &str,
 ) -> redis::RedisResult<Option<String>> {
-    redis_hash(conn).hget(key, field)
+    redis_hash(conn).hget(key, field).await
 }
 
-pub fn set_hash_field(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HSET", key_count = 1, key = %key))]
+pub async fn set_hash_field(
+    conn: AsyncPooledConnection,
     key: &str,
     field: &str,
     value: &str,
 ) -> redis::RedisResult<bool> {
-    redis_hash(conn).hset(key, field, value)
+    redis_hash(conn).hset(key, field, value).await
 }
 
-pub fn delete_hash_field(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HDEL", key_count = 1, key = %key))]
+pub async fn delete_hash_field(
+    conn: AsyncPooledConnection,
     key: &str,
     field: &str,
 ) -> redis::RedisResult<bool> {
-    let deleted = redis_hash(conn).hdel(key, &[field])?;
+    let deleted = redis_hash(conn).hdel(key, &[field]).await?;
     Ok(deleted > 0)
 }
 
-pub fn hash_exists(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HEXISTS", key_count = 1, key = %key))]
+pub async fn hash_exists(
+    conn: AsyncPooledConnection,
     key: &str,
     field: &str,
 ) -> redis::RedisResult<bool> {
-    redis_hash(conn).hexists(key, field)
+    redis_hash(conn).hexists(key, field).await
 }
 
 // =========================
 // Hash Operations
 // =========================
 
-pub fn get_all_hash_fields(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HGETALL", key_count = 1, key = %key))]
+pub async fn get_all_hash_fields(
+    conn: AsyncPooledConnection,
     key: &str,
 ) -> redis::RedisResult<std::collections::HashMap<String, String>> {
-    redis_hash(conn).hgetall(key)
+    redis_hash(conn).hgetall(key).await
 }
 
-pub fn get_hash_fields(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HMGET", key_count = 1, key = %key))]
+pub async fn get_hash_fields(
+    conn: AsyncPooledConnection,
     key: &str,
     fields: &[&str],
 ) -> redis::RedisResult<Vec<Option<String>>> {
-    redis_hash(conn).hmget(key, fields)
+    redis_hash(conn).hmget(key, fields).await
 }
 
-pub fn set_multiple_hash_fields(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HMSET", key_count = 1, key = %key))]
+pub async fn set_multiple_hash_fields(
+    conn: AsyncPooledConnection,
     key: &str,
     fields: &[(&str, &str)],
 ) -> redis::RedisResult<()> {
-    redis_hash(conn).hmset(key, fields)
+    redis_hash(conn).hmset(key, fields).await
 }
 
-pub fn get_hash_length(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<usize> {
-    redis_hash(conn).hlen(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HLEN", key_count = 1, key = %key))]
+pub async fn get_hash_length(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<usize> {
+    redis_hash(conn).hlen(key).await
 }
 
-pub fn get_hash_keys(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<Vec<String>> {
-    redis_hash(conn).hkeys(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HKEYS", key_count = 1, key = %key))]
+pub async fn get_hash_keys(
+    conn: AsyncPooledConnection,
+    key: &str,
+) -> redis::RedisResult<Vec<String>> {
+    redis_hash(conn).hkeys(key).await
 }
 
-pub fn get_hash_values(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<Vec<String>> {
-    redis_hash(conn).hvals(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HVALS", key_count = 1, key = %key))]
+pub async fn get_hash_values(
+    conn: AsyncPooledConnection,
+    key: &str,
+) -> redis::RedisResult<Vec<String>> {
+    redis_hash(conn).hvals(key).await
 }
 
-pub fn increment_hash_field(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HINCRBY", key_count = 1, key = %key))]
+pub async fn increment_hash_field(
+    conn: AsyncPooledConnection,
     key: &str,
     field: &str,
     increment: i64,
 ) -> redis::RedisResult<i64> {
-    redis_hash(conn).hincrby(key, field, increment)
+    redis_hash(conn).hincrby(key, field, increment).await
 }
 
-pub fn increment_hash_field_float(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HINCRBYFLOAT", key_count = 1, key = %key))]
+pub async fn increment_hash_field_float(
+    conn: AsyncPooledConnection,
     key: &str,
     field: &str,
     increment: f64,
 ) -> redis::RedisResult<f64> {
-    redis_hash(conn).hincrbyfloat(key, field, increment)
+    redis_hash(conn).hincrbyfloat(key, field, increment).await
 }
 
-pub fn set_hash_field_if_not_exists(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HSETNX", key_count = 1, key = %key))]
+pub async fn set_hash_field_if_not_exists(
+    conn: AsyncPooledConnection,
     key: &str,
     field: &str,
     value: &str,
 ) -> redis::RedisResult<bool> {
-    redis_hash(conn).hsetnx(key, field, value)
+    redis_hash(conn).hsetnx(key, field, value).await
 }
 
-pub fn get_random_hash_field(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HRANDFIELD", key_count = 1, key = %key))]
+pub async fn get_random_hash_field(
+    conn: AsyncPooledConnection,
     key: &str,
 ) -> redis::RedisResult<Option<String>> {
-    redis_hash(conn).hrandfield(key)
+    redis_hash(conn).hrandfield(key).await
 }
 
-pub fn get_random_hash_fields(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HRANDFIELD", key_count = 1, key = %key))]
+pub async fn get_random_hash_fields(
+    conn: AsyncPooledConnection,
     key: &str,
     count: isize,
 ) -> redis::RedisResult<Vec<String>> {
-    redis_hash(conn).hrandfield_count(key, count)
+    redis_hash(conn).hrandfield_count(key, count).await
 }
 
-pub fn get_random_hash_fields_with_values(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HRANDFIELD", key_count = 1, key = %key))]
+pub async fn get_random_hash_fields_with_values(
+    conn: AsyncPooledConnection,
     key: &str,
     count: isize,
 ) -> redis::RedisResult<Vec<(String, String)>> {
-    redis_hash(conn).hrandfield_withvalues(key, count)
+    redis_hash(conn).hrandfield_withvalues(key, count).await
 }
 
 // =========================
 // Hash Management
 // =========================
 
-pub fn delete_hash(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<bool> {
-    let exists = redis_hash(conn.clone()).exists(key)?;
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "DEL", key_count = 1, key = %key))]
+pub async fn delete_hash(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<bool> {
+    let exists = redis_hash(conn.clone()).exists(key).await?;
     if exists {
-        redis_hash(conn).del(key)?;
+        redis_hash(conn).del(key).await?;
         Ok(true)
     } else {
         Ok(false)
     }
 }
 
-pub fn hash_exists_key(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<bool> {
-    redis_hash(conn).exists(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "EXISTS", key_count = 1, key = %key))]
+pub async fn hash_exists_key(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<bool> {
+    redis_hash(conn).exists(key).await
 }
 
-pub fn get_hash_ttl(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<i64> {
-    redis_hash(conn).ttl(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "TTL", key_count = 1, key = %key))]
+pub async fn get_hash_ttl(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<i64> {
+    redis_hash(conn).ttl(key).await
 }
 
-pub fn set_hash_ttl(conn: Arc<Mutex<Connection>>, key: &str, ttl: u64) -> redis::RedisResult<bool> {
-    redis_hash(conn).expire(key, ttl)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "EXPIRE", key_count = 1, key = %key))]
+pub async fn set_hash_ttl(
+    conn: AsyncPooledConnection,
+    key: &str,
+    ttl: u64,
+) -> redis::RedisResult<bool> {
+    redis_hash(conn).expire(key, ttl).await
 }
 
 // =========================
 // Batch Operations
 // =========================
 
-pub fn get_multiple_hash_fields(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "HGET", key_count = hash_fields.len())
+)]
+pub async fn get_multiple_hash_fields(
+    conn: AsyncPooledConnection,
     hash_fields: Vec<(&str, &str)>,
 ) -> redis::RedisResult<Vec<Option<String>>> {
-    redis_hash(conn).hget_many(hash_fields)
+    redis_hash(conn).hget_many(hash_fields).await
 }
 
-pub fn set_multiple_hashes(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "HSET", key_count = hash_operations.len())
+)]
+pub async fn set_multiple_hashes(
+    conn: AsyncPooledConnection,
     hash_operations: Vec<(&str, Vec<(&str, &str)>)>,
 ) -> redis::RedisResult<Vec<bool>> {
-    redis_hash(conn).hset_many(hash_operations)
+    redis_hash(conn).hset_many(hash_operations).await
 }
 
-pub fn delete_multiple_hash_fields(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "HDEL", key_count = hash_fields.len())
+)]
+pub async fn delete_multiple_hash_fields(
+    conn: AsyncPooledConnection,
     hash_fields: Vec<(&str, Vec<&str>)>,
 ) -> redis::RedisResult<Vec<usize>> {
-    redis_hash(conn).hdel_many(hash_fields)
+    redis_hash(conn).hdel_many(hash_fields).await
 }
 
-pub fn check_multiple_hash_fields(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "HEXISTS", key_count = hash_fields.len())
+)]
+pub async fn check_multiple_hash_fields(
+    conn: AsyncPooledConnection,
     hash_fields: Vec<(&str, &str)>,
 ) -> redis::RedisResult<Vec<bool>> {
-    redis_hash(conn).hexists_many(hash_fields)
+    redis_hash(conn).hexists_many(hash_fields).await
 }
 
-pub fn get_multiple_hash_lengths(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "HLEN", key_count = keys.len(), keys = ?keys))]
+pub async fn get_multiple_hash_lengths(
+    conn: AsyncPooledConnection,
     keys: Vec<&str>,
 ) -> redis::RedisResult<Vec<usize>> {
-    redis_hash(conn).hlen_many(keys)
+    redis_hash(conn).hlen_many(keys).await
 }
@@ -0,0 +1,246 @@
+use dbx_adapter::redis::client::AsyncPooledConnection;
+use futures::StreamExt;
+use redis::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::{broadcast, Mutex, OnceCell};
+use tokio::task::JoinHandle;
+
+/// Number of messages buffered per topic before slow sockets start lagging
+const TOPIC_BUFFER_SIZE: usize = 1024;
+
+/// A channel name or a glob-style pattern, as given to SUBSCRIBE / PSUBSCRIBE
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Topic {
+    Channel(String),
+    Pattern(String),
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topic::Channel(channel) => write!(f, "channel {channel}"),
+            Topic::Pattern(pattern) => write!(f, "pattern {pattern}"),
+        }
+    }
+}
+
+/// A message received from Redis and fanned out to subscribed sockets
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PubSubMessage {
+    pub channel: String,
+    /// The pattern that matched, for messages delivered through PSUBSCRIBE
+    pub pattern: Option<String>,
+    pub payload: String,
+}
+
+/// A topic's Redis subscription, fanned out through a broadcast channel
+struct Listener {
+    /// Only used to attach more subscribers; the task holds the one sender,
+    /// so receivers see the channel close when the Redis subscription ends
+    receiver: broadcast::Receiver<PubSubMessage>,
+    task: JoinHandle<()>,
+}
+
+struct SharedTopic {
+    subscribers: usize,
+    /// Opened by the first subscriber while later ones wait on the cell,
+    /// not on the lock every topic shares
+    listener: Arc<OnceCell<Listener>>,
+}
+
+/// Shares one Redis pub/sub connection per topic across all connected sockets
+///
+/// The first subscriber to a topic opens the connection, later subscribers
+/// attach to the same broadcast channel, and the connection is closed once
+/// the last subscriber releases the topic. If Redis drops the connection,
+/// subscribers' receivers report the channel closed and they must release it.
+pub struct PubSubHub {
+    client: Arc<Client>,
+    topics: Mutex<HashMap<Topic, SharedTopic>>,
+}
+
+impl PubSubHub {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            topics: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Subscribes to a topic, opening the Redis subscription if nobody holds it yet
+    ///
+    /// Every successful call must be paired with a call to [`PubSubHub::release`].
+    pub async fn subscribe(
+        &self,
+        topic: &Topic,
+    ) -> redis::RedisResult<broadcast::Receiver<PubSubMessage>> {
+        let listener = {
+            let mut topics = self.topics.lock().await;
+            let shared = topics.entry(topic.clone()).or_insert_with(|| SharedTopic {
+                subscribers: 0,
+                listener: Arc::default(),
+            });
+            // The previous Redis connection went away; its subscribers release
+            // it as they see their channel close
+            if shared
+                .listener
+                .get()
+                .is_some_and(|listener| listener.task.is_finished())
+            {
+                shared.listener = Arc::default();
+            }
+            shared.subscribers += 1;
+            shared.listener.clone()
+        };
+
+        match listener
+            .get_or_try_init(|| self.spawn_listener(topic))
+            .await
+        {
+            Ok(listener) => Ok(listener.receiver.resubscribe()),
+            Err(e) => {
+                self.release(topic).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Releases one subscription, closing the Redis subscription when it was the last one
+    pub async fn release(&self, topic: &Topic) {
+        let mut topics = self.topics.lock().await;
+        if let Some(shared) = topics.get_mut(topic) {
+            shared.subscribers = shared.subscribers.saturating_sub(1);
+            if shared.subscribers == 0 {
+                if let Some(shared) = topics.remove(topic) {
+                    if let Some(listener) = shared.listener.get() {
+                        listener.task.abort();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of topics with an open Redis subscription
+    pub async fn topic_count(&self) -> usize {
+        self.topics
+            .lock()
+            .await
+            .values()
+            .filter(|shared| shared.listener.initialized())
+            .count()
+    }
+
+    async fn spawn_listener(&self, topic: &Topic) -> redis::RedisResult<Listener> {
+        let mut pubsub = self.client.get_async_pubsub().await?;
+        match topic {
+            Topic::Channel(channel) => pubsub.subscribe(channel).await?,
+            Topic::Pattern(pattern) => pubsub.psubscribe(pattern).await?,
+        }
+
+        let (sender, receiver) = broadcast::channel(TOPIC_BUFFER_SIZE);
+        let topic = topic.clone();
+        let task = tokio::spawn(async move {
+            let mut messages = pubsub.on_message();
+            while let Some(msg) = messages.next().await {
+                let pattern = if msg.from_pattern() {
+                    msg.get_pattern().ok()
+                } else {
+                    None
+                };
+                let payload = String::from_utf8_lossy(msg.get_payload_bytes()).into_owned();
+                // Sending fails only while no receiver is attached, so nothing is lost
+                let _ = sender.send(PubSubMessage {
+                    channel: msg.get_channel_name().to_string(),
+                    pattern,
+                    payload,
+                });
+            }
+            tracing::warn!("[PUBSUB] Redis subscription for {:?} closed", topic);
+        });
+        Ok(Listener { receiver, task })
+    }
+}
+
+// =========================
+// Publishing
+// =========================
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "PUBLISH", key_count = 0, channel = %channel))]
+pub async fn publish_message(
+    mut conn: AsyncPooledConnection,
+    channel: &str,
+    message: &str,
+) -> redis::RedisResult<usize> {
+    redis::cmd("PUBLISH")
+        .arg(channel)
+        .arg(message)
+        .query_async(&mut conn)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbx_adapter::memory::{MemoryServer, MemoryStore};
+    use std::time::Duration;
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[tokio::test]
+    async fn test_receivers_see_the_subscription_end() {
+        // A proxy in front of the backend, so the connection can be cut
+        let backend = MemoryServer::start(MemoryStore::new()).await.unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "redis://:{}@{}",
+            backend.password(),
+            listener.local_addr().unwrap()
+        );
+        let proxy = tokio::spawn(async move {
+            let (mut client, _) = listener.accept().await.unwrap();
+            let mut server = TcpStream::connect(backend.addr()).await.unwrap();
+            let _ = tokio::io::copy_bidirectional(&mut client, &mut server).await;
+        });
+
+        let hub = PubSubHub::new(Arc::new(Client::open(url).unwrap()));
+        let topic = Topic::Channel("news".to_string());
+        let mut receiver = hub.subscribe(&topic).await.unwrap();
+        proxy.abort();
+
+        let received = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap();
+        assert_eq!(received, Err(broadcast::error::RecvError::Closed));
+        hub.release(&topic).await;
+        assert_eq!(hub.topic_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_connecting_does_not_hold_up_other_topics() {
+        // Accepts connections but never answers, so SUBSCRIBE hangs
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("redis://{}", listener.local_addr().unwrap());
+        let _silent = tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+
+        let hub = Arc::new(PubSubHub::new(Arc::new(Client::open(url).unwrap())));
+        let pending = tokio::spawn({
+            let hub = hub.clone();
+            async move { hub.subscribe(&Topic::Channel("slow".to_string())).await }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let other = Topic::Pattern("other.*".to_string());
+        tokio::time::timeout(Duration::from_secs(1), hub.release(&other))
+            .await
+            .expect("the topic map was locked while connecting");
+        assert_eq!(hub.topic_count().await, 0);
+        pending.abort();
+    }
+}
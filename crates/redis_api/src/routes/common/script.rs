@@ -0,0 +1,334 @@
+use dbx_adapter::redis::client::AsyncPooledConnection;
+use dbx_adapter::redis::primitives::{
+    bitmap::RedisBitmap, hash::RedisHash, list::RedisList, set::RedisSet,
+    sorted_set::RedisSortedSet, stream::RedisStream, string::RedisString,
+};
+use redis::{Script, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// A script shipped with the adapter, callable by name
+struct BuiltinScript {
+    name: &'static str,
+    description: &'static str,
+    script: fn() -> Script,
+}
+
+const BUILTIN_SCRIPTS: &[BuiltinScript] = &[
+    // String scripts
+    BuiltinScript {
+        name: "get_set",
+        description: "Get the old value of KEYS[1] and set it to ARGV[1]",
+        script: RedisString::get_set_script,
+    },
+    BuiltinScript {
+        name: "set_if_not_exists",
+        description: "Set KEYS[1] to ARGV[1] only if it doesn't exist",
+        script: RedisString::set_if_not_exists_script,
+    },
+    BuiltinScript {
+        name: "compare_and_set_with_ttl",
+        description: "Set KEYS[1] to ARGV[2] with TTL ARGV[3] if its value is ARGV[1]",
+        script: RedisString::compare_and_set_with_ttl_script,
+    },
+    BuiltinScript {
+        name: "multi_counter",
+        description: "Increment every key by ARGV[1]",
+        script: RedisString::multi_counter_script,
+    },
+    BuiltinScript {
+        name: "multi_set_with_ttl",
+        description: "Set every key with TTL ARGV[1] to the following ARGV values",
+        script: RedisString::multi_set_with_ttl_script,
+    },
+    BuiltinScript {
+        name: "rate_limiter",
+        description: "Fixed window rate limiter: limit ARGV[1] per ARGV[2] seconds",
+        script: RedisString::rate_limiter_script,
+    },
+    // Hash scripts
+    BuiltinScript {
+        name: "hash_get_set",
+        description: "Get the old value of field ARGV[1] and set it to ARGV[2]",
+        script: RedisHash::get_set_script,
+    },
+    BuiltinScript {
+        name: "hash_set_if_not_exists",
+        description: "Set field ARGV[1] to ARGV[2] only if it doesn't exist",
+        script: RedisHash::set_if_not_exists_script,
+    },
+    BuiltinScript {
+        name: "hash_increment_and_get",
+        description: "Increment field ARGV[1] by ARGV[2] and return the new value",
+        script: RedisHash::increment_and_get_script,
+    },
+    BuiltinScript {
+        name: "hash_multi_set",
+        description: "Set the field-value pairs given in ARGV",
+        script: RedisHash::multi_set_script,
+    },
+    BuiltinScript {
+        name: "hash_multi_delete",
+        description: "Delete the fields given in ARGV",
+        script: RedisHash::multi_delete_script,
+    },
+    // Set scripts
+    BuiltinScript {
+        name: "set_move_member",
+        description: "Move member ARGV[1] from KEYS[1] to KEYS[2]",
+        script: RedisSet::move_member_script,
+    },
+    BuiltinScript {
+        name: "unique_visitor",
+        description: "Count unique visitors with a set",
+        script: RedisSet::unique_visitor_script,
+    },
+    BuiltinScript {
+        name: "unique_rate_limiter",
+        description: "Rate limiter with unique tokens",
+        script: RedisSet::unique_rate_limiter_script,
+    },
+    // Sorted set scripts
+    BuiltinScript {
+        name: "zset_add_and_get_rank",
+        description: "Add a member with a score and return its rank",
+        script: RedisSortedSet::add_and_get_rank_script,
+    },
+    BuiltinScript {
+        name: "normalized_leaderboard",
+        description: "Leaderboard with score normalization",
+        script: RedisSortedSet::normalized_leaderboard_script,
+    },
+    BuiltinScript {
+        name: "windowed_leaderboard",
+        description: "Time-windowed leaderboard",
+        script: RedisSortedSet::windowed_leaderboard_script,
+    },
+    BuiltinScript {
+        name: "top_k_tracker",
+        description: "Top-K tracking with automatic pruning",
+        script: RedisSortedSet::top_k_tracker_script,
+    },
+    BuiltinScript {
+        name: "percentile",
+        description: "Percentile calculation over a sorted set",
+        script: RedisSortedSet::percentile_script,
+    },
+    // Bitmap scripts
+    BuiltinScript {
+        name: "bloom_filter",
+        description: "Bloom filter over a bitmap",
+        script: RedisBitmap::bloom_filter_script,
+    },
+    BuiltinScript {
+        name: "bitmap_rate_limiter",
+        description: "Rate limiter with bitmaps",
+        script: RedisBitmap::bitmap_rate_limiter_script,
+    },
+    BuiltinScript {
+        name: "unique_visitor_bitmap",
+        description: "Count unique visitors with bitmaps",
+        script: RedisBitmap::unique_visitor_bitmap_script,
+    },
+    // List scripts
+    BuiltinScript {
+        name: "capped_push",
+        description: "Push ARGV[1] onto KEYS[1] and trim it to ARGV[2] elements",
+        script: RedisList::capped_push_script,
+    },
+    BuiltinScript {
+        name: "reliable_pop",
+        description: "Move a job to a processing list and record its claim time",
+        script: RedisList::reliable_pop_script,
+    },
+    BuiltinScript {
+        name: "reliable_ack",
+        description: "Acknowledge a job taken with reliable_pop",
+        script: RedisList::reliable_ack_script,
+    },
+    BuiltinScript {
+        name: "requeue_stale",
+        description: "Requeue jobs claimed before a deadline",
+        script: RedisList::requeue_stale_script,
+    },
+    // Stream scripts
+    BuiltinScript {
+        name: "stream_ack_and_delete",
+        description: "Acknowledge entries for group ARGV[1] and delete them",
+        script: RedisStream::ack_and_delete_script,
+    },
+    BuiltinScript {
+        name: "stream_move_entry",
+        description: "Move entry ARGV[1] from KEYS[1] to KEYS[2]",
+        script: RedisStream::move_entry_script,
+    },
+    BuiltinScript {
+        name: "stream_ensure_group",
+        description: "Create group ARGV[1] at ARGV[2] if it doesn't exist",
+        script: RedisStream::ensure_group_script,
+    },
+];
+
+/// Listing entry for a named script
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ScriptInfo {
+    pub name: String,
+    pub description: Option<String>,
+    pub sha: String,
+    pub builtin: bool,
+}
+
+struct RegisteredScript {
+    description: Option<String>,
+    script: Script,
+    builtin: bool,
+}
+
+/// Named Lua scripts callable through the API
+///
+/// Built-in scripts come from the adapter catalog. Custom scripts are kept in
+/// memory only and have to be registered again after a restart; invoking any
+/// script uses EVALSHA and falls back to loading it on NOSCRIPT.
+pub struct ScriptRegistry {
+    scripts: RwLock<HashMap<String, RegisteredScript>>,
+}
+
+impl Default for ScriptRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScriptRegistry {
+    pub fn new() -> Self {
+        let scripts = BUILTIN_SCRIPTS
+            .iter()
+            .map(|builtin| {
+                (
+                    builtin.name.to_string(),
+                    RegisteredScript {
+                        description: Some(builtin.description.to_string()),
+                        script: (builtin.script)(),
+                        builtin: true,
+                    },
+                )
+            })
+            .collect();
+        Self {
+            scripts: RwLock::new(scripts),
+        }
+    }
+
+    /// Lists all scripts sorted by name
+    pub fn list(&self) -> Vec<ScriptInfo> {
+        let scripts = self.scripts.read().unwrap();
+        let mut infos: Vec<ScriptInfo> = scripts
+            .iter()
+            .map(|(name, registered)| ScriptInfo {
+                name: name.clone(),
+                description: registered.description.clone(),
+                sha: registered.script.get_hash().to_string(),
+                builtin: registered.builtin,
+            })
+            .collect();
+        infos.sort_by(|a, b| a.name.cmp(&b.name));
+        infos
+    }
+
+    pub fn get(&self, name: &str) -> Option<Script> {
+        let scripts = self.scripts.read().unwrap();
+        scripts
+            .get(name)
+            .map(|registered| registered.script.clone())
+    }
+
+    pub fn is_builtin(&self, name: &str) -> bool {
+        let scripts = self.scripts.read().unwrap();
+        scripts
+            .get(name)
+            .is_some_and(|registered| registered.builtin)
+    }
+
+    /// Loads a custom script into Redis with SCRIPT LOAD and registers it under `name`
+    ///
+    /// Replaces an earlier custom script with the same name. Built-in names
+    /// cannot be overridden, so check [`ScriptRegistry::is_builtin`] first.
+    pub async fn register(
+        &self,
+        mut conn: AsyncPooledConnection,
+        name: &str,
+        source: &str,
+        description: Option<String>,
+    ) -> redis::RedisResult<ScriptInfo> {
+        if self.is_builtin(name) {
+            return Err(redis::RedisError::from((
+                redis::ErrorKind::ClientError,
+                "Cannot override a built-in script",
+                name.to_string(),
+            )));
+        }
+
+        let script = Script::new(source);
+        let sha = script.prepare_invoke().load_async(&mut conn).await?;
+
+        let mut scripts = self.scripts.write().unwrap();
+        scripts.insert(
+            name.to_string(),
+            RegisteredScript {
+                description: description.clone(),
+                script,
+                builtin: false,
+            },
+        );
+        Ok(ScriptInfo {
+            name: name.to_string(),
+            description,
+            sha,
+            builtin: false,
+        })
+    }
+
+    /// Removes a custom script, returning whether it was registered
+    pub fn unregister(&self, name: &str) -> bool {
+        let mut scripts = self.scripts.write().unwrap();
+        match scripts.get(name) {
+            Some(registered) if !registered.builtin => scripts.remove(name).is_some(),
+            _ => false,
+        }
+    }
+}
+
+// =========================
+// Script Execution
+// =========================
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "EVALSHA", key_count = keys.len(), keys = ?keys))]
+pub async fn eval_named_script(
+    mut conn: AsyncPooledConnection,
+    script: &Script,
+    keys: &[String],
+    args: &[String],
+) -> redis::RedisResult<serde_json::Value> {
+    let mut invocation = script.prepare_invoke();
+    for key in keys {
+        invocation.key(key);
+    }
+    for arg in args {
+        invocation.arg(arg);
+    }
+    let value: Value = invocation.invoke_async(&mut conn).await?;
+    Ok(redis_value_to_json(value))
+}
+
+/// Converts a script reply into JSON; bulk strings are decoded as UTF-8
+pub fn redis_value_to_json(value: Value) -> serde_json::Value {
+    match value {
+        Value::Nil => serde_json::Value::Null,
+        Value::Int(i) => serde_json::Value::from(i),
+        Value::Data(bytes) => serde_json::Value::String(String::from_utf8_lossy(&bytes).into()),
+        Value::Bulk(items) => items.into_iter().map(redis_value_to_json).collect(),
+        Value::Status(status) => serde_json::Value::String(status),
+        Value::Okay => serde_json::Value::String("OK".to_string()),
+    }
+}
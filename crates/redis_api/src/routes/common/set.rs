@@ -1,7 +1,6 @@
-use dbx_adapter::redis::primitives::set::RedisSet;
-use redis::Connection;
+use dbx_adapter::redis::async_primitives::set::AsyncRedisSet;
+use dbx_adapter::redis::client::AsyncPooledConnection;
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SetOperation {
@@ -30,199 +29,253 @@ pub struct SetOperationRequest {
     pub members: Option<Vec<String>>,
 }
 
-fn redis_set(conn: Arc<Mutex<Connection>>) -> RedisSet {
-    RedisSet::new(conn)
+fn redis_set(conn: AsyncPooledConnection) -> AsyncRedisSet<AsyncPooledConnection> {
+    AsyncRedisSet::new(conn)
 }
 
 // =========================
 // Single Set Operations
 // =========================
 
-pub fn add_to_set(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SADD", key_count = 1, key = %key))]
+pub async fn add_to_set(
+    conn: AsyncPooledConnection,
     key: &str,
     members: &[&str],
 ) -> redis::RedisResult<usize> {
-    redis_set(conn).sadd(key, members)
+    redis_set(conn).sadd(key, members).await
 }
 
-pub fn remove_from_set(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SREM", key_count = 1, key = %key))]
+pub async fn remove_from_set(
+    conn: AsyncPooledConnection,
     key: &str,
     members: &[&str],
 ) -> redis::RedisResult<usize> {
-    redis_set(conn).srem(key, members)
+    redis_set(conn).srem(key, members).await
 }
 
-pub fn get_set_members(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<Vec<String>> {
-    redis_set(conn).smembers(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SMEMBERS", key_count = 1, key = %key))]
+pub async fn get_set_members(
+    conn: AsyncPooledConnection,
+    key: &str,
+) -> redis::RedisResult<Vec<String>> {
+    redis_set(conn).smembers(key).await
 }
 
-pub fn set_exists(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SISMEMBER", key_count = 1, key = %key))]
+pub async fn set_exists(
+    conn: AsyncPooledConnection,
     key: &str,
     member: &str,
 ) -> redis::RedisResult<bool> {
-    redis_set(conn).sismember(key, member)
+    redis_set(conn).sismember(key, member).await
 }
 
-pub fn get_set_cardinality(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<usize> {
-    redis_set(conn).scard(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SCARD", key_count = 1, key = %key))]
+pub async fn get_set_cardinality(
+    conn: AsyncPooledConnection,
+    key: &str,
+) -> redis::RedisResult<usize> {
+    redis_set(conn).scard(key).await
 }
 
-pub fn get_random_set_member(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SRANDMEMBER", key_count = 1, key = %key))]
+pub async fn get_random_set_member(
+    conn: AsyncPooledConnection,
     key: &str,
 ) -> redis::RedisResult<Option<String>> {
-    redis_set(conn).srandmember(key)
+    redis_set(conn).srandmember(key).await
 }
 
-pub fn get_random_set_members(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SRANDMEMBER", key_count = 1, key = %key))]
+pub async fn get_random_set_members(
+    conn: AsyncPooledConnection,
     key: &str,
     count: usize,
 ) -> redis::RedisResult<Vec<String>> {
-    redis_set(conn).srandmember_count(key, count)
+    redis_set(conn).srandmember_count(key, count).await
 }
 
-pub fn pop_set_member(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SPOP", key_count = 1, key = %key))]
+pub async fn pop_set_member(
+    conn: AsyncPooledConnection,
     key: &str,
 ) -> redis::RedisResult<Option<String>> {
-    redis_set(conn).spop(key)
+    redis_set(conn).spop(key).await
 }
 
-pub fn pop_set_members(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SPOP", key_count = 1, key = %key))]
+pub async fn pop_set_members(
+    conn: AsyncPooledConnection,
     key: &str,
     count: usize,
 ) -> redis::RedisResult<Vec<String>> {
-    redis_set(conn).spop_count(key, count)
+    redis_set(conn).spop_count(key, count).await
 }
 
-pub fn move_set_member(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SMOVE", key_count = 2, destination = %destination, source = %source))]
+pub async fn move_set_member(
+    conn: AsyncPooledConnection,
     source: &str,
     destination: &str,
     member: &str,
 ) -> redis::RedisResult<bool> {
-    redis_set(conn).smove(source, destination, member)
+    redis_set(conn).smove(source, destination, member).await
 }
 
 // =========================
 // Set Operations
 // =========================
 
-pub fn intersect_sets(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SINTER", key_count = keys.len(), keys = ?keys))]
+pub async fn intersect_sets(
+    conn: AsyncPooledConnection,
     keys: &[&str],
 ) -> redis::RedisResult<Vec<String>> {
-    redis_set(conn).sinter(keys)
+    redis_set(conn).sinter(keys).await
 }
 
-pub fn union_sets(conn: Arc<Mutex<Connection>>, keys: &[&str]) -> redis::RedisResult<Vec<String>> {
-    redis_set(conn).sunion(keys)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SUNION", key_count = keys.len(), keys = ?keys))]
+pub async fn union_sets(
+    conn: AsyncPooledConnection,
+    keys: &[&str],
+) -> redis::RedisResult<Vec<String>> {
+    redis_set(conn).sunion(keys).await
 }
 
-pub fn difference_sets(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SDIFF", key_count = keys.len(), keys = ?keys))]
+pub async fn difference_sets(
+    conn: AsyncPooledConnection,
     keys: &[&str],
 ) -> redis::RedisResult<Vec<String>> {
-    redis_set(conn).sdiff(keys)
+    redis_set(conn).sdiff(keys).await
 }
 
-pub fn intersect_sets_store(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SINTERSTORE", key_count = keys.len() + 1, keys = ?keys, destination = %destination))]
+pub async fn intersect_sets_store(
+    conn: AsyncPooledConnection,
     destination: &str,
     keys: &[&str],
 ) -> redis::RedisResult<usize> {
-    redis_set(conn).sinterstore(destination, keys)
+    redis_set(conn).sinterstore(destination, keys).await
 }
 
-pub fn union_sets_store(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SUNIONSTORE", key_count = keys.len() + 1, keys = ?keys, destination = %destination))]
+pub async fn union_sets_store(
+    conn: AsyncPooledConnection,
     destination: &str,
     keys: &[&str],
 ) -> redis::RedisResult<usize> {
-    redis_set(conn).sunionstore(destination, keys)
+    redis_set(conn).sunionstore(destination, keys).await
 }
 
-pub fn difference_sets_store(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SDIFFSTORE", key_count = keys.len() + 1, keys = ?keys, destination = %destination))]
+pub async fn difference_sets_store(
+    conn: AsyncPooledConnection,
     destination: &str,
     keys: &[&str],
 ) -> redis::RedisResult<usize> {
-    redis_set(conn).sdiffstore(destination, keys)
+    redis_set(conn).sdiffstore(destination, keys).await
 }
 
 // =========================
 // Set Management
 // =========================
 
-pub fn delete_set(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<bool> {
-    let exists = redis_set(conn.clone()).exists(key)?;
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "DEL", key_count = 1, key = %key))]
+pub async fn delete_set(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<bool> {
+    let exists = redis_set(conn.clone()).exists(key).await?;
     if exists {
-        redis_set(conn).del(key)?;
+        redis_set(conn).del(key).await?;
         Ok(true)
     } else {
         Ok(false)
     }
 }
 
-pub fn set_exists_key(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<bool> {
-    redis_set(conn).exists(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "EXISTS", key_count = 1, key = %key))]
+pub async fn set_exists_key(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<bool> {
+    redis_set(conn).exists(key).await
 }
 
-pub fn get_set_ttl(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<i64> {
-    redis_set(conn).ttl(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "TTL", key_count = 1, key = %key))]
+pub async fn get_set_ttl(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<i64> {
+    redis_set(conn).ttl(key).await
 }
 
-pub fn set_set_ttl(conn: Arc<Mutex<Connection>>, key: &str, ttl: u64) -> redis::RedisResult<bool> {
-    redis_set(conn).expire(key, ttl)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "EXPIRE", key_count = 1, key = %key))]
+pub async fn set_set_ttl(
+    conn: AsyncPooledConnection,
+    key: &str,
+    ttl: u64,
+) -> redis::RedisResult<bool> {
+    redis_set(conn).expire(key, ttl).await
 }
 
 // =========================
 // Batch Operations
 // =========================
 
-pub fn add_to_multiple_sets(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "SADD", key_count = set_members.len())
+)]
+pub async fn add_to_multiple_sets(
+    conn: AsyncPooledConnection,
     set_members: Vec<(&str, Vec<&str>)>,
 ) -> redis::RedisResult<Vec<usize>> {
-    redis_set(conn).sadd_many(set_members)
+    redis_set(conn).sadd_many(set_members).await
 }
 
-pub fn remove_from_multiple_sets(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "SREM", key_count = set_members.len())
+)]
+pub async fn remove_from_multiple_sets(
+    conn: AsyncPooledConnection,
     set_members: Vec<(&str, Vec<&str>)>,
 ) -> redis::RedisResult<Vec<usize>> {
-    redis_set(conn).srem_many(set_members)
+    redis_set(conn).srem_many(set_members).await
 }
 
-pub fn get_multiple_set_members(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SMEMBERS", key_count = keys.len(), keys = ?keys))]
+pub async fn get_multiple_set_members(
+    conn: AsyncPooledConnection,
     keys: Vec<&str>,
 ) -> redis::RedisResult<Vec<Vec<String>>> {
-    redis_set(conn).smembers_many(keys)
+    redis_set(conn).smembers_many(keys).await
 }
 
-pub fn check_multiple_set_members(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "SISMEMBER", key_count = key_members.len())
+)]
+pub async fn check_multiple_set_members(
+    conn: AsyncPooledConnection,
     key_members: Vec<(&str, &str)>,
 ) -> redis::RedisResult<Vec<bool>> {
-    redis_set(conn).sismember_many(key_members)
+    redis_set(conn).sismember_many(key_members).await
 }
 
-pub fn get_multiple_set_cardinalities(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SCARD", key_count = keys.len(), keys = ?keys))]
+pub async fn get_multiple_set_cardinalities(
+    conn: AsyncPooledConnection,
     keys: Vec<&str>,
 ) -> redis::RedisResult<Vec<usize>> {
-    redis_set(conn).scard_many(keys)
+    redis_set(conn).scard_many(keys).await
 }
 
-pub fn delete_multiple_sets(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "DEL", key_count = keys.len(), keys = ?keys))]
+pub async fn delete_multiple_sets(
+    conn: AsyncPooledConnection,
     keys: Vec<&str>,
 ) -> redis::RedisResult<()> {
-    redis_set(conn).del_many(keys)
+    redis_set(conn).del_many(keys).await
 }
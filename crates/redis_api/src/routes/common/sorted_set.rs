@@ -0,0 +1,295 @@
+use dbx_adapter::redis::async_primitives::sorted_set::AsyncRedisSortedSet;
+use dbx_adapter::redis::client::AsyncPooledConnection;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScoredMember {
+    pub member: String,
+    pub score: f64,
+}
+
+fn redis_sorted_set(conn: AsyncPooledConnection) -> AsyncRedisSortedSet<AsyncPooledConnection> {
+    AsyncRedisSortedSet::new(conn)
+}
+
+fn to_scored_members(items: Vec<(String, f64)>) -> Vec<ScoredMember> {
+    items
+        .into_iter()
+        .map(|(member, score)| ScoredMember { member, score })
+        .collect()
+}
+
+// =========================
+// Single Sorted Set Operations
+// =========================
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZADD", key_count = 1, key = %key))]
+pub async fn add_to_sorted_set(
+    conn: AsyncPooledConnection,
+    key: &str,
+    members: &[ScoredMember],
+) -> redis::RedisResult<usize> {
+    let items: Vec<(f64, &str)> = members
+        .iter()
+        .map(|m| (m.score, m.member.as_str()))
+        .collect();
+    redis_sorted_set(conn).zadd(key, &items).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZREM", key_count = 1, key = %key))]
+pub async fn remove_from_sorted_set(
+    conn: AsyncPooledConnection,
+    key: &str,
+    members: &[&str],
+) -> redis::RedisResult<usize> {
+    redis_sorted_set(conn).zrem(key, members).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZRANGE", key_count = 1, key = %key))]
+pub async fn get_sorted_set_range(
+    conn: AsyncPooledConnection,
+    key: &str,
+    start: isize,
+    stop: isize,
+    reverse: bool,
+) -> redis::RedisResult<Vec<ScoredMember>> {
+    let zset = redis_sorted_set(conn);
+    let items = if reverse {
+        zset.zrevrange_withscores(key, start, stop).await?
+    } else {
+        zset.zrange_withscores(key, start, stop).await?
+    };
+    Ok(to_scored_members(items))
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZRANGEBYSCORE", key_count = 1, key = %key))]
+pub async fn get_sorted_set_range_by_score(
+    conn: AsyncPooledConnection,
+    key: &str,
+    min: f64,
+    max: f64,
+    limit: Option<(isize, isize)>,
+    reverse: bool,
+) -> redis::RedisResult<Vec<ScoredMember>> {
+    let zset = redis_sorted_set(conn);
+    let items = match (limit, reverse) {
+        (Some((offset, count)), false) => {
+            zset.zrangebyscore_limit_withscores(key, min, max, offset, count)
+                .await?
+        }
+        (Some((offset, count)), true) => {
+            zset.zrevrangebyscore_limit_withscores(key, max, min, offset, count)
+                .await?
+        }
+        (None, false) => zset.zrangebyscore_withscores(key, min, max).await?,
+        (None, true) => zset.zrevrangebyscore_withscores(key, max, min).await?,
+    };
+    Ok(to_scored_members(items))
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZSCORE", key_count = 1, key = %key))]
+pub async fn get_member_score(
+    conn: AsyncPooledConnection,
+    key: &str,
+    member: &str,
+) -> redis::RedisResult<Option<f64>> {
+    redis_sorted_set(conn).zscore(key, member).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZRANK", key_count = 1, key = %key))]
+pub async fn get_member_rank(
+    conn: AsyncPooledConnection,
+    key: &str,
+    member: &str,
+    reverse: bool,
+) -> redis::RedisResult<Option<usize>> {
+    if reverse {
+        redis_sorted_set(conn).zrevrank(key, member).await
+    } else {
+        redis_sorted_set(conn).zrank(key, member).await
+    }
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZINCRBY", key_count = 1, key = %key))]
+pub async fn increment_member_score(
+    conn: AsyncPooledConnection,
+    key: &str,
+    member: &str,
+    increment: f64,
+) -> redis::RedisResult<f64> {
+    redis_sorted_set(conn).zincrby(key, increment, member).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZCARD", key_count = 1, key = %key))]
+pub async fn get_sorted_set_cardinality(
+    conn: AsyncPooledConnection,
+    key: &str,
+) -> redis::RedisResult<usize> {
+    redis_sorted_set(conn).zcard(key).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZCOUNT", key_count = 1, key = %key))]
+pub async fn count_sorted_set_range(
+    conn: AsyncPooledConnection,
+    key: &str,
+    min: f64,
+    max: f64,
+) -> redis::RedisResult<usize> {
+    redis_sorted_set(conn).zcount(key, min, max).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZREMRANGEBYRANK", key_count = 1, key = %key))]
+pub async fn remove_sorted_set_range_by_rank(
+    conn: AsyncPooledConnection,
+    key: &str,
+    start: isize,
+    stop: isize,
+) -> redis::RedisResult<usize> {
+    redis_sorted_set(conn)
+        .zremrangebyrank(key, start, stop)
+        .await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZREMRANGEBYSCORE", key_count = 1, key = %key))]
+pub async fn remove_sorted_set_range_by_score(
+    conn: AsyncPooledConnection,
+    key: &str,
+    min: f64,
+    max: f64,
+) -> redis::RedisResult<usize> {
+    redis_sorted_set(conn).zremrangebyscore(key, min, max).await
+}
+
+// =========================
+// Sorted Set Operations
+// =========================
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZINTERSTORE", key_count = keys.len() + 1, keys = ?keys, destination = %destination))]
+pub async fn intersect_sorted_sets_store(
+    conn: AsyncPooledConnection,
+    destination: &str,
+    keys: &[&str],
+    weights: Option<&[f64]>,
+) -> redis::RedisResult<usize> {
+    match weights {
+        Some(weights) => {
+            redis_sorted_set(conn)
+                .zinterstore_weights(destination, keys, weights)
+                .await
+        }
+        None => redis_sorted_set(conn).zinterstore(destination, keys).await,
+    }
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZUNIONSTORE", key_count = keys.len() + 1, keys = ?keys, destination = %destination))]
+pub async fn union_sorted_sets_store(
+    conn: AsyncPooledConnection,
+    destination: &str,
+    keys: &[&str],
+    weights: Option<&[f64]>,
+) -> redis::RedisResult<usize> {
+    match weights {
+        Some(weights) => {
+            redis_sorted_set(conn)
+                .zunionstore_weights(destination, keys, weights)
+                .await
+        }
+        None => redis_sorted_set(conn).zunionstore(destination, keys).await,
+    }
+}
+
+// =========================
+// Sorted Set Management
+// =========================
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "DEL", key_count = 1, key = %key))]
+pub async fn delete_sorted_set(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<bool> {
+    let exists = redis_sorted_set(conn.clone()).exists(key).await?;
+    if exists {
+        redis_sorted_set(conn).del(key).await?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "EXISTS", key_count = 1, key = %key))]
+pub async fn sorted_set_exists_key(
+    conn: AsyncPooledConnection,
+    key: &str,
+) -> redis::RedisResult<bool> {
+    redis_sorted_set(conn).exists(key).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "TTL", key_count = 1, key = %key))]
+pub async fn get_sorted_set_ttl(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<i64> {
+    redis_sorted_set(conn).ttl(key).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "EXPIRE", key_count = 1, key = %key))]
+pub async fn set_sorted_set_ttl(
+    conn: AsyncPooledConnection,
+    key: &str,
+    ttl: u64,
+) -> redis::RedisResult<bool> {
+    redis_sorted_set(conn).expire(key, ttl).await
+}
+
+// =========================
+// Batch Operations
+// =========================
+
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "ZADD", key_count = set_members.len())
+)]
+pub async fn add_to_multiple_sorted_sets(
+    conn: AsyncPooledConnection,
+    set_members: Vec<(&str, Vec<(f64, &str)>)>,
+) -> redis::RedisResult<Vec<usize>> {
+    redis_sorted_set(conn).zadd_many(set_members).await
+}
+
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "ZREM", key_count = set_members.len())
+)]
+pub async fn remove_from_multiple_sorted_sets(
+    conn: AsyncPooledConnection,
+    set_members: Vec<(&str, Vec<&str>)>,
+) -> redis::RedisResult<Vec<usize>> {
+    redis_sorted_set(conn).zrem_many(set_members).await
+}
+
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "ZSCORE", key_count = key_members.len())
+)]
+pub async fn get_multiple_member_scores(
+    conn: AsyncPooledConnection,
+    key_members: Vec<(&str, &str)>,
+) -> redis::RedisResult<Vec<Option<f64>>> {
+    redis_sorted_set(conn).zscore_many(key_members).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "ZCARD", key_count = keys.len(), keys = ?keys))]
+pub async fn get_multiple_sorted_set_cardinalities(
+    conn: AsyncPooledConnection,
+    keys: Vec<&str>,
+) -> redis::RedisResult<Vec<usize>> {
+    redis_sorted_set(conn).zcard_many(keys).await
+}
+
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "DEL", key_count = keys.len(), keys = ?keys))]
+pub async fn delete_multiple_sorted_sets(
+    conn: AsyncPooledConnection,
+    keys: Vec<&str>,
+) -> redis::RedisResult<()> {
+    redis_sorted_set(conn).del_many(keys).await
+}
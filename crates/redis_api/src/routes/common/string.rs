@@ -1,7 +1,6 @@
-use dbx_adapter::redis::primitives::string::RedisString;
-use redis::Connection;
+use dbx_adapter::redis::async_primitives::string::AsyncRedisString;
+use dbx_adapter::redis::client::AsyncPooledConnection;
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
 
 // Type alias for complex return type
 type PatternGroupedResults = Vec<(String, Vec<(String, Option<String>)>)>;
@@ -31,36 +30,49 @@ pub struct StringInfo {
     pub size: usize,
 }
 
-fn redis_string(conn: Arc<Mutex<Connection>>) -> RedisString {
-    RedisString::new(conn)
+fn redis_string(conn: AsyncPooledConnection) -> AsyncRedisString<AsyncPooledConnection> {
+    AsyncRedisString::new(conn)
 }
 
 // =========================
 // Single Key Operations
 // =========================
 
-pub fn get_string(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<Option<String>> {
-    redis_string(conn).get(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "GET", key_count = 1, key = %key))]
+pub async fn get_string(
+    conn: AsyncPooledConnection,
+    key: &str,
+) -> redis::RedisResult<Option<String>> {
+    redis_string(conn).get(key).await
 }
 
-pub fn set_string(conn: Arc<Mutex<Connection>>, key: &str, value: &str) -> redis::RedisResult<()> {
-    redis_string(conn).set(key, value)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SET", key_count = 1, key = %key))]
+pub async fn set_string(
+    conn: AsyncPooledConnection,
+    key: &str,
+    value: &str,
+) -> redis::RedisResult<()> {
+    redis_string(conn).set(key, value).await
 }
 
-pub fn set_string_with_ttl(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "SETEX", key_count = 1, key = %key))]
+pub async fn set_string_with_ttl(
+    conn: AsyncPooledConnection,
     key: &str,
     value: &str,
     ttl: u64,
 ) -> redis::RedisResult<()> {
-    redis_string(conn).set_with_expiry(key, value, ttl as usize)
+    redis_string(conn)
+        .set_with_expiry(key, value, ttl as usize)
+        .await
 }
 
-pub fn delete_string(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<bool> {
-    // RedisString::del returns (), so we check existence first
-    let exists = redis_string(conn.clone()).exists(key)?;
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "DEL", key_count = 1, key = %key))]
+pub async fn delete_string(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<bool> {
+    // AsyncRedisString::del returns (), so we check existence first
+    let exists = redis_string(conn.clone()).exists(key).await?;
     if exists {
-        redis_string(conn).del(key)?;
+        redis_string(conn).del(key).await?;
         Ok(true)
     } else {
         // Return false if key doesn't exist (Redis DEL behavior - returns 0 for non-existent keys)
@@ -68,18 +80,19 @@ pub fn delete_string(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisRes
     }
 }
 
-pub fn get_string_info(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "GET", key_count = 1, key = %key))]
+pub async fn get_string_info(
+    conn: AsyncPooledConnection,
     key: &str,
 ) -> redis::RedisResult<Option<StringInfo>> {
     let redis_str = redis_string(conn.clone());
-    let type_ = if redis_str.exists(key)? {
+    let type_ = if redis_str.exists(key).await? {
         "string".to_string()
     } else {
         return Ok(None);
     };
-    let value = redis_str.get(key)?.unwrap_or_default();
-    let ttl = redis_str.ttl(key).ok();
+    let value = redis_str.get(key).await?.unwrap_or_default();
+    let ttl = redis_str.ttl(key).await.ok();
     let encoding = "raw".to_string();
     let size = value.len();
     Ok(Some(StringInfo {
@@ -92,45 +105,51 @@ pub fn get_string_info(
     }))
 }
 
-pub fn increment_string(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<i64> {
-    redis_string(conn).incr(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "INCR", key_count = 1, key = %key))]
+pub async fn increment_string(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<i64> {
+    redis_string(conn).incr(key).await
 }
 
-pub fn increment_string_by(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "INCRBY", key_count = 1, key = %key))]
+pub async fn increment_string_by(
+    conn: AsyncPooledConnection,
     key: &str,
     amount: i64,
 ) -> redis::RedisResult<i64> {
-    redis_string(conn).incr_by(key, amount)
+    redis_string(conn).incr_by(key, amount).await
 }
 
-pub fn decrement_string(conn: Arc<Mutex<Connection>>, key: &str) -> redis::RedisResult<i64> {
-    redis_string(conn).decr(key)
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "DECR", key_count = 1, key = %key))]
+pub async fn decrement_string(conn: AsyncPooledConnection, key: &str) -> redis::RedisResult<i64> {
+    redis_string(conn).decr(key).await
 }
 
-pub fn decrement_string_by(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "DECRBY", key_count = 1, key = %key))]
+pub async fn decrement_string_by(
+    conn: AsyncPooledConnection,
     key: &str,
     amount: i64,
 ) -> redis::RedisResult<i64> {
-    redis_string(conn).decr_by(key, amount)
+    redis_string(conn).decr_by(key, amount).await
 }
 
-pub fn append_string(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "APPEND", key_count = 1, key = %key))]
+pub async fn append_string(
+    conn: AsyncPooledConnection,
     key: &str,
     value: &str,
 ) -> redis::RedisResult<usize> {
-    redis_string(conn).append(key, value)
+    redis_string(conn).append(key, value).await
 }
 
-pub fn get_string_length(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "GET", key_count = 1, key = %key))]
+pub async fn get_string_length(
+    conn: AsyncPooledConnection,
     key: &str,
 ) -> redis::RedisResult<Option<usize>> {
     let redis_str = redis_string(conn);
-    if redis_str.exists(key)? {
-        let len = redis_str.get(key)?.map(|v| v.len()).unwrap_or(0);
+    if redis_str.exists(key).await? {
+        let len = redis_str.get(key).await?.map(|v| v.len()).unwrap_or(0);
         Ok(Some(len))
     } else {
         Ok(None)
@@ -141,17 +160,24 @@ pub fn get_string_length(
 // Batch Operations
 // =========================
 
-pub fn get_multiple_strings(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "MGET", key_count = keys.len(), keys = ?keys))]
+pub async fn get_multiple_strings(
+    conn: AsyncPooledConnection,
     keys: &[String],
 ) -> redis::RedisResult<Vec<Option<String>>> {
     let redis_str = redis_string(conn);
     let key_refs: Vec<&str> = keys.iter().map(|k| k.as_str()).collect();
-    redis_str.get_many(key_refs)
+    redis_str.get_many(key_refs).await
 }
 
-pub fn set_multiple_strings(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(
+    name = "redis",
+    level = "debug",
+    skip_all,
+    fields(command = "MSET", key_count = operations.len())
+)]
+pub async fn set_multiple_strings(
+    conn: AsyncPooledConnection,
     operations: &[StringOperation],
 ) -> redis::RedisResult<()> {
     let redis_str = redis_string(conn);
@@ -159,21 +185,24 @@ pub fn set_multiple_strings(
     for op in operations {
         if let Some(value) = &op.value {
             if let Some(ttl) = op.ttl {
-                redis_str.set_with_expiry(&op.key, value, ttl as usize)?;
+                redis_str
+                    .set_with_expiry(&op.key, value, ttl as usize)
+                    .await?;
             } else {
                 kvs.push((op.key.as_str(), value.as_str()));
             }
         }
     }
     if !kvs.is_empty() {
-        redis_str.set_many(kvs)?;
+        redis_str.set_many(kvs).await?;
     }
     Ok(())
 }
 
 /// Get multiple strings by patterns, expanding each pattern to matching keys
-pub fn get_strings_by_patterns(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "KEYS", key_count = 0, patterns = ?patterns))]
+pub async fn get_strings_by_patterns(
+    conn: AsyncPooledConnection,
     patterns: &[String],
 ) -> redis::RedisResult<Vec<(String, Option<String>)>> {
     let redis_str = redis_string(conn);
@@ -181,7 +210,7 @@ pub fn get_strings_by_patterns(
 
     for pattern in patterns {
         // Get all keys matching the pattern
-        let matching_keys = redis_str.keys(pattern)?;
+        let matching_keys = redis_str.keys(pattern).await?;
 
         if matching_keys.is_empty() {
             // If no keys match, add the pattern with None value
@@ -189,7 +218,7 @@ pub fn get_strings_by_patterns(
         } else {
             // Get values for all matching keys
             let key_refs: Vec<&str> = matching_keys.iter().map(|k| k.as_str()).collect();
-            let values = redis_str.get_many(key_refs)?;
+            let values = redis_str.get_many(key_refs).await?;
 
             // Combine keys with their values
             for (key, value) in matching_keys.into_iter().zip(values.into_iter()) {
@@ -202,8 +231,9 @@ pub fn get_strings_by_patterns(
 }
 
 /// Get multiple strings by patterns, returning results grouped by pattern
-pub fn get_strings_by_patterns_grouped(
-    conn: Arc<Mutex<Connection>>,
+#[tracing::instrument(name = "redis", level = "debug", skip_all, fields(command = "KEYS", key_count = 0, patterns = ?patterns))]
+pub async fn get_strings_by_patterns_grouped(
+    conn: AsyncPooledConnection,
     patterns: &[String],
 ) -> redis::RedisResult<PatternGroupedResults> {
     let redis_str = redis_string(conn);
@@ -211,7 +241,7 @@ pub fn get_strings_by_patterns_grouped(
 
     for pattern in patterns {
         // Get all keys matching the pattern
-        let matching_keys = redis_str.keys(pattern)?;
+        let matching_keys = redis_str.keys(pattern).await?;
 
         if matching_keys.is_empty() {
             // If no keys match, add the pattern with empty results
@@ -219,7 +249,7 @@ pub fn get_strings_by_patterns_grouped(
         } else {
             // Get values for all matching keys
             let key_refs: Vec<&str> = matching_keys.iter().map(|k| k.as_str()).collect();
-            let values = redis_str.get_many(key_refs)?;
+            let values = redis_str.get_many(key_refs).await?;
 
             // Combine keys with their values
             let pattern_results: Vec<(String, Option<String>)> =
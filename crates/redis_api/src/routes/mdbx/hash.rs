@@ -0,0 +1,180 @@
+use crate::error::ApiError;
+use crate::models::ApiResponse;
+use crate::policy::Access;
+use axum::{
+    extract::{Json, Path, State},
+    routing::{delete, get, post},
+    Router,
+};
+use dbx_adapter::mdbx::MdbxAdapter;
+use dbx_adapter::traits::{HashAdapter, KeyValueAdapter};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+struct SetHashFieldRequest {
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetMultipleHashFieldsRequest {
+    fields: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetHashFieldsRequest {
+    fields: Vec<String>,
+}
+
+// Single field operations
+async fn get_hash_field_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path((key, field)): Path<(String, String)>,
+) -> Result<ApiResponse<Option<String>>, ApiError> {
+    access.read([&key])?;
+    let value = db.hget(&key, &field).await?;
+    Ok(ApiResponse::success(value))
+}
+
+async fn set_hash_field_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path((key, field)): Path<(String, String)>,
+    Json(payload): Json<SetHashFieldRequest>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let created = db.hset_new(&key, &field, &payload.value)?;
+    Ok(ApiResponse::success(created))
+}
+
+async fn delete_hash_field_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path((key, field)): Path<(String, String)>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let deleted = db.hdel(&key, &field).await?;
+    Ok(ApiResponse::success(deleted))
+}
+
+async fn hash_exists_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path((key, field)): Path<(String, String)>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.read([&key])?;
+    let value = db.hget(&key, &field).await?;
+    Ok(ApiResponse::success(value.is_some()))
+}
+
+// Hash operations
+async fn get_all_hash_fields_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<HashMap<String, String>>, ApiError> {
+    access.read([&key])?;
+    let fields = db.hgetall(&key).await?;
+    Ok(ApiResponse::success(fields))
+}
+
+async fn get_hash_fields_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+    Json(payload): Json<GetHashFieldsRequest>,
+) -> Result<ApiResponse<Vec<Option<String>>>, ApiError> {
+    access.read([&key])?;
+    let mut fields = db.hgetall(&key).await?;
+    let values = payload
+        .fields
+        .iter()
+        .map(|field| fields.remove(field))
+        .collect();
+    Ok(ApiResponse::success(values))
+}
+
+async fn set_multiple_hash_fields_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+    Json(payload): Json<SetMultipleHashFieldsRequest>,
+) -> Result<ApiResponse<()>, ApiError> {
+    access.write([&key])?;
+    let field_values: Vec<(&str, &str)> = payload
+        .fields
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+    db.hset_many(&key, &field_values)?;
+    Ok(ApiResponse::success(()))
+}
+
+async fn get_hash_length_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.read([&key])?;
+    let fields = db.hgetall(&key).await?;
+    Ok(ApiResponse::success(fields.len()))
+}
+
+async fn get_hash_keys_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read([&key])?;
+    let fields = db.hgetall(&key).await?;
+    Ok(ApiResponse::success(fields.into_keys().collect()))
+}
+
+async fn get_hash_values_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read([&key])?;
+    let fields = db.hgetall(&key).await?;
+    Ok(ApiResponse::success(fields.into_values().collect()))
+}
+
+async fn delete_hash_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let deleted = db.delete(&key).await?;
+    Ok(ApiResponse::success(deleted))
+}
+
+async fn hash_exists_key_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.read([&key])?;
+    let fields = db.hgetall(&key).await?;
+    Ok(ApiResponse::success(!fields.is_empty()))
+}
+
+pub fn create_mdbx_hash_routes(db: Arc<MdbxAdapter>) -> Router {
+    Router::new()
+        .route("/hash/:key/:field", get(get_hash_field_handler))
+        .route("/hash/:key/:field", post(set_hash_field_handler))
+        .route("/hash/:key/:field", delete(delete_hash_field_handler))
+        .route("/hash/:key/:field/exists", get(hash_exists_handler))
+        .route("/hash/:key", get(get_all_hash_fields_handler))
+        .route("/hash/:key/fields", post(get_hash_fields_handler))
+        .route("/hash/:key/batch", post(set_multiple_hash_fields_handler))
+        .route("/hash/:key/length", get(get_hash_length_handler))
+        .route("/hash/:key/keys", get(get_hash_keys_handler))
+        .route("/hash/:key/values", get(get_hash_values_handler))
+        .route("/hash/:key", delete(delete_hash_handler))
+        .route("/hash/:key/exists", get(hash_exists_key_handler))
+        .with_state(db)
+}
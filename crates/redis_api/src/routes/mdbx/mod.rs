@@ -0,0 +1,9 @@
+//! HTTP routes backed by an MDBX environment
+//!
+//! These serve the string, hash and set routes under the same paths and JSON
+//! shapes as [`crate::routes::redis`], for the operations the MDBX adapter
+//! supports.
+
+pub mod hash;
+pub mod set;
+pub mod string;
@@ -0,0 +1,187 @@
+use crate::error::ApiError;
+use crate::models::ApiResponse;
+use crate::policy::Access;
+use axum::{
+    extract::{Json, Path, State},
+    routing::{delete, get, post},
+    Router,
+};
+use dbx_adapter::mdbx::MdbxAdapter;
+use dbx_adapter::traits::{KeyValueAdapter, SetAdapter};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+struct SetMemberRequest {
+    member: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetMembersRequest {
+    members: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetKeysRequest {
+    keys: Vec<String>,
+}
+
+/// How to combine sets
+enum SetOperation {
+    Intersect,
+    Union,
+    Difference,
+}
+
+/// Combine the sets stored at `keys`, treating missing keys as empty sets
+async fn combine_sets(
+    db: &MdbxAdapter,
+    keys: &[String],
+    operation: SetOperation,
+) -> Result<Vec<String>, ApiError> {
+    let mut sets = Vec::with_capacity(keys.len());
+    for key in keys {
+        let members = db.smembers(key).await?;
+        sets.push(members.into_iter().collect::<BTreeSet<String>>());
+    }
+
+    let mut sets = sets.into_iter();
+    let Some(first) = sets.next() else {
+        return Ok(Vec::new());
+    };
+    let result = sets.fold(first, |acc, set| match operation {
+        SetOperation::Intersect => &acc & &set,
+        SetOperation::Union => &acc | &set,
+        SetOperation::Difference => &acc - &set,
+    });
+    Ok(result.into_iter().collect())
+}
+
+// Add member to set
+async fn add_to_set_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+    Json(payload): Json<SetMemberRequest>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
+    let added = db.sadd_many(&key, &[&payload.member])?;
+    Ok(ApiResponse::success(added))
+}
+
+// Add multiple members to set
+async fn add_many_to_set_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+    Json(payload): Json<SetMembersRequest>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
+    let member_refs: Vec<&str> = payload.members.iter().map(|s| s.as_str()).collect();
+    let added = db.sadd_many(&key, &member_refs)?;
+    Ok(ApiResponse::success(added))
+}
+
+// Remove member from set
+async fn remove_from_set_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path((key, member)): Path<(String, String)>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
+    let removed = db.srem(&key, &member).await?;
+    Ok(ApiResponse::success(usize::from(removed)))
+}
+
+// Get all set members
+async fn get_set_members_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read([&key])?;
+    let members = db.smembers(&key).await?;
+    Ok(ApiResponse::success(members))
+}
+
+// Get set cardinality
+async fn get_set_cardinality_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.read([&key])?;
+    let cardinality = db.scard(&key)?;
+    Ok(ApiResponse::success(cardinality))
+}
+
+// Check if member exists in set
+async fn set_exists_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path((key, member)): Path<(String, String)>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.read([&key])?;
+    let exists = db.sismember(&key, &member).await?;
+    Ok(ApiResponse::success(exists))
+}
+
+// Intersect sets
+async fn intersect_sets_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Json(payload): Json<SetKeysRequest>,
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read(&payload.keys)?;
+    let result = combine_sets(&db, &payload.keys, SetOperation::Intersect).await?;
+    Ok(ApiResponse::success(result))
+}
+
+// Union sets
+async fn union_sets_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Json(payload): Json<SetKeysRequest>,
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read(&payload.keys)?;
+    let result = combine_sets(&db, &payload.keys, SetOperation::Union).await?;
+    Ok(ApiResponse::success(result))
+}
+
+// Difference of sets
+async fn difference_sets_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Json(payload): Json<SetKeysRequest>,
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read(&payload.keys)?;
+    let result = combine_sets(&db, &payload.keys, SetOperation::Difference).await?;
+    Ok(ApiResponse::success(result))
+}
+
+// Delete entire set
+async fn delete_set_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let deleted = db.delete(&key).await?;
+    Ok(ApiResponse::success(deleted))
+}
+
+pub fn create_mdbx_set_routes(db: Arc<MdbxAdapter>) -> Router {
+    Router::new()
+        .route("/set/:key", post(add_to_set_handler))
+        .route("/set/:key", delete(delete_set_handler))
+        .route("/set/:key/many", post(add_many_to_set_handler))
+        .route("/set/:key/members", get(get_set_members_handler))
+        .route("/set/:key/cardinality", get(get_set_cardinality_handler))
+        .route("/set/:key/:member/exists", get(set_exists_handler))
+        .route("/set/:key/:member", delete(remove_from_set_handler))
+        .route("/set/intersect", post(intersect_sets_handler))
+        .route("/set/union", post(union_sets_handler))
+        .route("/set/difference", post(difference_sets_handler))
+        .with_state(db)
+}
@@ -0,0 +1,112 @@
+use crate::error::{ApiError, ErrorCode};
+use crate::models::ApiResponse;
+use crate::policy::Access;
+use crate::routes::common::string::StringOperation;
+use axum::{
+    extract::{Json, Path, State},
+    routing::{any, delete, get, post},
+    Router,
+};
+use dbx_adapter::mdbx::MdbxAdapter;
+use dbx_adapter::traits::KeyValueAdapter;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+struct SetStringRequest {
+    value: String,
+    ttl: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchGetRequest {
+    keys: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchSetRequest {
+    operations: Vec<StringOperation>,
+}
+
+async fn get_string_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<Option<String>>, ApiError> {
+    access.read([&key])?;
+    let value = db.get(&key).await?;
+    Ok(ApiResponse::success(value))
+}
+
+async fn set_string_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+    Json(payload): Json<SetStringRequest>,
+) -> Result<ApiResponse<()>, ApiError> {
+    access.write([&key])?;
+    if let Some(ttl) = payload.ttl {
+        db.set_with_expiry(&key, &payload.value, ttl).await?;
+    } else {
+        db.set(&key, &payload.value).await?;
+    }
+    Ok(ApiResponse::success(()))
+}
+
+async fn delete_string_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let deleted = db.delete(&key).await?;
+    Ok(ApiResponse::success(deleted))
+}
+
+// Batch operations
+async fn batch_get_strings_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Json(payload): Json<BatchGetRequest>,
+) -> Result<ApiResponse<Vec<Option<String>>>, ApiError> {
+    access.read(&payload.keys)?;
+    let mut values = Vec::with_capacity(payload.keys.len());
+    for key in &payload.keys {
+        values.push(db.get(key).await?);
+    }
+    Ok(ApiResponse::success(values))
+}
+
+async fn batch_set_strings_handler(
+    access: Access,
+    State(db): State<Arc<MdbxAdapter>>,
+    Json(payload): Json<BatchSetRequest>,
+) -> Result<ApiResponse<()>, ApiError> {
+    access.write(payload.operations.iter().map(|op| &op.key))?;
+    for operation in &payload.operations {
+        let Some(value) = &operation.value else {
+            continue;
+        };
+        let result = match operation.ttl {
+            Some(ttl) => db.set_with_expiry(&operation.key, value, ttl).await,
+            None => db.set(&operation.key, value).await,
+        };
+        result?;
+    }
+    Ok(ApiResponse::success(()))
+}
+
+async fn method_not_allowed() -> ApiError {
+    ApiError::new(ErrorCode::MethodNotAllowed, "Method not allowed")
+}
+
+pub fn create_mdbx_string_routes(db: Arc<MdbxAdapter>) -> Router {
+    Router::new()
+        .route("/string/:key", get(get_string_handler))
+        .route("/string/:key", post(set_string_handler))
+        .route("/string/:key", delete(delete_string_handler))
+        .route("/string/:key", any(method_not_allowed))
+        .route("/string/batch/get", post(batch_get_strings_handler))
+        .route("/string/batch/set", post(batch_set_strings_handler))
+        .with_state(db)
+}
@@ -1,3 +1,7 @@
+pub mod backends;
 pub mod common;
+#[cfg(feature = "mdbx")]
+pub mod mdbx;
 pub mod redis;
 pub mod redis_ws;
+pub mod reload;
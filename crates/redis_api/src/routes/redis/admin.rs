@@ -1,16 +1,20 @@
+use crate::constants::errors::ErrorMessages;
+use crate::error::{ApiError, ErrorCode};
+use crate::models::ApiResponse;
+use crate::policy::Access;
 use crate::routes::common::admin::{
     config_get, config_get_all, config_reset_statistics, config_rewrite, config_set,
     flush_all_databases, flush_current_database, get_client_stats, get_database_size,
     get_memory_stats, get_server_info, get_server_info_section, get_server_stats, get_server_time,
     get_server_version, health_check, ping_server, server_status,
 };
+use crate::shutdown::Shutdown;
 use axum::{
     extract::{Json, Path, State},
-    http::StatusCode,
     routing::{delete, get, post},
     Router,
 };
-use dbx_adapter::redis::client::RedisPool;
+use dbx_adapter::redis::client::{PoolMetrics, RedisPool};
 use dbx_adapter::redis::primitives::admin::{HealthCheck, ServerStatus};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -26,88 +30,109 @@ struct ConfigSetPayload {
 // Basic Health & Status Handlers
 // =========================
 
-async fn ping_handler(State(pool): State<Arc<RedisPool>>) -> Result<Json<String>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let response = ping_server(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(response))
+async fn ping_handler(State(pool): State<Arc<RedisPool>>) -> Result<ApiResponse<String>, ApiError> {
+    let conn = pool.get_multiplexed_connection().await?;
+    let response = ping_server(conn).await?;
+    Ok(ApiResponse::success(response))
 }
 
-async fn info_handler(State(pool): State<Arc<RedisPool>>) -> Result<Json<String>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let info = get_server_info(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(info))
+async fn info_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+) -> Result<ApiResponse<String>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let info = get_server_info(conn).await?;
+    Ok(ApiResponse::success(info))
 }
 
 async fn info_section_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(section): Path<String>,
-) -> Result<Json<String>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let info = get_server_info_section(conn_arc, &section)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(info))
+) -> Result<ApiResponse<String>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let info = get_server_info_section(conn, &section).await?;
+    Ok(ApiResponse::success(info))
 }
 
-async fn dbsize_handler(State(pool): State<Arc<RedisPool>>) -> Result<Json<i64>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let size = get_database_size(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(size))
+async fn dbsize_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+) -> Result<ApiResponse<i64>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let size = get_database_size(conn).await?;
+    Ok(ApiResponse::success(size))
 }
 
-async fn time_handler(State(pool): State<Arc<RedisPool>>) -> Result<Json<(i64, i64)>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let time = get_server_time(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(time))
+async fn time_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+) -> Result<ApiResponse<(i64, i64)>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let time = get_server_time(conn).await?;
+    Ok(ApiResponse::success(time))
 }
 
-async fn version_handler(State(pool): State<Arc<RedisPool>>) -> Result<Json<String>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let version = get_server_version(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(version))
+async fn version_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+) -> Result<ApiResponse<String>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let version = get_server_version(conn).await?;
+    Ok(ApiResponse::success(version))
 }
 
 // =========================
 // Health Check Handlers
 // =========================
 
+fn not_draining(shutdown: &Shutdown) -> Result<(), ApiError> {
+    if shutdown.is_draining() {
+        return Err(ApiError::new(
+            ErrorCode::Unavailable,
+            ErrorMessages::SHUTTING_DOWN,
+        ));
+    }
+    Ok(())
+}
+
+/// Reports not ready while the server drains connections to shut down
 async fn health_check_handler(
+    shutdown: Shutdown,
     State(pool): State<Arc<RedisPool>>,
-) -> Result<Json<HealthCheck>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let health = health_check(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(health))
+) -> Result<ApiResponse<HealthCheck>, ApiError> {
+    not_draining(&shutdown)?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let health = health_check(conn).await?;
+    Ok(ApiResponse::success(health))
+}
+
+/// Ready once the default backend answers PING, and not while draining
+async fn readiness_handler(
+    shutdown: Shutdown,
+    State(pool): State<Option<Arc<RedisPool>>>,
+) -> Result<ApiResponse<String>, ApiError> {
+    not_draining(&shutdown)?;
+    if let Some(pool) = pool {
+        let conn = pool.get_multiplexed_connection().await?;
+        ping_server(conn).await?;
+    }
+    Ok(ApiResponse::success("ready".to_string()))
 }
 
 async fn server_status_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
-) -> Result<Json<ServerStatus>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let status = server_status(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(status))
+) -> Result<ApiResponse<ServerStatus>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let status = server_status(conn).await?;
+    Ok(ApiResponse::success(status))
 }
 
 // =========================
@@ -115,36 +140,41 @@ async fn server_status_handler(
 // =========================
 
 async fn memory_stats_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
-) -> Result<Json<HashMap<String, String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let stats = get_memory_stats(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(stats))
+) -> Result<ApiResponse<HashMap<String, String>>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let stats = get_memory_stats(conn).await?;
+    Ok(ApiResponse::success(stats))
 }
 
 async fn client_stats_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
-) -> Result<Json<HashMap<String, String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let stats = get_client_stats(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(stats))
+) -> Result<ApiResponse<HashMap<String, String>>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let stats = get_client_stats(conn).await?;
+    Ok(ApiResponse::success(stats))
 }
 
 async fn server_stats_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
-) -> Result<Json<HashMap<String, String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let stats = get_server_stats(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(stats))
+) -> Result<ApiResponse<HashMap<String, String>>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let stats = get_server_stats(conn).await?;
+    Ok(ApiResponse::success(stats))
+}
+
+async fn pool_stats_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+) -> Result<ApiResponse<PoolMetrics>, ApiError> {
+    access.admin()?;
+    Ok(ApiResponse::success(pool.metrics()))
 }
 
 // =========================
@@ -152,61 +182,58 @@ async fn server_stats_handler(
 // =========================
 
 async fn config_set_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<ConfigSetPayload>,
-) -> Result<StatusCode, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    config_set(conn_arc, &payload.parameter, &payload.value)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(StatusCode::OK)
+) -> Result<ApiResponse<()>, ApiError> {
+    access.admin()?;
+    access.writable()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    config_set(conn, &payload.parameter, &payload.value).await?;
+    Ok(ApiResponse::success(()))
 }
 
 async fn config_get_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(parameter): Path<String>,
-) -> Result<Json<String>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let value = config_get(conn_arc, &parameter).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(value))
+) -> Result<ApiResponse<String>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let value = config_get(conn, &parameter).await?;
+    Ok(ApiResponse::success(value))
 }
 
 async fn config_get_all_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
-) -> Result<Json<HashMap<String, String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let config = config_get_all(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(config))
+) -> Result<ApiResponse<HashMap<String, String>>, ApiError> {
+    access.admin()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let config = config_get_all(conn).await?;
+    Ok(ApiResponse::success(config))
 }
 
 async fn config_reset_statistics_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
-) -> Result<StatusCode, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    config_reset_statistics(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(StatusCode::OK)
+) -> Result<ApiResponse<()>, ApiError> {
+    access.admin()?;
+    access.writable()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    config_reset_statistics(conn).await?;
+    Ok(ApiResponse::success(()))
 }
 
 async fn config_rewrite_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
-) -> Result<StatusCode, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    config_rewrite(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(StatusCode::OK)
+) -> Result<ApiResponse<()>, ApiError> {
+    access.admin()?;
+    access.writable()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    config_rewrite(conn).await?;
+    Ok(ApiResponse::success(()))
 }
 
 // =========================
@@ -214,25 +241,32 @@ async fn config_rewrite_handler(
 // =========================
 
 async fn flush_current_database_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
-) -> Result<StatusCode, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    flush_current_database(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(StatusCode::OK)
+) -> Result<ApiResponse<()>, ApiError> {
+    access.admin()?;
+    access.writable()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    flush_current_database(conn).await?;
+    Ok(ApiResponse::success(()))
 }
 
 async fn flush_all_databases_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
-) -> Result<StatusCode, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    flush_all_databases(conn_arc).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(StatusCode::OK)
+) -> Result<ApiResponse<()>, ApiError> {
+    access.admin()?;
+    access.writable()?;
+    let conn = pool.get_multiplexed_connection().await?;
+    flush_all_databases(conn).await?;
+    Ok(ApiResponse::success(()))
+}
+
+/// The readiness probe, served outside authentication like `/metrics`
+pub fn create_readiness_routes(pool: Option<Arc<RedisPool>>) -> Router {
+    Router::new()
+        .route("/health/ready", get(readiness_handler))
+        .with_state(pool)
 }
 
 pub fn create_redis_admin_routes(pool: Arc<RedisPool>) -> Router {
@@ -251,6 +285,7 @@ pub fn create_redis_admin_routes(pool: Arc<RedisPool>) -> Router {
         .route("/admin/stats/memory", get(memory_stats_handler))
         .route("/admin/stats/clients", get(client_stats_handler))
         .route("/admin/stats/server", get(server_stats_handler))
+        .route("/admin/stats/pool", get(pool_stats_handler))
         // Configuration routes
         .route("/admin/config/set", post(config_set_handler))
         .route("/admin/config/get/:parameter", get(config_get_handler))
@@ -0,0 +1,286 @@
+use crate::error::ApiError;
+use crate::models::ApiResponse;
+use crate::policy::Access;
+use crate::routes::common::bitmap::{
+    bit_operation, bitmap_exists_key, count_bits, count_multiple_bitmaps, delete_bitmap,
+    find_bit_position, get_bit, get_bitmap_bytes, get_bitmap_ttl, get_multiple_bits, set_bit,
+    set_bitmap_ttl, set_multiple_bits, BitOperation, BitRangeUnit,
+};
+use axum::{
+    extract::{Json, Path, Query, State},
+    routing::{delete, get, post},
+    Router,
+};
+use dbx_adapter::redis::client::RedisPool;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+struct SetBitRequest {
+    value: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetBitsRequest {
+    bits: Vec<(usize, bool)>, // (offset, value) pairs
+}
+
+#[derive(Debug, Deserialize)]
+struct GetBitsRequest {
+    offsets: Vec<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitCountQuery {
+    start: Option<i64>,
+    end: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitPositionQuery {
+    bit: u8,
+    start: Option<i64>,
+    end: Option<i64>,
+    #[serde(default)]
+    unit: BitRangeUnit,
+}
+
+#[derive(Debug, Deserialize)]
+struct BytesQuery {
+    offset: u64,
+    length: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitOpRequest {
+    operation: BitOperation,
+    destination: String,
+    keys: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetBitmapTtlRequest {
+    ttl: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchBitmapKeysRequest {
+    keys: Vec<String>,
+}
+
+/// Both ends of a range must be given together, Redis rejects a lone start
+fn bit_range(start: Option<i64>, end: Option<i64>) -> Result<Option<(i64, i64)>, ApiError> {
+    match (start, end) {
+        (Some(start), Some(end)) => Ok(Some((start, end))),
+        (None, None) => Ok(None),
+        _ => Err(ApiError::invalid_argument(
+            "start and end must be given together",
+        )),
+    }
+}
+
+// Single bit operations
+async fn get_bit_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path((key, offset)): Path<(String, usize)>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let bit = get_bit(conn, &key, offset).await?;
+    Ok(ApiResponse::success(bit))
+}
+
+async fn set_bit_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path((key, offset)): Path<(String, usize)>,
+    Json(payload): Json<SetBitRequest>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let previous = set_bit(conn, &key, offset, payload.value).await?;
+    Ok(ApiResponse::success(previous))
+}
+
+async fn set_bits_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Json(payload): Json<SetBitsRequest>,
+) -> Result<ApiResponse<Vec<bool>>, ApiError> {
+    access.write([&key])?;
+    if payload.bits.is_empty() {
+        return Ok(ApiResponse::success(Vec::new()));
+    }
+
+    let conn = pool.get_multiplexed_connection().await?;
+    let previous = set_multiple_bits(conn, &key, payload.bits).await?;
+    Ok(ApiResponse::success(previous))
+}
+
+async fn get_bits_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Json(payload): Json<GetBitsRequest>,
+) -> Result<ApiResponse<Vec<bool>>, ApiError> {
+    access.read([&key])?;
+    if payload.offsets.is_empty() {
+        return Ok(ApiResponse::success(Vec::new()));
+    }
+
+    let conn = pool.get_multiplexed_connection().await?;
+    let bits = get_multiple_bits(conn, &key, payload.offsets).await?;
+    Ok(ApiResponse::success(bits))
+}
+
+// Bitmap queries
+async fn count_bits_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Query(query): Query<BitCountQuery>,
+) -> Result<ApiResponse<u64>, ApiError> {
+    access.read([&key])?;
+    let range = bit_range(query.start, query.end)?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let count = count_bits(conn, &key, range).await?;
+    Ok(ApiResponse::success(count))
+}
+
+async fn bit_position_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Query(query): Query<BitPositionQuery>,
+) -> Result<ApiResponse<i64>, ApiError> {
+    access.read([&key])?;
+    let bit = match query.bit {
+        0 => false,
+        1 => true,
+        _ => return Err(ApiError::invalid_argument("bit must be 0 or 1")),
+    };
+    let range = bit_range(query.start, query.end)?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let position = find_bit_position(conn, &key, bit, range, query.unit).await?;
+    Ok(ApiResponse::success(position))
+}
+
+async fn get_bytes_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Query(query): Query<BytesQuery>,
+) -> Result<ApiResponse<Vec<u8>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let bytes = get_bitmap_bytes(conn, &key, query.offset, query.length).await?;
+    Ok(ApiResponse::success(bytes))
+}
+
+// Multi-key operations
+async fn bit_operation_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Json(payload): Json<BitOpRequest>,
+) -> Result<ApiResponse<u64>, ApiError> {
+    access.write([&payload.destination])?;
+    access.read(&payload.keys)?;
+    let valid_key_count = match payload.operation {
+        BitOperation::Not => payload.keys.len() == 1,
+        _ => !payload.keys.is_empty(),
+    };
+    if !valid_key_count {
+        return Err(ApiError::invalid_argument(
+            "NOT takes exactly one key, other operations at least one",
+        ));
+    }
+
+    let conn = pool.get_multiplexed_connection().await?;
+    let key_refs: Vec<&str> = payload.keys.iter().map(|k| k.as_str()).collect();
+    let length = bit_operation(conn, payload.operation, &payload.destination, &key_refs).await?;
+    Ok(ApiResponse::success(length))
+}
+
+// Bitmap management
+async fn delete_bitmap_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let deleted = delete_bitmap(conn, &key).await?;
+    Ok(ApiResponse::success(deleted))
+}
+
+async fn bitmap_exists_key_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let exists = bitmap_exists_key(conn, &key).await?;
+    Ok(ApiResponse::success(exists))
+}
+
+async fn get_bitmap_ttl_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<i64>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let ttl = get_bitmap_ttl(conn, &key).await?;
+    Ok(ApiResponse::success(ttl))
+}
+
+async fn set_bitmap_ttl_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Json(payload): Json<SetBitmapTtlRequest>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let result = set_bitmap_ttl(conn, &key, payload.ttl).await?;
+    Ok(ApiResponse::success(result))
+}
+
+// Batch operations
+async fn batch_count_bitmaps_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Json(payload): Json<BatchBitmapKeysRequest>,
+) -> Result<ApiResponse<Vec<u64>>, ApiError> {
+    access.read(&payload.keys)?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let key_refs: Vec<&str> = payload.keys.iter().map(|k| k.as_str()).collect();
+    let counts = count_multiple_bitmaps(conn, key_refs).await?;
+    Ok(ApiResponse::success(counts))
+}
+
+pub fn create_redis_bitmap_routes(pool: Arc<RedisPool>) -> Router {
+    Router::new()
+        // Single bit operations
+        .route("/bitmap/:key/:offset", get(get_bit_handler))
+        .route("/bitmap/:key/:offset", post(set_bit_handler))
+        .route("/bitmap/:key/bits", post(set_bits_handler))
+        .route("/bitmap/:key/bits/get", post(get_bits_handler))
+        // Bitmap queries
+        .route("/bitmap/:key/count", get(count_bits_handler))
+        .route("/bitmap/:key/position", get(bit_position_handler))
+        .route("/bitmap/:key/bytes", get(get_bytes_handler))
+        // Multi-key operations
+        .route("/bitmap/op", post(bit_operation_handler))
+        // Bitmap management
+        .route("/bitmap/:key", delete(delete_bitmap_handler))
+        .route("/bitmap/:key/exists", get(bitmap_exists_key_handler))
+        .route("/bitmap/:key/ttl", get(get_bitmap_ttl_handler))
+        .route("/bitmap/:key/ttl", post(set_bitmap_ttl_handler))
+        // Batch operations
+        .route("/bitmap/batch/counts", post(batch_count_bitmaps_handler))
+        .with_state(pool)
+}
@@ -1,3 +1,6 @@
+use crate::error::ApiError;
+use crate::models::ApiResponse;
+use crate::policy::Access;
 use crate::routes::common::hash::{
     check_multiple_hash_fields, delete_hash, delete_hash_field, delete_multiple_hash_fields,
     get_all_hash_fields, get_hash_field, get_hash_fields, get_hash_keys, get_hash_length,
@@ -8,7 +11,6 @@ use crate::routes::common::hash::{
 };
 use axum::{
     extract::{Json, Path, State},
-    http::StatusCode,
     routing::{delete, get, post},
     Router,
 };
@@ -83,303 +85,266 @@ struct BatchGetHashLengthsRequest {
 
 // Single field operations
 async fn get_hash_field_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path((key, field)): Path<(String, String)>,
-) -> Result<Json<Option<String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let value =
-        get_hash_field(conn_arc, &key, &field).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(value))
+) -> Result<ApiResponse<Option<String>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let value = get_hash_field(conn, &key, &field).await?;
+    Ok(ApiResponse::success(value))
 }
 
 async fn set_hash_field_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path((key, field)): Path<(String, String)>,
     Json(payload): Json<SetHashFieldRequest>,
-) -> Result<Json<bool>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let result = set_hash_field(conn_arc, &key, &field, &payload.value)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(result))
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let result = set_hash_field(conn, &key, &field, &payload.value).await?;
+    Ok(ApiResponse::success(result))
 }
 
 async fn delete_hash_field_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path((key, field)): Path<(String, String)>,
-) -> Result<Json<bool>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let deleted =
-        delete_hash_field(conn_arc, &key, &field).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(deleted))
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let deleted = delete_hash_field(conn, &key, &field).await?;
+    Ok(ApiResponse::success(deleted))
 }
 
 async fn hash_exists_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path((key, field)): Path<(String, String)>,
-) -> Result<Json<bool>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let exists =
-        hash_exists(conn_arc, &key, &field).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(exists))
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let exists = hash_exists(conn, &key, &field).await?;
+    Ok(ApiResponse::success(exists))
 }
 
 // Hash operations
 async fn get_all_hash_fields_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<std::collections::HashMap<String, String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let fields =
-        get_all_hash_fields(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(fields))
+) -> Result<ApiResponse<std::collections::HashMap<String, String>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let fields = get_all_hash_fields(conn, &key).await?;
+    Ok(ApiResponse::success(fields))
 }
 
 async fn get_hash_fields_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
     Json(payload): Json<GetHashFieldsRequest>,
-) -> Result<Json<Vec<Option<String>>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<Vec<Option<String>>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
     let field_refs: Vec<&str> = payload.fields.iter().map(|f| f.as_str()).collect();
-    let values = get_hash_fields(conn_arc, &key, &field_refs)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(values))
+    let values = get_hash_fields(conn, &key, &field_refs).await?;
+    Ok(ApiResponse::success(values))
 }
 
 async fn set_multiple_hash_fields_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
     Json(payload): Json<SetMultipleHashFieldsRequest>,
-) -> Result<StatusCode, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<()>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
     let field_values: Vec<(&str, &str)> = payload
         .fields
         .iter()
         .map(|(k, v)| (k.as_str(), v.as_str()))
         .collect();
-    set_multiple_hash_fields(conn_arc, &key, &field_values)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(StatusCode::OK)
+    set_multiple_hash_fields(conn, &key, &field_values).await?;
+    Ok(ApiResponse::success(()))
 }
 
 async fn get_hash_length_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<usize>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let length = get_hash_length(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(length))
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let length = get_hash_length(conn, &key).await?;
+    Ok(ApiResponse::success(length))
 }
 
 async fn get_hash_keys_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<Vec<String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let keys = get_hash_keys(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(keys))
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let keys = get_hash_keys(conn, &key).await?;
+    Ok(ApiResponse::success(keys))
 }
 
 async fn get_hash_values_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<Vec<String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let values = get_hash_values(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(values))
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let values = get_hash_values(conn, &key).await?;
+    Ok(ApiResponse::success(values))
 }
 
 async fn increment_hash_field_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path((key, field)): Path<(String, String)>,
     Json(payload): Json<IncrementHashFieldRequest>,
-) -> Result<Json<i64>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let result = increment_hash_field(conn_arc, &key, &field, payload.increment)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(result))
+) -> Result<ApiResponse<i64>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let result = increment_hash_field(conn, &key, &field, payload.increment).await?;
+    Ok(ApiResponse::success(result))
 }
 
 async fn increment_hash_field_float_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path((key, field)): Path<(String, String)>,
     Json(payload): Json<IncrementHashFieldFloatRequest>,
-) -> Result<Json<f64>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let result = increment_hash_field_float(conn_arc, &key, &field, payload.increment)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(result))
+) -> Result<ApiResponse<f64>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let result = increment_hash_field_float(conn, &key, &field, payload.increment).await?;
+    Ok(ApiResponse::success(result))
 }
 
 async fn set_hash_field_if_not_exists_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path((key, field)): Path<(String, String)>,
     Json(payload): Json<SetHashFieldRequest>,
-) -> Result<Json<bool>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let result = set_hash_field_if_not_exists(conn_arc, &key, &field, &payload.value)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(result))
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let result = set_hash_field_if_not_exists(conn, &key, &field, &payload.value).await?;
+    Ok(ApiResponse::success(result))
 }
 
 async fn get_random_hash_field_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<Option<String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let field =
-        get_random_hash_field(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(field))
+) -> Result<ApiResponse<Option<String>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let field = get_random_hash_field(conn, &key).await?;
+    Ok(ApiResponse::success(field))
 }
 
 async fn get_random_hash_fields_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
     Json(payload): Json<GetRandomHashFieldsRequest>,
-) -> Result<Json<Vec<String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let fields = get_random_hash_fields(conn_arc, &key, payload.count)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(fields))
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let fields = get_random_hash_fields(conn, &key, payload.count).await?;
+    Ok(ApiResponse::success(fields))
 }
 
 async fn get_random_hash_fields_with_values_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
     Json(payload): Json<GetRandomHashFieldsWithValuesRequest>,
-) -> Result<Json<Vec<(String, String)>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let fields = get_random_hash_fields_with_values(conn_arc, &key, payload.count)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(fields))
+) -> Result<ApiResponse<Vec<(String, String)>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let fields = get_random_hash_fields_with_values(conn, &key, payload.count).await?;
+    Ok(ApiResponse::success(fields))
 }
 
 // Hash management
 async fn delete_hash_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<bool>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let deleted = delete_hash(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(deleted))
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let deleted = delete_hash(conn, &key).await?;
+    Ok(ApiResponse::success(deleted))
 }
 
 async fn hash_exists_key_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<bool>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let exists = hash_exists_key(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(exists))
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let exists = hash_exists_key(conn, &key).await?;
+    Ok(ApiResponse::success(exists))
 }
 
 async fn get_hash_ttl_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<i64>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let ttl = get_hash_ttl(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(ttl))
+) -> Result<ApiResponse<i64>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let ttl = get_hash_ttl(conn, &key).await?;
+    Ok(ApiResponse::success(ttl))
 }
 
 async fn set_hash_ttl_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
     Json(payload): Json<SetHashTtlRequest>,
-) -> Result<Json<bool>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let result =
-        set_hash_ttl(conn_arc, &key, payload.ttl).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(result))
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let result = set_hash_ttl(conn, &key, payload.ttl).await?;
+    Ok(ApiResponse::success(result))
 }
 
 // Batch operations
 async fn batch_get_hash_fields_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<BatchGetHashFieldsRequest>,
-) -> Result<Json<Vec<Option<String>>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<Vec<Option<String>>>, ApiError> {
+    access.read(payload.hash_fields.iter().map(|(key, _)| key))?;
+    let conn = pool.get_multiplexed_connection().await?;
     let hash_fields: Vec<(&str, &str)> = payload
         .hash_fields
         .iter()
         .map(|(k, f)| (k.as_str(), f.as_str()))
         .collect();
-    let values = get_multiple_hash_fields(conn_arc, hash_fields)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(values))
+    let values = get_multiple_hash_fields(conn, hash_fields).await?;
+    Ok(ApiResponse::success(values))
 }
 
 async fn batch_set_hash_fields_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<BatchSetHashFieldsRequest>,
-) -> Result<Json<Vec<bool>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<Vec<bool>>, ApiError> {
+    access.write(payload.hash_operations.iter().map(|(key, _)| key))?;
+    let conn = pool.get_multiplexed_connection().await?;
     let hash_operations: Vec<(&str, Vec<(&str, &str)>)> = payload
         .hash_operations
         .iter()
@@ -391,19 +356,17 @@ async fn batch_set_hash_fields_handler(
             (k.as_str(), field_values)
         })
         .collect();
-    let results = set_multiple_hashes(conn_arc, hash_operations)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(results))
+    let results = set_multiple_hashes(conn, hash_operations).await?;
+    Ok(ApiResponse::success(results))
 }
 
 async fn batch_delete_hash_fields_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<BatchDeleteHashFieldsRequest>,
-) -> Result<Json<Vec<usize>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<Vec<usize>>, ApiError> {
+    access.write(payload.hash_fields.iter().map(|(key, _)| key))?;
+    let conn = pool.get_multiplexed_connection().await?;
     let hash_fields: Vec<(&str, Vec<&str>)> = payload
         .hash_fields
         .iter()
@@ -412,41 +375,36 @@ async fn batch_delete_hash_fields_handler(
             (k.as_str(), field_refs)
         })
         .collect();
-    let results = delete_multiple_hash_fields(conn_arc, hash_fields)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(results))
+    let results = delete_multiple_hash_fields(conn, hash_fields).await?;
+    Ok(ApiResponse::success(results))
 }
 
 async fn batch_check_hash_fields_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<BatchCheckHashFieldsRequest>,
-) -> Result<Json<Vec<bool>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<Vec<bool>>, ApiError> {
+    access.read(payload.hash_fields.iter().map(|(key, _)| key))?;
+    let conn = pool.get_multiplexed_connection().await?;
     let hash_fields: Vec<(&str, &str)> = payload
         .hash_fields
         .iter()
         .map(|(k, f)| (k.as_str(), f.as_str()))
         .collect();
-    let results = check_multiple_hash_fields(conn_arc, hash_fields)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(results))
+    let results = check_multiple_hash_fields(conn, hash_fields).await?;
+    Ok(ApiResponse::success(results))
 }
 
 async fn batch_get_hash_lengths_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<BatchGetHashLengthsRequest>,
-) -> Result<Json<Vec<usize>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<Vec<usize>>, ApiError> {
+    access.read(&payload.keys)?;
+    let conn = pool.get_multiplexed_connection().await?;
     let key_refs: Vec<&str> = payload.keys.iter().map(|k| k.as_str()).collect();
-    let lengths = get_multiple_hash_lengths(conn_arc, key_refs)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(lengths))
+    let lengths = get_multiple_hash_lengths(conn, key_refs).await?;
+    Ok(ApiResponse::success(lengths))
 }
 
 pub fn create_redis_hash_routes(pool: Arc<RedisPool>) -> Router {
@@ -1,4 +1,8 @@
 pub mod admin;
+pub mod bitmap;
 pub mod hash;
+pub mod pubsub;
+pub mod script;
 pub mod set;
+pub mod sorted_set;
 pub mod string;
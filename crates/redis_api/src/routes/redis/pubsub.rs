@@ -0,0 +1,37 @@
+use crate::error::ApiError;
+use crate::models::ApiResponse;
+use crate::policy::Access;
+use crate::routes::common::pubsub::publish_message;
+use axum::{
+    extract::{Json, Path, State},
+    routing::post,
+    Router,
+};
+use dbx_adapter::redis::client::RedisPool;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+struct PublishRequest {
+    message: String,
+}
+
+// Publishing
+async fn publish_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(channel): Path<String>,
+    Json(payload): Json<PublishRequest>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&channel])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let receivers = publish_message(conn, &channel, &payload.message).await?;
+    Ok(ApiResponse::success(receivers))
+}
+
+pub fn create_redis_pubsub_routes(pool: Arc<RedisPool>) -> Router {
+    Router::new()
+        // Publishing
+        .route("/pubsub/:channel/publish", post(publish_handler))
+        .with_state(pool)
+}
@@ -0,0 +1,110 @@
+use crate::error::ApiError;
+use crate::models::ApiResponse;
+use crate::policy::{Access, Permission};
+use crate::routes::common::script::{eval_named_script, ScriptInfo, ScriptRegistry};
+use axum::{
+    extract::{Json, Path, State},
+    routing::{delete, get, post},
+    Router,
+};
+use dbx_adapter::redis::client::RedisPool;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct ScriptState {
+    pool: Arc<RedisPool>,
+    registry: Arc<ScriptRegistry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterScriptRequest {
+    source: String,
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EvalScriptRequest {
+    #[serde(default)]
+    keys: Vec<String>,
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+// Script catalog
+async fn list_scripts_handler(
+    access: Access,
+    State(state): State<ScriptState>,
+) -> Result<ApiResponse<Vec<ScriptInfo>>, ApiError> {
+    access.require(Permission::Read)?;
+    Ok(ApiResponse::success(state.registry.list()))
+}
+
+async fn register_script_handler(
+    access: Access,
+    State(state): State<ScriptState>,
+    Path(name): Path<String>,
+    Json(payload): Json<RegisterScriptRequest>,
+) -> Result<ApiResponse<ScriptInfo>, ApiError> {
+    access.admin()?;
+    access.writable()?;
+    if state.registry.is_builtin(&name) {
+        return Err(ApiError::conflict(format!(
+            "Cannot replace built-in script: {}",
+            name
+        )));
+    }
+
+    let conn = state.pool.get_multiplexed_connection().await?;
+    let info = state
+        .registry
+        .register(conn, &name, &payload.source, payload.description)
+        // SCRIPT LOAD rejects scripts that don't compile as invalid arguments
+        .await?;
+    Ok(ApiResponse::success(info))
+}
+
+async fn unregister_script_handler(
+    access: Access,
+    State(state): State<ScriptState>,
+    Path(name): Path<String>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.admin()?;
+    access.writable()?;
+    if state.registry.is_builtin(&name) {
+        return Err(ApiError::conflict(format!(
+            "Cannot unregister built-in script: {}",
+            name
+        )));
+    }
+    Ok(ApiResponse::success(state.registry.unregister(&name)))
+}
+
+// Script execution
+async fn eval_script_handler(
+    access: Access,
+    State(state): State<ScriptState>,
+    Path(name): Path<String>,
+    Json(payload): Json<EvalScriptRequest>,
+) -> Result<ApiResponse<serde_json::Value>, ApiError> {
+    access.write(&payload.keys)?;
+    let script = state
+        .registry
+        .get(&name)
+        .ok_or_else(|| ApiError::not_found(format!("Unknown script: {}", name)))?;
+
+    let conn = state.pool.get_multiplexed_connection().await?;
+    let result = eval_named_script(conn, &script, &payload.keys, &payload.args).await?;
+    Ok(ApiResponse::success(result))
+}
+
+pub fn create_redis_script_routes(pool: Arc<RedisPool>, registry: Arc<ScriptRegistry>) -> Router {
+    Router::new()
+        // Script catalog
+        .route("/script", get(list_scripts_handler))
+        .route("/script/:name", post(register_script_handler))
+        .route("/script/:name", delete(unregister_script_handler))
+        // Script execution
+        .route("/script/:name/eval", post(eval_script_handler))
+        .with_state(ScriptState { pool, registry })
+}
@@ -1,10 +1,12 @@
+use crate::error::ApiError;
+use crate::models::ApiResponse;
+use crate::policy::Access;
 use crate::routes::common::set::{
     add_to_set, delete_set, difference_sets, get_set_cardinality, get_set_members, intersect_sets,
     remove_from_set, set_exists, union_sets,
 };
 use axum::{
     extract::{Json, Path, State},
-    http::StatusCode,
     routing::{delete, get, post},
     Router,
 };
@@ -29,150 +31,133 @@ struct SetKeysRequest {
 
 // Add member to set
 async fn add_to_set_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
     Json(payload): Json<SetMemberRequest>,
-) -> Result<Json<usize>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let added = add_to_set(conn_arc, &key, &[&payload.member])
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(added))
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let added = add_to_set(conn, &key, &[&payload.member]).await?;
+    Ok(ApiResponse::success(added))
 }
 
 // Add multiple members to set
 async fn add_many_to_set_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
     Json(payload): Json<SetMembersRequest>,
-) -> Result<Json<usize>, StatusCode> {
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
     // If members array is empty, return 0 (no members added)
     if payload.members.is_empty() {
-        return Ok(Json(0));
+        return Ok(ApiResponse::success(0));
     }
 
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+    let conn = pool.get_multiplexed_connection().await?;
     let member_refs: Vec<&str> = payload.members.iter().map(|s| s.as_str()).collect();
-    let added =
-        add_to_set(conn_arc, &key, &member_refs).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(added))
+    let added = add_to_set(conn, &key, &member_refs).await?;
+    Ok(ApiResponse::success(added))
 }
 
 // Remove member from set
 async fn remove_from_set_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path((key, member)): Path<(String, String)>,
-) -> Result<Json<usize>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let removed = remove_from_set(conn_arc, &key, &[&member])
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(removed))
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let removed = remove_from_set(conn, &key, &[&member]).await?;
+    Ok(ApiResponse::success(removed))
 }
 
 // Get all set members
 async fn get_set_members_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<Vec<String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let members = get_set_members(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(members))
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let members = get_set_members(conn, &key).await?;
+    Ok(ApiResponse::success(members))
 }
 
 // Get set cardinality
 async fn get_set_cardinality_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<usize>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let cardinality =
-        get_set_cardinality(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(cardinality))
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let cardinality = get_set_cardinality(conn, &key).await?;
+    Ok(ApiResponse::success(cardinality))
 }
 
 // Check if member exists in set
 async fn set_exists_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path((key, member)): Path<(String, String)>,
-) -> Result<Json<bool>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let exists =
-        set_exists(conn_arc, &key, &member).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(exists))
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let exists = set_exists(conn, &key, &member).await?;
+    Ok(ApiResponse::success(exists))
 }
 
 // Intersect sets
 async fn intersect_sets_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<SetKeysRequest>,
-) -> Result<Json<Vec<String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read(&payload.keys)?;
+    let conn = pool.get_multiplexed_connection().await?;
     let key_refs: Vec<&str> = payload.keys.iter().map(|k| k.as_str()).collect();
-    let result =
-        intersect_sets(conn_arc, &key_refs).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(result))
+    let result = intersect_sets(conn, &key_refs).await?;
+    Ok(ApiResponse::success(result))
 }
 
 // Union sets
 async fn union_sets_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<SetKeysRequest>,
-) -> Result<Json<Vec<String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read(&payload.keys)?;
+    let conn = pool.get_multiplexed_connection().await?;
     let key_refs: Vec<&str> = payload.keys.iter().map(|k| k.as_str()).collect();
-    let result = union_sets(conn_arc, &key_refs).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(result))
+    let result = union_sets(conn, &key_refs).await?;
+    Ok(ApiResponse::success(result))
 }
 
 // Difference of sets
 async fn difference_sets_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<SetKeysRequest>,
-) -> Result<Json<Vec<String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    access.read(&payload.keys)?;
+    let conn = pool.get_multiplexed_connection().await?;
     let key_refs: Vec<&str> = payload.keys.iter().map(|k| k.as_str()).collect();
-    let result =
-        difference_sets(conn_arc, &key_refs).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(result))
+    let result = difference_sets(conn, &key_refs).await?;
+    Ok(ApiResponse::success(result))
 }
 
 // Delete entire set
 async fn delete_set_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<bool>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let deleted = delete_set(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(deleted))
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let deleted = delete_set(conn, &key).await?;
+    Ok(ApiResponse::success(deleted))
 }
 
 pub fn create_redis_set_routes(pool: Arc<RedisPool>) -> Router {
@@ -0,0 +1,490 @@
+use crate::error::ApiError;
+use crate::models::ApiResponse;
+use crate::policy::Access;
+use crate::routes::common::sorted_set::{
+    add_to_multiple_sorted_sets, add_to_sorted_set, count_sorted_set_range, delete_sorted_set,
+    get_member_rank, get_member_score, get_multiple_member_scores,
+    get_multiple_sorted_set_cardinalities, get_sorted_set_cardinality, get_sorted_set_range,
+    get_sorted_set_range_by_score, get_sorted_set_ttl, increment_member_score,
+    intersect_sorted_sets_store, remove_from_multiple_sorted_sets, remove_from_sorted_set,
+    remove_sorted_set_range_by_rank, remove_sorted_set_range_by_score, set_sorted_set_ttl,
+    sorted_set_exists_key, union_sorted_sets_store, ScoredMember,
+};
+use axum::{
+    extract::{Json, Path, Query, State},
+    routing::{delete, get, post},
+    Router,
+};
+use dbx_adapter::redis::client::RedisPool;
+use serde::Deserialize;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize)]
+struct ZAddRequest {
+    member: String,
+    score: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZAddManyRequest {
+    members: Vec<ScoredMember>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZIncrementRequest {
+    increment: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZRangeQuery {
+    start: Option<isize>,
+    stop: Option<isize>,
+    #[serde(default)]
+    rev: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZRangeByScoreQuery {
+    min: f64,
+    max: f64,
+    offset: Option<isize>,
+    count: Option<isize>,
+    #[serde(default)]
+    rev: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZRankQuery {
+    #[serde(default)]
+    rev: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZScoreBoundsRequest {
+    min: f64,
+    max: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZRankBoundsRequest {
+    start: isize,
+    stop: isize,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZStoreRequest {
+    destination: String,
+    keys: Vec<String>,
+    weights: Option<Vec<f64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetSortedSetTtlRequest {
+    ttl: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchAddSortedSetsRequest {
+    sorted_set_members: Vec<(String, Vec<ScoredMember>)>, // (key, [{member, score}]) pairs
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchRemoveSortedSetsRequest {
+    sorted_set_members: Vec<(String, Vec<String>)>, // (key, [members]) pairs
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchGetScoresRequest {
+    sorted_set_members: Vec<(String, String)>, // (key, member) pairs
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchGetCardinalitiesRequest {
+    keys: Vec<String>,
+}
+
+// Member operations
+async fn add_to_sorted_set_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Json(payload): Json<ZAddRequest>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let member = ScoredMember {
+        member: payload.member,
+        score: payload.score,
+    };
+    let added = add_to_sorted_set(conn, &key, &[member]).await?;
+    Ok(ApiResponse::success(added))
+}
+
+async fn add_many_to_sorted_set_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Json(payload): Json<ZAddManyRequest>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
+    // If members array is empty, return 0 (no members added)
+    if payload.members.is_empty() {
+        return Ok(ApiResponse::success(0));
+    }
+
+    let conn = pool.get_multiplexed_connection().await?;
+    let added = add_to_sorted_set(conn, &key, &payload.members).await?;
+    Ok(ApiResponse::success(added))
+}
+
+async fn remove_from_sorted_set_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path((key, member)): Path<(String, String)>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let removed = remove_from_sorted_set(conn, &key, &[&member]).await?;
+    Ok(ApiResponse::success(removed))
+}
+
+async fn get_member_score_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path((key, member)): Path<(String, String)>,
+) -> Result<ApiResponse<Option<f64>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let score = get_member_score(conn, &key, &member).await?;
+    Ok(ApiResponse::success(score))
+}
+
+async fn get_member_rank_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path((key, member)): Path<(String, String)>,
+    Query(query): Query<ZRankQuery>,
+) -> Result<ApiResponse<Option<usize>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let rank = get_member_rank(conn, &key, &member, query.rev).await?;
+    Ok(ApiResponse::success(rank))
+}
+
+async fn increment_member_score_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path((key, member)): Path<(String, String)>,
+    Json(payload): Json<ZIncrementRequest>,
+) -> Result<ApiResponse<f64>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let score = increment_member_score(conn, &key, &member, payload.increment).await?;
+    Ok(ApiResponse::success(score))
+}
+
+// Range operations
+async fn get_sorted_set_range_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Query(query): Query<ZRangeQuery>,
+) -> Result<ApiResponse<Vec<ScoredMember>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let members = get_sorted_set_range(
+        conn,
+        &key,
+        query.start.unwrap_or(0),
+        query.stop.unwrap_or(-1),
+        query.rev,
+    )
+    .await?;
+    Ok(ApiResponse::success(members))
+}
+
+async fn get_sorted_set_range_by_score_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Query(query): Query<ZRangeByScoreQuery>,
+) -> Result<ApiResponse<Vec<ScoredMember>>, ApiError> {
+    access.read([&key])?;
+    let limit = match (query.offset, query.count) {
+        (None, None) => None,
+        (offset, count) => Some((offset.unwrap_or(0), count.unwrap_or(-1))),
+    };
+
+    let conn = pool.get_multiplexed_connection().await?;
+    let members =
+        get_sorted_set_range_by_score(conn, &key, query.min, query.max, limit, query.rev).await?;
+    Ok(ApiResponse::success(members))
+}
+
+async fn get_sorted_set_cardinality_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let cardinality = get_sorted_set_cardinality(conn, &key).await?;
+    Ok(ApiResponse::success(cardinality))
+}
+
+async fn count_sorted_set_range_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Query(query): Query<ZScoreBoundsRequest>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let count = count_sorted_set_range(conn, &key, query.min, query.max).await?;
+    Ok(ApiResponse::success(count))
+}
+
+async fn remove_range_by_rank_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Json(payload): Json<ZRankBoundsRequest>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let removed = remove_sorted_set_range_by_rank(conn, &key, payload.start, payload.stop).await?;
+    Ok(ApiResponse::success(removed))
+}
+
+async fn remove_range_by_score_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Json(payload): Json<ZScoreBoundsRequest>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let removed = remove_sorted_set_range_by_score(conn, &key, payload.min, payload.max).await?;
+    Ok(ApiResponse::success(removed))
+}
+
+// Multi-key operations
+async fn intersect_store_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Json(payload): Json<ZStoreRequest>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&payload.destination])?;
+    access.read(&payload.keys)?;
+    if let Some(weights) = &payload.weights {
+        if weights.len() != payload.keys.len() {
+            return Err(ApiError::invalid_argument(
+                "weights must have one entry per key",
+            ));
+        }
+    }
+
+    let conn = pool.get_multiplexed_connection().await?;
+    let key_refs: Vec<&str> = payload.keys.iter().map(|k| k.as_str()).collect();
+    let stored = intersect_sorted_sets_store(
+        conn,
+        &payload.destination,
+        &key_refs,
+        payload.weights.as_deref(),
+    )
+    .await?;
+    Ok(ApiResponse::success(stored))
+}
+
+async fn union_store_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Json(payload): Json<ZStoreRequest>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    access.write([&payload.destination])?;
+    access.read(&payload.keys)?;
+    if let Some(weights) = &payload.weights {
+        if weights.len() != payload.keys.len() {
+            return Err(ApiError::invalid_argument(
+                "weights must have one entry per key",
+            ));
+        }
+    }
+
+    let conn = pool.get_multiplexed_connection().await?;
+    let key_refs: Vec<&str> = payload.keys.iter().map(|k| k.as_str()).collect();
+    let stored = union_sorted_sets_store(
+        conn,
+        &payload.destination,
+        &key_refs,
+        payload.weights.as_deref(),
+    )
+    .await?;
+    Ok(ApiResponse::success(stored))
+}
+
+// Sorted set management
+async fn delete_sorted_set_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let deleted = delete_sorted_set(conn, &key).await?;
+    Ok(ApiResponse::success(deleted))
+}
+
+async fn sorted_set_exists_key_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let exists = sorted_set_exists_key(conn, &key).await?;
+    Ok(ApiResponse::success(exists))
+}
+
+async fn get_sorted_set_ttl_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+) -> Result<ApiResponse<i64>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let ttl = get_sorted_set_ttl(conn, &key).await?;
+    Ok(ApiResponse::success(ttl))
+}
+
+async fn set_sorted_set_ttl_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Path(key): Path<String>,
+    Json(payload): Json<SetSortedSetTtlRequest>,
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let result = set_sorted_set_ttl(conn, &key, payload.ttl).await?;
+    Ok(ApiResponse::success(result))
+}
+
+// Batch operations
+async fn batch_add_sorted_sets_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Json(payload): Json<BatchAddSortedSetsRequest>,
+) -> Result<ApiResponse<Vec<usize>>, ApiError> {
+    access.write(payload.sorted_set_members.iter().map(|(key, _)| key))?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let set_members: Vec<(&str, Vec<(f64, &str)>)> = payload
+        .sorted_set_members
+        .iter()
+        .map(|(k, members)| {
+            let items: Vec<(f64, &str)> = members
+                .iter()
+                .map(|m| (m.score, m.member.as_str()))
+                .collect();
+            (k.as_str(), items)
+        })
+        .collect();
+    let results = add_to_multiple_sorted_sets(conn, set_members).await?;
+    Ok(ApiResponse::success(results))
+}
+
+async fn batch_remove_sorted_sets_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Json(payload): Json<BatchRemoveSortedSetsRequest>,
+) -> Result<ApiResponse<Vec<usize>>, ApiError> {
+    access.write(payload.sorted_set_members.iter().map(|(key, _)| key))?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let set_members: Vec<(&str, Vec<&str>)> = payload
+        .sorted_set_members
+        .iter()
+        .map(|(k, members)| {
+            let member_refs: Vec<&str> = members.iter().map(|m| m.as_str()).collect();
+            (k.as_str(), member_refs)
+        })
+        .collect();
+    let results = remove_from_multiple_sorted_sets(conn, set_members).await?;
+    Ok(ApiResponse::success(results))
+}
+
+async fn batch_get_scores_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Json(payload): Json<BatchGetScoresRequest>,
+) -> Result<ApiResponse<Vec<Option<f64>>>, ApiError> {
+    access.read(payload.sorted_set_members.iter().map(|(key, _)| key))?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let key_members: Vec<(&str, &str)> = payload
+        .sorted_set_members
+        .iter()
+        .map(|(k, m)| (k.as_str(), m.as_str()))
+        .collect();
+    let scores = get_multiple_member_scores(conn, key_members).await?;
+    Ok(ApiResponse::success(scores))
+}
+
+async fn batch_get_cardinalities_handler(
+    access: Access,
+    State(pool): State<Arc<RedisPool>>,
+    Json(payload): Json<BatchGetCardinalitiesRequest>,
+) -> Result<ApiResponse<Vec<usize>>, ApiError> {
+    access.read(&payload.keys)?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let key_refs: Vec<&str> = payload.keys.iter().map(|k| k.as_str()).collect();
+    let cardinalities = get_multiple_sorted_set_cardinalities(conn, key_refs).await?;
+    Ok(ApiResponse::success(cardinalities))
+}
+
+pub fn create_redis_sorted_set_routes(pool: Arc<RedisPool>) -> Router {
+    Router::new()
+        // Member operations
+        .route("/zset/:key", post(add_to_sorted_set_handler))
+        .route("/zset/:key/many", post(add_many_to_sorted_set_handler))
+        .route("/zset/:key/:member", delete(remove_from_sorted_set_handler))
+        .route("/zset/:key/:member/score", get(get_member_score_handler))
+        .route("/zset/:key/:member/rank", get(get_member_rank_handler))
+        .route(
+            "/zset/:key/:member/increment",
+            post(increment_member_score_handler),
+        )
+        // Range operations
+        .route("/zset/:key/range", get(get_sorted_set_range_handler))
+        .route(
+            "/zset/:key/range_by_score",
+            get(get_sorted_set_range_by_score_handler),
+        )
+        .route(
+            "/zset/:key/cardinality",
+            get(get_sorted_set_cardinality_handler),
+        )
+        .route("/zset/:key/count", get(count_sorted_set_range_handler))
+        .route(
+            "/zset/:key/remove_range_by_rank",
+            post(remove_range_by_rank_handler),
+        )
+        .route(
+            "/zset/:key/remove_range_by_score",
+            post(remove_range_by_score_handler),
+        )
+        // Multi-key operations
+        .route("/zset/intersect_store", post(intersect_store_handler))
+        .route("/zset/union_store", post(union_store_handler))
+        // Sorted set management
+        .route("/zset/:key", delete(delete_sorted_set_handler))
+        .route("/zset/:key/exists", get(sorted_set_exists_key_handler))
+        .route("/zset/:key/ttl", get(get_sorted_set_ttl_handler))
+        .route("/zset/:key/ttl", post(set_sorted_set_ttl_handler))
+        // Batch operations
+        .route("/zset/batch/add", post(batch_add_sorted_sets_handler))
+        .route("/zset/batch/remove", post(batch_remove_sorted_sets_handler))
+        .route("/zset/batch/scores", post(batch_get_scores_handler))
+        .route(
+            "/zset/batch/cardinalities",
+            post(batch_get_cardinalities_handler),
+        )
+        .with_state(pool)
+}
@@ -1,3 +1,6 @@
+use crate::error::{ApiError, ErrorCode};
+use crate::models::ApiResponse;
+use crate::policy::Access;
 use crate::routes::common::string::{
     delete_string, get_multiple_strings, get_string, get_string_info, get_strings_by_patterns,
     get_strings_by_patterns_grouped, set_multiple_strings, set_string, set_string_with_ttl,
@@ -5,8 +8,6 @@ use crate::routes::common::string::{
 };
 use axum::{
     extract::{Json, Path, State},
-    http::StatusCode,
-    response::IntoResponse,
     routing::{any, delete, get, post},
     Router,
 };
@@ -37,105 +38,94 @@ struct BatchGetPatternsRequest {
 }
 
 async fn get_string_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<Option<String>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let value = get_string(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(value))
+) -> Result<ApiResponse<Option<String>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let value = get_string(conn, &key).await?;
+    Ok(ApiResponse::success(value))
 }
 
 async fn set_string_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
     Json(payload): Json<SetStringRequest>,
-) -> Result<StatusCode, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<()>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
     if let Some(ttl) = payload.ttl {
-        set_string_with_ttl(conn_arc, &key, &payload.value, ttl)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        set_string_with_ttl(conn, &key, &payload.value, ttl).await?;
     } else {
-        set_string(conn_arc, &key, &payload.value)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        set_string(conn, &key, &payload.value).await?;
     }
-    Ok(StatusCode::OK)
+    Ok(ApiResponse::success(()))
 }
 
 async fn delete_string_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<bool>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let deleted = delete_string(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(deleted))
+) -> Result<ApiResponse<bool>, ApiError> {
+    access.write([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let deleted = delete_string(conn, &key).await?;
+    Ok(ApiResponse::success(deleted))
 }
 
 async fn get_string_info_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Path(key): Path<String>,
-) -> Result<Json<Option<StringInfo>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let info = get_string_info(conn_arc, &key).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(info))
+) -> Result<ApiResponse<Option<StringInfo>>, ApiError> {
+    access.read([&key])?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let info = get_string_info(conn, &key).await?;
+    Ok(ApiResponse::success(info))
 }
 
 // Batch operations
 async fn batch_get_strings_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<BatchGetRequest>,
-) -> Result<Json<Vec<Option<String>>>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    let values = get_multiple_strings(conn_arc, &payload.keys)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(Json(values))
+) -> Result<ApiResponse<Vec<Option<String>>>, ApiError> {
+    access.read(&payload.keys)?;
+    let conn = pool.get_multiplexed_connection().await?;
+    let values = get_multiple_strings(conn, &payload.keys).await?;
+    Ok(ApiResponse::success(values))
 }
 
 async fn batch_set_strings_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<BatchSetRequest>,
-) -> Result<StatusCode, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-    set_multiple_strings(conn_arc, &payload.operations)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    Ok(StatusCode::OK)
+) -> Result<ApiResponse<()>, ApiError> {
+    access.write(payload.operations.iter().map(|op| &op.key))?;
+    let conn = pool.get_multiplexed_connection().await?;
+    set_multiple_strings(conn, &payload.operations).await?;
+    Ok(ApiResponse::success(()))
 }
 
 async fn batch_get_patterns_handler(
+    access: Access,
     State(pool): State<Arc<RedisPool>>,
     Json(payload): Json<BatchGetPatternsRequest>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    let conn = pool
-        .get_connection()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+) -> Result<ApiResponse<serde_json::Value>, ApiError> {
+    access.read_patterns(&payload.patterns)?;
+    let conn = pool.get_multiplexed_connection().await?;
 
     if payload.patterns.is_empty() {
         // Always return an array for results if patterns is empty
         if payload.grouped.unwrap_or(false) {
-            return Ok(Json(serde_json::json!({
+            return Ok(ApiResponse::success(serde_json::json!({
                 "grouped": true,
                 "results": []
             })));
         } else {
-            return Ok(Json(serde_json::json!({
+            return Ok(ApiResponse::success(serde_json::json!({
                 "grouped": false,
                 "results": []
             })));
@@ -143,8 +133,7 @@ async fn batch_get_patterns_handler(
     }
 
     if payload.grouped.unwrap_or(false) {
-        let results = get_strings_by_patterns_grouped(conn_arc, &payload.patterns)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let results = get_strings_by_patterns_grouped(conn, &payload.patterns).await?;
 
         let grouped_results: Vec<serde_json::Value> = results
             .into_iter()
@@ -158,26 +147,25 @@ async fn batch_get_patterns_handler(
             })
             .collect();
 
-        Ok(Json(serde_json::json!({
+        Ok(ApiResponse::success(serde_json::json!({
             "grouped": true,
             "results": grouped_results
         })))
     } else {
-        let results = get_strings_by_patterns(conn_arc, &payload.patterns)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let results = get_strings_by_patterns(conn, &payload.patterns).await?;
 
         let key_value_map: std::collections::HashMap<String, Option<String>> =
             results.into_iter().collect();
 
-        Ok(Json(serde_json::json!({
+        Ok(ApiResponse::success(serde_json::json!({
             "grouped": false,
             "results": key_value_map
         })))
     }
 }
 
-async fn method_not_allowed() -> impl IntoResponse {
-    (StatusCode::METHOD_NOT_ALLOWED, "Method Not Allowed")
+async fn method_not_allowed() -> ApiError {
+    ApiError::new(ErrorCode::MethodNotAllowed, "Method not allowed")
 }
 
 pub fn create_redis_string_routes(pool: Arc<RedisPool>) -> Router {
@@ -9,15 +9,23 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::error::ApiError;
+use crate::logging::{redact, WsSpans};
+use crate::metrics::{ws_connection, ws_message};
+use crate::policy::{Access, AccessDenied};
+use crate::rate_limit::MessageQuota;
 use crate::routes::common::admin::{
     config_get, config_get_all, config_reset_statistics, config_rewrite, config_set,
     flush_all_databases, flush_current_database, get_client_stats, get_database_size,
     get_memory_stats, get_server_info, get_server_info_section, get_server_stats, get_server_time,
     get_server_version, health_check, ping_server, server_status,
 };
+use crate::shutdown::Shutdown;
 use dbx_adapter::redis::client::RedisPool;
 use dbx_adapter::redis::primitives::admin::{HealthCheck, ServerStatus};
 
+use super::{close_socket, until_shutdown};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type", content = "data")]
 pub enum AdminWsMessage {
@@ -102,116 +110,225 @@ pub enum AdminWsMessage {
     FlushAllResult,
 
     // Error message
-    #[serde(rename = "error")]
-    Error(String),
+    #[serde(rename = "error", with = "super::error_data")]
+    Error(ApiError),
+}
+
+impl AdminWsMessage {
+    /// Check that the caller may send this message
+    fn authorize(&self, access: &Access) -> Result<(), AccessDenied> {
+        match self {
+            AdminWsMessage::Ping | AdminWsMessage::Health => Ok(()),
+            AdminWsMessage::ConfigSet { .. }
+            | AdminWsMessage::ConfigResetStat
+            | AdminWsMessage::ConfigRewrite
+            | AdminWsMessage::FlushDb
+            | AdminWsMessage::FlushAll => access.admin().and_then(|()| access.writable()),
+            _ => access.admin(),
+        }
+    }
+
+    /// The message's `type` tag, as a metrics label
+    fn kind(&self) -> &'static str {
+        match self {
+            AdminWsMessage::Ping => "ping",
+            AdminWsMessage::Info { .. } => "info",
+            AdminWsMessage::DbSize => "dbsize",
+            AdminWsMessage::Time => "time",
+            AdminWsMessage::Version => "version",
+            AdminWsMessage::Health => "health",
+            AdminWsMessage::Status => "status",
+            AdminWsMessage::MemoryStats => "memory_stats",
+            AdminWsMessage::ClientStats => "client_stats",
+            AdminWsMessage::ServerStats => "server_stats",
+            AdminWsMessage::ConfigSet { .. } => "config_set",
+            AdminWsMessage::ConfigGet { .. } => "config_get",
+            AdminWsMessage::ConfigGetAll => "config_get_all",
+            AdminWsMessage::ConfigResetStat => "config_resetstat",
+            AdminWsMessage::ConfigRewrite => "config_rewrite",
+            AdminWsMessage::FlushDb => "flushdb",
+            AdminWsMessage::FlushAll => "flushall",
+            AdminWsMessage::PingResult { .. } => "ping_result",
+            AdminWsMessage::InfoResult { .. } => "info_result",
+            AdminWsMessage::DbSizeResult { .. } => "dbsize_result",
+            AdminWsMessage::TimeResult { .. } => "time_result",
+            AdminWsMessage::VersionResult { .. } => "version_result",
+            AdminWsMessage::HealthResult { .. } => "health_result",
+            AdminWsMessage::StatusResult { .. } => "status_result",
+            AdminWsMessage::MemoryStatsResult { .. } => "memory_stats_result",
+            AdminWsMessage::ClientStatsResult { .. } => "client_stats_result",
+            AdminWsMessage::ServerStatsResult { .. } => "server_stats_result",
+            AdminWsMessage::ConfigGetResult { .. } => "config_get_result",
+            AdminWsMessage::ConfigGetAllResult { .. } => "config_get_all_result",
+            AdminWsMessage::ConfigSetResult { .. } => "config_set_result",
+            AdminWsMessage::ConfigResetStatResult => "config_resetstat_result",
+            AdminWsMessage::ConfigRewriteResult => "config_rewrite_result",
+            AdminWsMessage::FlushDbResult => "flushdb_result",
+            AdminWsMessage::FlushAllResult => "flushall_result",
+            AdminWsMessage::Error(_) => "error",
+        }
+    }
 }
 
 async fn redis_ws_admin_handler(
+    access: Access,
+    quota: MessageQuota,
+    shutdown: Shutdown,
     ws: WebSocketUpgrade,
     axum::extract::State(pool): axum::extract::State<Arc<RedisPool>>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_redis_ws_admin_socket(socket, pool))
+    let spans = WsSpans::new("admin");
+    ws.on_upgrade(|socket| {
+        spans.clone().run(handle_redis_ws_admin_socket(
+            socket, pool, access, quota, spans, shutdown,
+        ))
+    })
 }
 
-async fn handle_redis_ws_admin_socket(socket: WebSocket, pool: Arc<RedisPool>) {
-    let (mut sender, mut receiver) = socket.split();
+async fn handle_redis_ws_admin_socket(
+    socket: WebSocket,
+    pool: Arc<RedisPool>,
+    access: Access,
+    mut quota: MessageQuota,
+    spans: WsSpans,
+    shutdown: Shutdown,
+) {
+    let _connection = ws_connection("admin");
+    let _socket = shutdown.open_socket();
+    let (mut sender, receiver) = socket.split();
+    let mut receiver = until_shutdown(receiver, &shutdown);
     while let Some(Ok(msg)) = receiver.next().await {
+        // Every frame counts, so unparsable ones can't be sent for free
+        if let Err(e) = quota.acquire() {
+            let _ = sender
+                .send(axum::extract::ws::Message::Text(
+                    serde_json::to_string(&AdminWsMessage::Error(e.into())).unwrap(),
+                ))
+                .await;
+            continue;
+        }
         if let axum::extract::ws::Message::Text(text) = msg {
-            if let Ok(message) = serde_json::from_str::<AdminWsMessage>(&text) {
-                let conn = match pool.get_connection() {
-                    Ok(c) => c,
-                    Err(e) => {
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&AdminWsMessage::Error(format!(
-                                    "Redis error: {e}"
-                                )))
-                                .unwrap(),
+            spans.start_message(&text).await;
+            let message = match serde_json::from_str::<AdminWsMessage>(&text) {
+                Ok(message) => message,
+                Err(e) => {
+                    tracing::debug!("[WS ADMIN] Failed to parse message: {}", redact(&e));
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&AdminWsMessage::Error(
+                                ApiError::invalid_argument(e.to_string()),
                             ))
-                            .await;
-                        continue;
-                    }
-                };
-                let conn_arc = Arc::new(std::sync::Mutex::new(conn));
+                            .unwrap(),
+                        ))
+                        .await;
+                    continue;
+                }
+            };
+            let _message = ws_message("admin", message.kind());
+            if let Err(e) = message.authorize(&access) {
+                let _ = sender
+                    .send(axum::extract::ws::Message::Text(
+                        serde_json::to_string(&AdminWsMessage::Error(e.into())).unwrap(),
+                    ))
+                    .await;
+                continue;
+            }
+            let conn = match pool.get_multiplexed_connection().await {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&AdminWsMessage::Error(e.into())).unwrap(),
+                        ))
+                        .await;
+                    continue;
+                }
+            };
 
-                match message {
-                    AdminWsMessage::Ping => {
-                        let response =
-                            ping_server(conn_arc.clone()).unwrap_or_else(|_| "ERROR".to_string());
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&(AdminWsMessage::PingResult { response }))
-                                    .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::Info { section } => {
-                        let info = if let Some(section) = section {
-                            get_server_info_section(conn_arc.clone(), &section)
-                                .unwrap_or_else(|_| "ERROR".to_string())
-                        } else {
-                            get_server_info(conn_arc.clone())
-                                .unwrap_or_else(|_| "ERROR".to_string())
-                        };
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&(AdminWsMessage::InfoResult { info }))
-                                    .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::DbSize => {
-                        let size = get_database_size(conn_arc.clone()).unwrap_or(-1);
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&(AdminWsMessage::DbSizeResult { size }))
-                                    .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::Time => {
-                        let time = get_server_time(conn_arc.clone()).unwrap_or((0, 0));
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(
-                                    &(AdminWsMessage::TimeResult {
-                                        seconds: time.0,
-                                        microseconds: time.1,
-                                    }),
-                                )
+            match message {
+                AdminWsMessage::Ping => {
+                    let response = ping_server(conn.clone())
+                        .await
+                        .unwrap_or_else(|_| "ERROR".to_string());
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&(AdminWsMessage::PingResult { response }))
                                 .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::Version => {
-                        let version = get_server_version(conn_arc.clone())
-                            .unwrap_or_else(|_| "UNKNOWN".to_string());
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&(AdminWsMessage::VersionResult { version }))
-                                    .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::Health => {
-                        let health =
-                            health_check(conn_arc.clone()).unwrap_or_else(|_| HealthCheck {
-                                is_healthy: false,
-                                ping_response: "ERROR".to_string(),
-                                database_size: -1,
-                                version: "UNKNOWN".to_string(),
-                                memory_usage: HashMap::new(),
-                            });
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&(AdminWsMessage::HealthResult { health }))
-                                    .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::Status => {
-                        let status =
-                            server_status(conn_arc.clone()).unwrap_or_else(|_| ServerStatus {
+                        ))
+                        .await;
+                }
+                AdminWsMessage::Info { section } => {
+                    let info = if let Some(section) = section {
+                        get_server_info_section(conn.clone(), &section)
+                            .await
+                            .unwrap_or_else(|_| "ERROR".to_string())
+                    } else {
+                        get_server_info(conn.clone())
+                            .await
+                            .unwrap_or_else(|_| "ERROR".to_string())
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&(AdminWsMessage::InfoResult { info })).unwrap(),
+                        ))
+                        .await;
+                }
+                AdminWsMessage::DbSize => {
+                    let size = get_database_size(conn.clone()).await.unwrap_or(-1);
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&(AdminWsMessage::DbSizeResult { size }))
+                                .unwrap(),
+                        ))
+                        .await;
+                }
+                AdminWsMessage::Time => {
+                    let time = get_server_time(conn.clone()).await.unwrap_or((0, 0));
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(
+                                &(AdminWsMessage::TimeResult {
+                                    seconds: time.0,
+                                    microseconds: time.1,
+                                }),
+                            )
+                            .unwrap(),
+                        ))
+                        .await;
+                }
+                AdminWsMessage::Version => {
+                    let version = get_server_version(conn.clone())
+                        .await
+                        .unwrap_or_else(|_| "UNKNOWN".to_string());
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&(AdminWsMessage::VersionResult { version }))
+                                .unwrap(),
+                        ))
+                        .await;
+                }
+                AdminWsMessage::Health => {
+                    let health = health_check(conn.clone())
+                        .await
+                        .unwrap_or_else(|_| HealthCheck {
+                            is_healthy: false,
+                            ping_response: "ERROR".to_string(),
+                            database_size: -1,
+                            version: "UNKNOWN".to_string(),
+                            memory_usage: HashMap::new(),
+                        });
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&(AdminWsMessage::HealthResult { health }))
+                                .unwrap(),
+                        ))
+                        .await;
+                }
+                AdminWsMessage::Status => {
+                    let status =
+                        server_status(conn.clone())
+                            .await
+                            .unwrap_or_else(|_| ServerStatus {
                                 timestamp: 0,
                                 uptime_seconds: 0,
                                 connected_clients: 0,
@@ -222,134 +339,128 @@ async fn handle_redis_ws_admin_socket(socket: WebSocket, pool: Arc<RedisPool>) {
                                 version: "UNKNOWN".to_string(),
                                 role: "UNKNOWN".to_string(),
                             });
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&(AdminWsMessage::StatusResult { status }))
-                                    .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::MemoryStats => {
-                        let stats = get_memory_stats(conn_arc.clone()).unwrap_or_default();
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(
-                                    &(AdminWsMessage::MemoryStatsResult { stats }),
-                                )
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&(AdminWsMessage::StatusResult { status }))
                                 .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::ClientStats => {
-                        let stats = get_client_stats(conn_arc.clone()).unwrap_or_default();
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(
-                                    &(AdminWsMessage::ClientStatsResult { stats }),
-                                )
+                        ))
+                        .await;
+                }
+                AdminWsMessage::MemoryStats => {
+                    let stats = get_memory_stats(conn.clone()).await.unwrap_or_default();
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&(AdminWsMessage::MemoryStatsResult { stats }))
                                 .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::ServerStats => {
-                        let stats = get_server_stats(conn_arc.clone()).unwrap_or_default();
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(
-                                    &(AdminWsMessage::ServerStatsResult { stats }),
-                                )
+                        ))
+                        .await;
+                }
+                AdminWsMessage::ClientStats => {
+                    let stats = get_client_stats(conn.clone()).await.unwrap_or_default();
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&(AdminWsMessage::ClientStatsResult { stats }))
                                 .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::ConfigSet { parameter, value } => {
-                        let res = config_set(conn_arc.clone(), &parameter, &value);
-                        let msg = match res {
-                            Ok(_) => AdminWsMessage::ConfigSetResult { parameter, value },
-                            Err(e) => AdminWsMessage::Error(format!("Config set error: {e}")),
-                        };
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&msg).unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::ConfigGet { parameter } => {
-                        let value = config_get(conn_arc.clone(), &parameter)
-                            .unwrap_or_else(|_| "ERROR".to_string());
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(
-                                    &(AdminWsMessage::ConfigGetResult { parameter, value }),
-                                )
+                        ))
+                        .await;
+                }
+                AdminWsMessage::ServerStats => {
+                    let stats = get_server_stats(conn.clone()).await.unwrap_or_default();
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&(AdminWsMessage::ServerStatsResult { stats }))
                                 .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::ConfigGetAll => {
-                        let config = config_get_all(conn_arc.clone()).unwrap_or_default();
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(
-                                    &(AdminWsMessage::ConfigGetAllResult { config }),
-                                )
+                        ))
+                        .await;
+                }
+                AdminWsMessage::ConfigSet { parameter, value } => {
+                    let res = config_set(conn.clone(), &parameter, &value).await;
+                    let msg = match res {
+                        Ok(_) => AdminWsMessage::ConfigSetResult { parameter, value },
+                        Err(e) => AdminWsMessage::Error(e.into()),
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&msg).unwrap(),
+                        ))
+                        .await;
+                }
+                AdminWsMessage::ConfigGet { parameter } => {
+                    let value = config_get(conn.clone(), &parameter)
+                        .await
+                        .unwrap_or_else(|_| "ERROR".to_string());
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(
+                                &(AdminWsMessage::ConfigGetResult { parameter, value }),
+                            )
+                            .unwrap(),
+                        ))
+                        .await;
+                }
+                AdminWsMessage::ConfigGetAll => {
+                    let config = config_get_all(conn.clone()).await.unwrap_or_default();
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&(AdminWsMessage::ConfigGetAllResult { config }))
                                 .unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::ConfigResetStat => {
-                        let res = config_reset_statistics(conn_arc.clone());
-                        let msg = match res {
-                            Ok(_) => AdminWsMessage::ConfigResetStatResult,
-                            Err(e) => AdminWsMessage::Error(format!("Config resetstat error: {e}")),
-                        };
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&msg).unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::ConfigRewrite => {
-                        let res = config_rewrite(conn_arc.clone());
-                        let msg = match res {
-                            Ok(_) => AdminWsMessage::ConfigRewriteResult,
-                            Err(e) => AdminWsMessage::Error(format!("Config rewrite error: {e}")),
-                        };
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&msg).unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::FlushDb => {
-                        let res = flush_current_database(conn_arc.clone());
-                        let msg = match res {
-                            Ok(_) => AdminWsMessage::FlushDbResult,
-                            Err(e) => AdminWsMessage::Error(format!("FlushDB error: {e}")),
-                        };
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&msg).unwrap(),
-                            ))
-                            .await;
-                    }
-                    AdminWsMessage::FlushAll => {
-                        let res = flush_all_databases(conn_arc.clone());
-                        let msg = match res {
-                            Ok(_) => AdminWsMessage::FlushAllResult,
-                            Err(e) => AdminWsMessage::Error(format!("FlushAll error: {e}")),
-                        };
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&msg).unwrap(),
-                            ))
-                            .await;
-                    }
-                    _ => {}
+                        ))
+                        .await;
+                }
+                AdminWsMessage::ConfigResetStat => {
+                    let res = config_reset_statistics(conn.clone()).await;
+                    let msg = match res {
+                        Ok(_) => AdminWsMessage::ConfigResetStatResult,
+                        Err(e) => AdminWsMessage::Error(e.into()),
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&msg).unwrap(),
+                        ))
+                        .await;
+                }
+                AdminWsMessage::ConfigRewrite => {
+                    let res = config_rewrite(conn.clone()).await;
+                    let msg = match res {
+                        Ok(_) => AdminWsMessage::ConfigRewriteResult,
+                        Err(e) => AdminWsMessage::Error(e.into()),
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&msg).unwrap(),
+                        ))
+                        .await;
                 }
+                AdminWsMessage::FlushDb => {
+                    let res = flush_current_database(conn.clone()).await;
+                    let msg = match res {
+                        Ok(_) => AdminWsMessage::FlushDbResult,
+                        Err(e) => AdminWsMessage::Error(e.into()),
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&msg).unwrap(),
+                        ))
+                        .await;
+                }
+                AdminWsMessage::FlushAll => {
+                    let res = flush_all_databases(conn.clone()).await;
+                    let msg = match res {
+                        Ok(_) => AdminWsMessage::FlushAllResult,
+                        Err(e) => AdminWsMessage::Error(e.into()),
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&msg).unwrap(),
+                        ))
+                        .await;
+                }
+                _ => {}
             }
         }
     }
+
+    close_socket(&mut sender, &shutdown).await;
 }
 
 pub fn create_redis_ws_admin_routes(pool: Arc<RedisPool>) -> Router {
@@ -0,0 +1,340 @@
+use axum::{
+    extract::{ws::WebSocket, WebSocketUpgrade},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::ApiError;
+use crate::logging::{redact, WsSpans};
+use crate::metrics::{ws_connection, ws_message};
+use crate::policy::{Access, AccessDenied};
+use crate::rate_limit::MessageQuota;
+use crate::routes::common::bitmap::{
+    bit_operation, count_bits, find_bit_position, get_bit, get_bitmap_bytes, get_multiple_bits,
+    set_bit, set_multiple_bits, BitOperation, BitRangeUnit,
+};
+use crate::shutdown::Shutdown;
+use dbx_adapter::redis::client::RedisPool;
+
+use super::{close_socket, until_shutdown};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum BitmapWsMessage {
+    #[serde(rename = "setbit")]
+    SetBit {
+        #[serde(rename = "data")]
+        data: SetBitData,
+    },
+    #[serde(rename = "getbit")]
+    GetBit {
+        #[serde(rename = "data")]
+        data: GetBitData,
+    },
+    #[serde(rename = "setbits")]
+    SetBits {
+        #[serde(rename = "data")]
+        data: SetBitsData,
+    },
+    #[serde(rename = "getbits")]
+    GetBits {
+        #[serde(rename = "data")]
+        data: GetBitsData,
+    },
+    #[serde(rename = "count")]
+    Count {
+        #[serde(rename = "data")]
+        data: CountData,
+    },
+    #[serde(rename = "position")]
+    Position {
+        #[serde(rename = "data")]
+        data: PositionData,
+    },
+    #[serde(rename = "bytes")]
+    Bytes {
+        #[serde(rename = "data")]
+        data: BytesData,
+    },
+    #[serde(rename = "bitop")]
+    BitOp {
+        #[serde(rename = "data")]
+        data: BitOpData,
+    },
+    // Response types
+    #[serde(rename = "result")]
+    Result {
+        #[serde(rename = "data")]
+        data: ResultData,
+    },
+    #[serde(rename = "error", with = "super::error_data")]
+    Error(ApiError),
+    #[serde(rename = "ping")]
+    Ping,
+    #[serde(rename = "pong")]
+    Pong,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetBitData {
+    pub key: String,
+    pub offset: usize,
+    pub value: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetBitData {
+    pub key: String,
+    pub offset: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetBitsData {
+    pub key: String,
+    pub bits: Vec<(usize, bool)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetBitsData {
+    pub key: String,
+    pub offsets: Vec<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CountData {
+    pub key: String,
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PositionData {
+    pub key: String,
+    pub bit: bool,
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    #[serde(default)]
+    pub unit: BitRangeUnit,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BytesData {
+    pub key: String,
+    pub offset: u64,
+    pub length: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BitOpData {
+    pub operation: BitOperation,
+    pub destination: String,
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResultData {
+    pub key: String,
+    pub value: Option<serde_json::Value>,
+}
+
+fn result_message<T: Serialize>(key: String, result: redis::RedisResult<T>) -> BitmapWsMessage {
+    match result {
+        Ok(value) => BitmapWsMessage::Result {
+            data: ResultData {
+                key,
+                value: Some(serde_json::json!(value)),
+            },
+        },
+        Err(e) => BitmapWsMessage::Error(e.into()),
+    }
+}
+
+fn range_from(start: Option<i64>, end: Option<i64>) -> Option<(i64, i64)> {
+    start.zip(end)
+}
+
+impl BitmapWsMessage {
+    /// Check that the caller may send this message
+    fn authorize(&self, access: &Access) -> Result<(), AccessDenied> {
+        match self {
+            BitmapWsMessage::SetBit { data } => access.write([&data.key]),
+            BitmapWsMessage::SetBits { data } => access.write([&data.key]),
+            BitmapWsMessage::GetBit { data } => access.read([&data.key]),
+            BitmapWsMessage::GetBits { data } => access.read([&data.key]),
+            BitmapWsMessage::Count { data } => access.read([&data.key]),
+            BitmapWsMessage::Position { data } => access.read([&data.key]),
+            BitmapWsMessage::Bytes { data } => access.read([&data.key]),
+            BitmapWsMessage::BitOp { data } => {
+                access.write([&data.destination])?;
+                access.read(&data.keys)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The message's `type` tag, as a metrics label
+    fn kind(&self) -> &'static str {
+        match self {
+            BitmapWsMessage::SetBit { .. } => "setbit",
+            BitmapWsMessage::GetBit { .. } => "getbit",
+            BitmapWsMessage::SetBits { .. } => "setbits",
+            BitmapWsMessage::GetBits { .. } => "getbits",
+            BitmapWsMessage::Count { .. } => "count",
+            BitmapWsMessage::Position { .. } => "position",
+            BitmapWsMessage::Bytes { .. } => "bytes",
+            BitmapWsMessage::BitOp { .. } => "bitop",
+            BitmapWsMessage::Result { .. } => "result",
+            BitmapWsMessage::Error(_) => "error",
+            BitmapWsMessage::Ping => "ping",
+            BitmapWsMessage::Pong => "pong",
+        }
+    }
+}
+
+async fn redis_ws_bitmap_handler(
+    access: Access,
+    quota: MessageQuota,
+    shutdown: Shutdown,
+    ws: WebSocketUpgrade,
+    axum::extract::State(pool): axum::extract::State<Arc<RedisPool>>,
+) -> impl IntoResponse {
+    let spans = WsSpans::new("bitmap");
+    ws.on_upgrade(|socket| {
+        spans.clone().run(handle_redis_ws_bitmap_socket(
+            socket, pool, access, quota, spans, shutdown,
+        ))
+    })
+}
+
+async fn handle_redis_ws_bitmap_socket(
+    socket: WebSocket,
+    pool: Arc<RedisPool>,
+    access: Access,
+    mut quota: MessageQuota,
+    spans: WsSpans,
+    shutdown: Shutdown,
+) {
+    let _connection = ws_connection("bitmap");
+    let _socket = shutdown.open_socket();
+    let (mut sender, receiver) = socket.split();
+    let mut receiver = until_shutdown(receiver, &shutdown);
+    while let Some(Ok(msg)) = receiver.next().await {
+        tracing::debug!("[WS BITMAP] Received WebSocket message: {:?}", redact(&msg));
+        // Every frame counts, so unparsable ones can't be sent for free
+        if let Err(e) = quota.acquire() {
+            let _ = sender
+                .send(axum::extract::ws::Message::Text(
+                    serde_json::to_string(&BitmapWsMessage::Error(e.into())).unwrap(),
+                ))
+                .await;
+            continue;
+        }
+        if let axum::extract::ws::Message::Text(text) = msg {
+            spans.start_message(&text).await;
+            match serde_json::from_str::<BitmapWsMessage>(&text) {
+                Ok(message) => {
+                    tracing::debug!("[WS BITMAP] Parsed message: {:?}", redact(&message));
+                    let _message = ws_message("bitmap", message.kind());
+                    if let Err(e) = message.authorize(&access) {
+                        let _ = sender
+                            .send(axum::extract::ws::Message::Text(
+                                serde_json::to_string(&BitmapWsMessage::Error(e.into())).unwrap(),
+                            ))
+                            .await;
+                        continue;
+                    }
+                    let conn = match pool.get_multiplexed_connection().await {
+                        Ok(c) => c,
+                        Err(e) => {
+                            let _ = sender
+                                .send(axum::extract::ws::Message::Text(
+                                    serde_json::to_string(&BitmapWsMessage::Error(ApiError::from(
+                                        e,
+                                    )))
+                                    .unwrap(),
+                                ))
+                                .await;
+                            continue;
+                        }
+                    };
+
+                    let response = match message {
+                        BitmapWsMessage::SetBit { data } => {
+                            let result = set_bit(conn, &data.key, data.offset, data.value).await;
+                            result_message(data.key, result)
+                        }
+                        BitmapWsMessage::GetBit { data } => {
+                            let result = get_bit(conn, &data.key, data.offset).await;
+                            result_message(data.key, result)
+                        }
+                        BitmapWsMessage::SetBits { data } => {
+                            let result = set_multiple_bits(conn, &data.key, data.bits).await;
+                            result_message(data.key, result)
+                        }
+                        BitmapWsMessage::GetBits { data } => {
+                            let result = get_multiple_bits(conn, &data.key, data.offsets).await;
+                            result_message(data.key, result)
+                        }
+                        BitmapWsMessage::Count { data } => {
+                            let range = range_from(data.start, data.end);
+                            let result = count_bits(conn, &data.key, range).await;
+                            result_message(data.key, result)
+                        }
+                        BitmapWsMessage::Position { data } => {
+                            let range = range_from(data.start, data.end);
+                            let result =
+                                find_bit_position(conn, &data.key, data.bit, range, data.unit)
+                                    .await;
+                            result_message(data.key, result)
+                        }
+                        BitmapWsMessage::Bytes { data } => {
+                            let result =
+                                get_bitmap_bytes(conn, &data.key, data.offset, data.length).await;
+                            result_message(data.key, result)
+                        }
+                        BitmapWsMessage::BitOp { data } => {
+                            let key_refs: Vec<&str> =
+                                data.keys.iter().map(|k| k.as_str()).collect();
+                            let result =
+                                bit_operation(conn, data.operation, &data.destination, &key_refs)
+                                    .await;
+                            result_message(data.destination, result)
+                        }
+                        BitmapWsMessage::Ping => BitmapWsMessage::Pong,
+                        _ => continue,
+                    };
+
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&response).unwrap(),
+                        ))
+                        .await;
+                }
+                Err(e) => {
+                    tracing::debug!("[WS BITMAP] Failed to parse message: {}", redact(&e));
+                    tracing::debug!("[WS BITMAP] Raw message: {}", redact(&text));
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&BitmapWsMessage::Error(
+                                ApiError::invalid_argument(e.to_string()),
+                            ))
+                            .unwrap(),
+                        ))
+                        .await;
+                }
+            }
+        }
+    }
+
+    close_socket(&mut sender, &shutdown).await;
+}
+
+pub fn create_redis_ws_bitmap_routes(pool: Arc<RedisPool>) -> Router {
+    Router::new()
+        .route("/bitmap/ws", get(redis_ws_bitmap_handler))
+        .with_state(pool)
+}
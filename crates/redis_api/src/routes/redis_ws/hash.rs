@@ -8,12 +8,20 @@ use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use crate::error::ApiError;
+use crate::logging::{redact, WsSpans};
+use crate::metrics::{ws_connection, ws_message};
+use crate::policy::{Access, AccessDenied};
+use crate::rate_limit::MessageQuota;
 use crate::routes::common::hash::{
     delete_hash_field, get_all_hash_fields, get_hash_field, set_hash_field,
     set_multiple_hash_fields,
 };
+use crate::shutdown::Shutdown;
 use dbx_adapter::redis::client::RedisPool;
 
+use super::{close_socket, until_shutdown};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type", content = "data")]
 pub enum HashWsMessage {
@@ -53,134 +61,213 @@ pub enum HashWsMessage {
         field: String,
         deleted: bool,
     },
-    #[serde(rename = "error")]
-    Error(String),
+    #[serde(rename = "error", with = "super::error_data")]
+    Error(ApiError),
     #[serde(rename = "ping")]
     Ping,
     #[serde(rename = "pong")]
     Pong,
 }
 
+impl HashWsMessage {
+    /// Check that the caller may send this message
+    fn authorize(&self, access: &Access) -> Result<(), AccessDenied> {
+        match self {
+            HashWsMessage::Get { key, .. }
+            | HashWsMessage::GetAll { key }
+            | HashWsMessage::Exists { key, .. } => access.read([key]),
+            HashWsMessage::Set { key, .. }
+            | HashWsMessage::Del { key, .. }
+            | HashWsMessage::BatchSet { key, .. } => access.write([key]),
+            _ => Ok(()),
+        }
+    }
+
+    /// The message's `type` tag, as a metrics label
+    fn kind(&self) -> &'static str {
+        match self {
+            HashWsMessage::Get { .. } => "get",
+            HashWsMessage::Set { .. } => "set",
+            HashWsMessage::GetAll { .. } => "get_all",
+            HashWsMessage::Del { .. } => "del",
+            HashWsMessage::Exists { .. } => "exists",
+            HashWsMessage::BatchSet { .. } => "batch_set",
+            HashWsMessage::Result { .. } => "result",
+            HashWsMessage::AllResult { .. } => "all_result",
+            HashWsMessage::Deleted { .. } => "deleted",
+            HashWsMessage::Error(_) => "error",
+            HashWsMessage::Ping => "ping",
+            HashWsMessage::Pong => "pong",
+        }
+    }
+}
+
 async fn redis_ws_hash_handler(
+    access: Access,
+    quota: MessageQuota,
+    shutdown: Shutdown,
     ws: WebSocketUpgrade,
     axum::extract::State(pool): axum::extract::State<Arc<RedisPool>>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_redis_ws_hash_socket(socket, pool))
+    let spans = WsSpans::new("hash");
+    ws.on_upgrade(|socket| {
+        spans.clone().run(handle_redis_ws_hash_socket(
+            socket, pool, access, quota, spans, shutdown,
+        ))
+    })
 }
 
-async fn handle_redis_ws_hash_socket(socket: WebSocket, pool: Arc<RedisPool>) {
-    let (mut sender, mut receiver) = socket.split();
+async fn handle_redis_ws_hash_socket(
+    socket: WebSocket,
+    pool: Arc<RedisPool>,
+    access: Access,
+    mut quota: MessageQuota,
+    spans: WsSpans,
+    shutdown: Shutdown,
+) {
+    let _connection = ws_connection("hash");
+    let _socket = shutdown.open_socket();
+    let (mut sender, receiver) = socket.split();
+    let mut receiver = until_shutdown(receiver, &shutdown);
     while let Some(Ok(msg)) = receiver.next().await {
+        // Every frame counts, so unparsable ones can't be sent for free
+        if let Err(e) = quota.acquire() {
+            let _ = sender
+                .send(axum::extract::ws::Message::Text(
+                    serde_json::to_string(&HashWsMessage::Error(e.into())).unwrap(),
+                ))
+                .await;
+            continue;
+        }
         if let axum::extract::ws::Message::Text(text) = msg {
-            if let Ok(message) = serde_json::from_str::<HashWsMessage>(&text) {
-                let conn = match pool.get_connection() {
-                    Ok(c) => c,
-                    Err(e) => {
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&HashWsMessage::Error(format!(
-                                    "Redis error: {e}"
-                                )))
-                                .unwrap(),
-                            ))
-                            .await;
-                        continue;
-                    }
-                };
-                let conn_arc = Arc::new(std::sync::Mutex::new(conn));
-                match message {
-                    HashWsMessage::Get { key, field } => {
-                        let value = get_hash_field(conn_arc.clone(), &key, &field)
-                            .ok()
-                            .flatten();
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(
-                                    &(HashWsMessage::Result {
-                                        key,
-                                        field: Some(field),
-                                        value,
-                                    }),
-                                )
-                                .unwrap(),
-                            ))
-                            .await;
-                    }
-                    HashWsMessage::Set { key, field, value } => {
-                        let res = set_hash_field(conn_arc.clone(), &key, &field, &value);
-                        let msg = match res {
-                            Ok(_) => HashWsMessage::Result {
-                                key,
-                                field: Some(field),
-                                value: Some(value),
-                            },
-                            Err(e) => HashWsMessage::Error(format!("Set error: {e}")),
-                        };
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&msg).unwrap(),
-                            ))
-                            .await;
-                    }
-                    HashWsMessage::Del { key, field } => {
-                        let deleted =
-                            delete_hash_field(conn_arc.clone(), &key, &field).unwrap_or(false);
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(
-                                    &(HashWsMessage::Deleted {
-                                        key,
-                                        field,
-                                        deleted,
-                                    }),
-                                )
-                                .unwrap(),
+            spans.start_message(&text).await;
+            let message = match serde_json::from_str::<HashWsMessage>(&text) {
+                Ok(message) => message,
+                Err(e) => {
+                    tracing::debug!("[WS HASH] Failed to parse message: {}", redact(&e));
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&HashWsMessage::Error(
+                                ApiError::invalid_argument(e.to_string()),
                             ))
-                            .await;
-                    }
-                    HashWsMessage::GetAll { key } => {
-                        let fields =
-                            get_all_hash_fields(conn_arc.clone(), &key).unwrap_or_default();
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&(HashWsMessage::AllResult { key, fields }))
-                                    .unwrap(),
-                            ))
-                            .await;
-                    }
-                    HashWsMessage::BatchSet { key, fields } => {
-                        let field_refs: Vec<(&str, &str)> = fields
-                            .iter()
-                            .map(|(f, v)| (f.as_str(), v.as_str()))
-                            .collect();
-                        let res = set_multiple_hash_fields(conn_arc.clone(), &key, &field_refs);
-                        let msg = match res {
-                            Ok(_) => HashWsMessage::Result {
-                                key,
-                                field: None,
-                                value: Some("Batch set success".to_string()),
-                            },
-                            Err(e) => HashWsMessage::Error(format!("Batch set error: {e}")),
-                        };
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&msg).unwrap(),
-                            ))
-                            .await;
-                    }
-                    HashWsMessage::Ping => {
-                        let pong = HashWsMessage::Pong;
-                        let _ = sender
-                            .send(axum::extract::ws::Message::Text(
-                                serde_json::to_string(&pong).unwrap(),
-                            ))
-                            .await;
-                    }
-                    _ => {}
+                            .unwrap(),
+                        ))
+                        .await;
+                    continue;
+                }
+            };
+            let _message = ws_message("hash", message.kind());
+            if let Err(e) = message.authorize(&access) {
+                let _ = sender
+                    .send(axum::extract::ws::Message::Text(
+                        serde_json::to_string(&HashWsMessage::Error(e.into())).unwrap(),
+                    ))
+                    .await;
+                continue;
+            }
+            let conn = match pool.get_multiplexed_connection().await {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&HashWsMessage::Error(e.into())).unwrap(),
+                        ))
+                        .await;
+                    continue;
+                }
+            };
+            match message {
+                HashWsMessage::Get { key, field } => {
+                    let msg = match get_hash_field(conn.clone(), &key, &field).await {
+                        Ok(value) => HashWsMessage::Result {
+                            key,
+                            field: Some(field),
+                            value,
+                        },
+                        Err(e) => HashWsMessage::Error(e.into()),
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&msg).unwrap(),
+                        ))
+                        .await;
                 }
+                HashWsMessage::Set { key, field, value } => {
+                    let res = set_hash_field(conn.clone(), &key, &field, &value).await;
+                    let msg = match res {
+                        Ok(_) => HashWsMessage::Result {
+                            key,
+                            field: Some(field),
+                            value: Some(value),
+                        },
+                        Err(e) => HashWsMessage::Error(e.into()),
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&msg).unwrap(),
+                        ))
+                        .await;
+                }
+                HashWsMessage::Del { key, field } => {
+                    let msg = match delete_hash_field(conn.clone(), &key, &field).await {
+                        Ok(deleted) => HashWsMessage::Deleted {
+                            key,
+                            field,
+                            deleted,
+                        },
+                        Err(e) => HashWsMessage::Error(e.into()),
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&msg).unwrap(),
+                        ))
+                        .await;
+                }
+                HashWsMessage::GetAll { key } => {
+                    let msg = match get_all_hash_fields(conn.clone(), &key).await {
+                        Ok(fields) => HashWsMessage::AllResult { key, fields },
+                        Err(e) => HashWsMessage::Error(e.into()),
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&msg).unwrap(),
+                        ))
+                        .await;
+                }
+                HashWsMessage::BatchSet { key, fields } => {
+                    let field_refs: Vec<(&str, &str)> = fields
+                        .iter()
+                        .map(|(f, v)| (f.as_str(), v.as_str()))
+                        .collect();
+                    let res = set_multiple_hash_fields(conn.clone(), &key, &field_refs).await;
+                    let msg = match res {
+                        Ok(_) => HashWsMessage::Result {
+                            key,
+                            field: None,
+                            value: Some("Batch set success".to_string()),
+                        },
+                        Err(e) => HashWsMessage::Error(e.into()),
+                    };
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&msg).unwrap(),
+                        ))
+                        .await;
+                }
+                HashWsMessage::Ping => {
+                    let pong = HashWsMessage::Pong;
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&pong).unwrap(),
+                        ))
+                        .await;
+                }
+                _ => {}
             }
         }
     }
+
+    close_socket(&mut sender, &shutdown).await;
 }
 
 pub fn create_redis_ws_hash_routes(pool: Arc<RedisPool>) -> Router {
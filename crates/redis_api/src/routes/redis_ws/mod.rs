@@ -1,4 +1,70 @@
 pub mod admin;
+pub mod bitmap;
 pub mod hash;
+pub mod pubsub;
+pub mod script;
 pub mod set;
+pub mod sorted_set;
 pub mod string;
+
+use axum::extract::ws::{close_code, CloseFrame, Message};
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use std::pin::Pin;
+
+use crate::constants::errors::ErrorMessages;
+use crate::shutdown::Shutdown;
+
+/// Serde adapter for the `Error` variant of internally tagged messages
+///
+/// Internal tagging would flatten the error into the message, so it goes in a
+/// `data` field like every other payload:
+/// `{"type": "error", "data": {"code": "WRONG_TYPE", "message": "..."}}`.
+pub(crate) mod error_data {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::error::ApiError;
+
+    #[derive(Serialize, Deserialize)]
+    struct ErrorData<T> {
+        data: T,
+    }
+
+    pub fn serialize<S: Serializer>(error: &ApiError, serializer: S) -> Result<S::Ok, S::Error> {
+        ErrorData { data: error }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ApiError, D::Error> {
+        ErrorData::<ApiError>::deserialize(deserializer).map(|error| error.data)
+    }
+}
+
+/// Messages from the client, ending early when the server starts shutting down
+///
+/// The message being handled when shutdown starts still gets its reply.
+pub(crate) fn until_shutdown<'a, S>(
+    receiver: S,
+    shutdown: &'a Shutdown,
+) -> Pin<Box<dyn Stream<Item = S::Item> + Send + 'a>>
+where
+    S: Stream + Send + 'a,
+{
+    Box::pin(receiver.take_until(shutdown.draining()))
+}
+
+/// Close a socket the server is done with, telling the client why
+///
+/// Sockets only end on the server's side while it shuts down; otherwise the
+/// client has already gone and there is nothing to send.
+pub(crate) async fn close_socket<S>(sender: &mut S, shutdown: &Shutdown)
+where
+    S: Sink<Message> + Unpin,
+{
+    if shutdown.is_draining() {
+        let _ = sender
+            .send(Message::Close(Some(CloseFrame {
+                code: close_code::AWAY,
+                reason: ErrorMessages::SHUTTING_DOWN.into(),
+            })))
+            .await;
+    }
+}
@@ -0,0 +1,453 @@
+use axum::{
+    extract::{ws::WebSocket, WebSocketUpgrade},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+
+use crate::error::{ApiError, ErrorCode};
+use crate::logging::{redact, WsSpans};
+use crate::metrics::{ws_connection, ws_message};
+use crate::policy::{Access, AccessDenied};
+use crate::rate_limit::MessageQuota;
+use crate::routes::common::pubsub::{publish_message, PubSubHub, PubSubMessage, Topic};
+use crate::shutdown::Shutdown;
+use dbx_adapter::redis::client::RedisPool;
+
+use super::{close_socket, until_shutdown};
+
+/// Number of outgoing messages queued per socket
+const OUTGOING_BUFFER_SIZE: usize = 256;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum PubSubWsMessage {
+    #[serde(rename = "subscribe")]
+    Subscribe {
+        #[serde(rename = "data")]
+        data: ChannelsData,
+    },
+    #[serde(rename = "psubscribe")]
+    PSubscribe {
+        #[serde(rename = "data")]
+        data: PatternsData,
+    },
+    #[serde(rename = "unsubscribe")]
+    Unsubscribe {
+        #[serde(rename = "data")]
+        data: ChannelsData,
+    },
+    #[serde(rename = "punsubscribe")]
+    PUnsubscribe {
+        #[serde(rename = "data")]
+        data: PatternsData,
+    },
+    #[serde(rename = "publish")]
+    Publish {
+        #[serde(rename = "data")]
+        data: PublishData,
+    },
+    // Response types
+    #[serde(rename = "subscriptions")]
+    Subscriptions {
+        #[serde(rename = "data")]
+        data: SubscriptionsData,
+    },
+    #[serde(rename = "published")]
+    Published {
+        #[serde(rename = "data")]
+        data: PublishedData,
+    },
+    #[serde(rename = "message")]
+    Message {
+        #[serde(rename = "data")]
+        data: PubSubMessage,
+    },
+    #[serde(rename = "error", with = "super::error_data")]
+    Error(ApiError),
+    #[serde(rename = "ping")]
+    Ping,
+    #[serde(rename = "pong")]
+    Pong,
+}
+
+/// Channels to (un)subscribe; an empty list unsubscribes from every channel
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChannelsData {
+    #[serde(default)]
+    pub channels: Vec<String>,
+}
+
+/// Patterns to (un)subscribe; an empty list unsubscribes from every pattern
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PatternsData {
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PublishData {
+    pub channel: String,
+    pub message: String,
+}
+
+/// The socket's subscriptions after a subscribe or unsubscribe request
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubscriptionsData {
+    pub channels: Vec<String>,
+    pub patterns: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PublishedData {
+    pub channel: String,
+    pub receivers: usize,
+}
+
+#[derive(Clone)]
+struct PubSubWsState {
+    pool: Arc<RedisPool>,
+    hub: Arc<PubSubHub>,
+}
+
+/// Forwards one shared hub topic to a socket
+struct Forwarder {
+    /// Tells a subscription that ended apart from a newer one to the same topic
+    id: u64,
+    task: JoinHandle<()>,
+}
+
+/// Per-socket subscriptions, each forwarding a shared hub topic to this socket
+struct SocketSubscriptions {
+    hub: Arc<PubSubHub>,
+    outgoing: mpsc::Sender<PubSubWsMessage>,
+    /// Forwarders whose Redis subscription ended, for the socket to give back
+    closed: mpsc::UnboundedSender<(Topic, u64)>,
+    forwarders: HashMap<Topic, Forwarder>,
+    next_id: u64,
+}
+
+impl SocketSubscriptions {
+    async fn subscribe(&mut self, topic: Topic) -> Result<(), ApiError> {
+        if self.forwarders.contains_key(&topic) {
+            return Ok(());
+        }
+        let mut receiver = self.hub.subscribe(&topic).await?;
+        let outgoing = self.outgoing.clone();
+        let closed = self.closed.clone();
+        let id = self.next_id;
+        self.next_id += 1;
+        let closed_topic = topic.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let message = match receiver.recv().await {
+                    Ok(data) => PubSubWsMessage::Message { data },
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        PubSubWsMessage::Error(ApiError::new(
+                            ErrorCode::Unavailable,
+                            format!("Subscriber too slow, dropped {skipped} messages"),
+                        ))
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        let _ = closed.send((closed_topic, id));
+                        break;
+                    }
+                };
+                if outgoing.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        self.forwarders.insert(topic, Forwarder { id, task });
+        Ok(())
+    }
+
+    async fn unsubscribe(&mut self, topic: &Topic) {
+        if let Some(forwarder) = self.forwarders.remove(topic) {
+            forwarder.task.abort();
+            self.hub.release(topic).await;
+        }
+    }
+
+    /// Unsubscribes from the given topics, or from every topic matching `filter` if none are given
+    async fn unsubscribe_many(&mut self, topics: Vec<Topic>, filter: fn(&Topic) -> bool) {
+        let topics = if topics.is_empty() {
+            self.forwarders
+                .keys()
+                .filter(|t| filter(t))
+                .cloned()
+                .collect()
+        } else {
+            topics
+        };
+        for topic in topics {
+            self.unsubscribe(&topic).await;
+        }
+    }
+
+    /// Drops a forwarder whose Redis subscription ended and tells the client
+    async fn close(
+        &mut self,
+        topic: Topic,
+        id: u64,
+    ) -> Result<(), mpsc::error::SendError<PubSubWsMessage>> {
+        // The client may have unsubscribed, and even subscribed again, meanwhile
+        if self.forwarders.get(&topic).map(|forwarder| forwarder.id) != Some(id) {
+            return Ok(());
+        }
+        self.unsubscribe(&topic).await;
+        self.outgoing
+            .send(PubSubWsMessage::Error(ApiError::new(
+                ErrorCode::Unavailable,
+                format!("Redis closed the subscription to {topic}, subscribe again"),
+            )))
+            .await?;
+        self.outgoing.send(self.summary()).await
+    }
+
+    async fn unsubscribe_all(&mut self) {
+        self.unsubscribe_many(Vec::new(), |_| true).await;
+    }
+
+    fn summary(&self) -> PubSubWsMessage {
+        let mut channels = Vec::new();
+        let mut patterns = Vec::new();
+        for topic in self.forwarders.keys() {
+            match topic {
+                Topic::Channel(channel) => channels.push(channel.clone()),
+                Topic::Pattern(pattern) => patterns.push(pattern.clone()),
+            }
+        }
+        channels.sort();
+        patterns.sort();
+        PubSubWsMessage::Subscriptions {
+            data: SubscriptionsData { channels, patterns },
+        }
+    }
+}
+
+impl PubSubWsMessage {
+    /// Check that the caller may send this message
+    fn authorize(&self, access: &Access) -> Result<(), AccessDenied> {
+        match self {
+            PubSubWsMessage::Subscribe { data } => access.read(&data.channels),
+            PubSubWsMessage::PSubscribe { data } => access.read_patterns(&data.patterns),
+            PubSubWsMessage::Publish { data } => access.write([&data.channel]),
+            _ => Ok(()),
+        }
+    }
+
+    /// The message's `type` tag, as a metrics label
+    fn kind(&self) -> &'static str {
+        match self {
+            PubSubWsMessage::Subscribe { .. } => "subscribe",
+            PubSubWsMessage::PSubscribe { .. } => "psubscribe",
+            PubSubWsMessage::Unsubscribe { .. } => "unsubscribe",
+            PubSubWsMessage::PUnsubscribe { .. } => "punsubscribe",
+            PubSubWsMessage::Publish { .. } => "publish",
+            PubSubWsMessage::Subscriptions { .. } => "subscriptions",
+            PubSubWsMessage::Published { .. } => "published",
+            PubSubWsMessage::Message { .. } => "message",
+            PubSubWsMessage::Error(_) => "error",
+            PubSubWsMessage::Ping => "ping",
+            PubSubWsMessage::Pong => "pong",
+        }
+    }
+}
+
+async fn redis_ws_pubsub_handler(
+    access: Access,
+    quota: MessageQuota,
+    shutdown: Shutdown,
+    ws: WebSocketUpgrade,
+    axum::extract::State(state): axum::extract::State<PubSubWsState>,
+) -> impl IntoResponse {
+    let spans = WsSpans::new("pubsub");
+    ws.on_upgrade(|socket| {
+        spans.clone().run(handle_redis_ws_pubsub_socket(
+            socket, state, access, quota, spans, shutdown,
+        ))
+    })
+}
+
+async fn handle_redis_ws_pubsub_socket(
+    socket: WebSocket,
+    state: PubSubWsState,
+    access: Access,
+    mut quota: MessageQuota,
+    spans: WsSpans,
+    shutdown: Shutdown,
+) {
+    let _connection = ws_connection("pubsub");
+    let _socket = shutdown.open_socket();
+    let (mut sender, receiver) = socket.split();
+    let mut receiver = until_shutdown(receiver, &shutdown);
+
+    // Pushed messages and replies share one writer so they never interleave mid-frame
+    let (outgoing, mut outgoing_rx) = mpsc::channel::<PubSubWsMessage>(OUTGOING_BUFFER_SIZE);
+    let writer = tokio::spawn(async move {
+        while let Some(message) = outgoing_rx.recv().await {
+            let text = serde_json::to_string(&message).unwrap();
+            if sender
+                .send(axum::extract::ws::Message::Text(text))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+        sender
+    });
+
+    let (closed, mut closed_rx) = mpsc::unbounded_channel();
+    let mut subscriptions = SocketSubscriptions {
+        hub: state.hub.clone(),
+        outgoing: outgoing.clone(),
+        closed,
+        forwarders: HashMap::new(),
+        next_id: 0,
+    };
+
+    loop {
+        let msg = tokio::select! {
+            msg = receiver.next() => match msg {
+                Some(Ok(msg)) => msg,
+                _ => break,
+            },
+            Some((topic, id)) = closed_rx.recv() => {
+                if subscriptions.close(topic, id).await.is_err() {
+                    break;
+                }
+                continue;
+            }
+        };
+        tracing::debug!("[WS PUBSUB] Received WebSocket message: {:?}", redact(&msg));
+        // Every frame counts, so unparsable ones can't be sent for free
+        if let Err(e) = quota.acquire() {
+            if outgoing
+                .send(PubSubWsMessage::Error(e.into()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+            continue;
+        }
+        if let axum::extract::ws::Message::Text(text) = msg {
+            spans.start_message(&text).await;
+            match serde_json::from_str::<PubSubWsMessage>(&text) {
+                Ok(message) => {
+                    tracing::debug!("[WS PUBSUB] Parsed message: {:?}", redact(&message));
+                    let _message = ws_message("pubsub", message.kind());
+                    if let Err(e) = message.authorize(&access) {
+                        if outgoing
+                            .send(PubSubWsMessage::Error(e.into()))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                        continue;
+                    }
+                    let response = match message {
+                        PubSubWsMessage::Subscribe { data } => {
+                            let mut error = None;
+                            for channel in data.channels {
+                                if let Err(e) =
+                                    subscriptions.subscribe(Topic::Channel(channel)).await
+                                {
+                                    error = Some(e);
+                                    break;
+                                }
+                            }
+                            error.map_or_else(|| subscriptions.summary(), PubSubWsMessage::Error)
+                        }
+                        PubSubWsMessage::PSubscribe { data } => {
+                            let mut error = None;
+                            for pattern in data.patterns {
+                                if let Err(e) =
+                                    subscriptions.subscribe(Topic::Pattern(pattern)).await
+                                {
+                                    error = Some(e);
+                                    break;
+                                }
+                            }
+                            error.map_or_else(|| subscriptions.summary(), PubSubWsMessage::Error)
+                        }
+                        PubSubWsMessage::Unsubscribe { data } => {
+                            let topics = data.channels.into_iter().map(Topic::Channel).collect();
+                            subscriptions
+                                .unsubscribe_many(topics, |t| matches!(t, Topic::Channel(_)))
+                                .await;
+                            subscriptions.summary()
+                        }
+                        PubSubWsMessage::PUnsubscribe { data } => {
+                            let topics = data.patterns.into_iter().map(Topic::Pattern).collect();
+                            subscriptions
+                                .unsubscribe_many(topics, |t| matches!(t, Topic::Pattern(_)))
+                                .await;
+                            subscriptions.summary()
+                        }
+                        PubSubWsMessage::Publish { data } => {
+                            match state.pool.get_multiplexed_connection().await {
+                                Ok(conn) => {
+                                    match publish_message(conn, &data.channel, &data.message).await
+                                    {
+                                        Ok(receivers) => PubSubWsMessage::Published {
+                                            data: PublishedData {
+                                                channel: data.channel,
+                                                receivers,
+                                            },
+                                        },
+                                        Err(e) => PubSubWsMessage::Error(e.into()),
+                                    }
+                                }
+                                Err(e) => PubSubWsMessage::Error(e.into()),
+                            }
+                        }
+                        PubSubWsMessage::Ping => PubSubWsMessage::Pong,
+                        _ => continue,
+                    };
+
+                    if outgoing.send(response).await.is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!("[WS PUBSUB] Failed to parse message: {}", redact(&e));
+                    tracing::debug!("[WS PUBSUB] Raw message: {}", redact(&text));
+                    if outgoing
+                        .send(PubSubWsMessage::Error(ApiError::invalid_argument(
+                            e.to_string(),
+                        )))
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    // Give the shared Redis subscriptions back so idle topics get closed
+    subscriptions.unsubscribe_all().await;
+    // With every sender gone the writer flushes the queued replies and stops
+    drop(subscriptions);
+    drop(outgoing);
+    if let Ok(mut sender) = writer.await {
+        close_socket(&mut sender, &shutdown).await;
+    }
+}
+
+pub fn create_redis_ws_pubsub_routes(pool: Arc<RedisPool>, hub: Arc<PubSubHub>) -> Router {
+    Router::new()
+        .route("/pubsub/ws", get(redis_ws_pubsub_handler))
+        .with_state(PubSubWsState { pool, hub })
+}
@@ -0,0 +1,286 @@
+use axum::{
+    extract::{ws::WebSocket, WebSocketUpgrade},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::ApiError;
+use crate::logging::{redact, WsSpans};
+use crate::metrics::{ws_connection, ws_message};
+use crate::policy::{Access, AccessDenied, Permission};
+use crate::rate_limit::MessageQuota;
+use crate::routes::common::script::{eval_named_script, ScriptInfo, ScriptRegistry};
+use crate::shutdown::Shutdown;
+use dbx_adapter::redis::client::RedisPool;
+
+use super::{close_socket, until_shutdown};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum ScriptWsMessage {
+    #[serde(rename = "list")]
+    List,
+    #[serde(rename = "eval")]
+    Eval {
+        #[serde(rename = "data")]
+        data: EvalData,
+    },
+    #[serde(rename = "register")]
+    Register {
+        #[serde(rename = "data")]
+        data: RegisterData,
+    },
+    #[serde(rename = "unregister")]
+    Unregister {
+        #[serde(rename = "data")]
+        data: UnregisterData,
+    },
+    // Response types
+    #[serde(rename = "scripts")]
+    Scripts {
+        #[serde(rename = "data")]
+        data: Vec<ScriptInfo>,
+    },
+    #[serde(rename = "result")]
+    Result {
+        #[serde(rename = "data")]
+        data: ResultData,
+    },
+    #[serde(rename = "registered")]
+    Registered {
+        #[serde(rename = "data")]
+        data: ScriptInfo,
+    },
+    #[serde(rename = "unregistered")]
+    Unregistered {
+        #[serde(rename = "data")]
+        data: UnregisteredData,
+    },
+    #[serde(rename = "error", with = "super::error_data")]
+    Error(ApiError),
+    #[serde(rename = "ping")]
+    Ping,
+    #[serde(rename = "pong")]
+    Pong,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EvalData {
+    pub name: String,
+    #[serde(default)]
+    pub keys: Vec<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RegisterData {
+    pub name: String,
+    pub source: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnregisterData {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResultData {
+    pub name: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnregisteredData {
+    pub name: String,
+    pub removed: bool,
+}
+
+#[derive(Clone)]
+struct ScriptWsState {
+    pool: Arc<RedisPool>,
+    registry: Arc<ScriptRegistry>,
+}
+
+impl ScriptWsMessage {
+    /// Check that the caller may send this message
+    fn authorize(&self, access: &Access) -> Result<(), AccessDenied> {
+        match self {
+            ScriptWsMessage::List => access.require(Permission::Read),
+            ScriptWsMessage::Eval { data } => access.write(&data.keys),
+            ScriptWsMessage::Register { .. } | ScriptWsMessage::Unregister { .. } => {
+                access.admin().and_then(|()| access.writable())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The message's `type` tag, as a metrics label
+    fn kind(&self) -> &'static str {
+        match self {
+            ScriptWsMessage::List => "list",
+            ScriptWsMessage::Eval { .. } => "eval",
+            ScriptWsMessage::Register { .. } => "register",
+            ScriptWsMessage::Unregister { .. } => "unregister",
+            ScriptWsMessage::Scripts { .. } => "scripts",
+            ScriptWsMessage::Result { .. } => "result",
+            ScriptWsMessage::Registered { .. } => "registered",
+            ScriptWsMessage::Unregistered { .. } => "unregistered",
+            ScriptWsMessage::Error(_) => "error",
+            ScriptWsMessage::Ping => "ping",
+            ScriptWsMessage::Pong => "pong",
+        }
+    }
+}
+
+async fn redis_ws_script_handler(
+    access: Access,
+    quota: MessageQuota,
+    shutdown: Shutdown,
+    ws: WebSocketUpgrade,
+    axum::extract::State(state): axum::extract::State<ScriptWsState>,
+) -> impl IntoResponse {
+    let spans = WsSpans::new("script");
+    ws.on_upgrade(|socket| {
+        spans.clone().run(handle_redis_ws_script_socket(
+            socket, state, access, quota, spans, shutdown,
+        ))
+    })
+}
+
+async fn handle_redis_ws_script_socket(
+    socket: WebSocket,
+    state: ScriptWsState,
+    access: Access,
+    mut quota: MessageQuota,
+    spans: WsSpans,
+    shutdown: Shutdown,
+) {
+    let _connection = ws_connection("script");
+    let _socket = shutdown.open_socket();
+    let (mut sender, receiver) = socket.split();
+    let mut receiver = until_shutdown(receiver, &shutdown);
+    while let Some(Ok(msg)) = receiver.next().await {
+        tracing::debug!("[WS SCRIPT] Received WebSocket message: {:?}", redact(&msg));
+        // Every frame counts, so unparsable ones can't be sent for free
+        if let Err(e) = quota.acquire() {
+            let _ = sender
+                .send(axum::extract::ws::Message::Text(
+                    serde_json::to_string(&ScriptWsMessage::Error(e.into())).unwrap(),
+                ))
+                .await;
+            continue;
+        }
+        if let axum::extract::ws::Message::Text(text) = msg {
+            spans.start_message(&text).await;
+            match serde_json::from_str::<ScriptWsMessage>(&text) {
+                Ok(message) => {
+                    tracing::debug!("[WS SCRIPT] Parsed message: {:?}", redact(&message));
+                    let _message = ws_message("script", message.kind());
+                    if let Err(e) = message.authorize(&access) {
+                        let _ = sender
+                            .send(axum::extract::ws::Message::Text(
+                                serde_json::to_string(&ScriptWsMessage::Error(e.into())).unwrap(),
+                            ))
+                            .await;
+                        continue;
+                    }
+                    let response = match message {
+                        ScriptWsMessage::List => ScriptWsMessage::Scripts {
+                            data: state.registry.list(),
+                        },
+                        ScriptWsMessage::Eval { data } => match state.registry.get(&data.name) {
+                            Some(script) => match state.pool.get_multiplexed_connection().await {
+                                Ok(conn) => {
+                                    match eval_named_script(conn, &script, &data.keys, &data.args)
+                                        .await
+                                    {
+                                        Ok(value) => ScriptWsMessage::Result {
+                                            data: ResultData {
+                                                name: data.name,
+                                                value,
+                                            },
+                                        },
+                                        Err(e) => ScriptWsMessage::Error(e.into()),
+                                    }
+                                }
+                                Err(e) => ScriptWsMessage::Error(e.into()),
+                            },
+                            None => ScriptWsMessage::Error(ApiError::not_found(format!(
+                                "Unknown script: {}",
+                                data.name
+                            ))),
+                        },
+                        ScriptWsMessage::Register { data } => {
+                            match state.pool.get_multiplexed_connection().await {
+                                Ok(conn) => {
+                                    match state
+                                        .registry
+                                        .register(conn, &data.name, &data.source, data.description)
+                                        .await
+                                    {
+                                        Ok(info) => ScriptWsMessage::Registered { data: info },
+                                        Err(e) => ScriptWsMessage::Error(e.into()),
+                                    }
+                                }
+                                Err(e) => ScriptWsMessage::Error(e.into()),
+                            }
+                        }
+                        ScriptWsMessage::Unregister { data } => {
+                            if state.registry.is_builtin(&data.name) {
+                                ScriptWsMessage::Error(ApiError::conflict(format!(
+                                    "Cannot unregister built-in script: {}",
+                                    data.name
+                                )))
+                            } else {
+                                let removed = state.registry.unregister(&data.name);
+                                ScriptWsMessage::Unregistered {
+                                    data: UnregisteredData {
+                                        name: data.name,
+                                        removed,
+                                    },
+                                }
+                            }
+                        }
+                        ScriptWsMessage::Ping => ScriptWsMessage::Pong,
+                        _ => continue,
+                    };
+
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&response).unwrap(),
+                        ))
+                        .await;
+                }
+                Err(e) => {
+                    tracing::debug!("[WS SCRIPT] Failed to parse message: {}", redact(&e));
+                    tracing::debug!("[WS SCRIPT] Raw message: {}", redact(&text));
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&ScriptWsMessage::Error(
+                                ApiError::invalid_argument(e.to_string()),
+                            ))
+                            .unwrap(),
+                        ))
+                        .await;
+                }
+            }
+        }
+    }
+
+    close_socket(&mut sender, &shutdown).await;
+}
+
+pub fn create_redis_ws_script_routes(
+    pool: Arc<RedisPool>,
+    registry: Arc<ScriptRegistry>,
+) -> Router {
+    Router::new()
+        .route("/script/ws", get(redis_ws_script_handler))
+        .with_state(ScriptWsState { pool, registry })
+}
@@ -8,12 +8,20 @@ use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use crate::error::ApiError;
+use crate::logging::{redact, WsSpans};
+use crate::metrics::{ws_connection, ws_message};
+use crate::policy::{Access, AccessDenied};
+use crate::rate_limit::MessageQuota;
 use crate::routes::common::set::{
     add_to_set, difference_sets, get_set_cardinality, get_set_members, intersect_sets,
     remove_from_set, set_exists, union_sets,
 };
+use crate::shutdown::Shutdown;
 use dbx_adapter::redis::client::RedisPool;
 
+use super::{close_socket, until_shutdown};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type")]
 pub enum SetWsMessage {
@@ -103,8 +111,8 @@ pub enum SetWsMessage {
         #[serde(rename = "data")]
         data: ResultData,
     },
-    #[serde(rename = "error")]
-    Error(String),
+    #[serde(rename = "error", with = "super::error_data")]
+    Error(ApiError),
     #[serde(rename = "ping")]
     Ping,
     #[serde(rename = "pong")]
@@ -211,182 +219,255 @@ pub struct ResultData {
     pub value: Option<serde_json::Value>,
 }
 
+impl SetWsMessage {
+    /// Check that the caller may send this message
+    fn authorize(&self, access: &Access) -> Result<(), AccessDenied> {
+        match self {
+            SetWsMessage::Add { data } => access.write([&data.key]),
+            SetWsMessage::Remove { data } => access.write([&data.key]),
+            SetWsMessage::Members { data } => access.read([&data.key]),
+            SetWsMessage::Exists { data } => access.read([&data.key]),
+            SetWsMessage::Cardinality { data } => access.read([&data.key]),
+            SetWsMessage::Intersect { data } => access.read(&data.keys),
+            SetWsMessage::Union { data } => access.read(&data.keys),
+            SetWsMessage::Difference { data } => access.read(&data.keys),
+            _ => Ok(()),
+        }
+    }
+
+    /// The message's `type` tag, as a metrics label
+    fn kind(&self) -> &'static str {
+        match self {
+            SetWsMessage::Add { .. } => "add",
+            SetWsMessage::Remove { .. } => "remove",
+            SetWsMessage::Members { .. } => "members",
+            SetWsMessage::Exists { .. } => "exists",
+            SetWsMessage::Cardinality { .. } => "cardinality",
+            SetWsMessage::Intersect { .. } => "intersect",
+            SetWsMessage::Union { .. } => "union",
+            SetWsMessage::Difference { .. } => "difference",
+            SetWsMessage::Added { .. } => "added",
+            SetWsMessage::Removed { .. } => "removed",
+            SetWsMessage::MembersResult { .. } => "members_result",
+            SetWsMessage::ExistsResult { .. } => "exists_result",
+            SetWsMessage::CardinalityResult { .. } => "cardinality_result",
+            SetWsMessage::IntersectResult { .. } => "intersect_result",
+            SetWsMessage::UnionResult { .. } => "union_result",
+            SetWsMessage::DifferenceResult { .. } => "difference_result",
+            SetWsMessage::Result { .. } => "result",
+            SetWsMessage::Error(_) => "error",
+            SetWsMessage::Ping => "ping",
+            SetWsMessage::Pong => "pong",
+        }
+    }
+}
+
 async fn redis_ws_set_handler(
+    access: Access,
+    quota: MessageQuota,
+    shutdown: Shutdown,
     ws: WebSocketUpgrade,
     axum::extract::State(pool): axum::extract::State<Arc<RedisPool>>,
 ) -> impl IntoResponse {
     println!("[DEBUG] WebSocket upgrade requested for /redis_ws/set/ws");
-    ws.on_upgrade(|socket| handle_redis_ws_set_socket(socket, pool))
+    let spans = WsSpans::new("set");
+    ws.on_upgrade(|socket| {
+        spans.clone().run(handle_redis_ws_set_socket(
+            socket, pool, access, quota, spans, shutdown,
+        ))
+    })
 }
 
-async fn handle_redis_ws_set_socket(socket: WebSocket, pool: Arc<RedisPool>) {
-    let (mut sender, mut receiver) = socket.split();
+async fn handle_redis_ws_set_socket(
+    socket: WebSocket,
+    pool: Arc<RedisPool>,
+    access: Access,
+    mut quota: MessageQuota,
+    spans: WsSpans,
+    shutdown: Shutdown,
+) {
+    let _connection = ws_connection("set");
+    let _socket = shutdown.open_socket();
+    let (mut sender, receiver) = socket.split();
+    let mut receiver = until_shutdown(receiver, &shutdown);
     while let Some(Ok(msg)) = receiver.next().await {
-        tracing::debug!("[WS SET] Received WebSocket message: {:?}", msg);
+        tracing::debug!("[WS SET] Received WebSocket message: {:?}", redact(&msg));
+        // Every frame counts, so unparsable ones can't be sent for free
+        if let Err(e) = quota.acquire() {
+            let _ = sender
+                .send(axum::extract::ws::Message::Text(
+                    serde_json::to_string(&SetWsMessage::Error(e.into())).unwrap(),
+                ))
+                .await;
+            continue;
+        }
         if let axum::extract::ws::Message::Text(text) = msg {
-            tracing::debug!("[WS SET] Received text: {}", text);
+            spans.start_message(&text).await;
+            tracing::debug!("[WS SET] Received text: {}", redact(&text));
             match serde_json::from_str::<SetWsMessage>(&text) {
                 Ok(message) => {
-                    tracing::debug!("[WS SET] Parsed message: {:?}", message);
-                    let conn = match pool.get_connection() {
+                    tracing::debug!("[WS SET] Parsed message: {:?}", redact(&message));
+                    let _message = ws_message("set", message.kind());
+                    if let Err(e) = message.authorize(&access) {
+                        let _ = sender
+                            .send(axum::extract::ws::Message::Text(
+                                serde_json::to_string(&SetWsMessage::Error(e.into())).unwrap(),
+                            ))
+                            .await;
+                        continue;
+                    }
+                    let conn = match pool.get_multiplexed_connection().await {
                         Ok(c) => c,
                         Err(e) => {
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(&SetWsMessage::Error(format!(
-                                        "Redis error: {e}"
-                                    )))
-                                    .unwrap(),
+                                    serde_json::to_string(&SetWsMessage::Error(e.into())).unwrap(),
                                 ))
                                 .await;
                             continue;
                         }
                     };
-                    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
 
                     match message {
                         SetWsMessage::Add { data } => {
-                            let added = add_to_set(conn_arc.clone(), &data.key, &[&data.member])
-                                .unwrap_or(0);
+                            let msg =
+                                match add_to_set(conn.clone(), &data.key, &[&data.member]).await {
+                                    Ok(added) => SetWsMessage::Added {
+                                        data: AddedData {
+                                            key: data.key,
+                                            member: data.member,
+                                            added,
+                                        },
+                                    },
+                                    Err(e) => SetWsMessage::Error(e.into()),
+                                };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(SetWsMessage::Added {
-                                            data: AddedData {
-                                                key: data.key,
-                                                member: data.member,
-                                                added,
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         SetWsMessage::Remove { data } => {
-                            let removed =
-                                remove_from_set(conn_arc.clone(), &data.key, &[&data.member])
-                                    .unwrap_or(0);
+                            let msg =
+                                match remove_from_set(conn.clone(), &data.key, &[&data.member])
+                                    .await
+                                {
+                                    Ok(removed) => SetWsMessage::Removed {
+                                        data: RemovedData {
+                                            key: data.key,
+                                            member: data.member,
+                                            removed,
+                                        },
+                                    },
+                                    Err(e) => SetWsMessage::Error(e.into()),
+                                };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(SetWsMessage::Removed {
-                                            data: RemovedData {
-                                                key: data.key,
-                                                member: data.member,
-                                                removed,
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         SetWsMessage::Members { data } => {
-                            let members =
-                                get_set_members(conn_arc.clone(), &data.key).unwrap_or_default();
+                            let msg = match get_set_members(conn.clone(), &data.key).await {
+                                Ok(members) => SetWsMessage::Result {
+                                    data: ResultData {
+                                        key: data.key,
+                                        value: Some(serde_json::json!(members)),
+                                    },
+                                },
+                                Err(e) => SetWsMessage::Error(e.into()),
+                            };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(SetWsMessage::Result {
-                                            data: ResultData {
-                                                key: data.key,
-                                                value: Some(serde_json::json!(members)),
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         SetWsMessage::Exists { data } => {
-                            let exists = set_exists(conn_arc.clone(), &data.key, &data.member)
-                                .unwrap_or(false);
+                            let msg = match set_exists(conn.clone(), &data.key, &data.member).await
+                            {
+                                Ok(exists) => SetWsMessage::ExistsResult {
+                                    data: ExistsResultData {
+                                        key: data.key,
+                                        member: data.member,
+                                        exists,
+                                    },
+                                },
+                                Err(e) => SetWsMessage::Error(e.into()),
+                            };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(SetWsMessage::ExistsResult {
-                                            data: ExistsResultData {
-                                                key: data.key,
-                                                member: data.member,
-                                                exists,
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         SetWsMessage::Cardinality { data } => {
-                            let cardinality =
-                                get_set_cardinality(conn_arc.clone(), &data.key).unwrap_or(0);
+                            let msg = match get_set_cardinality(conn.clone(), &data.key).await {
+                                Ok(cardinality) => SetWsMessage::CardinalityResult {
+                                    data: CardinalityResultData {
+                                        key: data.key,
+                                        cardinality,
+                                    },
+                                },
+                                Err(e) => SetWsMessage::Error(e.into()),
+                            };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(SetWsMessage::CardinalityResult {
-                                            data: CardinalityResultData {
-                                                key: data.key,
-                                                cardinality,
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         SetWsMessage::Intersect { data } => {
                             let key_refs: Vec<&str> =
                                 data.keys.iter().map(|k| k.as_str()).collect();
-                            let intersection =
-                                intersect_sets(conn_arc.clone(), &key_refs).unwrap_or_default();
+                            let msg = match intersect_sets(conn.clone(), &key_refs).await {
+                                Ok(intersection) => SetWsMessage::IntersectResult {
+                                    data: IntersectResultData {
+                                        keys: data.keys,
+                                        intersection,
+                                    },
+                                },
+                                Err(e) => SetWsMessage::Error(e.into()),
+                            };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(SetWsMessage::IntersectResult {
-                                            data: IntersectResultData {
-                                                keys: data.keys,
-                                                intersection,
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         SetWsMessage::Union { data } => {
                             let key_refs: Vec<&str> =
                                 data.keys.iter().map(|k| k.as_str()).collect();
-                            let union = union_sets(conn_arc.clone(), &key_refs).unwrap_or_default();
+                            let msg = match union_sets(conn.clone(), &key_refs).await {
+                                Ok(union) => SetWsMessage::UnionResult {
+                                    data: UnionResultData {
+                                        keys: data.keys,
+                                        union,
+                                    },
+                                },
+                                Err(e) => SetWsMessage::Error(e.into()),
+                            };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(SetWsMessage::UnionResult {
-                                            data: UnionResultData {
-                                                keys: data.keys,
-                                                union,
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         SetWsMessage::Difference { data } => {
                             let key_refs: Vec<&str> =
                                 data.keys.iter().map(|k| k.as_str()).collect();
-                            let difference =
-                                difference_sets(conn_arc.clone(), &key_refs).unwrap_or_default();
+                            let msg = match difference_sets(conn.clone(), &key_refs).await {
+                                Ok(difference) => SetWsMessage::DifferenceResult {
+                                    data: DifferenceResultData {
+                                        keys: data.keys,
+                                        difference,
+                                    },
+                                },
+                                Err(e) => SetWsMessage::Error(e.into()),
+                            };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(SetWsMessage::DifferenceResult {
-                                            data: DifferenceResultData {
-                                                keys: data.keys,
-                                                difference,
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
@@ -402,12 +483,22 @@ async fn handle_redis_ws_set_socket(socket: WebSocket, pool: Arc<RedisPool>) {
                     }
                 }
                 Err(e) => {
-                    tracing::error!("[WS SET] Failed to parse message: {}", e);
-                    tracing::error!("[WS SET] Raw message: {}", text);
+                    tracing::debug!("[WS SET] Failed to parse message: {}", redact(&e));
+                    tracing::debug!("[WS SET] Raw message: {}", redact(&text));
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&SetWsMessage::Error(
+                                ApiError::invalid_argument(e.to_string()),
+                            ))
+                            .unwrap(),
+                        ))
+                        .await;
                 }
             }
         }
     }
+
+    close_socket(&mut sender, &shutdown).await;
 }
 
 pub fn create_redis_ws_set_routes(pool: Arc<RedisPool>) -> Router {
@@ -0,0 +1,522 @@
+use axum::{
+    extract::{ws::WebSocket, WebSocketUpgrade},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::ApiError;
+use crate::logging::{redact, WsSpans};
+use crate::metrics::{ws_connection, ws_message};
+use crate::policy::{Access, AccessDenied};
+use crate::rate_limit::MessageQuota;
+use crate::routes::common::sorted_set::{
+    add_to_sorted_set, count_sorted_set_range, get_member_rank, get_member_score,
+    get_sorted_set_cardinality, get_sorted_set_range, get_sorted_set_range_by_score,
+    increment_member_score, remove_from_sorted_set, ScoredMember,
+};
+use crate::shutdown::Shutdown;
+use dbx_adapter::redis::client::RedisPool;
+
+use super::{close_socket, until_shutdown};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum SortedSetWsMessage {
+    #[serde(rename = "add")]
+    Add {
+        #[serde(rename = "data")]
+        data: AddData,
+    },
+    #[serde(rename = "remove")]
+    Remove {
+        #[serde(rename = "data")]
+        data: RemoveData,
+    },
+    #[serde(rename = "score")]
+    Score {
+        #[serde(rename = "data")]
+        data: MemberData,
+    },
+    #[serde(rename = "rank")]
+    Rank {
+        #[serde(rename = "data")]
+        data: RankData,
+    },
+    #[serde(rename = "increment")]
+    Increment {
+        #[serde(rename = "data")]
+        data: IncrementData,
+    },
+    #[serde(rename = "range")]
+    Range {
+        #[serde(rename = "data")]
+        data: RangeData,
+    },
+    #[serde(rename = "range_by_score")]
+    RangeByScore {
+        #[serde(rename = "data")]
+        data: RangeByScoreData,
+    },
+    #[serde(rename = "cardinality")]
+    Cardinality {
+        #[serde(rename = "data")]
+        data: CardinalityData,
+    },
+    #[serde(rename = "count")]
+    Count {
+        #[serde(rename = "data")]
+        data: CountData,
+    },
+    // Response types
+    #[serde(rename = "added")]
+    Added {
+        #[serde(rename = "data")]
+        data: AddedData,
+    },
+    #[serde(rename = "removed")]
+    Removed {
+        #[serde(rename = "data")]
+        data: RemovedData,
+    },
+    #[serde(rename = "score_result")]
+    ScoreResult {
+        #[serde(rename = "data")]
+        data: ScoreResultData,
+    },
+    #[serde(rename = "rank_result")]
+    RankResult {
+        #[serde(rename = "data")]
+        data: RankResultData,
+    },
+    #[serde(rename = "range_result")]
+    RangeResult {
+        #[serde(rename = "data")]
+        data: RangeResultData,
+    },
+    #[serde(rename = "cardinality_result")]
+    CardinalityResult {
+        #[serde(rename = "data")]
+        data: CardinalityResultData,
+    },
+    #[serde(rename = "count_result")]
+    CountResult {
+        #[serde(rename = "data")]
+        data: CountResultData,
+    },
+    #[serde(rename = "error", with = "super::error_data")]
+    Error(ApiError),
+    #[serde(rename = "ping")]
+    Ping,
+    #[serde(rename = "pong")]
+    Pong,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AddData {
+    pub key: String,
+    pub member: String,
+    pub score: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoveData {
+    pub key: String,
+    pub member: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemberData {
+    pub key: String,
+    pub member: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RankData {
+    pub key: String,
+    pub member: String,
+    #[serde(default)]
+    pub rev: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IncrementData {
+    pub key: String,
+    pub member: String,
+    pub increment: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RangeData {
+    pub key: String,
+    pub start: isize,
+    pub stop: isize,
+    #[serde(default)]
+    pub rev: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RangeByScoreData {
+    pub key: String,
+    pub min: f64,
+    pub max: f64,
+    pub offset: Option<isize>,
+    pub count: Option<isize>,
+    #[serde(default)]
+    pub rev: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CardinalityData {
+    pub key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CountData {
+    pub key: String,
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AddedData {
+    pub key: String,
+    pub member: String,
+    pub added: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemovedData {
+    pub key: String,
+    pub member: String,
+    pub removed: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScoreResultData {
+    pub key: String,
+    pub member: String,
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RankResultData {
+    pub key: String,
+    pub member: String,
+    pub rank: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RangeResultData {
+    pub key: String,
+    pub members: Vec<ScoredMember>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CardinalityResultData {
+    pub key: String,
+    pub cardinality: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CountResultData {
+    pub key: String,
+    pub count: usize,
+}
+
+impl SortedSetWsMessage {
+    /// Check that the caller may send this message
+    fn authorize(&self, access: &Access) -> Result<(), AccessDenied> {
+        match self {
+            SortedSetWsMessage::Add { data } => access.write([&data.key]),
+            SortedSetWsMessage::Remove { data } => access.write([&data.key]),
+            SortedSetWsMessage::Increment { data } => access.write([&data.key]),
+            SortedSetWsMessage::Score { data } => access.read([&data.key]),
+            SortedSetWsMessage::Rank { data } => access.read([&data.key]),
+            SortedSetWsMessage::Range { data } => access.read([&data.key]),
+            SortedSetWsMessage::RangeByScore { data } => access.read([&data.key]),
+            SortedSetWsMessage::Cardinality { data } => access.read([&data.key]),
+            SortedSetWsMessage::Count { data } => access.read([&data.key]),
+            _ => Ok(()),
+        }
+    }
+
+    /// The message's `type` tag, as a metrics label
+    fn kind(&self) -> &'static str {
+        match self {
+            SortedSetWsMessage::Add { .. } => "add",
+            SortedSetWsMessage::Remove { .. } => "remove",
+            SortedSetWsMessage::Score { .. } => "score",
+            SortedSetWsMessage::Rank { .. } => "rank",
+            SortedSetWsMessage::Increment { .. } => "increment",
+            SortedSetWsMessage::Range { .. } => "range",
+            SortedSetWsMessage::RangeByScore { .. } => "range_by_score",
+            SortedSetWsMessage::Cardinality { .. } => "cardinality",
+            SortedSetWsMessage::Count { .. } => "count",
+            SortedSetWsMessage::Added { .. } => "added",
+            SortedSetWsMessage::Removed { .. } => "removed",
+            SortedSetWsMessage::ScoreResult { .. } => "score_result",
+            SortedSetWsMessage::RankResult { .. } => "rank_result",
+            SortedSetWsMessage::RangeResult { .. } => "range_result",
+            SortedSetWsMessage::CardinalityResult { .. } => "cardinality_result",
+            SortedSetWsMessage::CountResult { .. } => "count_result",
+            SortedSetWsMessage::Error(_) => "error",
+            SortedSetWsMessage::Ping => "ping",
+            SortedSetWsMessage::Pong => "pong",
+        }
+    }
+}
+
+async fn redis_ws_sorted_set_handler(
+    access: Access,
+    quota: MessageQuota,
+    shutdown: Shutdown,
+    ws: WebSocketUpgrade,
+    axum::extract::State(pool): axum::extract::State<Arc<RedisPool>>,
+) -> impl IntoResponse {
+    let spans = WsSpans::new("zset");
+    ws.on_upgrade(|socket| {
+        spans.clone().run(handle_redis_ws_sorted_set_socket(
+            socket, pool, access, quota, spans, shutdown,
+        ))
+    })
+}
+
+async fn handle_redis_ws_sorted_set_socket(
+    socket: WebSocket,
+    pool: Arc<RedisPool>,
+    access: Access,
+    mut quota: MessageQuota,
+    spans: WsSpans,
+    shutdown: Shutdown,
+) {
+    let _connection = ws_connection("zset");
+    let _socket = shutdown.open_socket();
+    let (mut sender, receiver) = socket.split();
+    let mut receiver = until_shutdown(receiver, &shutdown);
+    while let Some(Ok(msg)) = receiver.next().await {
+        tracing::debug!("[WS ZSET] Received WebSocket message: {:?}", redact(&msg));
+        // Every frame counts, so unparsable ones can't be sent for free
+        if let Err(e) = quota.acquire() {
+            let _ = sender
+                .send(axum::extract::ws::Message::Text(
+                    serde_json::to_string(&SortedSetWsMessage::Error(e.into())).unwrap(),
+                ))
+                .await;
+            continue;
+        }
+        if let axum::extract::ws::Message::Text(text) = msg {
+            spans.start_message(&text).await;
+            match serde_json::from_str::<SortedSetWsMessage>(&text) {
+                Ok(message) => {
+                    tracing::debug!("[WS ZSET] Parsed message: {:?}", redact(&message));
+                    let _message = ws_message("zset", message.kind());
+                    if let Err(e) = message.authorize(&access) {
+                        let _ = sender
+                            .send(axum::extract::ws::Message::Text(
+                                serde_json::to_string(&SortedSetWsMessage::Error(e.into()))
+                                    .unwrap(),
+                            ))
+                            .await;
+                        continue;
+                    }
+                    let conn = match pool.get_multiplexed_connection().await {
+                        Ok(c) => c,
+                        Err(e) => {
+                            let _ = sender
+                                .send(axum::extract::ws::Message::Text(
+                                    serde_json::to_string(&SortedSetWsMessage::Error(
+                                        ApiError::from(e),
+                                    ))
+                                    .unwrap(),
+                                ))
+                                .await;
+                            continue;
+                        }
+                    };
+
+                    let response = match message {
+                        SortedSetWsMessage::Add { data } => {
+                            let member = ScoredMember {
+                                member: data.member.clone(),
+                                score: data.score,
+                            };
+                            match add_to_sorted_set(conn.clone(), &data.key, &[member]).await {
+                                Ok(added) => SortedSetWsMessage::Added {
+                                    data: AddedData {
+                                        key: data.key,
+                                        member: data.member,
+                                        added,
+                                    },
+                                },
+                                Err(e) => SortedSetWsMessage::Error(e.into()),
+                            }
+                        }
+                        SortedSetWsMessage::Remove { data } => {
+                            match remove_from_sorted_set(conn.clone(), &data.key, &[&data.member])
+                                .await
+                            {
+                                Ok(removed) => SortedSetWsMessage::Removed {
+                                    data: RemovedData {
+                                        key: data.key,
+                                        member: data.member,
+                                        removed,
+                                    },
+                                },
+                                Err(e) => SortedSetWsMessage::Error(e.into()),
+                            }
+                        }
+                        SortedSetWsMessage::Score { data } => {
+                            match get_member_score(conn.clone(), &data.key, &data.member).await {
+                                Ok(score) => SortedSetWsMessage::ScoreResult {
+                                    data: ScoreResultData {
+                                        key: data.key,
+                                        member: data.member,
+                                        score,
+                                    },
+                                },
+                                Err(e) => SortedSetWsMessage::Error(e.into()),
+                            }
+                        }
+                        SortedSetWsMessage::Rank { data } => {
+                            match get_member_rank(conn.clone(), &data.key, &data.member, data.rev)
+                                .await
+                            {
+                                Ok(rank) => SortedSetWsMessage::RankResult {
+                                    data: RankResultData {
+                                        key: data.key,
+                                        member: data.member,
+                                        rank,
+                                    },
+                                },
+                                Err(e) => SortedSetWsMessage::Error(e.into()),
+                            }
+                        }
+                        SortedSetWsMessage::Increment { data } => {
+                            match increment_member_score(
+                                conn.clone(),
+                                &data.key,
+                                &data.member,
+                                data.increment,
+                            )
+                            .await
+                            {
+                                Ok(score) => SortedSetWsMessage::ScoreResult {
+                                    data: ScoreResultData {
+                                        key: data.key,
+                                        member: data.member,
+                                        score: Some(score),
+                                    },
+                                },
+                                Err(e) => SortedSetWsMessage::Error(e.into()),
+                            }
+                        }
+                        SortedSetWsMessage::Range { data } => {
+                            match get_sorted_set_range(
+                                conn.clone(),
+                                &data.key,
+                                data.start,
+                                data.stop,
+                                data.rev,
+                            )
+                            .await
+                            {
+                                Ok(members) => SortedSetWsMessage::RangeResult {
+                                    data: RangeResultData {
+                                        key: data.key,
+                                        members,
+                                    },
+                                },
+                                Err(e) => SortedSetWsMessage::Error(e.into()),
+                            }
+                        }
+                        SortedSetWsMessage::RangeByScore { data } => {
+                            let limit = match (data.offset, data.count) {
+                                (None, None) => None,
+                                (offset, count) => Some((offset.unwrap_or(0), count.unwrap_or(-1))),
+                            };
+                            match get_sorted_set_range_by_score(
+                                conn.clone(),
+                                &data.key,
+                                data.min,
+                                data.max,
+                                limit,
+                                data.rev,
+                            )
+                            .await
+                            {
+                                Ok(members) => SortedSetWsMessage::RangeResult {
+                                    data: RangeResultData {
+                                        key: data.key,
+                                        members,
+                                    },
+                                },
+                                Err(e) => SortedSetWsMessage::Error(e.into()),
+                            }
+                        }
+                        SortedSetWsMessage::Cardinality { data } => {
+                            match get_sorted_set_cardinality(conn.clone(), &data.key).await {
+                                Ok(cardinality) => SortedSetWsMessage::CardinalityResult {
+                                    data: CardinalityResultData {
+                                        key: data.key,
+                                        cardinality,
+                                    },
+                                },
+                                Err(e) => SortedSetWsMessage::Error(e.into()),
+                            }
+                        }
+                        SortedSetWsMessage::Count { data } => {
+                            match count_sorted_set_range(
+                                conn.clone(),
+                                &data.key,
+                                data.min,
+                                data.max,
+                            )
+                            .await
+                            {
+                                Ok(count) => SortedSetWsMessage::CountResult {
+                                    data: CountResultData {
+                                        key: data.key,
+                                        count,
+                                    },
+                                },
+                                Err(e) => SortedSetWsMessage::Error(e.into()),
+                            }
+                        }
+                        SortedSetWsMessage::Ping => SortedSetWsMessage::Pong,
+                        _ => continue,
+                    };
+
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&response).unwrap(),
+                        ))
+                        .await;
+                }
+                Err(e) => {
+                    tracing::debug!("[WS ZSET] Failed to parse message: {}", redact(&e));
+                    tracing::debug!("[WS ZSET] Raw message: {}", redact(&text));
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&SortedSetWsMessage::Error(
+                                ApiError::invalid_argument(e.to_string()),
+                            ))
+                            .unwrap(),
+                        ))
+                        .await;
+                }
+            }
+        }
+    }
+
+    close_socket(&mut sender, &shutdown).await;
+}
+
+pub fn create_redis_ws_sorted_set_routes(pool: Arc<RedisPool>) -> Router {
+    Router::new()
+        .route("/zset/ws", get(redis_ws_sorted_set_handler))
+        .with_state(pool)
+}
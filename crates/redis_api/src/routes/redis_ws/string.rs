@@ -8,12 +8,20 @@ use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use crate::error::ApiError;
+use crate::logging::{redact, WsSpans};
+use crate::metrics::{ws_connection, ws_message};
+use crate::policy::{Access, AccessDenied};
+use crate::rate_limit::MessageQuota;
 use crate::routes::common::string::{
     delete_string, get_multiple_strings, get_string, get_string_info, set_multiple_strings,
-    set_string, StringInfo, StringOperation,
+    set_string, set_string_with_ttl, StringInfo, StringOperation,
 };
+use crate::shutdown::Shutdown;
 use dbx_adapter::redis::client::RedisPool;
 
+use super::{close_socket, until_shutdown};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type")]
 pub enum StringWsMessage {
@@ -67,8 +75,8 @@ pub enum StringWsMessage {
         #[serde(rename = "data")]
         data: DeletedData,
     },
-    #[serde(rename = "error")]
-    Error(String),
+    #[serde(rename = "error", with = "super::error_data")]
+    Error(ApiError),
     #[serde(rename = "ping")]
     Ping,
     #[serde(rename = "pong")]
@@ -130,29 +138,102 @@ pub struct DeletedData {
     pub deleted: bool,
 }
 
+impl StringWsMessage {
+    /// Check that the caller may send this message
+    fn authorize(&self, access: &Access) -> Result<(), AccessDenied> {
+        match self {
+            StringWsMessage::Get { data } => access.read([&data.key]),
+            StringWsMessage::Info { data } => access.read([&data.key]),
+            StringWsMessage::BatchGet { data } => access.read(&data.keys),
+            StringWsMessage::Set { data } => access.write([&data.key]),
+            StringWsMessage::Del { data } => access.write([&data.key]),
+            StringWsMessage::BatchSet { data } => {
+                access.write(data.operations.iter().map(|op| &op.key))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The message's `type` tag, as a metrics label
+    fn kind(&self) -> &'static str {
+        match self {
+            StringWsMessage::Get { .. } => "get",
+            StringWsMessage::Set { .. } => "set",
+            StringWsMessage::Del { .. } => "del",
+            StringWsMessage::Info { .. } => "info",
+            StringWsMessage::BatchGet { .. } => "batch_get",
+            StringWsMessage::BatchSet { .. } => "batch_set",
+            StringWsMessage::Result { .. } => "result",
+            StringWsMessage::BatchResult { .. } => "batch_result",
+            StringWsMessage::InfoResult { .. } => "info_result",
+            StringWsMessage::Deleted { .. } => "deleted",
+            StringWsMessage::Error(_) => "error",
+            StringWsMessage::Ping => "ping",
+            StringWsMessage::Pong => "pong",
+        }
+    }
+}
+
 async fn redis_ws_string_handler(
+    access: Access,
+    quota: MessageQuota,
+    shutdown: Shutdown,
     ws: WebSocketUpgrade,
     axum::extract::State(pool): axum::extract::State<Arc<RedisPool>>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_redis_ws_string_socket(socket, pool))
+    let spans = WsSpans::new("string");
+    ws.on_upgrade(|socket| {
+        spans.clone().run(handle_redis_ws_string_socket(
+            socket, pool, access, quota, spans, shutdown,
+        ))
+    })
 }
 
-async fn handle_redis_ws_string_socket(socket: WebSocket, pool: Arc<RedisPool>) {
-    let (mut sender, mut receiver) = socket.split();
+async fn handle_redis_ws_string_socket(
+    socket: WebSocket,
+    pool: Arc<RedisPool>,
+    access: Access,
+    mut quota: MessageQuota,
+    spans: WsSpans,
+    shutdown: Shutdown,
+) {
+    let _connection = ws_connection("string");
+    let _socket = shutdown.open_socket();
+    let (mut sender, receiver) = socket.split();
+    let mut receiver = until_shutdown(receiver, &shutdown);
     while let Some(Ok(msg)) = receiver.next().await {
-        tracing::debug!("[WS STRING] Received WebSocket message: {:?}", msg);
+        tracing::debug!("[WS STRING] Received WebSocket message: {:?}", redact(&msg));
+        // Every frame counts, so unparsable ones can't be sent for free
+        if let Err(e) = quota.acquire() {
+            let _ = sender
+                .send(axum::extract::ws::Message::Text(
+                    serde_json::to_string(&StringWsMessage::Error(e.into())).unwrap(),
+                ))
+                .await;
+            continue;
+        }
         if let axum::extract::ws::Message::Text(text) = msg {
-            tracing::debug!("[WS STRING] Received text: {}", text);
+            spans.start_message(&text).await;
+            tracing::debug!("[WS STRING] Received text: {}", redact(&text));
             match serde_json::from_str::<StringWsMessage>(&text) {
                 Ok(message) => {
-                    tracing::debug!("[WS STRING] Parsed message: {:?}", message);
-                    let conn = match pool.get_connection() {
+                    tracing::debug!("[WS STRING] Parsed message: {:?}", redact(&message));
+                    let _message = ws_message("string", message.kind());
+                    if let Err(e) = message.authorize(&access) {
+                        let _ = sender
+                            .send(axum::extract::ws::Message::Text(
+                                serde_json::to_string(&StringWsMessage::Error(e.into())).unwrap(),
+                            ))
+                            .await;
+                        continue;
+                    }
+                    let conn = match pool.get_multiplexed_connection().await {
                         Ok(c) => c,
                         Err(e) => {
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(&StringWsMessage::Error(format!(
-                                        "Redis error: {e}"
+                                    serde_json::to_string(&StringWsMessage::Error(ApiError::from(
+                                        e,
                                     )))
                                     .unwrap(),
                                 ))
@@ -160,37 +241,29 @@ async fn handle_redis_ws_string_socket(socket: WebSocket, pool: Arc<RedisPool>)
                             continue;
                         }
                     };
-                    let conn_arc = Arc::new(std::sync::Mutex::new(conn));
 
                     match message {
                         StringWsMessage::Get { data } => {
-                            let value = get_string(conn_arc.clone(), &data.key).ok().flatten();
+                            let msg = match get_string(conn.clone(), &data.key).await {
+                                Ok(value) => StringWsMessage::Result {
+                                    data: ResultData {
+                                        key: data.key,
+                                        value,
+                                    },
+                                },
+                                Err(e) => StringWsMessage::Error(e.into()),
+                            };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(StringWsMessage::Result {
-                                            data: ResultData {
-                                                key: data.key,
-                                                value,
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         StringWsMessage::Set { data } => {
                             let res = if let Some(ttl) = data.ttl {
-                                set_string(conn_arc.clone(), &data.key, &data.value).and_then(
-                                    |_| {
-                                        redis::cmd("EXPIRE")
-                                            .arg(&data.key)
-                                            .arg(ttl)
-                                            .query(&mut *conn_arc.lock().unwrap())
-                                    },
-                                )
+                                set_string_with_ttl(conn.clone(), &data.key, &data.value, ttl).await
                             } else {
-                                set_string(conn_arc.clone(), &data.key, &data.value)
+                                set_string(conn.clone(), &data.key, &data.value).await
                             };
                             let msg = match res {
                                 Ok(_) => StringWsMessage::Result {
@@ -199,7 +272,7 @@ async fn handle_redis_ws_string_socket(socket: WebSocket, pool: Arc<RedisPool>)
                                         value: Some(data.value),
                                     },
                                 },
-                                Err(e) => StringWsMessage::Error(format!("Set error: {e}")),
+                                Err(e) => StringWsMessage::Error(e.into()),
                             };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
@@ -208,54 +281,52 @@ async fn handle_redis_ws_string_socket(socket: WebSocket, pool: Arc<RedisPool>)
                                 .await;
                         }
                         StringWsMessage::Del { data } => {
-                            let deleted =
-                                delete_string(conn_arc.clone(), &data.key).unwrap_or(false);
+                            let msg = match delete_string(conn.clone(), &data.key).await {
+                                Ok(deleted) => StringWsMessage::Deleted {
+                                    data: DeletedData {
+                                        key: data.key,
+                                        deleted,
+                                    },
+                                },
+                                Err(e) => StringWsMessage::Error(e.into()),
+                            };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(StringWsMessage::Deleted {
-                                            data: DeletedData {
-                                                key: data.key,
-                                                deleted,
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         StringWsMessage::Info { data } => {
-                            let info = get_string_info(conn_arc.clone(), &data.key).ok().flatten();
+                            let msg = match get_string_info(conn.clone(), &data.key).await {
+                                Ok(info) => StringWsMessage::InfoResult {
+                                    data: InfoResultData { info },
+                                },
+                                Err(e) => StringWsMessage::Error(e.into()),
+                            };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(StringWsMessage::InfoResult {
-                                            data: InfoResultData { info },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         StringWsMessage::BatchGet { data } => {
-                            let values = get_multiple_strings(conn_arc.clone(), &data.keys)
-                                .unwrap_or_default();
+                            let msg = match get_multiple_strings(conn.clone(), &data.keys).await {
+                                Ok(values) => StringWsMessage::BatchResult {
+                                    data: BatchResultData {
+                                        keys: data.keys,
+                                        values,
+                                    },
+                                },
+                                Err(e) => StringWsMessage::Error(e.into()),
+                            };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
-                                    serde_json::to_string(
-                                        &(StringWsMessage::BatchResult {
-                                            data: BatchResultData {
-                                                keys: data.keys,
-                                                values,
-                                            },
-                                        }),
-                                    )
-                                    .unwrap(),
+                                    serde_json::to_string(&msg).unwrap(),
                                 ))
                                 .await;
                         }
                         StringWsMessage::BatchSet { data } => {
-                            let res = set_multiple_strings(conn_arc.clone(), &data.operations);
+                            let res = set_multiple_strings(conn.clone(), &data.operations).await;
                             let msg = match res {
                                 Ok(_) => StringWsMessage::Result {
                                     data: ResultData {
@@ -266,7 +337,7 @@ async fn handle_redis_ws_string_socket(socket: WebSocket, pool: Arc<RedisPool>)
                                         )),
                                     },
                                 },
-                                Err(e) => StringWsMessage::Error(format!("Batch set error: {e}")),
+                                Err(e) => StringWsMessage::Error(e.into()),
                             };
                             let _ = sender
                                 .send(axum::extract::ws::Message::Text(
@@ -286,12 +357,22 @@ async fn handle_redis_ws_string_socket(socket: WebSocket, pool: Arc<RedisPool>)
                     }
                 }
                 Err(e) => {
-                    tracing::error!("[WS STRING] Failed to parse message: {}", e);
-                    tracing::error!("[WS STRING] Raw message: {}", text);
+                    tracing::debug!("[WS STRING] Failed to parse message: {}", redact(&e));
+                    tracing::debug!("[WS STRING] Raw message: {}", redact(&text));
+                    let _ = sender
+                        .send(axum::extract::ws::Message::Text(
+                            serde_json::to_string(&StringWsMessage::Error(
+                                ApiError::invalid_argument(e.to_string()),
+                            ))
+                            .unwrap(),
+                        ))
+                        .await;
                 }
             }
         }
     }
+
+    close_socket(&mut sender, &shutdown).await;
 }
 
 pub fn create_redis_ws_string_routes(pool: Arc<RedisPool>) -> Router {
@@ -0,0 +1,38 @@
+use axum::{extract::State, routing::get, Router};
+use std::sync::Arc;
+
+use crate::constants::errors::ErrorMessages;
+use crate::error::{ApiError, ErrorCode};
+use crate::models::ApiResponse;
+use crate::policy::Access;
+use crate::reload::{ReloadReport, ReloadStatus, ReloadTrigger, Reloader};
+
+async fn reload_status_handler(
+    access: Access,
+    State(reloader): State<Arc<Reloader>>,
+) -> Result<ApiResponse<ReloadStatus>, ApiError> {
+    access.admin()?;
+    Ok(ApiResponse::success(reloader.status()))
+}
+
+async fn reload_handler(
+    access: Access,
+    State(reloader): State<Arc<Reloader>>,
+) -> Result<ApiResponse<ReloadReport>, ApiError> {
+    access.admin()?;
+    // Loading the file and the policy it names is blocking I/O
+    let report = tokio::task::spawn_blocking(move || reloader.reload(ReloadTrigger::Api))
+        .await
+        .map_err(|_| ApiError::new(ErrorCode::Internal, ErrorMessages::INTERNAL_SERVER_ERROR))?
+        .ok_or_else(|| ApiError::conflict(ErrorMessages::RELOAD_NOT_ENABLED))?;
+    Ok(ApiResponse::success(report))
+}
+
+pub fn create_reload_routes(reloader: Arc<Reloader>) -> Router {
+    Router::new()
+        .route(
+            "/admin/reload",
+            get(reload_status_handler).post(reload_handler),
+        )
+        .with_state(reloader)
+}
@@ -1,133 +1,407 @@
 use axum::http::StatusCode;
-use axum::{response::Html, routing::get, Router};
+use axum::{
+    middleware::{from_fn, from_fn_with_state},
+    response::Html,
+    routing::get,
+    Extension, Router,
+};
 use std::fs;
+use std::path::Path;
 use std::sync::Arc;
-use tracing::info;
+use std::time::Duration;
+use tracing::{info, warn};
 
-use crate::{config::Config, constants::errors::ErrorMessages};
+use crate::config::ConfigLoader;
+use crate::metrics::{observe_pool, serve_metrics, track_requests};
+use crate::middleware::{
+    apply_policy, cors, rate_limit, require_auth, trace_handler, trace_request,
+};
+use crate::reload::{Reloader, Runtime, RuntimeState};
+use crate::routes::backends::{create_backend_routes, Backend};
+use crate::routes::common::pubsub::PubSubHub;
+use crate::routes::common::script::ScriptRegistry;
+use crate::shutdown::{self, Shutdown};
+use crate::tls;
+use crate::{
+    config::{Config, DatabaseType, RedisTlsConfig},
+    constants::{defaults::Defaults, errors::ErrorMessages},
+};
 
-use dbx_adapter::redis::{client::RedisPool, RedisPoolAdapter};
+#[cfg(feature = "mdbx")]
+use dbx_adapter::mdbx::{mdbx_path, MdbxAdapter};
+use dbx_adapter::memory::{is_memory_url, MemoryServer, MemoryStore};
+use dbx_adapter::redis::{
+    async_primitives::admin::AsyncAdminOperations,
+    client::{RedisPool, RedisPoolConfig, RedisTls},
+};
+use dbx_adapter::sqlite::{sqlite_path, SqliteAdapter};
 
 pub struct Server {
     config: Config,
     redis_pool: Option<Arc<RedisPool>>,
+    /// Keeps the in-process backend alive when serving a `memory://` URL
+    memory_backend: Option<Arc<MemoryServer>>,
+    /// Named backends from `backends`, each under `/redis/{name}`
+    backends: Vec<Backend>,
+    /// Authentication, roles, rate limits and CORS, swapped on reload
+    runtime: Arc<Runtime>,
+    reloader: Arc<Reloader>,
+    shutdown: Shutdown,
+    #[cfg(feature = "mdbx")]
+    mdbx: Option<Arc<MdbxAdapter>>,
 }
 
 impl Server {
     pub async fn new(config: Config) -> anyhow::Result<Self> {
-        info!("Connecting to Redis at {}", config.database_url);
+        let state = RuntimeState::from_config(&config)?;
+        if state.auth.is_some() {
+            info!("Authentication enabled for /redis and /redis_ws routes");
+        }
+        let runtime = Arc::new(Runtime::new(state, config.read_only));
+        let reloader = Arc::new(Reloader::new(runtime.clone(), config.clone(), None));
 
-        let pool = RedisPool::new(&config.database_url, config.pool_size)?;
-        let pool_adapter = RedisPoolAdapter::new(pool.clone());
-        let redis = pool_adapter.get_instance()?;
-        let ping_result = redis.ping();
+        if DatabaseType::from_url(&config.database_url) == DatabaseType::Mdbx {
+            return Self::new_mdbx(config, runtime, reloader);
+        }
 
-        let redis_pool = match ping_result {
-            Ok(true) => {
-                info!("Successfully connected to Redis with connection pool");
-                Some(Arc::new(pool))
-            }
-            Ok(false) => {
-                return Err(anyhow::anyhow!(ErrorMessages::REDIS_PING_FAILED));
-            }
-            Err(e) => {
-                return Err(anyhow::anyhow!(
-                    "{}{}",
-                    ErrorMessages::REDIS_CONNECTION_FAILED,
-                    e
-                ));
-            }
-        };
+        let default = connect(
+            Defaults::BACKEND,
+            &config.database_url,
+            config.pool_size,
+            &config.redis_tls,
+        )
+        .await?;
+        let mut backends = Vec::new();
+        for (name, backend) in &config.backends {
+            let backend = connect(
+                name,
+                &backend.database_url,
+                backend.pool_size.unwrap_or(config.pool_size),
+                &backend.redis_tls,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Backend `{}`: {}", name, e))?;
+            backends.push(backend);
+        }
+
+        Ok(Self {
+            config,
+            redis_pool: Some(default.pool),
+            memory_backend: default.memory,
+            backends,
+            runtime,
+            reloader,
+            shutdown: Shutdown::default(),
+            #[cfg(feature = "mdbx")]
+            mdbx: None,
+        })
+    }
+
+    /// Open the MDBX environment named by an `mdbx://path` URL
+    #[cfg(feature = "mdbx")]
+    fn new_mdbx(
+        config: Config,
+        runtime: Arc<Runtime>,
+        reloader: Arc<Reloader>,
+    ) -> anyhow::Result<Self> {
+        let path = mdbx_path(&config.database_url)
+            .ok_or_else(|| anyhow::anyhow!(ErrorMessages::MDBX_PATH_MISSING))?;
+        let adapter = MdbxAdapter::open(path)?;
+        info!("Using MDBX environment at {}", path);
 
-        Ok(Self { config, redis_pool })
+        Ok(Self {
+            config,
+            redis_pool: None,
+            memory_backend: None,
+            backends: Vec::new(),
+            runtime,
+            reloader,
+            shutdown: Shutdown::default(),
+            mdbx: Some(Arc::new(adapter)),
+        })
     }
 
+    #[cfg(not(feature = "mdbx"))]
+    fn new_mdbx(
+        _config: Config,
+        _runtime: Arc<Runtime>,
+        _reloader: Arc<Reloader>,
+    ) -> anyhow::Result<Self> {
+        Err(anyhow::anyhow!(ErrorMessages::MDBX_NOT_ENABLED))
+    }
+
+    /// Whether the server runs on the in-process backend (`memory://` or `sqlite://`)
+    pub fn is_memory_backend(&self) -> bool {
+        self.memory_backend.is_some()
+    }
+
+    /// Reload the configuration from `loader`, on `SIGHUP`, when its file
+    /// changes and on `POST /redis/admin/reload`
+    ///
+    /// `loader` should be the one the server's configuration was loaded with.
+    pub fn with_reload(mut self, loader: ConfigLoader) -> Self {
+        self.reloader = Arc::new(Reloader::new(
+            self.runtime.clone(),
+            self.config.clone(),
+            Some(loader),
+        ));
+        self
+    }
+
+    /// Handle to start a graceful shutdown from outside, as `SIGTERM` does
+    pub fn shutdown_handle(&self) -> Shutdown {
+        self.shutdown.clone()
+    }
+
+    /// The configuration the server started with
     pub fn config(&self) -> &Config {
         &self.config
     }
 
+    /// The default backend followed by the named ones, or none without Redis
+    fn all_backends(&self) -> Arc<[Backend]> {
+        let Some(pool) = &self.redis_pool else {
+            return Arc::from([]);
+        };
+        std::iter::once(Backend {
+            name: Defaults::BACKEND.to_string(),
+            pool: pool.clone(),
+            memory: self.memory_backend.clone(),
+        })
+        .chain(self.backends.iter().cloned())
+        .collect()
+    }
+
     /// Create the application router
     pub fn create_router(&self) -> Router {
+        // Everything under /redis and /redis_ws goes here so it sits behind auth
+        let mut api = Router::new();
+
+        // The default backend is served under /redis and /redis_ws, and each
+        // named backend below them under its name
+        if let Some(pool) = &self.redis_pool {
+            let (http, ws) = redis_routes(pool);
+            api = api.nest("/redis", http).nest("/redis_ws", ws);
+            for backend in &self.backends {
+                let (http, ws) = redis_routes(&backend.pool);
+                api = api
+                    .nest(&format!("/redis/{}", backend.name), http)
+                    .nest(&format!("/redis_ws/{}", backend.name), ws);
+            }
+
+            // The routes hold the named in-process backends, keeping them alive
+            // as long as the router
+            api = api.nest("/redis", create_backend_routes(self.all_backends()));
+        }
+
+        // MDBX serves the string, hash and set routes under the same paths
+        #[cfg(feature = "mdbx")]
+        if let Some(db) = &self.mdbx {
+            api = api
+                .nest(
+                    "/redis",
+                    crate::routes::mdbx::string::create_mdbx_string_routes(db.clone()),
+                )
+                .nest(
+                    "/redis",
+                    crate::routes::mdbx::hash::create_mdbx_hash_routes(db.clone()),
+                )
+                .nest(
+                    "/redis",
+                    crate::routes::mdbx::set::create_mdbx_set_routes(db.clone()),
+                );
+        }
+
+        api = api.nest(
+            "/redis",
+            crate::routes::reload::create_reload_routes(self.reloader.clone()),
+        );
+
+        // Layers run in the reverse order they are added: authentication,
+        // then the policy, then rate limiting by the authenticated identity,
+        // then the handler's span. Each reads the current runtime state, and
+        // passes requests through when its part is not configured.
+        api = api
+            .route_layer(from_fn(trace_handler))
+            .route_layer(from_fn_with_state(self.runtime.clone(), rate_limit))
+            .route_layer(from_fn_with_state(self.runtime.clone(), apply_policy))
+            .route_layer(from_fn_with_state(self.runtime.clone(), require_auth));
+        if self.config.metrics_enabled {
+            api = api.route_layer(from_fn(track_requests));
+        }
+
         let mut router = Router::new()
             .route("/", get(serve_landing_page))
-            .route("/redis_ws", get(serve_landing_page));
+            .route("/redis_ws", get(serve_landing_page))
+            .merge(api)
+            // Probes can't authenticate, so readiness sits outside auth like /metrics
+            .merge(crate::routes::redis::admin::create_readiness_routes(
+                self.redis_pool.clone(),
+            ));
 
-        // Add Redis admin routes if Redis pool is available
-        if let Some(pool) = &self.redis_pool {
-            let redis_string_routes =
-                crate::routes::redis::string::create_redis_string_routes(pool.clone());
-            let redis_hash_routes =
-                crate::routes::redis::hash::create_redis_hash_routes(pool.clone());
-            let redis_set_routes = crate::routes::redis::set::create_redis_set_routes(pool.clone());
-            let redis_admin_routes =
-                crate::routes::redis::admin::create_redis_admin_routes(pool.clone());
-            let redis_ws_string_routes =
-                crate::routes::redis_ws::string::create_redis_ws_string_routes(pool.clone());
-            let redis_ws_hash_routes =
-                crate::routes::redis_ws::hash::create_redis_ws_hash_routes(pool.clone());
-            let redis_ws_set_routes =
-                crate::routes::redis_ws::set::create_redis_ws_set_routes(pool.clone());
-            let redis_ws_admin_routes =
-                crate::routes::redis_ws::admin::create_redis_ws_admin_routes(pool.clone());
-
-            router = router
-                .nest("/redis", redis_string_routes)
-                .nest("/redis", redis_hash_routes)
-                .nest("/redis", redis_set_routes)
-                .nest("/redis", redis_admin_routes)
-                .nest("/redis_ws", redis_ws_string_routes)
-                .nest("/redis_ws", redis_ws_hash_routes)
-                .nest("/redis_ws", redis_ws_set_routes)
-                .nest("/redis_ws", redis_ws_admin_routes);
+        if self.config.metrics_enabled {
+            let backends = self.all_backends();
+            router = router.route("/metrics", get(move || serve_metrics(backends.clone())));
         }
 
-        router
+        router = router.layer(Extension(self.shutdown.clone()));
+
+        // Routes open new backend connections (e.g. for pub/sub), so the
+        // in-memory backend must live as long as the router, not the Server
+        if let Some(backend) = &self.memory_backend {
+            router = router.layer(Extension(backend.clone()));
+        }
+
+        // Preflights are answered here, before they reach authentication
+        router = router.layer(cors(self.runtime.clone()));
+
+        // Outermost, so everything a request logs carries its request ID
+        router.layer(from_fn(trace_request))
     }
 
     /// Run the server
     pub async fn run(self, addr: std::net::SocketAddr) -> anyhow::Result<()> {
-        let app = self.create_router();
-
+        let (http, ws) = if self.config.tls.is_enabled() {
+            ("https", "wss")
+        } else {
+            ("http", "ws")
+        };
         info!("Starting Redis API server on {}", addr);
-        info!("HTTP API available at http://{}", addr);
-        info!("RedisWs API available at ws://{}/redis_ws", addr);
+        info!("HTTP API available at {http}://{}", addr);
+        info!("RedisWs API available at {ws}://{}/redis_ws", addr);
+        info!(
+            "Redis Admin HTTP API available at {http}://{}/redis/admin",
+            addr
+        );
         info!(
-            "Redis Admin HTTP API available at http://{}/redis/admin",
+            "Redis Admin WebSocket API available at {ws}://{}/redis_ws/admin/ws",
             addr
         );
         info!(
-            "Redis Admin WebSocket API available at ws://{}/redis_ws/admin/ws",
+            "Redis String HTTP API available at {http}://{}/redis/string",
             addr
         );
         info!(
-            "Redis String HTTP API available at http://{}/redis/string",
+            "Redis String WebSocket API available at {ws}://{}/redis_ws/string/ws",
             addr
         );
         info!(
-            "Redis String WebSocket API available at ws://{}/redis_ws/string/ws",
+            "Redis Hash HTTP API available at {http}://{}/redis/hash",
             addr
         );
         info!(
-            "Redis Hash HTTP API available at http://{}/redis/hash",
+            "Redis Hash WebSocket API available at {ws}://{}/redis_ws/hash/ws",
             addr
         );
         info!(
-            "Redis Hash WebSocket API available at ws://{}/redis_ws/hash/ws",
+            "Redis Set HTTP API available at {http}://{}/redis/set",
             addr
         );
-        info!("Redis Set HTTP API available at http://{}/redis/set", addr);
         info!(
-            "Redis Set WebSocket API available at ws://{}/redis_ws/set/ws",
+            "Redis Set WebSocket API available at {ws}://{}/redis_ws/set/ws",
             addr
         );
+        info!(
+            "Redis Sorted Set HTTP API available at {http}://{}/redis/zset",
+            addr
+        );
+        info!(
+            "Redis Sorted Set WebSocket API available at {ws}://{}/redis_ws/zset/ws",
+            addr
+        );
+        info!(
+            "Redis Bitmap HTTP API available at {http}://{}/redis/bitmap",
+            addr
+        );
+        info!(
+            "Redis Bitmap WebSocket API available at {ws}://{}/redis_ws/bitmap/ws",
+            addr
+        );
+        info!(
+            "Redis Pub/Sub HTTP API available at {http}://{}/redis/pubsub",
+            addr
+        );
+        info!(
+            "Redis Pub/Sub WebSocket API available at {ws}://{}/redis_ws/pubsub/ws",
+            addr
+        );
+        info!(
+            "Redis Script HTTP API available at {http}://{}/redis/script",
+            addr
+        );
+        info!(
+            "Redis Script WebSocket API available at {ws}://{}/redis_ws/script/ws",
+            addr
+        );
+        for backend in &self.backends {
+            info!(
+                "Backend {} available at {http}://{}/redis/{} and {ws}://{}/redis_ws/{}",
+                backend.name, addr, backend.name, addr, backend.name
+            );
+        }
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(
-            listener,
-            app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
-        )
-        .await?;
+        self.serve(listener).await
+    }
+
+    /// Serve connections from `listener` until shut down
+    ///
+    /// Connections are HTTPS when `tls` is configured, plain HTTP otherwise.
+    /// `SIGTERM`, `SIGINT` or [`Shutdown::begin`] on the
+    /// [`shutdown_handle`](Self::shutdown_handle) stop the server accepting.
+    /// It then waits for in-flight requests and WebSockets, for up to
+    /// `shutdown_timeout_secs`, before returning.
+    pub async fn serve(self, listener: tokio::net::TcpListener) -> anyhow::Result<()> {
+        let app = self.create_router();
+        self.reloader.watch();
+
+        let acceptor = tls::acceptor(&self.config.tls)?;
+
+        let shutdown = self.shutdown.clone();
+        tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move {
+                tokio::select! {
+                    _ = shutdown::signal() => shutdown.begin(),
+                    _ = shutdown.draining() => {}
+                }
+                info!("Shutting down: draining in-flight requests and WebSockets");
+            }
+        });
 
+        // Upgraded WebSockets are no longer the listener's, so wait for them too
+        let drained = async {
+            match acceptor {
+                Some(acceptor) => {
+                    let handshake_timeout =
+                        Duration::from_secs(Defaults::TLS_HANDSHAKE_TIMEOUT_SECS);
+                    tls::serve(listener, acceptor, handshake_timeout, app, shutdown.clone()).await?
+                }
+                None => {
+                    let stop = shutdown.clone();
+                    axum::serve(
+                        listener,
+                        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+                    )
+                    .with_graceful_shutdown(async move { stop.draining().await })
+                    .await?
+                }
+            }
+            shutdown.sockets_closed().await;
+            anyhow::Ok(())
+        };
+        let deadline = Duration::from_secs(self.config.shutdown_timeout_secs);
+        tokio::select! {
+            result = drained => result?,
+            _ = async {
+                shutdown.draining().await;
+                tokio::time::sleep(deadline).await;
+            } => warn!(
+                "Shutdown timed out after {}s; dropping remaining requests and WebSockets",
+                deadline.as_secs()
+            ),
+        }
+        info!("Server stopped");
         Ok(())
     }
 }
@@ -137,6 +411,13 @@ impl Clone for Server {
         Self {
             config: self.config.clone(),
             redis_pool: self.redis_pool.clone(),
+            memory_backend: self.memory_backend.clone(),
+            backends: self.backends.clone(),
+            runtime: self.runtime.clone(),
+            reloader: self.reloader.clone(),
+            shutdown: self.shutdown.clone(),
+            #[cfg(feature = "mdbx")]
+            mdbx: self.mdbx.clone(),
         }
     }
 }
@@ -182,3 +463,145 @@ async fn serve_landing_page() -> Result<Html<String>, StatusCode> {
         }
     }
 }
+
+/// The HTTP and WebSocket routes for one Redis backend, to nest under
+/// `/redis` and `/redis_ws`
+fn redis_routes(pool: &Arc<RedisPool>) -> (Router, Router) {
+    let redis_string_routes =
+        crate::routes::redis::string::create_redis_string_routes(pool.clone());
+    let redis_hash_routes = crate::routes::redis::hash::create_redis_hash_routes(pool.clone());
+    let redis_set_routes = crate::routes::redis::set::create_redis_set_routes(pool.clone());
+    let redis_sorted_set_routes =
+        crate::routes::redis::sorted_set::create_redis_sorted_set_routes(pool.clone());
+    let redis_bitmap_routes =
+        crate::routes::redis::bitmap::create_redis_bitmap_routes(pool.clone());
+    let redis_pubsub_routes =
+        crate::routes::redis::pubsub::create_redis_pubsub_routes(pool.clone());
+    let script_registry = Arc::new(ScriptRegistry::new());
+    let redis_script_routes = crate::routes::redis::script::create_redis_script_routes(
+        pool.clone(),
+        script_registry.clone(),
+    );
+    let redis_admin_routes = crate::routes::redis::admin::create_redis_admin_routes(pool.clone());
+    let redis_ws_string_routes =
+        crate::routes::redis_ws::string::create_redis_ws_string_routes(pool.clone());
+    let redis_ws_hash_routes =
+        crate::routes::redis_ws::hash::create_redis_ws_hash_routes(pool.clone());
+    let redis_ws_set_routes =
+        crate::routes::redis_ws::set::create_redis_ws_set_routes(pool.clone());
+    let redis_ws_sorted_set_routes =
+        crate::routes::redis_ws::sorted_set::create_redis_ws_sorted_set_routes(pool.clone());
+    let redis_ws_bitmap_routes =
+        crate::routes::redis_ws::bitmap::create_redis_ws_bitmap_routes(pool.clone());
+    let pubsub_hub = Arc::new(PubSubHub::new(pool.client().clone()));
+    let redis_ws_pubsub_routes =
+        crate::routes::redis_ws::pubsub::create_redis_ws_pubsub_routes(pool.clone(), pubsub_hub);
+    let redis_ws_script_routes = crate::routes::redis_ws::script::create_redis_ws_script_routes(
+        pool.clone(),
+        script_registry,
+    );
+    let redis_ws_admin_routes =
+        crate::routes::redis_ws::admin::create_redis_ws_admin_routes(pool.clone());
+
+    let http = Router::new()
+        .merge(redis_string_routes)
+        .merge(redis_hash_routes)
+        .merge(redis_set_routes)
+        .merge(redis_sorted_set_routes)
+        .merge(redis_bitmap_routes)
+        .merge(redis_pubsub_routes)
+        .merge(redis_script_routes)
+        .merge(redis_admin_routes);
+    let ws = Router::new()
+        .merge(redis_ws_string_routes)
+        .merge(redis_ws_hash_routes)
+        .merge(redis_ws_set_routes)
+        .merge(redis_ws_sorted_set_routes)
+        .merge(redis_ws_bitmap_routes)
+        .merge(redis_ws_pubsub_routes)
+        .merge(redis_ws_script_routes)
+        .merge(redis_ws_admin_routes);
+    (http, ws)
+}
+
+/// Open a pool to the backend at `url` and check it answers PING
+///
+/// memory:// runs an in-process Redis-compatible backend and points the pool
+/// at it, so every route works unchanged; sqlite:// does the same with the
+/// keyspace loaded from and written through to a SQLite file.
+async fn connect(
+    name: &str,
+    url: &str,
+    pool_size: u32,
+    redis_tls: &RedisTlsConfig,
+) -> anyhow::Result<Backend> {
+    let (redis_url, memory) = if is_memory_url(url) {
+        let backend = MemoryServer::start(MemoryStore::new()).await?;
+        info!(
+            backend = name,
+            "Using in-memory backend at {}",
+            backend.addr()
+        );
+        (backend.url(), Some(Arc::new(backend)))
+    } else if DatabaseType::from_url(url) == DatabaseType::Sqlite {
+        let path =
+            sqlite_path(url).ok_or_else(|| anyhow::anyhow!(ErrorMessages::SQLITE_PATH_MISSING))?;
+        let sqlite = SqliteAdapter::open(path)?;
+        let store = MemoryStore::with_persistence(Arc::new(sqlite.clone()));
+        let loaded = sqlite.load_into(&store)?;
+        let backend = MemoryServer::start(store).await?;
+        info!(
+            backend = name,
+            "Using SQLite database {} ({} keys) at {}",
+            path,
+            loaded,
+            backend.addr()
+        );
+        (backend.url(), Some(Arc::new(backend)))
+    } else {
+        info!(backend = name, "Connecting to Redis at {}", url);
+        (url.to_string(), None)
+    };
+
+    let tls = RedisTls::from_files(
+        redis_tls.ca_cert_path.as_deref().map(Path::new),
+        redis_tls
+            .client_cert_path
+            .as_deref()
+            .zip(redis_tls.client_key_path.as_deref())
+            .map(|(cert, key)| (Path::new(cert), Path::new(key))),
+    )?;
+    let pool = RedisPool::with_config(
+        &redis_url,
+        RedisPoolConfig {
+            max_size: pool_size,
+            tls,
+            ..RedisPoolConfig::default()
+        },
+    )?;
+    observe_pool(&pool, name);
+    let ping_result = match pool.get_multiplexed_connection().await {
+        Ok(conn) => AsyncAdminOperations::new(conn).ping().await,
+        Err(e) => Err(e),
+    };
+
+    match ping_result {
+        Ok(pong) if pong == "PONG" => {
+            info!(
+                backend = name,
+                "Successfully connected to Redis with connection pool"
+            );
+            Ok(Backend {
+                name: name.to_string(),
+                pool: Arc::new(pool),
+                memory,
+            })
+        }
+        Ok(_) => Err(anyhow::anyhow!(ErrorMessages::REDIS_PING_FAILED)),
+        Err(e) => Err(anyhow::anyhow!(
+            "{}{}",
+            ErrorMessages::REDIS_CONNECTION_FAILED,
+            e
+        )),
+    }
+}
@@ -0,0 +1,119 @@
+//! Graceful shutdown
+//!
+//! On `SIGTERM` or `SIGINT` the server stops accepting connections and the
+//! health check reports it is not ready. In-flight requests run to completion,
+//! and each WebSocket finishes the message it is handling, then gets a close
+//! frame saying the server is going away. Whatever is still running when
+//! `shutdown_timeout_secs` passes is dropped.
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Shared shutdown state, added to every request's extensions
+#[derive(Debug, Clone, Default)]
+pub struct Shutdown(Arc<ShutdownState>);
+
+#[derive(Debug)]
+struct ShutdownState {
+    draining: watch::Sender<bool>,
+    /// WebSockets still open
+    sockets: watch::Sender<usize>,
+}
+
+impl Default for ShutdownState {
+    fn default() -> Self {
+        Self {
+            draining: watch::Sender::new(false),
+            sockets: watch::Sender::new(0),
+        }
+    }
+}
+
+impl Shutdown {
+    /// Start draining: stop accepting, report not ready and close WebSockets
+    pub fn begin(&self) {
+        self.0.draining.send_replace(true);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        *self.0.draining.borrow()
+    }
+
+    /// Wait until draining starts
+    pub async fn draining(&self) {
+        // The sender lives as long as `self`, so this only returns once draining
+        let _ = self
+            .0
+            .draining
+            .subscribe()
+            .wait_for(|draining| *draining)
+            .await;
+    }
+
+    /// Count a WebSocket as open until the guard is dropped
+    pub fn open_socket(&self) -> OpenSocket {
+        self.0.sockets.send_modify(|open| *open += 1);
+        OpenSocket(self.clone())
+    }
+
+    /// Wait until every WebSocket has closed
+    pub async fn sockets_closed(&self) {
+        let _ = self.0.sockets.subscribe().wait_for(|open| *open == 0).await;
+    }
+}
+
+/// An open WebSocket, see [`Shutdown::open_socket`]
+pub struct OpenSocket(Shutdown);
+
+impl Drop for OpenSocket {
+    fn drop(&mut self) {
+        self.0 .0.sockets.send_modify(|open| *open -= 1);
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Shutdown {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // Routers built without the extension never shut down
+        Ok(parts
+            .extensions
+            .get::<Shutdown>()
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+/// Wait for `SIGTERM`, or `SIGINT` (Ctrl-C)
+pub async fn signal() {
+    let interrupt = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("Cannot listen for SIGINT: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("Cannot listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = interrupt => {}
+        _ = terminate => {}
+    }
+}
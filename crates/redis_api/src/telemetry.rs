@@ -0,0 +1,124 @@
+//! OpenTelemetry trace export
+//!
+//! When an OTLP endpoint is configured, this crate's spans are exported over
+//! OTLP/HTTP to `<endpoint>/v1/traces`: each request and the handler it was
+//! routed to, each WebSocket connection and message, and each Redis call with
+//! its command name and key count. A request carrying a W3C `traceparent`
+//! header continues the caller's trace.
+
+use axum::http::HeaderMap;
+use opentelemetry::propagation::{Extractor, TextMapPropagator};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::config::TelemetryConfig;
+
+/// Path of the trace export endpoint below the collector's base URL
+const TRACES_PATH: &str = "/v1/traces";
+
+/// Exports spans to an OTLP collector
+///
+/// Spans still buffered are exported when this is dropped.
+pub struct Telemetry {
+    provider: SdkTracerProvider,
+}
+
+impl Telemetry {
+    /// Set up trace export, or `None` when no endpoint is configured
+    pub fn from_config(config: &TelemetryConfig) -> anyhow::Result<Option<Self>> {
+        let Some(endpoint) = &config.otlp_endpoint else {
+            return Ok(None);
+        };
+
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{}{}", endpoint.trim_end_matches('/'), TRACES_PATH))
+            .build()
+            .map_err(|e| anyhow::anyhow!("Invalid OTLP endpoint {}: {}", endpoint, e))?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(config.service_name.clone())
+                    .build(),
+            )
+            .build();
+        Ok(Some(Self { provider }))
+    }
+
+    /// A layer exporting this crate's spans, Redis calls included
+    pub fn layer<S>(&self) -> impl Layer<S>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        tracing_opentelemetry::layer()
+            .with_tracer(self.provider.tracer(env!("CARGO_PKG_NAME")))
+            .with_filter(Targets::new().with_target(env!("CARGO_CRATE_NAME"), Level::DEBUG))
+    }
+
+    /// Export every span that has ended so far
+    pub fn flush(&self) -> anyhow::Result<()> {
+        self.provider
+            .force_flush()
+            .map_err(|e| anyhow::anyhow!("Failed to export spans: {}", e))
+    }
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            tracing::warn!("Failed to shut down trace export: {}", e);
+        }
+    }
+}
+
+/// The trace a request's `traceparent` header continues, if it has one
+pub fn remote_context(headers: &HeaderMap) -> opentelemetry::Context {
+    TraceContextPropagator::new().extract(&HeaderExtractor(headers))
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|name| name.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TraceContextExt;
+
+    #[test]
+    fn test_remote_context_from_traceparent() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+        let context = remote_context(&headers);
+        let span = context.span();
+        let span_context = span.span_context();
+        assert!(span_context.is_remote());
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+
+        assert!(!remote_context(&HeaderMap::new()).has_active_span());
+    }
+}
@@ -0,0 +1,209 @@
+//! HTTPS and WSS serving
+//!
+//! With `tls.cert_path` and `tls.key_path` set the server terminates TLS
+//! itself. Setting `tls.client_ca_path` as well makes it mutual: clients must
+//! present a certificate signed by that CA before any request is read.
+
+use axum::Router;
+use hyper_util::rt::TokioIo;
+use hyper_util::service::TowerToHyperService;
+use std::fs::File;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinSet;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+use tower::Service;
+use tracing::{debug, error};
+
+use crate::config::TlsConfig;
+use crate::shutdown::Shutdown;
+
+/// Pause after an accept error that is not specific to one connection
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Build the TLS acceptor, or `None` when HTTPS is not configured
+pub fn acceptor(config: &TlsConfig) -> anyhow::Result<Option<TlsAcceptor>> {
+    let (Some(cert_path), Some(key_path)) = (&config.cert_path, &config.key_path) else {
+        return Ok(None);
+    };
+    let certs = read_certs(cert_path)?;
+    let key = read_key(key_path)?;
+
+    let builder = match &config.client_ca_path {
+        Some(ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in read_certs(ca_path)? {
+                roots.add(cert)?;
+            }
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+                .build()
+                .map_err(|e| anyhow::anyhow!("Invalid client CA {}: {}", ca_path, e))?;
+            ServerConfig::builder().with_client_cert_verifier(verifier)
+        }
+        None => ServerConfig::builder().with_no_client_auth(),
+    };
+    let mut server_config = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| anyhow::anyhow!("Invalid TLS certificate or key: {}", e))?;
+    // Connections are served over HTTP/1.1, which WebSocket upgrades need
+    server_config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    Ok(Some(TlsAcceptor::from(Arc::new(server_config))))
+}
+
+fn read_certs(path: &str) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let file = File::open(path).map_err(|e| anyhow::anyhow!("Cannot read {}: {}", path, e))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Invalid PEM in {}: {}", path, e))?;
+    if certs.is_empty() {
+        return Err(anyhow::anyhow!("No certificates found in {}", path));
+    }
+    Ok(certs)
+}
+
+fn read_key(path: &str) -> anyhow::Result<PrivateKeyDer<'static>> {
+    let file = File::open(path).map_err(|e| anyhow::anyhow!("Cannot read {}: {}", path, e))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .map_err(|e| anyhow::anyhow!("Invalid PEM in {}: {}", path, e))?
+        .ok_or_else(|| anyhow::anyhow!("No private key found in {}", path))
+}
+
+/// Serve `app` over TLS until shutdown starts and every connection has finished
+///
+/// Mirrors `axum::serve` with graceful shutdown: once draining starts no more
+/// connections are accepted and open ones close after their current request.
+/// Clients that have not finished the handshake within `handshake_timeout`
+/// are disconnected.
+pub async fn serve(
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    handshake_timeout: Duration,
+    app: Router,
+    shutdown: Shutdown,
+) -> anyhow::Result<()> {
+    let mut make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+    let mut connections = JoinSet::new();
+
+    loop {
+        let (socket, remote_addr) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) if is_connection_error(&e) => continue,
+                // E.g. out of file descriptors: back off rather than spin
+                Err(e) => {
+                    error!("Failed to accept connection: {}", e);
+                    tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    continue;
+                }
+            },
+            Some(_) = connections.join_next(), if !connections.is_empty() => continue,
+            _ = shutdown.draining() => break,
+        };
+        let service = match make_service.call(remote_addr).await {
+            Ok(service) => service,
+            Err(infallible) => match infallible {},
+        };
+        let acceptor = acceptor.clone();
+        let shutdown = shutdown.clone();
+
+        connections.spawn(async move {
+            let stream =
+                match tokio::time::timeout(handshake_timeout, acceptor.accept(socket)).await {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(e)) => {
+                        debug!(client = %remote_addr, "TLS handshake failed: {}", e);
+                        return;
+                    }
+                    Err(_) => {
+                        debug!(client = %remote_addr, "TLS handshake timed out");
+                        return;
+                    }
+                };
+            let connection = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), TowerToHyperService::new(service))
+                .with_upgrades();
+            tokio::pin!(connection);
+
+            let result = tokio::select! {
+                result = connection.as_mut() => result,
+                _ = shutdown.draining() => {
+                    connection.as_mut().graceful_shutdown();
+                    connection.await
+                }
+            };
+            if let Err(e) = result {
+                debug!(client = %remote_addr, "Connection error: {}", e);
+            }
+        });
+    }
+
+    drop(listener);
+    while connections.join_next().await.is_some() {}
+    Ok(())
+}
+
+/// Errors that only affect the connection being accepted, as in `axum::serve`
+fn is_connection_error(e: &std::io::Error) -> bool {
+    matches!(
+        e.kind(),
+        std::io::ErrorKind::ConnectionRefused
+            | std::io::ErrorKind::ConnectionAborted
+            | std::io::ErrorKind::ConnectionReset
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    fn test_acceptor() -> TlsAcceptor {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let key = PrivateKeyDer::Pkcs8(cert.serialize_private_key_der().into());
+        let config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert.serialize_der().unwrap().into()], key)
+            .unwrap();
+        TlsAcceptor::from(Arc::new(config))
+    }
+
+    #[tokio::test]
+    async fn test_stalled_handshakes_are_dropped() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(
+            listener,
+            test_acceptor(),
+            Duration::from_millis(100),
+            Router::new(),
+            Shutdown::default(),
+        ));
+
+        // Connect but never send a ClientHello
+        let mut socket = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let mut buf = [0; 1];
+        let read = tokio::time::timeout(Duration::from_secs(5), socket.read(&mut buf))
+            .await
+            .expect("the server kept the stalled connection open");
+        assert_eq!(read.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_accept_errors_that_need_a_backoff() {
+        use std::io::{Error, ErrorKind};
+
+        assert!(is_connection_error(&Error::from(
+            ErrorKind::ConnectionReset
+        )));
+        // EMFILE and ENFILE surface as other errors and must not be retried at once
+        assert!(!is_connection_error(&Error::from_raw_os_error(24)));
+        assert!(!is_connection_error(&Error::from_raw_os_error(23)));
+    }
+}
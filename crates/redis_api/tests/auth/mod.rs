@@ -0,0 +1,185 @@
+use crate::{test_config, TestServer};
+use dbx_redis_api::config::{AuthConfig, PolicyConfig};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio_tungstenite::tungstenite::Message;
+
+const API_KEY: &str = "test-api-key";
+
+async fn start_auth_server() -> TestServer {
+    let mut config = test_config();
+    config.auth = AuthConfig {
+        api_keys: vec![API_KEY.to_string()],
+        ..AuthConfig::default()
+    };
+    let server = TestServer::with_config(config)
+        .await
+        .expect("Failed to create test server");
+    server.start().await.expect("Failed to start test server");
+    server
+}
+
+#[tokio::test]
+async fn test_http_requires_credentials() {
+    let server = start_auth_server().await;
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/redis/admin/ping", server.addr);
+
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.status().as_u16(), 401);
+    assert_eq!(res.headers()["www-authenticate"], "Bearer");
+    let body: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(body["code"], "UNAUTHORIZED");
+
+    let res = client
+        .get(&url)
+        .bearer_auth("wrong-key")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 401);
+
+    let res = client
+        .get(&url)
+        .header("X-API-Key", API_KEY)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+
+    let res = client.get(&url).bearer_auth(API_KEY).send().await.unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+
+    // The landing page stays public
+    let res = client
+        .get(format!("http://{}/", server.addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn test_websocket_requires_credentials() {
+    let server = start_auth_server().await;
+    let url = format!("ws://{}/redis_ws/string/ws", server.addr);
+
+    assert!(tokio_tungstenite::connect_async(&url).await.is_err());
+    assert!(
+        tokio_tungstenite::connect_async(format!("{}?token={}", url, API_KEY))
+            .await
+            .is_ok()
+    );
+}
+
+const TENANT_KEY: &str = "tenant42-api-key";
+const ADMIN_KEY: &str = "admin-api-key";
+
+async fn start_policy_server() -> TestServer {
+    let mut config = test_config();
+    config.auth = AuthConfig {
+        api_key_roles: [
+            (TENANT_KEY.to_string(), "tenant42".to_string()),
+            (ADMIN_KEY.to_string(), "admin".to_string()),
+        ]
+        .into(),
+        ..AuthConfig::default()
+    };
+    config.policy = serde_json::from_value::<PolicyConfig>(json!({
+        "roles": {
+            "admin": { "permissions": ["read", "write", "admin"] },
+            "tenant42": {
+                "permissions": ["read", "write"],
+                "key_patterns": ["tenant42:*"]
+            }
+        }
+    }))
+    .unwrap();
+    let server = TestServer::with_config(config)
+        .await
+        .expect("Failed to create test server");
+    server.start().await.expect("Failed to start test server");
+    server
+}
+
+#[tokio::test]
+async fn test_http_role_scoping() {
+    let server = start_policy_server().await;
+    let client = reqwest::Client::new();
+    let base = format!("http://{}/redis", server.addr);
+
+    let res = client
+        .post(format!("{}/string/tenant42:greeting", base))
+        .bearer_auth(TENANT_KEY)
+        .json(&json!({"value": "hello"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+
+    let res = client
+        .get(format!("{}/string/tenant7:greeting", base))
+        .bearer_auth(TENANT_KEY)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 403);
+
+    let res = client
+        .post(format!("{}/string/batch/get", base))
+        .bearer_auth(TENANT_KEY)
+        .json(&json!({"keys": ["tenant42:greeting", "tenant7:greeting"]}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 403);
+
+    let res = client
+        .delete(format!("{}/admin/flushdb", base))
+        .bearer_auth(TENANT_KEY)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 403);
+
+    let res = client
+        .get(format!("{}/admin/config/all", base))
+        .bearer_auth(ADMIN_KEY)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn test_websocket_role_scoping() {
+    let server = start_policy_server().await;
+    let url = format!(
+        "ws://{}/redis_ws/string/ws?token={}",
+        server.addr, TENANT_KEY
+    );
+    let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+    ws.send(Message::Text(
+        json!({"type": "get", "data": {"key": "tenant7:greeting"}}).to_string(),
+    ))
+    .await
+    .unwrap();
+    let reply: serde_json::Value = match ws.next().await.unwrap().unwrap() {
+        Message::Text(text) => serde_json::from_str(&text).unwrap(),
+        other => panic!("Unexpected message: {:?}", other),
+    };
+    assert_eq!(reply["type"], "error");
+    assert_eq!(reply["data"]["code"], "FORBIDDEN");
+
+    ws.send(Message::Text(
+        json!({"type": "get", "data": {"key": "tenant42:greeting"}}).to_string(),
+    ))
+    .await
+    .unwrap();
+    let reply: serde_json::Value = match ws.next().await.unwrap().unwrap() {
+        Message::Text(text) => serde_json::from_str(&text).unwrap(),
+        other => panic!("Unexpected message: {:?}", other),
+    };
+    assert_eq!(reply["type"], "result");
+}
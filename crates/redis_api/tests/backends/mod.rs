@@ -0,0 +1,116 @@
+use crate::common::response_data;
+use crate::{test_config, TestServer};
+use dbx_redis_api::config::BackendConfig;
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+/// A server with `cache` and `sessions` backends next to the default one
+async fn start_server() -> TestServer {
+    let mut config = test_config();
+    config.database_url = "memory://".to_string();
+    for name in ["cache", "sessions"] {
+        config.backends.insert(
+            name.to_string(),
+            BackendConfig {
+                database_url: "memory://".to_string(),
+                pool_size: Some(2),
+                redis_tls: Default::default(),
+            },
+        );
+    }
+    let server = TestServer::with_config(config)
+        .await
+        .expect("Failed to create test server");
+    server.start().await.expect("Failed to start test server");
+    server
+}
+
+async fn get_string(base_url: &str, key: &str) -> Option<String> {
+    let res = reqwest::get(format!("{base_url}/string/{key}"))
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+    response_data(res).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_backends_are_separate() {
+    let server = start_server().await;
+    let base = format!("http://{}/redis", server.addr);
+
+    let res = reqwest::Client::new()
+        .post(format!("{base}/cache/string/greeting"))
+        .json(&json!({ "value": "from cache" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+
+    assert_eq!(
+        get_string(&format!("{base}/cache"), "greeting").await,
+        Some("from cache".to_string())
+    );
+    assert_eq!(
+        get_string(&format!("{base}/sessions"), "greeting").await,
+        None
+    );
+    assert_eq!(get_string(&base, "greeting").await, None);
+
+    let res = reqwest::get(format!("{base}/sessions/admin/health"))
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+
+    let res = reqwest::get(format!("{base}/unknown/string/greeting"))
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn test_backend_health() {
+    let server = start_server().await;
+
+    let res = reqwest::get(format!("http://{}/redis/admin/backends", server.addr))
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+    let backends: Vec<Value> = response_data(res).await.unwrap();
+
+    let names: Vec<&str> = backends
+        .iter()
+        .map(|backend| backend["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["default", "cache", "sessions"]);
+    assert!(backends.iter().all(|backend| backend["healthy"] == true));
+    assert_eq!(backends[1]["pool"]["max_size"], 2);
+}
+
+#[tokio::test]
+async fn test_websocket_backend() {
+    let server = start_server().await;
+
+    let (ws, _) = connect_async(format!("ws://{}/redis_ws/sessions/string/ws", server.addr))
+        .await
+        .expect("Failed to connect");
+    let (mut write, mut read) = ws.split();
+    write
+        .send(Message::Text(
+            json!({ "type": "set", "data": { "key": "user", "value": "alice" } }).to_string(),
+        ))
+        .await
+        .unwrap();
+    let response = read.next().await.unwrap().unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(response.to_text().unwrap()).unwrap()["type"],
+        "result"
+    );
+
+    let base = format!("http://{}/redis", server.addr);
+    assert_eq!(
+        get_string(&format!("{base}/sessions"), "user").await,
+        Some("alice".to_string())
+    );
+    assert_eq!(get_string(&format!("{base}/cache"), "user").await, None);
+}
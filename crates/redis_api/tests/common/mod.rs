@@ -1,4 +1,6 @@
-use reqwest::Client;
+use reqwest::{Client, Response};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use serde_json::json;
 use std::time::Duration;
 
@@ -44,6 +46,16 @@ pub fn generate_special_chars_value() -> String {
 }
 
 // HTTP client utilities
+#[derive(Deserialize)]
+struct Envelope<T> {
+    data: T,
+}
+
+/// Read the `data` of a successful response envelope
+pub async fn response_data<T: DeserializeOwned>(res: Response) -> reqwest::Result<T> {
+    Ok(res.json::<Envelope<T>>().await?.data)
+}
+
 pub fn create_http_client() -> Client {
     Client::new()
 }
@@ -55,7 +67,7 @@ pub async fn set_string(
     value: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let res = client
-        .post(&format!("{}/redis/string/{}", base_url, key))
+        .post(format!("{}/redis/string/{}", base_url, key))
         .json(&json!({"value": value}))
         .send()
         .await?;
@@ -73,12 +85,12 @@ pub async fn get_string(
     key: &str,
 ) -> Result<Option<String>, Box<dyn std::error::Error>> {
     let res = client
-        .get(&format!("{}/redis/string/{}", base_url, key))
+        .get(format!("{}/redis/string/{}", base_url, key))
         .send()
         .await?;
 
     if res.status() == 200 {
-        let body: Option<String> = res.json().await?;
+        let body: Option<String> = response_data(res).await?;
         Ok(body)
     } else {
         Err(format!("Failed to get string: {}", res.status()).into())
@@ -91,12 +103,12 @@ pub async fn delete_string(
     key: &str,
 ) -> Result<bool, Box<dyn std::error::Error>> {
     let res = client
-        .delete(&format!("{}/redis/string/{}", base_url, key))
+        .delete(format!("{}/redis/string/{}", base_url, key))
         .send()
         .await?;
 
     if res.status() == 200 {
-        let deleted: bool = res.json().await?;
+        let deleted: bool = response_data(res).await?;
         Ok(deleted)
     } else {
         Err(format!("Failed to delete string: {}", res.status()).into())
@@ -147,7 +159,7 @@ pub async fn batch_set_strings(
         .collect();
 
     let res = client
-        .post(&format!("{}/redis/string/batch/set", base_url))
+        .post(format!("{}/redis/string/batch/set", base_url))
         .json(&json!({"operations": batch_ops}))
         .send()
         .await?;
@@ -162,13 +174,13 @@ pub async fn batch_get_strings(
     keys: &[String],
 ) -> Result<Vec<Option<String>>, Box<dyn std::error::Error>> {
     let res = client
-        .post(&format!("{}/redis/string/batch/get", base_url))
+        .post(format!("{}/redis/string/batch/get", base_url))
         .json(&json!({"keys": keys}))
         .send()
         .await?;
 
     assert_status_ok(res.status().as_u16());
-    let values: Vec<Option<String>> = res.json().await?;
+    let values: Vec<Option<String>> = response_data(res).await?;
     Ok(values)
 }
 
@@ -0,0 +1,58 @@
+use crate::{test_config, TestServer};
+use dbx_redis_api::config::CorsConfig;
+
+async fn start_server(allowed_origins: &[&str]) -> TestServer {
+    let mut config = test_config();
+    config.cors = CorsConfig {
+        allowed_origins: allowed_origins
+            .iter()
+            .map(|origin| origin.to_string())
+            .collect(),
+    };
+    let server = TestServer::with_config(config)
+        .await
+        .expect("Failed to create test server");
+    server.start().await.expect("Failed to start test server");
+    server
+}
+
+#[tokio::test]
+async fn test_cors_allowed_origin() {
+    let server = start_server(&["https://app.example.com"]).await;
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/redis/string/cors_key", server.addr);
+
+    let res = client
+        .request(reqwest::Method::OPTIONS, &url)
+        .header("origin", "https://app.example.com")
+        .header("access-control-request-method", "POST")
+        .header("access-control-request-headers", "content-type")
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    assert_eq!(
+        res.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+
+    let res = client
+        .get(&url)
+        .header("origin", "https://evil.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert!(res.headers().get("access-control-allow-origin").is_none());
+}
+
+#[tokio::test]
+async fn test_cors_off_by_default() {
+    let server = start_server(&[]).await;
+    let res = reqwest::Client::new()
+        .get(format!("http://{}/redis/admin/ping", server.addr))
+        .header("origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert!(res.headers().get("access-control-allow-origin").is_none());
+}
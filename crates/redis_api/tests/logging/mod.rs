@@ -0,0 +1,57 @@
+use crate::get_test_server;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+
+#[tokio::test]
+async fn test_request_id_is_echoed() {
+    let server = get_test_server().await;
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/redis/admin/ping", server.addr);
+
+    let res = client
+        .get(&url)
+        .header("X-Request-Id", "client-request-1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+    assert_eq!(res.headers()["x-request-id"], "client-request-1");
+
+    // Without one, or with one that can't be logged as is, the server makes one up
+    for header in [None, Some("has spaces")] {
+        let mut request = client.get(&url);
+        if let Some(header) = header {
+            request = request.header("X-Request-Id", header);
+        }
+        let res = request.send().await.unwrap();
+        let request_id = res.headers()["x-request-id"].to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(request_id).is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_websocket_request_ids() {
+    let server = get_test_server().await;
+    let mut request = format!("ws://{}/redis_ws/string/ws", server.addr)
+        .into_client_request()
+        .unwrap();
+    request
+        .headers_mut()
+        .insert("X-Request-Id", "client-socket-1".parse().unwrap());
+    let (mut ws, response) = tokio_tungstenite::connect_async(request).await.unwrap();
+    assert_eq!(response.headers()["x-request-id"], "client-socket-1");
+
+    // Messages may carry their own ID
+    ws.send(Message::Text(
+        json!({"type": "get", "request_id": "client-message-1", "data": {"key": "logging"}})
+            .to_string(),
+    ))
+    .await
+    .unwrap();
+    let reply: serde_json::Value = match ws.next().await.unwrap().unwrap() {
+        Message::Text(text) => serde_json::from_str(&text).unwrap(),
+        other => panic!("Unexpected message: {:?}", other),
+    };
+    assert_eq!(reply["type"], "result");
+}
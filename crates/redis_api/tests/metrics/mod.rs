@@ -0,0 +1,51 @@
+use crate::get_test_server;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio_tungstenite::tungstenite::Message;
+
+#[tokio::test]
+async fn test_metrics_endpoint() {
+    let server = get_test_server().await;
+    let client = reqwest::Client::new();
+
+    let res = client
+        .get(format!("http://{}/redis/admin/ping", server.addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+
+    let url = format!("ws://{}/redis_ws/string/ws", server.addr);
+    let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+    ws.send(Message::Text(
+        json!({"type": "get", "data": {"key": "metrics"}}).to_string(),
+    ))
+    .await
+    .unwrap();
+    ws.next().await.unwrap().unwrap();
+
+    let res = client
+        .get(format!("http://{}/metrics", server.addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+    assert!(res.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain"));
+    let body = res.text().await.unwrap();
+
+    for expected in [
+        r#"dbx_http_requests_total{method="GET",route="/redis/admin/ping",status="200"}"#,
+        r#"dbx_http_request_duration_seconds_count{method="GET",route="/redis/admin/ping"}"#,
+        r#"dbx_ws_messages_total{endpoint="string",type="get"}"#,
+        r#"dbx_ws_connections{endpoint="string"}"#,
+        r#"dbx_redis_pool_checkout_wait_seconds_count{backend="default"}"#,
+        r#"dbx_redis_pool_connections{backend="default",state="idle"}"#,
+        r#"dbx_redis_connected_clients{backend="default"}"#,
+        r#"dbx_redis_keyspace_hit_rate{backend="default"}"#,
+    ] {
+        assert!(body.contains(expected), "missing {expected} in:\n{body}");
+    }
+}
@@ -1,9 +1,23 @@
+pub mod auth;
+pub mod backends;
 pub mod common;
+pub mod config;
+pub mod cors;
+pub mod logging;
+pub mod metrics;
+pub mod rate_limit;
 pub mod redis;
 pub mod redis_ws;
+pub mod reload;
+pub mod shutdown;
+pub mod telemetry;
+pub mod tls;
 
 use dbx_redis_api::{
-    config::{Config, DatabaseType},
+    config::{
+        AuthConfig, Config, CorsConfig, LoggingConfig, PolicyConfig, RateLimitConfig,
+        RedisTlsConfig, TelemetryConfig, TlsConfig,
+    },
     constants::defaults::Defaults,
     server::Server,
 };
@@ -23,18 +37,14 @@ pub struct TestServer {
 
 impl TestServer {
     pub async fn new() -> anyhow::Result<Self> {
-        let config = Config {
-            database_url: std::env::var("REDIS_URL")
-                .unwrap_or_else(|_| Defaults::REDIS_URL.to_string()),
-            host: std::env::var("HOST").unwrap_or_else(|_| Defaults::HOST.to_string()),
-            port: 0, // Use port 0 for random available port
-            pool_size: std::env::var("POOL_SIZE")
-                .unwrap_or_else(|_| Defaults::POOL_SIZE.to_string())
-                .parse()
-                .unwrap_or(Defaults::POOL_SIZE),
-        };
+        Self::with_config(test_config()).await
+    }
+
+    pub async fn with_config(config: Config) -> anyhow::Result<Self> {
+        Self::with_server(Server::new(config).await?)
+    }
 
-        let server = Server::new(config).await?;
+    pub fn with_server(server: Server) -> anyhow::Result<Self> {
         // Bind to port 0 to get a random available port
         let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
         let addr = listener.local_addr()?;
@@ -60,6 +70,33 @@ impl TestServer {
     }
 }
 
+/// Server configuration shared by the tests
+pub fn test_config() -> Config {
+    Config {
+        // Without REDIS_URL the tests run hermetically on the in-memory backend
+        database_url: std::env::var("REDIS_URL").unwrap_or_else(|_| "memory://".to_string()),
+        host: std::env::var("HOST").unwrap_or_else(|_| Defaults::HOST.to_string()),
+        port: 0, // Use port 0 for random available port
+        pool_size: std::env::var("POOL_SIZE")
+            .unwrap_or_else(|_| Defaults::POOL_SIZE.to_string())
+            .parse()
+            .unwrap_or(Defaults::POOL_SIZE),
+        redis_tls: RedisTlsConfig::default(),
+        backends: Default::default(),
+        tls: TlsConfig::default(),
+        auth: AuthConfig::default(),
+        policy: PolicyConfig::default(),
+        policy_file: None,
+        cors: CorsConfig::default(),
+        read_only: false,
+        shutdown_timeout_secs: Defaults::SHUTDOWN_TIMEOUT_SECS,
+        rate_limit: RateLimitConfig::default(),
+        metrics_enabled: true,
+        logging: LoggingConfig::default(),
+        telemetry: TelemetryConfig::default(),
+    }
+}
+
 pub async fn get_test_server() -> Arc<TestServer> {
     let test_server = TestServer::new()
         .await
@@ -80,3 +117,16 @@ pub async fn get_test_ws_base_url() -> String {
     let server = get_test_server().await;
     format!("http://{}", server.addr)
 }
+
+/// Base URL of a test server backed by a real Redis, or `None` when
+/// `REDIS_URL` doesn't point at one
+///
+/// The in-memory backend can't run Lua, so scripting tests need Redis.
+pub async fn get_redis_test_base_url() -> Option<String> {
+    let url = test_config().database_url;
+    if !url.starts_with("redis://") && !url.starts_with("rediss://") {
+        eprintln!("Skipping: REDIS_URL is not set to a Redis server");
+        return None;
+    }
+    Some(get_test_base_url().await)
+}
@@ -0,0 +1,153 @@
+use crate::{test_config, TestServer};
+use dbx_redis_api::config::{AuthConfig, RateLimitConfig};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio_tungstenite::tungstenite::Message;
+
+async fn start_limited_server(rate_limit: RateLimitConfig, api_keys: &[&str]) -> TestServer {
+    let mut config = test_config();
+    config.rate_limit = rate_limit;
+    config.auth = AuthConfig {
+        api_keys: api_keys.iter().map(|key| key.to_string()).collect(),
+        ..AuthConfig::default()
+    };
+    let server = TestServer::with_config(config)
+        .await
+        .expect("Failed to create test server");
+    server.start().await.expect("Failed to start test server");
+    server
+}
+
+#[tokio::test]
+async fn test_http_requests_limited_by_ip() {
+    let server = start_limited_server(
+        RateLimitConfig {
+            requests: 2,
+            ..RateLimitConfig::default()
+        },
+        &[],
+    )
+    .await;
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/redis/admin/ping", server.addr);
+
+    for _ in 0..2 {
+        let res = client.get(&url).send().await.unwrap();
+        assert_eq!(res.status().as_u16(), 200);
+    }
+    let res = client.get(&url).send().await.unwrap();
+    assert_eq!(res.status().as_u16(), 429);
+    let retry_after: u64 = res.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after >= 1);
+
+    // The landing page is not limited
+    let res = client
+        .get(format!("http://{}/", server.addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn test_http_requests_limited_by_api_key() {
+    let server = start_limited_server(
+        RateLimitConfig {
+            requests: 1,
+            ..RateLimitConfig::default()
+        },
+        &["first-key", "second-key"],
+    )
+    .await;
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/redis/admin/ping", server.addr);
+
+    let res = client
+        .get(&url)
+        .bearer_auth("first-key")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+    let res = client
+        .get(&url)
+        .bearer_auth("first-key")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 429);
+
+    let res = client
+        .get(&url)
+        .bearer_auth("second-key")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn test_websocket_message_quota() {
+    let server = start_limited_server(
+        RateLimitConfig {
+            ws_messages: 1,
+            ..RateLimitConfig::default()
+        },
+        &[],
+    )
+    .await;
+    let url = format!("ws://{}/redis_ws/string/ws", server.addr);
+    let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+    let mut replies = Vec::new();
+    for _ in 0..2 {
+        ws.send(Message::Text(
+            json!({"type": "get", "data": {"key": "limited"}}).to_string(),
+        ))
+        .await
+        .unwrap();
+        let reply: serde_json::Value = match ws.next().await.unwrap().unwrap() {
+            Message::Text(text) => serde_json::from_str(&text).unwrap(),
+            other => panic!("Unexpected message: {:?}", other),
+        };
+        replies.push(reply["type"].clone());
+    }
+    assert_eq!(replies, ["result", "error"]);
+}
+
+#[tokio::test]
+async fn test_websocket_quota_counts_unparsable_frames() {
+    let server = start_limited_server(
+        RateLimitConfig {
+            ws_messages: 2,
+            ..RateLimitConfig::default()
+        },
+        &[],
+    )
+    .await;
+    let url = format!("ws://{}/redis_ws/string/ws", server.addr);
+    let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+    ws.send(Message::Text("not json".to_string()))
+        .await
+        .unwrap();
+    ws.send(Message::Binary(vec![0xde, 0xad])).await.unwrap();
+    ws.send(Message::Text(
+        json!({"type": "get", "data": {"key": "limited"}}).to_string(),
+    ))
+    .await
+    .unwrap();
+    let mut codes = Vec::new();
+    for _ in 0..2 {
+        let reply: serde_json::Value = match ws.next().await.unwrap().unwrap() {
+            Message::Text(text) => serde_json::from_str(&text).unwrap(),
+            other => panic!("Unexpected message: {:?}", other),
+        };
+        codes.push(reply["data"]["code"].clone());
+    }
+    assert_eq!(codes, ["INVALID_ARGUMENT", "RATE_LIMITED"]);
+}
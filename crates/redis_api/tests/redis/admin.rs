@@ -1,4 +1,4 @@
-use crate::common::TestContext;
+use crate::common::{response_data, TestContext};
 use crate::get_test_base_url;
 use serde_json::Value;
 
@@ -12,7 +12,7 @@ async fn test_admin_ping() {
         .await
         .unwrap();
     assert_eq!(res.status().as_u16(), 200);
-    let body: String = res.json().await.unwrap();
+    let body: String = response_data(res).await.unwrap();
     assert_eq!(body, "PONG");
 }
 
@@ -26,7 +26,7 @@ async fn test_admin_info() {
         .await
         .unwrap();
     assert_eq!(res.status().as_u16(), 200);
-    let body: String = res.json().await.unwrap();
+    let body: String = response_data(res).await.unwrap();
     assert!(body.contains("redis_version"));
 }
 
@@ -40,7 +40,7 @@ async fn test_admin_dbsize() {
         .await
         .unwrap();
     assert_eq!(res.status().as_u16(), 200);
-    let body: i64 = res.json().await.unwrap();
+    let body: i64 = response_data(res).await.unwrap();
     assert!(body >= 0);
 }
 
@@ -54,7 +54,7 @@ async fn test_admin_health() {
         .await
         .unwrap();
     assert_eq!(res.status().as_u16(), 200);
-    let body: Value = res.json().await.unwrap();
+    let body: Value = response_data(res).await.unwrap();
     assert!(body["is_healthy"].as_bool().unwrap_or(false));
     assert!(body["ping_response"].as_str().unwrap_or("") == "PONG");
 }
@@ -69,7 +69,7 @@ async fn test_admin_status() {
         .await
         .unwrap();
     assert_eq!(res.status().as_u16(), 200);
-    let body: Value = res.json().await.unwrap();
+    let body: Value = response_data(res).await.unwrap();
     assert!(body["uptime_seconds"].as_i64().unwrap_or(0) >= 0);
     assert!(body["version"].as_str().is_some());
 }
@@ -84,7 +84,7 @@ async fn test_admin_memory_stats() {
         .await
         .unwrap();
     assert_eq!(res.status().as_u16(), 200);
-    let body: Value = res.json().await.unwrap();
+    let body: Value = response_data(res).await.unwrap();
     assert!(body.get("used_memory").is_some());
 }
 
@@ -98,7 +98,7 @@ async fn test_admin_config_all() {
         .await
         .unwrap();
     assert_eq!(res.status().as_u16(), 200);
-    let body: Value = res.json().await.unwrap();
+    let body: Value = response_data(res).await.unwrap();
     assert!(body.get("maxmemory").is_some() || body.get("timeout").is_some());
 }
 
@@ -0,0 +1,106 @@
+use super::super::common::response_data;
+use super::super::get_test_base_url;
+use reqwest::Client;
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[tokio::test]
+async fn test_bitmap_set_get_and_count() {
+    let base_url = get_test_base_url().await;
+    let client = Client::new();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let key = format!("test_bitmap_key_{}", timestamp);
+
+    // Set a few bits
+    let res = client
+        .post(format!("{}/redis/bitmap/{}/bits", base_url, key))
+        .json(&json!({"bits": [[0, true], [3, true], [9, true]]}))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+
+    // Read a single bit
+    let res = client
+        .get(format!("{}/redis/bitmap/{}/3", base_url, key))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let bit: bool = response_data(res).await.unwrap();
+    assert!(bit);
+
+    // Count set bits
+    let res = client
+        .get(format!("{}/redis/bitmap/{}/count", base_url, key))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let count: u64 = response_data(res).await.unwrap();
+    assert_eq!(count, 3);
+
+    // Read the first byte (bits 0 and 3 set)
+    let res = client
+        .get(format!(
+            "{}/redis/bitmap/{}/bytes?offset=0&length=1",
+            base_url, key
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let bytes: Vec<u8> = response_data(res).await.unwrap();
+    assert_eq!(bytes, vec![0b1001_0000]);
+}
+
+#[tokio::test]
+async fn test_bitmap_bitop() {
+    let base_url = get_test_base_url().await;
+    let client = Client::new();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let day1 = format!("test_bitmap_day1_{}", timestamp);
+    let day2 = format!("test_bitmap_day2_{}", timestamp);
+    let dest = format!("test_bitmap_both_{}", timestamp);
+
+    for (key, offset) in [(&day1, 1), (&day1, 2), (&day2, 2)] {
+        let _ = client
+            .post(format!("{}/redis/bitmap/{}/{}", base_url, key, offset))
+            .json(&json!({"value": true}))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    // AND the two days together
+    let res = client
+        .post(format!("{}/redis/bitmap/op", base_url))
+        .json(&json!({"operation": "and", "destination": dest, "keys": [day1, day2]}))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+
+    let res = client
+        .get(format!("{}/redis/bitmap/{}/count", base_url, dest))
+        .send()
+        .await
+        .unwrap();
+    let count: u64 = response_data(res).await.unwrap();
+    assert_eq!(count, 1);
+
+    // NOT only accepts a single source key
+    let res = client
+        .post(format!("{}/redis/bitmap/op", base_url))
+        .json(&json!({"operation": "not", "destination": dest, "keys": [day1, day2]}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::BAD_REQUEST);
+}
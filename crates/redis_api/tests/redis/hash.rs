@@ -1,3 +1,4 @@
+use super::super::common::response_data;
 use super::super::get_test_base_url;
 use reqwest::Client;
 use serde_json::json;
@@ -17,7 +18,7 @@ async fn test_hash_set_and_get() {
 
     // Set hash field
     let res = client
-        .post(&format!("{}/redis/hash/{}/{}", base_url, key, field))
+        .post(format!("{}/redis/hash/{}/{}", base_url, key, field))
         .json(&json!({"value": value}))
         .send()
         .await
@@ -26,12 +27,12 @@ async fn test_hash_set_and_get() {
 
     // Get hash field
     let res = client
-        .get(&format!("{}/redis/hash/{}/{}", base_url, key, field))
+        .get(format!("{}/redis/hash/{}/{}", base_url, key, field))
         .send()
         .await
         .unwrap();
     assert!(res.status().is_success());
-    let got: Option<String> = res.json().await.unwrap();
+    let got: Option<String> = response_data(res).await.unwrap();
     assert_eq!(got, Some(value.to_string()));
 }
 
@@ -49,7 +50,7 @@ async fn test_hash_delete() {
 
     // Set hash field
     let _ = client
-        .post(&format!("{}/redis/hash/{}/{}", base_url, key, field))
+        .post(format!("{}/redis/hash/{}/{}", base_url, key, field))
         .json(&json!({"value": value}))
         .send()
         .await
@@ -57,11 +58,11 @@ async fn test_hash_delete() {
 
     // Delete hash field
     let res = client
-        .delete(&format!("{}/redis/hash/{}/{}", base_url, key, field))
+        .delete(format!("{}/redis/hash/{}/{}", base_url, key, field))
         .send()
         .await
         .unwrap();
     assert!(res.status().is_success());
-    let deleted: bool = res.json().await.unwrap();
+    let deleted: bool = response_data(res).await.unwrap();
     assert!(deleted);
 }
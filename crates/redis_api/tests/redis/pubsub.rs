@@ -0,0 +1,26 @@
+use super::super::common::response_data;
+use super::super::get_test_base_url;
+use reqwest::Client;
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[tokio::test]
+async fn test_publish_without_subscribers() {
+    let base_url = get_test_base_url().await;
+    let client = Client::new();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let channel = format!("test_channel_{}", timestamp);
+
+    let res = client
+        .post(format!("{}/redis/pubsub/{}/publish", base_url, channel))
+        .json(&json!({"message": "hello"}))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let receivers: usize = response_data(res).await.unwrap();
+    assert_eq!(receivers, 0);
+}
@@ -0,0 +1,97 @@
+use super::super::common::response_data;
+use super::super::get_redis_test_base_url;
+use reqwest::Client;
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[tokio::test]
+async fn test_builtin_script_list_and_eval() {
+    let Some(base_url) = get_redis_test_base_url().await else {
+        return;
+    };
+    let client = Client::new();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let key = format!("test_script_cas_{}", timestamp);
+
+    // Built-in scripts are listed
+    let res = client
+        .get(format!("{}/redis/script", base_url))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let scripts: Vec<serde_json::Value> = response_data(res).await.unwrap();
+    assert!(scripts
+        .iter()
+        .any(|s| s["name"] == "compare_and_set_with_ttl" && s["builtin"] == true));
+
+    // Call one by name
+    let _ = client
+        .post(format!("{}/redis/string/{}", base_url, key))
+        .json(&json!({"value": "old"}))
+        .send()
+        .await
+        .unwrap();
+    let res = client
+        .post(format!(
+            "{}/redis/script/compare_and_set_with_ttl/eval",
+            base_url
+        ))
+        .json(&json!({"keys": [key], "args": ["old", "new", "60"]}))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let result: serde_json::Value = response_data(res).await.unwrap();
+    assert_eq!(result, json!(1));
+
+    // Unknown scripts are rejected
+    let res = client
+        .post(format!("{}/redis/script/does_not_exist/eval", base_url))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_register_custom_script() {
+    let Some(base_url) = get_redis_test_base_url().await else {
+        return;
+    };
+    let client = Client::new();
+
+    let res = client
+        .post(format!("{}/redis/script/echo_args", base_url))
+        .json(&json!({"source": "return ARGV", "description": "Echo arguments"}))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let info: serde_json::Value = response_data(res).await.unwrap();
+    assert_eq!(info["builtin"], false);
+    assert_eq!(info["sha"].as_str().unwrap().len(), 40);
+
+    let res = client
+        .post(format!("{}/redis/script/echo_args/eval", base_url))
+        .json(&json!({"args": ["a", "b"]}))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let result: serde_json::Value = response_data(res).await.unwrap();
+    assert_eq!(result, json!(["a", "b"]));
+
+    // Built-in names cannot be overridden
+    let res = client
+        .post(format!("{}/redis/script/rate_limiter", base_url))
+        .json(&json!({"source": "return 1"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::CONFLICT);
+}
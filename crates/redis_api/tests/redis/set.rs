@@ -1,3 +1,4 @@
+use super::super::common::response_data;
 use super::super::get_test_base_url;
 use reqwest::Client;
 use serde_json::json;
@@ -16,23 +17,23 @@ async fn test_set_add_and_members() {
 
     // Add member to set
     let res = client
-        .post(&format!("{}/redis/set/{}", base_url, key))
+        .post(format!("{}/redis/set/{}", base_url, key))
         .json(&json!({"member": member}))
         .send()
         .await
         .unwrap();
     assert!(res.status().is_success());
-    let added: usize = res.json().await.unwrap();
+    let added: usize = response_data(res).await.unwrap();
     assert!(added >= 1);
 
     // Get set members
     let res = client
-        .get(&format!("{}/redis/set/{}/members", base_url, key))
+        .get(format!("{}/redis/set/{}/members", base_url, key))
         .send()
         .await
         .unwrap();
     assert!(res.status().is_success());
-    let members: Vec<String> = res.json().await.unwrap();
+    let members: Vec<String> = response_data(res).await.unwrap();
     assert!(members.contains(&member.to_string()));
 }
 
@@ -49,7 +50,7 @@ async fn test_set_remove() {
 
     // Add member to set
     let _ = client
-        .post(&format!("{}/redis/set/{}", base_url, key))
+        .post(format!("{}/redis/set/{}", base_url, key))
         .json(&json!({"member": member}))
         .send()
         .await
@@ -57,11 +58,11 @@ async fn test_set_remove() {
 
     // Remove member from set
     let res = client
-        .delete(&format!("{}/redis/set/{}/{}", base_url, key, member))
+        .delete(format!("{}/redis/set/{}/{}", base_url, key, member))
         .send()
         .await
         .unwrap();
     assert!(res.status().is_success());
-    let removed: usize = res.json().await.unwrap();
+    let removed: usize = response_data(res).await.unwrap();
     assert!(removed >= 1);
 }
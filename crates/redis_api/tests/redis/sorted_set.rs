@@ -0,0 +1,95 @@
+use super::super::common::response_data;
+use super::super::get_test_base_url;
+use reqwest::Client;
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[tokio::test]
+async fn test_zset_add_and_range() {
+    let base_url = get_test_base_url().await;
+    let client = Client::new();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let key = format!("test_zset_key_{}", timestamp);
+
+    // Add members with scores
+    let res = client
+        .post(format!("{}/redis/zset/{}/many", base_url, key))
+        .json(&json!({"members": [
+            {"member": "alice", "score": 30.0},
+            {"member": "bob", "score": 10.0},
+            {"member": "carol", "score": 20.0}
+        ]}))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let added: usize = response_data(res).await.unwrap();
+    assert_eq!(added, 3);
+
+    // Get leaderboard (highest score first)
+    let res = client
+        .get(format!("{}/redis/zset/{}/range?rev=true", base_url, key))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let members: Vec<serde_json::Value> = response_data(res).await.unwrap();
+    let names: Vec<&str> = members
+        .iter()
+        .map(|m| m["member"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["alice", "carol", "bob"]);
+
+    // Get reverse rank of a member
+    let res = client
+        .get(format!("{}/redis/zset/{}/bob/rank?rev=true", base_url, key))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let rank: Option<usize> = response_data(res).await.unwrap();
+    assert_eq!(rank, Some(2));
+}
+
+#[tokio::test]
+async fn test_zset_increment_and_score() {
+    let base_url = get_test_base_url().await;
+    let client = Client::new();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let key = format!("test_zset_key_incr_{}", timestamp);
+
+    // Add member
+    let _ = client
+        .post(format!("{}/redis/zset/{}", base_url, key))
+        .json(&json!({"member": "player1", "score": 5.0}))
+        .send()
+        .await
+        .unwrap();
+
+    // Increment score
+    let res = client
+        .post(format!("{}/redis/zset/{}/player1/increment", base_url, key))
+        .json(&json!({"increment": 2.5}))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let score: f64 = response_data(res).await.unwrap();
+    assert_eq!(score, 7.5);
+
+    // Read score back
+    let res = client
+        .get(format!("{}/redis/zset/{}/player1/score", base_url, key))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let score: Option<f64> = response_data(res).await.unwrap();
+    assert_eq!(score, Some(7.5));
+}
@@ -1,7 +1,7 @@
 use crate::common::{
-    assert_status_method_not_allowed, assert_status_not_found, assert_status_ok,
-    create_http_client, delete_string, generate_large_value, generate_special_chars_value,
-    generate_test_key, generate_test_value, get_string, set_string, TestContext,
+    assert_status_ok, create_http_client, delete_string, generate_large_value,
+    generate_special_chars_value, generate_test_key, generate_test_value, get_string,
+    response_data, set_string, TestContext,
 };
 use crate::get_test_base_url;
 use serde_json::json;
@@ -205,7 +205,7 @@ async fn test_string_operations_with_ttl() {
     // Set string with TTL
     let res = ctx
         .client
-        .post(&format!("{}/redis/string/{}", ctx.base_url, test_key))
+        .post(format!("{}/redis/string/{}", ctx.base_url, test_key))
         .json(&json!({
             "value": test_value,
             "ttl": 1 // 1 second TTL
@@ -254,7 +254,7 @@ async fn test_batch_string_operations() {
 
     let res = ctx
         .client
-        .post(&format!("{}/redis/string/batch/set", ctx.base_url))
+        .post(format!("{}/redis/string/batch/set", ctx.base_url))
         .json(&json!({"operations": batch_ops}))
         .send()
         .await
@@ -266,14 +266,14 @@ async fn test_batch_string_operations() {
     let keys: Vec<String> = operations.iter().map(|(key, _)| key.to_string()).collect();
     let res = ctx
         .client
-        .post(&format!("{}/redis/string/batch/get", ctx.base_url))
+        .post(format!("{}/redis/string/batch/get", ctx.base_url))
         .json(&json!({"keys": keys}))
         .send()
         .await
         .unwrap();
 
     assert_status_ok(res.status().as_u16());
-    let values: Vec<Option<String>> = res.json().await.unwrap();
+    let values: Vec<Option<String>> = response_data(res).await.unwrap();
 
     // Verify all values
     for (i, (_, expected_value)) in operations.iter().enumerate() {
@@ -291,7 +291,7 @@ async fn test_string_error_handling() {
     // Try to get string with invalid key format
     let res = ctx
         .client
-        .get(&format!("{}/redis/string/{}", ctx.base_url, invalid_key))
+        .get(format!("{}/redis/string/{}", ctx.base_url, invalid_key))
         .send()
         .await
         .unwrap();
@@ -300,12 +300,94 @@ async fn test_string_error_handling() {
     assert!(res.status().is_client_error() || res.status().is_success());
 }
 
+#[tokio::test]
+async fn test_string_error_codes() {
+    let mut ctx = TestContext::new(get_test_base_url().await);
+    let test_key = generate_test_key("error_codes", None);
+    ctx.add_test_key(test_key.clone());
+
+    set_string(&ctx.client, &ctx.base_url, &test_key, "value")
+        .await
+        .unwrap();
+
+    // Reading a string as a hash is the caller's mistake, not a server fault
+    let res = ctx
+        .client
+        .get(format!("{}/redis/hash/{}/field", ctx.base_url, test_key))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 409);
+    let body: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(body["success"], false);
+    assert_eq!(body["code"], "WRONG_TYPE");
+    assert!(body["error"].as_str().unwrap().contains("WRONGTYPE"));
+
+    // Redis rejects a zero TTL
+    let res = ctx
+        .client
+        .post(format!("{}/redis/string/{}", ctx.base_url, test_key))
+        .json(&json!({"value": "value", "ttl": 0}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 400);
+    let body: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(body["code"], "INVALID_ARGUMENT");
+
+    ctx.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_string_response_envelope() {
+    let mut ctx = TestContext::new(get_test_base_url().await);
+    let test_key = generate_test_key("envelope", None);
+    ctx.add_test_key(test_key.clone());
+
+    set_string(&ctx.client, &ctx.base_url, &test_key, "value")
+        .await
+        .unwrap();
+
+    let res = ctx
+        .client
+        .get(format!("{}/redis/string/{}", ctx.base_url, test_key))
+        .send()
+        .await
+        .unwrap();
+    assert_status_ok(res.status().as_u16());
+    let request_id = res.headers()["x-request-id"].to_str().unwrap().to_string();
+    let body: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(body["version"], 1);
+    assert_eq!(body["success"], true);
+    assert_eq!(body["data"], "value");
+    assert!(body["error"].is_null());
+    assert!(body["code"].is_null());
+    assert_eq!(body["request_id"], request_id);
+    assert!(body["duration_ms"].as_f64().unwrap() >= 0.0);
+
+    // Errors carry the same envelope
+    let res = ctx
+        .client
+        .put(format!("{}/redis/string/{}", ctx.base_url, test_key))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 405);
+    let body: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(body["version"], 1);
+    assert_eq!(body["success"], false);
+    assert_eq!(body["code"], "METHOD_NOT_ALLOWED");
+    assert!(body["request_id"].is_string());
+
+    ctx.cleanup().await;
+}
+
 #[tokio::test]
 async fn test_batch_get_patterns() {
     let mut ctx = TestContext::new(get_test_base_url().await);
 
     // Create test keys with patterns
-    let test_keys = vec![
+    let test_keys = [
         "tokenBalance:0x123:ethereum:100",
         "tokenBalance:0x123:ethereum:200",
         "tokenBalancePending:0x123:ethereum:50",
@@ -313,7 +395,7 @@ async fn test_batch_get_patterns() {
         "otherKey:0x456:ethereum:300",
     ];
 
-    let test_values = vec!["100.5", "200.0", "50.25", "75.75", "300.0"];
+    let test_values = ["100.5", "200.0", "50.25", "75.75", "300.0"];
 
     // Set all test keys
     for (key, value) in test_keys.iter().zip(test_values.iter()) {
@@ -326,7 +408,7 @@ async fn test_batch_get_patterns() {
     // Test flat pattern matching
     let res = ctx
         .client
-        .post(&format!("{}/redis/string/batch/patterns", ctx.base_url))
+        .post(format!("{}/redis/string/batch/patterns", ctx.base_url))
         .json(&json!({
             "patterns": [
                 "tokenBalance:0x123:ethereum:*",
@@ -339,7 +421,7 @@ async fn test_batch_get_patterns() {
         .unwrap();
 
     assert_status_ok(res.status().as_u16());
-    let result: serde_json::Value = res.json().await.unwrap();
+    let result: serde_json::Value = response_data(res).await.unwrap();
 
     assert_eq!(result["grouped"], false);
     let results = result["results"].as_object().unwrap();
@@ -354,7 +436,7 @@ async fn test_batch_get_patterns() {
     // Test grouped pattern matching
     let res = ctx
         .client
-        .post(&format!("{}/redis/string/batch/patterns", ctx.base_url))
+        .post(format!("{}/redis/string/batch/patterns", ctx.base_url))
         .json(&json!({
             "patterns": [
                 "tokenBalance:0x123:ethereum:*",
@@ -367,7 +449,7 @@ async fn test_batch_get_patterns() {
         .unwrap();
 
     assert_status_ok(res.status().as_u16());
-    let result: serde_json::Value = res.json().await.unwrap();
+    let result: serde_json::Value = response_data(res).await.unwrap();
 
     assert_eq!(result["grouped"], true);
     let results = result["results"].as_array().unwrap();
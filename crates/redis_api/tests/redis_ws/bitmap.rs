@@ -0,0 +1,33 @@
+use super::super::get_test_ws_base_url;
+use futures::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio_tungstenite::connect_async;
+
+#[tokio::test]
+async fn test_ws_bitmap_setbit_and_count() {
+    let base_url = get_test_ws_base_url().await.replace("http", "ws");
+    let url = format!("{}/redis_ws/bitmap/ws", base_url);
+    let (mut ws, _) = connect_async(url).await.expect("Failed to connect");
+    let key = "ws_test_bitmap_key";
+
+    // Set a bit
+    let set_msg =
+        json!({"type": "setbit", "data": {"key": key, "offset": 7, "value": true}}).to_string();
+    ws.send(tokio_tungstenite::tungstenite::Message::Text(set_msg))
+        .await
+        .unwrap();
+    let _ = ws.next().await;
+
+    // Get the bit back
+    let get_msg = json!({"type": "getbit", "data": {"key": key, "offset": 7}}).to_string();
+    ws.send(tokio_tungstenite::tungstenite::Message::Text(get_msg))
+        .await
+        .unwrap();
+    if let Some(Ok(tokio_tungstenite::tungstenite::Message::Text(resp))) = ws.next().await {
+        let v: serde_json::Value = serde_json::from_str(&resp).unwrap();
+        assert_eq!(v["type"], "result");
+        assert_eq!(v["data"]["value"], true);
+    } else {
+        panic!("No response from ws");
+    }
+}
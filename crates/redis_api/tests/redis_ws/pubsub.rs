@@ -0,0 +1,53 @@
+use super::super::get_test_ws_base_url;
+use crate::common::response_data;
+use futures::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+async fn next_json<S>(ws: &mut S) -> serde_json::Value
+where
+    S: StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+{
+    match tokio::time::timeout(std::time::Duration::from_secs(5), ws.next()).await {
+        Ok(Some(Ok(Message::Text(resp)))) => serde_json::from_str(&resp).unwrap(),
+        other => panic!("No response from ws: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_ws_pubsub_shared_subscription() {
+    let base_url = get_test_ws_base_url().await;
+    let ws_url = format!("{}/redis_ws/pubsub/ws", base_url.replace("http", "ws"));
+    let channel = "ws_test_pubsub_channel";
+
+    // Two sockets subscribe to the same channel
+    let (mut first, _) = connect_async(&ws_url).await.expect("Failed to connect");
+    let (mut second, _) = connect_async(&ws_url).await.expect("Failed to connect");
+    let subscribe = json!({"type": "subscribe", "data": {"channels": [channel]}}).to_string();
+    for ws in [&mut first, &mut second] {
+        ws.send(Message::Text(subscribe.clone())).await.unwrap();
+        let v = next_json(ws).await;
+        assert_eq!(v["type"], "subscriptions");
+        assert_eq!(v["data"]["channels"], json!([channel]));
+    }
+
+    // Publishing over HTTP reaches the single shared Redis subscriber
+    let res = reqwest::Client::new()
+        .post(format!("{}/redis/pubsub/{}/publish", base_url, channel))
+        .json(&json!({"message": "hello"}))
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+    let receivers: usize = response_data(res).await.unwrap();
+    assert_eq!(receivers, 1);
+
+    // Both sockets get the message pushed
+    for ws in [&mut first, &mut second] {
+        let v = next_json(ws).await;
+        assert_eq!(v["type"], "message");
+        assert_eq!(v["data"]["channel"], channel);
+        assert_eq!(v["data"]["payload"], "hello");
+    }
+}
@@ -0,0 +1,44 @@
+use super::super::get_redis_test_base_url;
+use futures::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio_tungstenite::connect_async;
+
+#[tokio::test]
+async fn test_ws_script_register_and_eval() {
+    let Some(base_url) = get_redis_test_base_url().await else {
+        return;
+    };
+    let base_url = base_url.replace("http", "ws");
+    let url = format!("{}/redis_ws/script/ws", base_url);
+    let (mut ws, _) = connect_async(url).await.expect("Failed to connect");
+
+    // Register a custom script
+    let register_msg = json!({
+        "type": "register",
+        "data": {"name": "ws_sum", "source": "return tonumber(ARGV[1]) + tonumber(ARGV[2])"}
+    })
+    .to_string();
+    ws.send(tokio_tungstenite::tungstenite::Message::Text(register_msg))
+        .await
+        .unwrap();
+    if let Some(Ok(tokio_tungstenite::tungstenite::Message::Text(resp))) = ws.next().await {
+        let v: serde_json::Value = serde_json::from_str(&resp).unwrap();
+        assert_eq!(v["type"], "registered");
+    } else {
+        panic!("No response from ws");
+    }
+
+    // Run it by name
+    let eval_msg =
+        json!({"type": "eval", "data": {"name": "ws_sum", "args": ["2", "3"]}}).to_string();
+    ws.send(tokio_tungstenite::tungstenite::Message::Text(eval_msg))
+        .await
+        .unwrap();
+    if let Some(Ok(tokio_tungstenite::tungstenite::Message::Text(resp))) = ws.next().await {
+        let v: serde_json::Value = serde_json::from_str(&resp).unwrap();
+        assert_eq!(v["type"], "result");
+        assert_eq!(v["data"]["value"], 5);
+    } else {
+        panic!("No response from ws");
+    }
+}
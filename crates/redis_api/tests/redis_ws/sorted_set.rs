@@ -0,0 +1,39 @@
+use super::super::get_test_ws_base_url;
+use futures::{SinkExt, StreamExt};
+use serde_json::json;
+use tokio_tungstenite::connect_async;
+
+#[tokio::test]
+async fn test_ws_zset_add_and_range() {
+    let base_url = get_test_ws_base_url().await.replace("http", "ws");
+    let url = format!("{}/redis_ws/zset/ws", base_url);
+    let (mut ws, _) = connect_async(url).await.expect("Failed to connect");
+    let key = "ws_test_zset_key";
+    let member = "member1";
+
+    // Add member to sorted set
+    let add_msg =
+        json!({"type": "add", "data": {"key": key, "member": member, "score": 42.0}}).to_string();
+    ws.send(tokio_tungstenite::tungstenite::Message::Text(add_msg))
+        .await
+        .unwrap();
+    let _ = ws.next().await;
+
+    // Get sorted set range
+    let range_msg =
+        json!({"type": "range", "data": {"key": key, "start": 0, "stop": -1}}).to_string();
+    ws.send(tokio_tungstenite::tungstenite::Message::Text(range_msg))
+        .await
+        .unwrap();
+    if let Some(Ok(tokio_tungstenite::tungstenite::Message::Text(resp))) = ws.next().await {
+        let v: serde_json::Value = serde_json::from_str(&resp).unwrap();
+        assert_eq!(v["type"], "range_result");
+        assert!(v["data"]["members"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|m| m["member"] == member && m["score"] == 42.0));
+    } else {
+        panic!("No response from ws");
+    }
+}
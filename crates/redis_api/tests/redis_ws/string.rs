@@ -291,7 +291,7 @@ async fn test_redis_ws_string_info() {
 #[tokio::test]
 async fn test_redis_ws_batch_string_operations() {
     let (mut write, mut read) = connect_to_string_ws().await;
-    let operations = vec![
+    let operations = [
         ("batch_key_1", "batch_value_1"),
         ("batch_key_2", "batch_value_2"),
         ("batch_key_3", "batch_value_3"),
@@ -412,3 +412,28 @@ async fn test_redis_ws_ping_pong() {
 mod tests {
     // Empty for now - WebSocket tests will be implemented later
 }
+
+#[tokio::test]
+async fn test_redis_ws_error_codes() {
+    let (mut write, mut read) = connect_to_string_ws().await;
+
+    // Redis rejects a zero TTL
+    let set_message = json!({
+        "type": "set",
+        "data": {
+            "key": "ws_test_error_codes",
+            "value": "value",
+            "ttl": 0
+        }
+    });
+    let response = send_message_and_get_response(&mut write, &mut read, set_message).await;
+    assert_eq!(response["type"], "error");
+    assert_eq!(response["data"]["code"], "INVALID_ARGUMENT");
+    assert!(response["data"]["message"].is_string());
+
+    // Messages that don't parse are answered too
+    let unknown = json!({"type": "no_such_type"});
+    let response = send_message_and_get_response(&mut write, &mut read, unknown).await;
+    assert_eq!(response["type"], "error");
+    assert_eq!(response["data"]["code"], "INVALID_ARGUMENT");
+}
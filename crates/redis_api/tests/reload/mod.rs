@@ -0,0 +1,170 @@
+use crate::{common::response_data, test_config, TestServer};
+use dbx_redis_api::{config::ConfigLoader, server::Server};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+const INITIAL: &str = r#"
+database_url = "memory://"
+
+[auth]
+api_keys = ["old-key"]
+"#;
+
+struct ConfigFile(PathBuf);
+
+impl ConfigFile {
+    fn new(content: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("dbx-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, content).unwrap();
+        Self(path)
+    }
+
+    fn write(&self, content: &str) {
+        std::fs::write(&self.0, content).unwrap();
+    }
+}
+
+impl Drop for ConfigFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Start a server that reloads from `file` and nothing else
+async fn start_server(file: &ConfigFile) -> TestServer {
+    let loader = ConfigLoader::default().file(&file.0);
+    let server = Server::new(loader.load().unwrap())
+        .await
+        .expect("Failed to create test server")
+        .with_reload(loader);
+    let server = TestServer::with_server(server).expect("Failed to create test server");
+    server.start().await.expect("Failed to start test server");
+    server
+}
+
+async fn reload(server: &TestServer, key: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("http://{}/redis/admin/reload", server.addr))
+        .bearer_auth(key)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_reload_applies_changes() {
+    let file = ConfigFile::new(INITIAL);
+    let server = start_server(&file).await;
+    let client = reqwest::Client::new();
+    let base_url = format!("http://{}", server.addr);
+
+    let res = client
+        .get(format!("{base_url}/redis/admin/reload"))
+        .bearer_auth("old-key")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+    let status: Value = response_data(res).await.unwrap();
+    assert_eq!(status["enabled"], true);
+    assert_eq!(status["reloads"], 0);
+
+    file.write(
+        r#"
+database_url = "memory://"
+port = 4000
+read_only = true
+
+[auth]
+api_keys = ["new-key"]
+
+[cors]
+allowed_origins = ["https://app.example.com"]
+"#,
+    );
+    let res = reload(&server, "old-key").await;
+    assert_eq!(res.status().as_u16(), 200);
+    let report: Value = response_data(res).await.unwrap();
+    assert_eq!(report["trigger"], "api");
+    assert_eq!(report["success"], true);
+    assert_eq!(report["changed"], json!(["auth", "cors", "read_only"]));
+    assert_eq!(report["restart_required"], json!(["port"]));
+
+    // The listener is untouched, but the old key is gone
+    let url = format!("{base_url}/redis/string/reload_key");
+    let res = client
+        .get(&url)
+        .bearer_auth("old-key")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 401);
+    let res = client
+        .get(&url)
+        .bearer_auth("new-key")
+        .header("origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+    assert_eq!(
+        res.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+
+    let res = client
+        .post(&url)
+        .bearer_auth("new-key")
+        .json(&json!({ "value": "v" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 403);
+    let body: Value = res.json().await.unwrap();
+    assert_eq!(body["error"], "Server is in read-only mode");
+
+    let res = client
+        .get(format!("{base_url}/redis/admin/reload"))
+        .bearer_auth("new-key")
+        .send()
+        .await
+        .unwrap();
+    let status: Value = response_data(res).await.unwrap();
+    assert_eq!(status["reloads"], 1);
+    assert_eq!(status["last"]["changed"], report["changed"]);
+}
+
+#[tokio::test]
+async fn test_invalid_reload_keeps_config() {
+    let file = ConfigFile::new(INITIAL);
+    let server = start_server(&file).await;
+
+    file.write("database_url = \"memory://\"\npool_size = 0\n");
+    let res = reload(&server, "old-key").await;
+    assert_eq!(res.status().as_u16(), 200);
+    let report: Value = response_data(res).await.unwrap();
+    assert_eq!(report["success"], false);
+    assert!(report["error"].as_str().unwrap().contains("pool_size"));
+    assert_eq!(report["changed"], json!([]));
+
+    // Still authenticating with the original keys
+    let res = reqwest::Client::new()
+        .get(format!("http://{}/redis/admin/ping", server.addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn test_reload_not_enabled() {
+    let server = TestServer::with_config(test_config())
+        .await
+        .expect("Failed to create test server");
+    server.start().await.expect("Failed to start test server");
+
+    let res = reload(&server, "unused").await;
+    assert_eq!(res.status().as_u16(), 409);
+    let body: Value = res.json().await.unwrap();
+    assert_eq!(body["code"], "CONFLICT");
+}
@@ -0,0 +1,102 @@
+use crate::{test_config, TestServer};
+use dbx_redis_api::config::AuthConfig;
+use dbx_redis_api::server::Server;
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::protocol::{frame::coding::CloseCode, Message},
+};
+
+#[tokio::test]
+async fn test_health_not_ready_while_draining() {
+    let server = TestServer::with_config(test_config())
+        .await
+        .expect("Failed to create test server");
+    server.start().await.expect("Failed to start test server");
+    let url = format!("http://{}/redis/admin/health", server.addr);
+
+    let res = reqwest::get(&url).await.unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+
+    server.server.shutdown_handle().begin();
+    let res = reqwest::get(&url).await.unwrap();
+    assert_eq!(res.status().as_u16(), 503);
+    let body: Value = res.json().await.unwrap();
+    assert_eq!(body["code"], "UNAVAILABLE");
+    assert_eq!(body["error"], "Server is shutting down");
+}
+
+#[tokio::test]
+async fn test_readiness_needs_no_credentials() {
+    let mut config = test_config();
+    config.auth = AuthConfig {
+        api_keys: vec!["test-api-key".to_string()],
+        ..AuthConfig::default()
+    };
+    let server = TestServer::with_config(config)
+        .await
+        .expect("Failed to create test server");
+    server.start().await.expect("Failed to start test server");
+    let url = format!("http://{}/health/ready", server.addr);
+
+    let res = reqwest::get(&url).await.unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+    let res = reqwest::get(format!("http://{}/redis/admin/health", server.addr))
+        .await
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 401);
+
+    server.server.shutdown_handle().begin();
+    let res = reqwest::get(&url).await.unwrap();
+    assert_eq!(res.status().as_u16(), 503);
+    let body: Value = res.json().await.unwrap();
+    assert_eq!(body["code"], "UNAVAILABLE");
+}
+
+#[tokio::test]
+async fn test_graceful_shutdown_closes_websockets() {
+    let mut config = test_config();
+    config.shutdown_timeout_secs = 5;
+    let server = Server::new(config)
+        .await
+        .expect("Failed to create test server");
+    let shutdown = server.shutdown_handle();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let serving = tokio::spawn(server.serve(listener));
+
+    let (ws, _) = connect_async(format!("ws://{addr}/redis_ws/string/ws"))
+        .await
+        .expect("Failed to connect");
+    let (mut write, mut read) = ws.split();
+    write
+        .send(Message::Text(json!({ "type": "ping" }).to_string()))
+        .await
+        .unwrap();
+    let pong = read.next().await.unwrap().unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(pong.to_text().unwrap()).unwrap()["type"],
+        "pong"
+    );
+
+    shutdown.begin();
+    let close = tokio::time::timeout(Duration::from_secs(5), read.next())
+        .await
+        .expect("Socket not closed")
+        .unwrap()
+        .unwrap();
+    let Message::Close(Some(frame)) = close else {
+        panic!("Expected a close frame, got {close:?}");
+    };
+    assert_eq!(frame.code, CloseCode::Away);
+    assert_eq!(frame.reason, "Server is shutting down");
+
+    tokio::time::timeout(Duration::from_secs(5), serving)
+        .await
+        .expect("Server did not stop")
+        .unwrap()
+        .expect("Server failed");
+    assert!(tokio::net::TcpStream::connect(addr).await.is_err());
+}
@@ -0,0 +1,89 @@
+use crate::get_test_server;
+use axum::{body::Bytes, extract::State, routing::post, Router};
+use dbx_redis_api::{config::TelemetryConfig, telemetry::Telemetry};
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::prelude::*;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+/// Stands in for an OTLP collector, keeping every export request it receives
+async fn start_collector() -> (String, Arc<Mutex<Vec<Bytes>>>) {
+    let exports = Arc::new(Mutex::new(Vec::new()));
+    let app = Router::new()
+        .route(
+            "/v1/traces",
+            post(
+                |State(exports): State<Arc<Mutex<Vec<Bytes>>>>, body: Bytes| async move {
+                    exports.lock().unwrap().push(body);
+                },
+            ),
+        )
+        .with_state(exports.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (endpoint, exports)
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[tokio::test]
+async fn test_spans_exported_to_collector() {
+    let (endpoint, exports) = start_collector().await;
+    let telemetry = Telemetry::from_config(&TelemetryConfig {
+        otlp_endpoint: Some(endpoint),
+        ..TelemetryConfig::default()
+    })
+    .unwrap()
+    .unwrap();
+    // The test runtime is single-threaded, so the server's spans are all
+    // recorded by this thread's subscriber
+    let _subscriber = tracing_subscriber::registry()
+        .with(telemetry.layer())
+        .set_default();
+
+    let server = get_test_server().await;
+    let res = reqwest::Client::new()
+        .get(format!("http://{}/redis/string/telemetry", server.addr))
+        .header(
+            "traceparent",
+            format!("00-{}-00f067aa0ba902b7-01", TRACE_ID),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert!(res.status().is_success());
+
+    // Exporting blocks, and the collector needs this thread to answer
+    tokio::task::spawn_blocking(move || telemetry.flush())
+        .await
+        .unwrap()
+        .unwrap();
+
+    // OTLP/HTTP bodies are protobuf, which stores strings and IDs as raw bytes
+    let exports = exports.lock().unwrap().concat();
+    let trace_id: Vec<u8> = (0..TRACE_ID.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&TRACE_ID[i..i + 2], 16).unwrap())
+        .collect();
+    assert!(
+        contains(&exports, &trace_id),
+        "request did not join the trace"
+    );
+    for expected in [
+        "dbx-redis-api",
+        "GET /redis/string/:key",
+        "redis",
+        "command",
+        "key_count",
+    ] {
+        assert!(
+            contains(&exports, expected.as_bytes()),
+            "missing {expected} in export"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::test_config;
+use dbx_redis_api::config::Config;
+use dbx_redis_api::server::Server;
+use futures::{SinkExt, StreamExt};
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType,
+    ExtendedKeyUsagePurpose, IsCa, SanType,
+};
+use serde_json::{json, Value};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use tokio_tungstenite::{connect_async_tls_with_config, tungstenite::Message, Connector};
+
+/// A throwaway CA, with a server certificate and a client certificate it signed
+struct Certs {
+    dir: PathBuf,
+    ca: String,
+    client_cert: String,
+    client_key: String,
+}
+
+impl Certs {
+    fn generate() -> Self {
+        let mut ca_params = CertificateParams::new(Vec::new());
+        ca_params.distinguished_name = common_name("dbx test CA");
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = Certificate::from_params(ca_params).unwrap();
+
+        let mut server_params = CertificateParams::new(vec!["localhost".into()]);
+        server_params
+            .subject_alt_names
+            .push(SanType::IpAddress(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        server_params.distinguished_name = common_name("localhost");
+        server_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        let server = Certificate::from_params(server_params).unwrap();
+
+        let mut client_params = CertificateParams::new(vec!["client".into()]);
+        client_params.distinguished_name = common_name("client");
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client = Certificate::from_params(client_params).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("dbx-tls-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let certs = Self {
+            ca: ca.serialize_pem().unwrap(),
+            client_cert: client.serialize_pem_with_signer(&ca).unwrap(),
+            client_key: client.serialize_private_key_pem(),
+            dir,
+        };
+        std::fs::write(certs.path("ca.pem"), &certs.ca).unwrap();
+        std::fs::write(
+            certs.path("server.pem"),
+            server.serialize_pem_with_signer(&ca).unwrap(),
+        )
+        .unwrap();
+        std::fs::write(certs.path("server.key"), server.serialize_private_key_pem()).unwrap();
+        certs
+    }
+
+    fn path(&self, name: &str) -> String {
+        self.dir.join(name).to_string_lossy().into_owned()
+    }
+
+    fn config(&self, mutual: bool) -> Config {
+        let mut config = test_config();
+        config.tls.cert_path = Some(self.path("server.pem"));
+        config.tls.key_path = Some(self.path("server.key"));
+        if mutual {
+            config.tls.client_ca_path = Some(self.path("ca.pem"));
+        }
+        config
+    }
+
+    fn client(&self, identity: bool) -> reqwest::Client {
+        let mut builder = reqwest::Client::builder()
+            .add_root_certificate(reqwest::Certificate::from_pem(self.ca.as_bytes()).unwrap());
+        if identity {
+            builder = builder.identity(
+                reqwest::Identity::from_pkcs8_pem(
+                    self.client_cert.as_bytes(),
+                    self.client_key.as_bytes(),
+                )
+                .unwrap(),
+            );
+        }
+        builder.build().unwrap()
+    }
+}
+
+/// Distinct subjects, so no certificate is mistaken for self-signed
+fn common_name(name: &str) -> DistinguishedName {
+    let mut distinguished_name = DistinguishedName::new();
+    distinguished_name.push(DnType::CommonName, name);
+    distinguished_name
+}
+
+impl Drop for Certs {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+async fn serve(config: Config) -> SocketAddr {
+    let server = Server::new(config)
+        .await
+        .expect("Failed to create test server");
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(server.serve(listener));
+    addr
+}
+
+#[tokio::test]
+async fn test_https() {
+    let certs = Certs::generate();
+    let addr = serve(certs.config(false)).await;
+    let url = format!("https://localhost:{}/redis/admin/ping", addr.port());
+
+    let res = certs.client(false).get(&url).send().await.unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+
+    // Plain HTTP is not spoken on a TLS port
+    let plain = format!("http://{addr}/redis/admin/ping");
+    assert!(reqwest::get(&plain).await.is_err());
+}
+
+#[tokio::test]
+async fn test_wss() {
+    let certs = Certs::generate();
+    let addr = serve(certs.config(false)).await;
+
+    let connector = native_tls::TlsConnector::builder()
+        .add_root_certificate(native_tls::Certificate::from_pem(certs.ca.as_bytes()).unwrap())
+        .build()
+        .unwrap();
+    let (ws, _) = connect_async_tls_with_config(
+        format!("wss://localhost:{}/redis_ws/string/ws", addr.port()),
+        None,
+        false,
+        Some(Connector::NativeTls(connector)),
+    )
+    .await
+    .expect("Failed to connect");
+    let (mut write, mut read) = ws.split();
+    write
+        .send(Message::Text(json!({ "type": "ping" }).to_string()))
+        .await
+        .unwrap();
+    let pong = read.next().await.unwrap().unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(pong.to_text().unwrap()).unwrap()["type"],
+        "pong"
+    );
+}
+
+#[tokio::test]
+async fn test_mutual_tls() {
+    let certs = Certs::generate();
+    let addr = serve(certs.config(true)).await;
+    let url = format!("https://localhost:{}/redis/admin/ping", addr.port());
+
+    assert!(certs.client(false).get(&url).send().await.is_err());
+
+    let res = certs.client(true).get(&url).send().await.unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+}
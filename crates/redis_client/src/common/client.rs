@@ -21,21 +21,34 @@ pub trait WebSocketClientBase {
 /// Common HTTP response handling utilities
 pub mod http {
     use super::*;
-    use reqwest::Response;
+    use crate::common::types::ApiResponse;
+    use reqwest::{RequestBuilder, Response};
 
-    /// Handle HTTP response and extract JSON data
+    /// Send a request, with the current trace context if `trace-context` is enabled
+    pub async fn send(request: RequestBuilder) -> Result<Response> {
+        #[cfg(feature = "trace-context")]
+        let request = match crate::common::trace_context::traceparent() {
+            Some(traceparent) => request.header(
+                crate::common::trace_context::TRACEPARENT_HEADER,
+                traceparent,
+            ),
+            None => request,
+        };
+        Ok(request.send().await?)
+    }
+
+    /// Handle HTTP response and extract the data from its envelope
     pub async fn handle_response<T>(response: Response, operation: &str) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
         if response.status().is_success() {
-            let data: T = response.json().await?;
-            Ok(data)
+            let body: ApiResponse<serde_json::Value> = response.json().await?;
+            // A `null` result, e.g. a missing key, arrives as no data
+            let data = body.data.unwrap_or(serde_json::Value::Null);
+            Ok(serde_json::from_value(data)?)
         } else {
-            Err(DbxError::Api {
-                status: response.status().as_u16(),
-                message: format!("Failed to {}: HTTP {}", operation, response.status()),
-            })
+            Err(api_error(response, operation).await)
         }
     }
 
@@ -44,10 +57,24 @@ pub mod http {
         if response.status().is_success() {
             Ok(())
         } else {
-            Err(DbxError::Api {
-                status: response.status().as_u16(),
-                message: format!("Failed to {}: HTTP {}", operation, response.status()),
-            })
+            Err(api_error(response, operation).await)
+        }
+    }
+
+    /// Describe a failed response, with the server's error code and message when it sent them
+    async fn api_error(response: Response, operation: &str) -> DbxError {
+        let status = response.status();
+        let (code, detail) = match response.json::<ApiResponse<serde_json::Value>>().await {
+            Ok(body) => (body.code, body.error),
+            Err(_) => (None, None),
+        };
+        DbxError::Api {
+            status: status.as_u16(),
+            code,
+            message: match detail {
+                Some(detail) => format!("Failed to {}: {}", operation, detail),
+                None => format!("Failed to {}: HTTP {}", operation, status),
+            },
         }
     }
 }
@@ -79,12 +106,14 @@ pub mod websocket {
                 }
                 _ => Err(DbxError::Api {
                     status: 0,
+                    code: None,
                     message: "Unexpected WebSocket message type".to_string(),
                 }),
             }
         } else {
             Err(DbxError::Api {
                 status: 0,
+                code: None,
                 message: "No response received from WebSocket".to_string(),
             })
         }
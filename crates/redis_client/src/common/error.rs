@@ -5,7 +5,12 @@ use std::fmt;
 #[derive(Debug)]
 pub enum DbxError {
     /// API returned an error response
-    Api { status: u16, message: String },
+    Api {
+        status: u16,
+        /// Stable error code sent by the server, e.g. `WRONG_TYPE`
+        code: Option<String>,
+        message: String,
+    },
     /// Invalid URL
     InvalidUrl(url::ParseError),
     /// JSON serialization/deserialization error
@@ -17,7 +22,16 @@ pub enum DbxError {
 impl fmt::Display for DbxError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            DbxError::Api { status, message } => write!(f, "API error ({status}): {message}"),
+            DbxError::Api {
+                status,
+                code: Some(code),
+                message,
+            } => write!(f, "API error ({status} {code}): {message}"),
+            DbxError::Api {
+                status,
+                code: None,
+                message,
+            } => write!(f, "API error ({status}): {message}"),
             DbxError::InvalidUrl(e) => write!(f, "Invalid URL: {e}"),
             DbxError::Json(e) => write!(f, "JSON error: {e}"),
             DbxError::Other(e) => write!(f, "Other error: {e}"),
@@ -27,6 +41,16 @@ impl fmt::Display for DbxError {
 
 impl std::error::Error for DbxError {}
 
+impl DbxError {
+    /// Stable error code sent by the server, if this is an API error that had one
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            DbxError::Api { code, .. } => code.as_deref(),
+            _ => None,
+        }
+    }
+}
+
 impl From<url::ParseError> for DbxError {
     fn from(err: url::ParseError) -> Self {
         DbxError::InvalidUrl(err)
@@ -4,6 +4,8 @@ pub mod client;
 pub mod error;
 pub mod set;
 pub mod string;
+#[cfg(feature = "trace-context")]
+pub mod trace_context;
 pub mod types;
 
 use crate::error::Result;
@@ -0,0 +1,60 @@
+//! W3C trace context propagation
+//!
+//! HTTP requests carry the caller's current span as a `traceparent` header,
+//! so the server's spans join the caller's trace. The span is taken from the
+//! current `tracing` span when it is exported through `tracing-opentelemetry`,
+//! otherwise from the current OpenTelemetry context.
+
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry::Context;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Header carrying the trace context
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// The `traceparent` value for the current span, if there is one
+pub fn traceparent() -> Option<String> {
+    let context = tracing::Span::current().context();
+    let context = if context.has_active_span() {
+        context
+    } else {
+        Context::current()
+    };
+
+    let span = context.span();
+    let span_context = span.span_context();
+    span_context.is_valid().then(|| {
+        format!(
+            "00-{}-{}-{:02x}",
+            span_context.trace_id(),
+            span_context.span_id(),
+            span_context.trace_flags().to_u8()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{SpanContext, SpanId, TraceFlags, TraceId, TraceState};
+
+    #[test]
+    fn test_traceparent_from_current_context() {
+        assert_eq!(traceparent(), None);
+
+        let span_context = SpanContext::new(
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+            SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+        let _context = Context::new()
+            .with_remote_span_context(span_context)
+            .attach();
+        assert_eq!(
+            traceparent().as_deref(),
+            Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+        );
+    }
+}
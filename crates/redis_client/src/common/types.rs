@@ -1,11 +1,50 @@
 use serde::{Deserialize, Serialize};
 
-/// Generic API response wrapper
+/// Envelope around every REST response
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ApiResponse<T> {
+    /// Envelope version
+    #[serde(default)]
+    pub version: u32,
     pub success: bool,
     pub data: Option<T>,
     pub error: Option<String>,
+    /// Stable code for the error, e.g. `WRONG_TYPE`
+    #[serde(default)]
+    pub code: Option<String>,
+    /// ID of the request, as echoed in the `X-Request-Id` header
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// Time the server took to serve the request, in milliseconds
+    #[serde(default)]
+    pub duration_ms: Option<f64>,
+}
+
+/// Credentials sent with every request to an API that has authentication enabled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credentials {
+    /// An API key, sent in the `X-API-Key` header
+    ApiKey(String),
+    /// A bearer token (JWT or API key), sent in the `Authorization` header
+    Bearer(String),
+}
+
+impl Credentials {
+    /// Name of the header carrying the credentials
+    pub fn header_name(&self) -> &'static str {
+        match self {
+            Credentials::ApiKey(_) => "X-API-Key",
+            Credentials::Bearer(_) => "Authorization",
+        }
+    }
+
+    /// Value of the header carrying the credentials
+    pub fn header_value(&self) -> String {
+        match self {
+            Credentials::ApiKey(key) => key.clone(),
+            Credentials::Bearer(token) => format!("Bearer {token}"),
+        }
+    }
 }
 
 /// Pattern search results
@@ -11,6 +11,7 @@
 //! - **websocket**: WebSocket client support
 //! - **string**: String operations support (enabled by default)
 //! - **set**: Set operations support (enabled by default)
+//! - **trace-context**: Send the current trace context with HTTP requests
 //!
 //! # Example
 //!
@@ -1,6 +1,9 @@
 //! HTTP client for DBX Redis API
 
 use crate::error::Result;
+use crate::Credentials;
+#[cfg(feature = "http")]
+use reqwest::header::{HeaderMap, HeaderValue};
 #[cfg(feature = "http")]
 use reqwest::Client;
 use std::time::Duration;
@@ -27,16 +30,31 @@ pub struct HttpClient {
 impl HttpClient {
     /// Create a new HTTP client with the given base URL
     pub fn new(base_url: &str) -> Result<Self> {
-        let base_url = Url::parse(base_url)?;
-        let client = Client::builder().timeout(Duration::from_secs(30)).build()?;
-
-        Ok(Self { client, base_url })
+        Self::build(base_url, Duration::from_secs(30), None)
     }
 
     /// Create a new HTTP client with custom timeout
     pub fn with_timeout(base_url: &str, timeout: Duration) -> Result<Self> {
+        Self::build(base_url, timeout, None)
+    }
+
+    /// Create a new HTTP client that sends credentials with every request
+    pub fn with_credentials(base_url: &str, credentials: Credentials) -> Result<Self> {
+        Self::build(base_url, Duration::from_secs(30), Some(&credentials))
+    }
+
+    fn build(base_url: &str, timeout: Duration, credentials: Option<&Credentials>) -> Result<Self> {
         let base_url = Url::parse(base_url)?;
-        let client = Client::builder().timeout(timeout).build()?;
+        let mut builder = Client::builder().timeout(timeout);
+        if let Some(credentials) = credentials {
+            let mut value = HeaderValue::from_str(&credentials.header_value())
+                .map_err(|e| anyhow::anyhow!("Invalid credentials: {e}"))?;
+            value.set_sensitive(true);
+            let mut headers = HeaderMap::new();
+            headers.insert(credentials.header_name(), value);
+            builder = builder.default_headers(headers);
+        }
+        let client = builder.build()?;
 
         Ok(Self { client, base_url })
     }
@@ -97,4 +115,19 @@ mod tests {
         let client2 = client1.clone();
         assert_eq!(client1.base_url(), client2.base_url());
     }
+
+    #[test]
+    fn test_http_client_with_credentials() {
+        let client = HttpClient::with_credentials(
+            "http://localhost:8080",
+            Credentials::Bearer("token".to_string()),
+        )
+        .unwrap();
+        assert_eq!(client.base_url().as_str(), "http://localhost:8080/");
+        assert!(HttpClient::with_credentials(
+            "http://localhost:8080",
+            Credentials::ApiKey("bad\nkey".to_string()),
+        )
+        .is_err());
+    }
 }
@@ -38,7 +38,7 @@ impl SetOperations for HttpSetClient {
             member: member.to_string(),
         };
 
-        let response = self.client.post(url).json(&request).send().await?;
+        let response = http::send(self.client.post(url).json(&request)).await?;
         http::handle_response(response, &format!("add member to set: {key}")).await
     }
 
@@ -51,7 +51,7 @@ impl SetOperations for HttpSetClient {
             members: members.iter().map(|&s| s.to_string()).collect(),
         };
 
-        let response = self.client.post(url).json(&request).send().await?;
+        let response = http::send(self.client.post(url).json(&request)).await?;
         http::handle_response(response, &format!("add members to set: {key}")).await
     }
 
@@ -62,7 +62,7 @@ impl SetOperations for HttpSetClient {
             key = key,
             member = member
         ))?;
-        let response = self.client.delete(url).send().await?;
+        let response = http::send(self.client.delete(url)).await?;
         http::handle_response(response, &format!("remove member from set: {key}")).await
     }
 
@@ -71,7 +71,7 @@ impl SetOperations for HttpSetClient {
         let url = self
             .base_url
             .join(&format!("redis/set/{key}/members", key = key))?;
-        let response = self.client.get(url).send().await?;
+        let response = http::send(self.client.get(url)).await?;
         http::handle_response(response, &format!("get members of set: {key}")).await
     }
 
@@ -80,7 +80,7 @@ impl SetOperations for HttpSetClient {
         let url = self
             .base_url
             .join(&format!("redis/set/{key}/cardinality", key = key))?;
-        let response = self.client.get(url).send().await?;
+        let response = http::send(self.client.get(url)).await?;
         http::handle_response(response, &format!("get cardinality of set: {key}")).await
     }
 
@@ -91,7 +91,7 @@ impl SetOperations for HttpSetClient {
             key = key,
             member = member
         ))?;
-        let response = self.client.get(url).send().await?;
+        let response = http::send(self.client.get(url)).await?;
         http::handle_response(response, &format!("check member existence in set: {key}")).await
     }
 
@@ -102,7 +102,7 @@ impl SetOperations for HttpSetClient {
             keys: keys.to_vec(),
         };
 
-        let response = self.client.post(url).json(&request).send().await?;
+        let response = http::send(self.client.post(url).json(&request)).await?;
         http::handle_response(response, "intersect sets").await
     }
 
@@ -113,7 +113,7 @@ impl SetOperations for HttpSetClient {
             keys: keys.to_vec(),
         };
 
-        let response = self.client.post(url).json(&request).send().await?;
+        let response = http::send(self.client.post(url).json(&request)).await?;
         http::handle_response(response, "union sets").await
     }
 
@@ -124,14 +124,14 @@ impl SetOperations for HttpSetClient {
             keys: keys.to_vec(),
         };
 
-        let response = self.client.post(url).json(&request).send().await?;
+        let response = http::send(self.client.post(url).json(&request)).await?;
         http::handle_response(response, "get set difference").await
     }
 
     /// Delete a set by key
     async fn delete(&mut self, key: &str) -> Result<bool> {
         let url = self.base_url.join(&format!("redis/set/{key}", key = key))?;
-        let response = self.client.delete(url).send().await?;
+        let response = http::send(self.client.delete(url)).await?;
         http::handle_response(response, &format!("delete set: {key}")).await
     }
 }
@@ -35,7 +35,7 @@ impl StringOperations for HttpStringClient {
     /// Get a string value by key
     async fn get(&mut self, key: &str) -> Result<Option<String>> {
         let url = self.base_url.join(&format!("redis/string/{key}"))?;
-        let response = self.client.get(url).send().await?;
+        let response = http::send(self.client.get(url)).await?;
         http::handle_response(response, &format!("get string for key: {key}")).await
     }
 
@@ -47,21 +47,21 @@ impl StringOperations for HttpStringClient {
             ttl,
         };
 
-        let response = self.client.post(url).json(&request).send().await?;
+        let response = http::send(self.client.post(url).json(&request)).await?;
         http::handle_empty_response(response, &format!("set string for key: {}", key)).await
     }
 
     /// Delete a string value
     async fn delete(&mut self, key: &str) -> Result<bool> {
         let url = self.base_url.join(&format!("redis/string/{key}"))?;
-        let response = self.client.delete(url).send().await?;
+        let response = http::send(self.client.delete(url)).await?;
         http::handle_response(response, &format!("delete string for key: {key}")).await
     }
 
     /// Get string information
     async fn info(&mut self, key: &str) -> Result<Option<StringInfo>> {
         let url = self.base_url.join(&format!("redis/string/{key}/info"))?;
-        let response = self.client.get(url).send().await?;
+        let response = http::send(self.client.get(url)).await?;
         http::handle_response(response, &format!("get string info for key: {key}")).await
     }
 
@@ -72,7 +72,7 @@ impl StringOperations for HttpStringClient {
             keys: keys.to_vec(),
         };
 
-        let response = self.client.post(url).json(&request).send().await?;
+        let response = http::send(self.client.post(url).json(&request)).await?;
         http::handle_response(response, "batch get strings").await
     }
 
@@ -83,7 +83,7 @@ impl StringOperations for HttpStringClient {
             operations: operations.to_vec(),
         };
 
-        let response = self.client.post(url).json(&request).send().await?;
+        let response = http::send(self.client.post(url).json(&request)).await?;
         http::handle_empty_response(response, "batch set strings").await
     }
 
@@ -99,7 +99,7 @@ impl StringOperations for HttpStringClient {
             grouped,
         };
 
-        let response = self.client.post(url).json(&request).send().await?;
+        let response = http::send(self.client.post(url).json(&request)).await?;
         http::handle_response(response, "get strings by patterns").await
     }
 }
@@ -1,9 +1,14 @@
 //! WebSocket client for DBX Redis API
 
 use crate::error::Result;
+use crate::Credentials;
 use futures_util::{SinkExt, StreamExt};
 use serde_json::Value;
 use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 use url::Url;
 
 pub mod set;
@@ -15,13 +20,24 @@ pub use string::WsStringClient;
 /// WebSocket client for interacting with the DBX Redis API
 pub struct WsClient {
     base_url: Url,
+    credentials: Option<Credentials>,
 }
 
 impl WsClient {
     /// Create a new WebSocket client with the given URL
     pub async fn new(ws_url: &str) -> Result<Self> {
         let url = Url::parse(ws_url)?;
-        Ok(Self { base_url: url })
+        Ok(Self {
+            base_url: url,
+            credentials: None,
+        })
+    }
+
+    /// Create a new WebSocket client that sends credentials when connecting
+    pub async fn with_credentials(ws_url: &str, credentials: Credentials) -> Result<Self> {
+        let mut client = Self::new(ws_url).await?;
+        client.credentials = Some(credentials);
+        Ok(client)
     }
 
     /// Create a new WebSocket client with custom timeout
@@ -34,7 +50,7 @@ impl WsClient {
     pub async fn string(&mut self) -> Result<WsStringClient> {
         let mut ws_url = self.base_url.clone();
         ws_url.set_path(&format!("{}/string/ws", ws_url.path()));
-        let (stream, _) = tokio_tungstenite::connect_async(ws_url).await?;
+        let stream = self.connect(ws_url).await?;
         Ok(WsStringClient::new(stream, self.base_url.clone()))
     }
 
@@ -42,7 +58,7 @@ impl WsClient {
     pub async fn set(&mut self) -> Result<WsSetClient> {
         let mut ws_url = self.base_url.clone();
         ws_url.set_path(&format!("{}/set/ws", ws_url.path()));
-        let (stream, _) = tokio_tungstenite::connect_async(ws_url).await?;
+        let stream = self.connect(ws_url).await?;
         Ok(WsSetClient::new(stream, self.base_url.clone()))
     }
 
@@ -51,10 +67,23 @@ impl WsClient {
         &self.base_url
     }
 
+    /// Open a connection, authenticating the upgrade request if credentials are set
+    async fn connect(&self, url: Url) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+        let mut request = url.into_client_request()?;
+        if let Some(credentials) = &self.credentials {
+            let value = HeaderValue::from_str(&credentials.header_value())
+                .map_err(|e| anyhow::anyhow!("Invalid credentials: {e}"))?;
+            request
+                .headers_mut()
+                .insert(credentials.header_name(), value);
+        }
+        let (stream, _) = tokio_tungstenite::connect_async(request).await?;
+        Ok(stream)
+    }
+
     /// Send a raw WebSocket message
     pub async fn send_message(&mut self, message: Value) -> Result<Value> {
-        let url = self.base_url.clone();
-        let (mut stream, _) = tokio_tungstenite::connect_async(url).await?;
+        let mut stream = self.connect(self.base_url.clone()).await?;
 
         let message_str = serde_json::to_string(&message)?;
         stream
@@ -69,12 +98,14 @@ impl WsClient {
                 }
                 _ => Err(crate::error::DbxError::Api {
                     status: 0,
+                    code: None,
                     message: "Unexpected WebSocket message type".to_string(),
                 }),
             }
         } else {
             Err(crate::error::DbxError::Api {
                 status: 0,
+                code: None,
                 message: "No response received from WebSocket".to_string(),
             })
         }
@@ -85,6 +116,7 @@ impl Clone for WsClient {
     fn clone(&self) -> Self {
         Self {
             base_url: self.base_url.clone(),
+            credentials: self.credentials.clone(),
         }
     }
 }
@@ -122,9 +122,13 @@ async fn test_set_info_serialization() -> Result<()> {
 #[tokio::test]
 async fn test_api_response_serialization() -> Result<()> {
     let api_response = ApiResponse {
+        version: 1,
         success: true,
         data: Some("test_data".to_string()),
         error: None,
+        code: None,
+        request_id: Some("request-1".to_string()),
+        duration_ms: Some(1.5),
     };
 
     let json = serde_json::to_string(&api_response)?;
@@ -133,6 +137,15 @@ async fn test_api_response_serialization() -> Result<()> {
     assert_eq!(api_response.success, deserialized.success);
     assert_eq!(api_response.data, deserialized.data);
     assert_eq!(api_response.error, deserialized.error);
+    assert_eq!(api_response.code, deserialized.code);
+    assert_eq!(api_response.request_id, deserialized.request_id);
+
+    // Error envelopes carry a code and no data
+    let error: ApiResponse<String> = serde_json::from_str(
+        r#"{"version":1,"success":false,"data":null,"error":"no such key","code":"NOT_FOUND"}"#,
+    )?;
+    assert!(error.data.is_none());
+    assert_eq!(error.code.as_deref(), Some("NOT_FOUND"));
 
     Ok(())
 }
@@ -4,20 +4,69 @@ pub mod common;
 pub mod redis;
 pub mod redis_ws;
 
-/// Test utilities and common functionality
+// Test utilities and common functionality
 
 /// Test utilities and helpers
 pub mod utils {
+    use std::sync::OnceLock;
     use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
     /// Get test HTTP server URL
     pub fn http_test_url() -> String {
-        std::env::var("TEST_HTTP_URL").unwrap_or_else(|_| "http://localhost:3000".to_string())
+        std::env::var("TEST_HTTP_URL").unwrap_or_else(|_| format!("http://{}", local_server()))
     }
 
     /// Get test WebSocket server URL
     pub fn ws_test_url() -> String {
-        std::env::var("TEST_WS_URL").unwrap_or_else(|_| "ws://localhost:3000/redis_ws".to_string())
+        std::env::var("TEST_WS_URL").unwrap_or_else(|_| format!("ws://{}/redis_ws", local_server()))
+    }
+
+    /// Address of an in-process API server on the in-memory backend, started on first use
+    ///
+    /// It runs on its own runtime so it outlives the runtime of each test.
+    fn local_server() -> std::net::SocketAddr {
+        static ADDR: OnceLock<std::net::SocketAddr> = OnceLock::new();
+        *ADDR.get_or_init(|| {
+            let addr = std::net::TcpListener::bind("127.0.0.1:0")
+                .and_then(|listener| listener.local_addr())
+                .expect("Failed to pick a port for the test server");
+            std::thread::spawn(move || {
+                let runtime = tokio::runtime::Runtime::new().expect("Failed to build runtime");
+                runtime.block_on(async move {
+                    let config = dbx_redis_api::config::Config {
+                        database_url: "memory://".to_string(),
+                        host: addr.ip().to_string(),
+                        port: addr.port(),
+                        pool_size: 10,
+                        redis_tls: Default::default(),
+                        backends: Default::default(),
+                        tls: Default::default(),
+                        auth: Default::default(),
+                        policy: Default::default(),
+                        policy_file: None,
+                        cors: Default::default(),
+                        read_only: false,
+                        shutdown_timeout_secs: 30,
+                        rate_limit: Default::default(),
+                        metrics_enabled: true,
+                        logging: Default::default(),
+                        telemetry: Default::default(),
+                    };
+                    let server = dbx_redis_api::server::Server::new(config)
+                        .await
+                        .expect("Failed to create test server");
+                    server.run(addr).await.expect("Test server failed");
+                });
+            });
+            // Wait until the server accepts connections
+            for _ in 0..100 {
+                if std::net::TcpStream::connect(addr).is_ok() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            addr
+        })
     }
 
     /// Generate a unique test key
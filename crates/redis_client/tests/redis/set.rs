@@ -105,7 +105,7 @@ async fn test_set_concurrent_operations() -> Result<()> {
     let client = HttpClient::new(&utils::http_test_url())?;
 
     let test_key = utils::unique_key("concurrent_set");
-    let _test_members = vec!["member1", "member2", "member3", "member4", "member5"];
+    let _test_members = ["member1", "member2", "member3", "member4", "member5"];
 
     // Spawn multiple concurrent operations
     let handles: Vec<_> = (0..5)
@@ -112,7 +112,7 @@ async fn test_websocket_string_pattern_operations() -> Result<()> {
 
 #[tokio::test]
 async fn test_websocket_string_concurrent_operations() -> Result<()> {
-    let mut client = WsClient::new(&utils::ws_test_url()).await?;
+    let client = WsClient::new(&utils::ws_test_url()).await?;
 
     // Spawn multiple concurrent operations
     let handles: Vec<_> = (0..5)